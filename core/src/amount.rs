@@ -128,6 +128,10 @@ impl Amount {
     pub fn wrapping_sub(&self, other: Amount) -> Amount {
         self.raw.wrapping_sub(other.raw).into()
     }
+
+    pub fn saturating_add(&self, other: Amount) -> Amount {
+        self.raw.saturating_add(other.raw).into()
+    }
 }
 
 impl From<u128> for Amount {
@@ -21,7 +21,9 @@ use send_block::JsonSendBlock;
 pub use send_block::{valid_send_block_predecessor, SendBlock, SendBlockArgs};
 
 mod state_block;
-pub use state_block::{EpochBlockArgs, JsonStateBlock, StateBlock, StateBlockArgs};
+pub use state_block::{
+    EpochBlockArgs, JsonStateBlock, StateBlock, StateBlockArgs, UnsignedStateBlock,
+};
 
 mod builders;
 pub use builders::*;
@@ -314,6 +314,80 @@ pub struct JsonStateBlock {
     pub work: WorkNonce,
 }
 
+/// A state block template without signature. It is prepared by a node that has
+/// access to the ledger and signed on an offline machine that holds the key.
+#[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct UnsignedStateBlock {
+    pub account: Account,
+    pub previous: BlockHash,
+    pub representative: Account,
+    pub balance: Amount,
+    pub link: Link,
+    pub work: WorkNonce,
+    /// The hash that will be signed. It is checked again before signing.
+    pub hash: BlockHash,
+}
+
+impl UnsignedStateBlock {
+    pub fn new(
+        account: Account,
+        previous: BlockHash,
+        representative: PublicKey,
+        balance: Amount,
+        link: Link,
+        work: u64,
+    ) -> Self {
+        let hashables = StateHashables {
+            account,
+            previous,
+            representative,
+            balance,
+            link,
+        };
+        Self {
+            account,
+            previous,
+            representative: representative.into(),
+            balance,
+            link,
+            work: work.into(),
+            hash: hashables.hash(),
+        }
+    }
+
+    fn hashables(&self) -> StateHashables {
+        StateHashables {
+            account: self.account,
+            previous: self.previous,
+            representative: self.representative.into(),
+            balance: self.balance,
+            link: self.link,
+        }
+    }
+
+    pub fn sign(&self, key: &PrivateKey) -> Result<Block> {
+        if key.account() != self.account {
+            bail!(
+                "the key does not belong to account {}",
+                self.account.encode_account()
+            );
+        }
+        let hashables = self.hashables();
+        if hashables.hash() != self.hash {
+            bail!("the block hash does not match the block fields");
+        }
+        Ok(StateBlockArgs {
+            key,
+            previous: self.previous,
+            representative: self.representative.into(),
+            balance: self.balance,
+            link: self.link,
+            work: self.work.into(),
+        }
+        .into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -384,4 +458,51 @@ mod tests {
 }"#
         );
     }
+
+    #[test]
+    fn sign_unsigned_block() {
+        let key = PrivateKey::from(42);
+        let unsigned = UnsignedStateBlock::new(
+            key.account(),
+            BlockHash::from(1),
+            PublicKey::from(2),
+            Amount::raw(3),
+            Link::from(4),
+            5,
+        );
+
+        let block = unsigned.sign(&key).unwrap();
+
+        let expected: Block = StateBlockArgs {
+            key: &key,
+            previous: BlockHash::from(1),
+            representative: PublicKey::from(2),
+            balance: Amount::raw(3),
+            link: Link::from(4),
+            work: 5,
+        }
+        .into();
+        assert_eq!(block, expected);
+        assert_eq!(block.hash(), unsigned.hash);
+    }
+
+    #[test]
+    fn reject_unsigned_block_with_wrong_key_or_hash() {
+        let key = PrivateKey::from(42);
+        let mut unsigned = UnsignedStateBlock::new(
+            key.account(),
+            BlockHash::from(1),
+            PublicKey::from(2),
+            Amount::raw(3),
+            Link::from(4),
+            5,
+        );
+        assert!(unsigned.sign(&PrivateKey::from(43)).is_err());
+
+        unsigned.balance = Amount::raw(1000);
+        assert_eq!(
+            unsigned.sign(&key).unwrap_err().to_string(),
+            "the block hash does not match the block fields"
+        );
+    }
 }
@@ -1,29 +1,263 @@
 use crate::RawKey;
 use argon2::{Variant, Version};
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
+
+/// The Argon2 variant that is used for deriving keys from passwords
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum KdfVariant {
+    #[default]
+    Argon2d,
+    Argon2i,
+    Argon2id,
+}
+
+impl KdfVariant {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KdfVariant::Argon2d => "argon2d",
+            KdfVariant::Argon2i => "argon2i",
+            KdfVariant::Argon2id => "argon2id",
+        }
+    }
+
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            KdfVariant::Argon2d => 0,
+            KdfVariant::Argon2i => 1,
+            KdfVariant::Argon2id => 2,
+        }
+    }
+
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(KdfVariant::Argon2d),
+            1 => Some(KdfVariant::Argon2i),
+            2 => Some(KdfVariant::Argon2id),
+            _ => None,
+        }
+    }
+
+    fn argon2_variant(&self) -> Variant {
+        match self {
+            KdfVariant::Argon2d => Variant::Argon2d,
+            KdfVariant::Argon2i => Variant::Argon2i,
+            KdfVariant::Argon2id => Variant::Argon2id,
+        }
+    }
+}
+
+impl FromStr for KdfVariant {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "argon2d" => Ok(KdfVariant::Argon2d),
+            "argon2i" => Ok(KdfVariant::Argon2i),
+            "argon2id" => Ok(KdfVariant::Argon2id),
+            _ => Err(anyhow!("unsupported key derivation function: {}", s)),
+        }
+    }
+}
+
+impl Display for KdfVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 /// Key derivation function
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct KeyDerivationFunction {
-    kdf_work: u32,
+    variant: KdfVariant,
+    mem_cost: u32,
+    time_cost: u32,
+    lanes: u32,
 }
 
 impl KeyDerivationFunction {
+    /// 4 GiB
+    pub const MAX_MEM_COST: u32 = 4 * 1024 * 1024;
+    pub const MAX_TIME_COST: u32 = 16;
+    pub const MAX_LANES: u32 = 64;
+
+    /// The legacy KDF: Argon2d with a single pass over a single lane
     pub fn new(kdf_work: u32) -> Self {
-        Self { kdf_work }
+        Self {
+            variant: KdfVariant::Argon2d,
+            mem_cost: kdf_work,
+            time_cost: 1,
+            lanes: 1,
+        }
+    }
+
+    pub fn with_params(
+        variant: KdfVariant,
+        mem_cost: u32,
+        time_cost: u32,
+        lanes: u32,
+    ) -> anyhow::Result<Self> {
+        let kdf = Self {
+            variant,
+            mem_cost,
+            time_cost,
+            lanes,
+        };
+        kdf.validate()?;
+        Ok(kdf)
+    }
+
+    /// Restores the KDF that is described by the given parameters
+    pub fn from_params(params: &KdfParams) -> anyhow::Result<Self> {
+        if params.version != Version::Version10.as_u32() {
+            bail!("unsupported key derivation function");
+        }
+        let variant = params
+            .algorithm
+            .parse()
+            .map_err(|_| anyhow!("unsupported key derivation function"))?;
+        Self::with_params(variant, params.mem_cost, params.time_cost, params.lanes)
+    }
+
+    pub fn variant(&self) -> KdfVariant {
+        self.variant
+    }
+
+    pub fn mem_cost(&self) -> u32 {
+        self.mem_cost
     }
 
-    pub fn hash_password(&self, password: &str, salt: &[u8; 32]) -> RawKey {
-        let config = argon2::Config {
+    pub fn time_cost(&self) -> u32 {
+        self.time_cost
+    }
+
+    pub fn lanes(&self) -> u32 {
+        self.lanes
+    }
+
+    pub fn params(&self) -> KdfParams {
+        KdfParams {
+            algorithm: self.variant.as_str().to_owned(),
+            version: Version::Version10.as_u32(),
+            mem_cost: self.mem_cost,
+            time_cost: self.time_cost,
+            lanes: self.lanes,
+        }
+    }
+
+    pub fn hash_password(&self, password: &str, salt: &[u8; 32]) -> anyhow::Result<RawKey> {
+        let hash = argon2::hash_raw(password.as_bytes(), salt, &self.config())
+            .map_err(|e| anyhow!("key derivation failed: {}", e))?;
+        Ok(RawKey::from_bytes(hash.as_slice().try_into().unwrap()))
+    }
+
+    fn config(&self) -> argon2::Config<'static> {
+        argon2::Config {
             hash_length: 32,
-            lanes: 1,
-            mem_cost: self.kdf_work,
-            time_cost: 1,
-            variant: Variant::Argon2d,
+            lanes: self.lanes,
+            mem_cost: self.mem_cost,
+            time_cost: self.time_cost,
+            variant: self.variant.argon2_variant(),
             version: Version::Version10,
             ..Default::default()
-        };
+        }
+    }
+
+    /// The parameters can come from untrusted input like backup files, so
+    /// they are capped to keep a key derivation from exhausting the node
+    fn validate(&self) -> anyhow::Result<()> {
+        let min_mem_cost = self.lanes.checked_mul(8);
+        if !(1..=Self::MAX_LANES).contains(&self.lanes)
+            || !(1..=Self::MAX_TIME_COST).contains(&self.time_cost)
+            || self.mem_cost > Self::MAX_MEM_COST
+            || min_mem_cost.map_or(true, |min| self.mem_cost < min)
+        {
+            bail!("invalid key derivation function parameters");
+        }
+        Ok(())
+    }
+}
+
+/// Parameters of the key derivation function, as they are stored alongside
+/// data that was encrypted with a derived key
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub algorithm: String,
+    pub version: u32,
+    pub mem_cost: u32,
+    pub time_cost: u32,
+    pub lanes: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params_roundtrip() {
+        let kdf = KeyDerivationFunction::new(64);
+        let restored = KeyDerivationFunction::from_params(&kdf.params()).unwrap();
+        let salt = [1; 32];
+        assert_eq!(
+            restored.hash_password("foo", &salt).unwrap(),
+            kdf.hash_password("foo", &salt).unwrap()
+        );
+    }
+
+    #[test]
+    fn params_roundtrip_with_custom_variant() {
+        let kdf = KeyDerivationFunction::with_params(KdfVariant::Argon2id, 32, 2, 2).unwrap();
+        let restored = KeyDerivationFunction::from_params(&kdf.params()).unwrap();
+        assert_eq!(restored, kdf);
+    }
+
+    #[test]
+    fn variants_derive_different_keys() {
+        let salt = [1; 32];
+        let argon2d = KeyDerivationFunction::new(64);
+        let argon2id = KeyDerivationFunction::with_params(KdfVariant::Argon2id, 64, 1, 1).unwrap();
+        assert_ne!(
+            argon2d.hash_password("foo", &salt).unwrap(),
+            argon2id.hash_password("foo", &salt).unwrap()
+        );
+    }
+
+    #[test]
+    fn reject_unknown_algorithm() {
+        let mut params = KeyDerivationFunction::new(64).params();
+        params.algorithm = "scrypt".to_owned();
+        assert!(KeyDerivationFunction::from_params(&params).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_params() {
+        assert!(KeyDerivationFunction::with_params(KdfVariant::Argon2d, 64, 0, 1).is_err());
+        assert!(KeyDerivationFunction::with_params(KdfVariant::Argon2d, 64, 1, 0).is_err());
+        assert!(KeyDerivationFunction::with_params(KdfVariant::Argon2d, 8, 1, 2).is_err());
+    }
+
+    #[test]
+    fn reject_too_expensive_params() {
+        let max_mem = KeyDerivationFunction::MAX_MEM_COST;
+        assert!(
+            KeyDerivationFunction::with_params(KdfVariant::Argon2d, max_mem + 1, 1, 1).is_err()
+        );
+        assert!(KeyDerivationFunction::with_params(KdfVariant::Argon2d, 64, 17, 1).is_err());
+        assert!(KeyDerivationFunction::with_params(KdfVariant::Argon2d, max_mem, 1, 65).is_err());
+        assert!(
+            KeyDerivationFunction::with_params(KdfVariant::Argon2d, u32::MAX, 1, u32::MAX).is_err()
+        );
+    }
 
-        let hash = argon2::hash_raw(password.as_bytes(), salt, &config).unwrap();
-        RawKey::from_bytes(hash.as_slice().try_into().unwrap())
+    #[test]
+    fn variant_byte_roundtrip() {
+        for variant in [
+            KdfVariant::Argon2d,
+            KdfVariant::Argon2i,
+            KdfVariant::Argon2id,
+        ] {
+            assert_eq!(KdfVariant::from_u8(variant.as_u8()), Some(variant));
+            assert_eq!(variant.as_str().parse::<KdfVariant>().unwrap(), variant);
+        }
     }
 }
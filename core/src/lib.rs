@@ -71,8 +71,14 @@ mod unchecked_info;
 pub use unchecked_info::{UncheckedInfo, UncheckedKey};
 
 mod kdf;
-pub use kdf::KeyDerivationFunction;
+pub use kdf::{KdfParams, KdfVariant, KeyDerivationFunction};
+
+mod mnemonic;
+pub use mnemonic::Mnemonic;
+
+mod wallet_backup;
 use utils::{BufferWriter, Deserialize, Serialize, Stream};
+pub use wallet_backup::{WalletBackup, WalletBackupContents};
 
 use std::{
     fmt::{Debug, Display, Write},
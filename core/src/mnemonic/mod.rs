@@ -0,0 +1,239 @@
+use crate::RawKey;
+use sha2::{Digest, Sha256};
+use std::{fmt::Display, str::FromStr, sync::OnceLock};
+
+const WORD_BITS: usize = 11;
+
+/// BIP39 english wordlist
+fn wordlist() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| include_str!("english.txt").lines().collect())
+}
+
+/// A BIP39 mnemonic phrase. Wallet seeds are encoded directly as the
+/// mnemonic entropy, so a 32 byte seed results in 24 words.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Mnemonic {
+    entropy: Vec<u8>,
+}
+
+impl Mnemonic {
+    pub fn from_entropy(entropy: &[u8]) -> anyhow::Result<Self> {
+        if entropy.len() < 16 || entropy.len() > 32 || !entropy.len().is_multiple_of(4) {
+            bail!("invalid mnemonic entropy length: {}", entropy.len());
+        }
+        Ok(Self {
+            entropy: entropy.to_vec(),
+        })
+    }
+
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    pub fn words(&self) -> Vec<&'static str> {
+        let words = wordlist();
+        let mut bits = to_bits(&self.entropy);
+        let checksum = checksum_bits(&self.entropy);
+        bits.extend_from_slice(&checksum);
+        bits.chunks(WORD_BITS)
+            .map(|chunk| words[from_bits(chunk)])
+            .collect()
+    }
+
+    /// The 32 byte seed encoded by a 24 word mnemonic
+    pub fn to_seed(&self) -> anyhow::Result<RawKey> {
+        let bytes: [u8; 32] = self
+            .entropy
+            .as_slice()
+            .try_into()
+            .map_err(|_| anyhow!("a seed mnemonic must consist of 24 words"))?;
+        Ok(RawKey::from_bytes(bytes))
+    }
+}
+
+impl From<&RawKey> for Mnemonic {
+    fn from(seed: &RawKey) -> Self {
+        Self {
+            entropy: seed.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = anyhow::Error;
+
+    fn from_str(phrase: &str) -> Result<Self, Self::Err> {
+        let words = wordlist();
+        let phrase = phrase.to_lowercase();
+        let indices = phrase
+            .split_whitespace()
+            .map(|word| {
+                words
+                    .binary_search(&word)
+                    .map_err(|_| anyhow!("invalid mnemonic word: {}", word))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        if indices.len() < 12 || indices.len() > 24 || !indices.len().is_multiple_of(3) {
+            bail!("invalid mnemonic word count: {}", indices.len());
+        }
+
+        let mut bits = Vec::with_capacity(indices.len() * WORD_BITS);
+        for index in indices {
+            for i in (0..WORD_BITS).rev() {
+                bits.push((index >> i) & 1 == 1);
+            }
+        }
+
+        let checksum_len = bits.len() / 33;
+        let (entropy_bits, checksum) = bits.split_at(bits.len() - checksum_len);
+        let entropy: Vec<u8> = entropy_bits
+            .chunks(8)
+            .map(from_bits)
+            .map(|b| b as u8)
+            .collect();
+        if checksum != checksum_bits(&entropy) {
+            bail!("invalid mnemonic checksum");
+        }
+
+        Ok(Self { entropy })
+    }
+}
+
+impl Display for Mnemonic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.words().join(" "))
+    }
+}
+
+fn checksum_bits(entropy: &[u8]) -> Vec<bool> {
+    let hash = Sha256::digest(entropy);
+    let mut bits = to_bits(&hash);
+    bits.truncate(entropy.len() * 8 / 32);
+    bits
+}
+
+fn to_bits(bytes: &[u8]) -> Vec<bool> {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+        .collect()
+}
+
+fn from_bits(bits: &[bool]) -> usize {
+    bits.iter().fold(0, |acc, bit| (acc << 1) | *bit as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wordlist_is_sorted() {
+        let words = wordlist();
+        assert_eq!(words.len(), 2048);
+        assert!(words.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    // Test vectors from https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+    const VECTORS: [(&str, &str); 8] = [
+        (
+            "00000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffff",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo when",
+        ),
+        (
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art",
+        ),
+        (
+            "8080808080808080808080808080808080808080808080808080808080808080",
+            "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic bless",
+        ),
+        (
+            "9e885d952ad362caeb4efe34a8e91bd2",
+            "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic",
+        ),
+        (
+            "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+            "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length",
+        ),
+        (
+            "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+            "void come effort suffer camp survey warrior heavy shoot primary clutch crush open amazing screen patrol group space point ten exist slush involve unfold",
+        ),
+    ];
+
+    #[test]
+    fn encode() {
+        for (entropy, phrase) in VECTORS {
+            let mnemonic = Mnemonic::from_entropy(&hex::decode(entropy).unwrap()).unwrap();
+            assert_eq!(mnemonic.to_string(), phrase);
+        }
+    }
+
+    #[test]
+    fn decode() {
+        for (entropy, phrase) in VECTORS {
+            let mnemonic: Mnemonic = phrase.parse().unwrap();
+            assert_eq!(hex::encode(mnemonic.entropy()), entropy);
+        }
+    }
+
+    #[test]
+    fn decode_ignores_case_and_extra_whitespace() {
+        let mnemonic: Mnemonic =
+            "  Legal winner thank year wave sausage\nworth useful legal winner thank YELLOW "
+                .parse()
+                .unwrap();
+        assert_eq!(
+            hex::encode(mnemonic.entropy()),
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f"
+        );
+    }
+
+    #[test]
+    fn invalid_checksum() {
+        let result = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"
+            .parse::<Mnemonic>();
+        assert_eq!(result.unwrap_err().to_string(), "invalid mnemonic checksum");
+    }
+
+    #[test]
+    fn invalid_word() {
+        let result = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon foo"
+            .parse::<Mnemonic>();
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "invalid mnemonic word: foo"
+        );
+    }
+
+    #[test]
+    fn invalid_word_count() {
+        assert!("abandon abandon about".parse::<Mnemonic>().is_err());
+    }
+
+    #[test]
+    fn seed_roundtrip() {
+        let seed = RawKey::from(123456);
+        let mnemonic = Mnemonic::from(&seed);
+        assert_eq!(mnemonic.words().len(), 24);
+        let decoded: Mnemonic = mnemonic.to_string().parse().unwrap();
+        assert_eq!(decoded.to_seed().unwrap(), seed);
+    }
+
+    #[test]
+    fn only_24_words_can_be_converted_to_a_seed() {
+        let mnemonic: Mnemonic = VECTORS[0].1.parse().unwrap();
+        assert!(mnemonic.to_seed().is_err());
+    }
+}
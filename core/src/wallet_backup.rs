@@ -0,0 +1,218 @@
+use crate::{Account, KdfParams, KeyDerivationFunction, RawKey};
+use anyhow::Context;
+use blake2::{
+    digest::{consts::U32, KeyInit, Mac},
+    Blake2bMac,
+};
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+type Aes256Ctr = ctr::Ctr64BE<aes::Aes256>;
+type Blake2bMac256 = Blake2bMac<U32>;
+
+/// Everything that is needed to recreate a wallet
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletBackupContents {
+    pub seed: RawKey,
+    pub deterministic_index: u32,
+    pub adhoc_keys: Vec<RawKey>,
+    pub watch_accounts: Vec<Account>,
+    pub representative: Account,
+}
+
+/// A self-describing, password protected wallet backup.
+///
+/// The wallet contents are encrypted with AES-256-CTR and authenticated with a
+/// keyed Blake2b MAC. Both keys are derived from the backup password with the
+/// KDF parameters stored in the backup, so a backup can be restored by a node
+/// with a different `kdf_work` setting.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletBackup {
+    pub version: u32,
+    pub kdf: KdfParams,
+    pub salt: String,
+    pub iv: String,
+    pub ciphertext: String,
+    pub mac: String,
+}
+
+impl WalletBackup {
+    pub const VERSION_CURRENT: u32 = 1;
+
+    pub fn encrypt(
+        contents: &WalletBackupContents,
+        password: &str,
+        kdf: &KeyDerivationFunction,
+    ) -> anyhow::Result<Self> {
+        let salt: [u8; 32] = thread_rng().gen();
+        let iv: [u8; 16] = thread_rng().gen();
+        let keys = BackupKeys::derive(kdf, password, &salt)?;
+
+        let mut data = serde_json::to_vec(contents).unwrap();
+        Aes256Ctr::new(&keys.encryption.into(), &iv.into()).apply_keystream(&mut data);
+        let mac = keys.mac(Self::VERSION_CURRENT, &iv, &data).finalize();
+
+        Ok(Self {
+            version: Self::VERSION_CURRENT,
+            kdf: kdf.params(),
+            salt: hex::encode_upper(salt),
+            iv: hex::encode_upper(iv),
+            ciphertext: hex::encode_upper(data),
+            mac: hex::encode_upper(mac.into_bytes()),
+        })
+    }
+
+    /// Checks the integrity of the backup and decrypts it. A wrong password
+    /// cannot be distinguished from a corrupted backup.
+    pub fn decrypt(&self, password: &str) -> anyhow::Result<WalletBackupContents> {
+        if self.version != Self::VERSION_CURRENT {
+            bail!("unsupported wallet backup version: {}", self.version);
+        }
+        let kdf = KeyDerivationFunction::from_params(&self.kdf)?;
+        let salt: [u8; 32] = decode_fixed(&self.salt, "salt")?;
+        let iv: [u8; 16] = decode_fixed(&self.iv, "iv")?;
+        let mut data = hex::decode(&self.ciphertext).context("invalid backup ciphertext")?;
+        let mac = hex::decode(&self.mac).context("invalid backup mac")?;
+
+        let keys = BackupKeys::derive(&kdf, password, &salt)?;
+        keys.mac(self.version, &iv, &data)
+            .verify_slice(&mac)
+            .map_err(|_| anyhow!("wallet backup integrity check failed"))?;
+
+        Aes256Ctr::new(&keys.encryption.into(), &iv.into()).apply_keystream(&mut data);
+        serde_json::from_slice(&data).context("invalid wallet backup contents")
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(json).context("invalid wallet backup format")
+    }
+}
+
+struct BackupKeys {
+    encryption: [u8; 32],
+    authentication: [u8; 32],
+}
+
+impl BackupKeys {
+    /// Derives separate keys for encryption and authentication from the password
+    fn derive(
+        kdf: &KeyDerivationFunction,
+        password: &str,
+        salt: &[u8; 32],
+    ) -> anyhow::Result<Self> {
+        let key = kdf.hash_password(password, salt)?;
+        Ok(Self {
+            encryption: Self::sub_key(&key, b"encryption"),
+            authentication: Self::sub_key(&key, b"authentication"),
+        })
+    }
+
+    fn sub_key(key: &RawKey, label: &[u8]) -> [u8; 32] {
+        let mut mac = <Blake2bMac256 as KeyInit>::new_from_slice(key.as_bytes()).unwrap();
+        mac.update(label);
+        mac.finalize().into_bytes().into()
+    }
+
+    fn mac(&self, version: u32, iv: &[u8], ciphertext: &[u8]) -> Blake2bMac256 {
+        let mut mac = <Blake2bMac256 as KeyInit>::new_from_slice(&self.authentication).unwrap();
+        mac.update(&version.to_be_bytes());
+        mac.update(iv);
+        mac.update(ciphertext);
+        mac
+    }
+}
+
+fn decode_fixed<const N: usize>(hex_str: &str, name: &str) -> anyhow::Result<[u8; N]> {
+    hex::decode(hex_str)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| anyhow!("invalid backup {}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_and_decrypt() {
+        let contents = test_contents();
+        let backup = WalletBackup::encrypt(&contents, "secret", &test_kdf()).unwrap();
+        assert_eq!(backup.version, WalletBackup::VERSION_CURRENT);
+        assert_eq!(backup.kdf, test_kdf().params());
+        assert_eq!(backup.decrypt("secret").unwrap(), contents);
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let backup = WalletBackup::encrypt(&test_contents(), "secret", &test_kdf()).unwrap();
+        let restored = WalletBackup::from_json(&backup.to_json()).unwrap();
+        assert_eq!(restored, backup);
+    }
+
+    #[test]
+    fn wrong_password() {
+        let backup = WalletBackup::encrypt(&test_contents(), "secret", &test_kdf()).unwrap();
+        assert_eq!(
+            backup.decrypt("wrong").unwrap_err().to_string(),
+            "wallet backup integrity check failed"
+        );
+    }
+
+    #[test]
+    fn tampered_ciphertext() {
+        let mut backup = WalletBackup::encrypt(&test_contents(), "secret", &test_kdf()).unwrap();
+        let mut data = hex::decode(&backup.ciphertext).unwrap();
+        data[0] ^= 1;
+        backup.ciphertext = hex::encode_upper(data);
+        assert_eq!(
+            backup.decrypt("secret").unwrap_err().to_string(),
+            "wallet backup integrity check failed"
+        );
+    }
+
+    #[test]
+    fn tampered_kdf_params() {
+        let mut backup = WalletBackup::encrypt(&test_contents(), "secret", &test_kdf()).unwrap();
+        backup.kdf.mem_cost = 16;
+        assert!(backup.decrypt("secret").is_err());
+    }
+
+    #[test]
+    fn reject_too_expensive_kdf_params() {
+        let mut backup = WalletBackup::encrypt(&test_contents(), "secret", &test_kdf()).unwrap();
+        backup.kdf.lanes = u32::MAX;
+        assert_eq!(
+            backup.decrypt("secret").unwrap_err().to_string(),
+            "invalid key derivation function parameters"
+        );
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let mut backup = WalletBackup::encrypt(&test_contents(), "secret", &test_kdf()).unwrap();
+        backup.version = 2;
+        assert_eq!(
+            backup.decrypt("secret").unwrap_err().to_string(),
+            "unsupported wallet backup version: 2"
+        );
+    }
+
+    fn test_kdf() -> KeyDerivationFunction {
+        KeyDerivationFunction::new(8)
+    }
+
+    fn test_contents() -> WalletBackupContents {
+        WalletBackupContents {
+            seed: RawKey::from(1),
+            deterministic_index: 3,
+            adhoc_keys: vec![RawKey::from(2), RawKey::from(3)],
+            watch_accounts: vec![Account::from(4)],
+            representative: Account::from(5),
+        }
+    }
+}
@@ -11,6 +11,7 @@ pub struct DaemonBuilder {
     network: Networks,
     node_builder: NodeBuilder,
     node_started: Option<Box<dyn FnMut(Arc<Node>) + Send>>,
+    rpc_port: Option<u16>,
 }
 
 impl DaemonBuilder {
@@ -19,6 +20,7 @@ impl DaemonBuilder {
             network,
             node_builder: NodeBuilder::new(network),
             node_started: None,
+            rpc_port: None,
         }
     }
 
@@ -37,6 +39,13 @@ impl DaemonBuilder {
         self
     }
 
+    /// Overrides the RPC port of the config file. This is needed when a read-only
+    /// node shares the data path with the node that writes the ledger
+    pub fn rpc_port(mut self, port: u16) -> Self {
+        self.rpc_port = Some(port);
+        self
+    }
+
     pub fn on_node_started(mut self, callback: impl FnMut(Arc<Node>) + Send + 'static) -> Self {
         self.node_started = Some(Box::new(callback));
         self
@@ -68,8 +77,12 @@ impl DaemonBuilder {
                 _ = shutdown => {}
             }
         };
-        if daemon_config.rpc_enable {
-            let socket_addr = rpc_config.listening_addr()?;
+        // A read-only node is only useful for serving RPC requests
+        if daemon_config.rpc_enable || node.flags.read_only {
+            let mut socket_addr = rpc_config.listening_addr()?;
+            if let Some(port) = self.rpc_port {
+                socket_addr.set_port(port);
+            }
             let listener = TcpListener::bind(socket_addr).await?;
             run_rpc_server(
                 node.clone(),
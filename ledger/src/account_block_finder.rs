@@ -0,0 +1,117 @@
+use crate::Ledger;
+use rsban_core::{Account, AccountInfo, BlockHash, SavedBlock};
+use rsban_store_lmdb::Transaction;
+
+/// A point in the history of an account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountHistoryPoint {
+    /// Block height, starting with 1 for the open block
+    Height(u64),
+    /// Local timestamp in seconds since the UNIX epoch. Resolves to the latest
+    /// block that was added to the ledger at or before that time
+    Timestamp(u64),
+}
+
+/// Finds the block that defines the state of an account at a given point in its history
+pub(crate) struct AccountBlockFinder<'a> {
+    ledger: &'a Ledger,
+    txn: &'a dyn Transaction,
+    account: Account,
+    info: AccountInfo,
+}
+
+impl<'a> AccountBlockFinder<'a> {
+    pub fn new(ledger: &'a Ledger, txn: &'a dyn Transaction, account: Account) -> Option<Self> {
+        let info = ledger.account_info(txn, &account)?;
+        Some(Self {
+            ledger,
+            txn,
+            account,
+            info,
+        })
+    }
+
+    pub fn find(&self, point: AccountHistoryPoint) -> Option<SavedBlock> {
+        match point {
+            AccountHistoryPoint::Height(height) => self.block_at_height(height),
+            AccountHistoryPoint::Timestamp(timestamp) => self.block_at_timestamp(timestamp),
+        }
+    }
+
+    /// Resolves the hash of the block at the given height without loading that
+    /// block, so it also works for pruned blocks
+    pub fn hash_at_height(&self, height: u64) -> Option<BlockHash> {
+        if height == 0 || height > self.info.block_count {
+            return None;
+        }
+
+        if self.ledger.block_height_index_enabled() {
+            return self
+                .ledger
+                .store
+                .block_height
+                .get(self.txn, &self.account, height);
+        }
+
+        // Without the index the chain has to be walked from the nearest end.
+        // Pruning removes the oldest blocks, so the walk from the open block
+        // falls back to the walk from the head.
+        if height <= self.info.block_count / 2 {
+            if let Some(hash) = self.walk_from_open(height) {
+                return Some(hash);
+            }
+        }
+        self.walk_from_head(height)
+    }
+
+    fn walk_from_open(&self, height: u64) -> Option<BlockHash> {
+        let mut hash = self.info.open_block;
+        for _ in 1..height {
+            hash = self.ledger.any().block_successor(self.txn, &hash)?;
+        }
+        Some(hash)
+    }
+
+    fn walk_from_head(&self, height: u64) -> Option<BlockHash> {
+        let mut hash = self.info.head;
+        for _ in height..self.info.block_count {
+            hash = self.ledger.get_block(self.txn, &hash)?.previous();
+        }
+        Some(hash)
+    }
+
+    fn block_at_height(&self, height: u64) -> Option<SavedBlock> {
+        let hash = self.hash_at_height(height)?;
+        self.ledger.get_block(self.txn, &hash)
+    }
+
+    fn block_at_timestamp(&self, timestamp: u64) -> Option<SavedBlock> {
+        if self.ledger.block_height_index_enabled() {
+            // Binary search for the last block that isn't newer than the timestamp
+            let mut result = None;
+            let mut low = 1;
+            let mut high = self.info.block_count;
+            while low <= high {
+                let middle = low + (high - low) / 2;
+                let block = self.block_at_height(middle)?;
+                if block.timestamp() <= timestamp {
+                    result = Some(block);
+                    low = middle + 1;
+                } else {
+                    high = middle - 1;
+                }
+            }
+            return result;
+        }
+
+        let mut hash = self.info.head;
+        while !hash.is_zero() {
+            let block = self.ledger.get_block(self.txn, &hash)?;
+            if block.timestamp() <= timestamp {
+                return Some(block);
+            }
+            hash = block.previous();
+        }
+        None
+    }
+}
@@ -2,7 +2,7 @@ use crate::Ledger;
 use rsban_core::{
     Account, AccountInfo, Amount, Block, BlockSideband, PendingInfo, PendingKey, SavedBlock,
 };
-use rsban_store_lmdb::LmdbWriteTransaction;
+use rsban_store_lmdb::{IncomingSend, LmdbWriteTransaction};
 use std::sync::atomic::Ordering;
 
 #[derive(Debug, PartialEq)]
@@ -43,6 +43,7 @@ impl<'a> BlockInserter<'a> {
         let sideband = self.instructions.set_sideband.clone();
         let saved_block = SavedBlock::new(self.block.clone(), sideband);
         self.ledger.store.block.put(self.txn, &saved_block);
+        self.update_block_height_index(&saved_block);
         self.update_account();
         self.delete_old_pending_info();
         self.insert_new_pending_info();
@@ -59,6 +60,17 @@ impl<'a> BlockInserter<'a> {
         saved_block
     }
 
+    fn update_block_height_index(&mut self, block: &SavedBlock) {
+        if self.ledger.block_height_index_enabled() {
+            self.ledger.store.block_height.put(
+                self.txn,
+                &self.instructions.account,
+                block.height(),
+                &block.hash(),
+            );
+        }
+    }
+
     fn update_account(&mut self) {
         self.ledger.update_account(
             self.txn,
@@ -77,6 +89,19 @@ impl<'a> BlockInserter<'a> {
     fn insert_new_pending_info(&mut self) {
         if let Some((key, info)) = &self.instructions.insert_pending {
             self.ledger.store.pending.put(self.txn, key, info);
+
+            if self.ledger.incoming_index_enabled() {
+                let send = IncomingSend {
+                    hash: key.send_block_hash,
+                    timestamp: self.instructions.set_sideband.timestamp,
+                    source: info.source,
+                    amount: info.amount,
+                };
+                self.ledger
+                    .store
+                    .incoming
+                    .put(self.txn, &key.receiving_account, &send);
+            }
         }
     }
 
@@ -104,6 +129,7 @@ impl<'a> BlockInserter<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::LedgerContext;
     use rsban_core::{BlockHash, PublicKey, TestBlockBuilder};
 
     #[test]
@@ -182,6 +208,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn update_block_height_index() {
+        let (mut block, instructions) = open_state_block_instructions();
+        let ctx = LedgerContext::empty();
+        ctx.ledger.enable_block_height_index();
+
+        insert(&ctx.ledger, &mut block, &instructions);
+
+        assert_eq!(
+            ctx.ledger.store.block_height.get(
+                &ctx.ledger.read_txn(),
+                &instructions.account,
+                instructions.set_sideband.height
+            ),
+            Some(block.hash())
+        );
+    }
+
+    #[test]
+    fn update_incoming_index() {
+        let (mut block, mut instructions) = open_state_block_instructions();
+        let destination = Account::from(1000);
+        let pending_info = PendingInfo::new_test_instance();
+        instructions.insert_pending = Some((
+            PendingKey::new(destination, block.hash()),
+            pending_info.clone(),
+        ));
+        let ctx = LedgerContext::empty();
+        ctx.ledger.enable_incoming_index();
+
+        insert(&ctx.ledger, &mut block, &instructions);
+
+        let txn = ctx.ledger.read_txn();
+        let incoming: Vec<_> = ctx
+            .ledger
+            .store
+            .incoming
+            .iter(&txn, &destination, 0)
+            .collect();
+        assert_eq!(
+            incoming,
+            vec![IncomingSend {
+                hash: block.hash(),
+                timestamp: instructions.set_sideband.timestamp,
+                source: pending_info.source,
+                amount: pending_info.amount,
+            }]
+        );
+    }
+
     fn insert(
         ledger: &Ledger,
         block: &mut Block,
@@ -27,6 +27,7 @@ impl<'a> RollbackInstructionsExecutor<'a> {
     pub(crate) fn execute(&mut self) {
         self.update_pending_table();
         self.update_account_table();
+        self.update_incoming_index();
         self.update_block_table();
         self.roll_back_representative_cache();
         self.ledger
@@ -46,11 +47,40 @@ impl<'a> RollbackInstructionsExecutor<'a> {
             .block
             .del(self.txn, &self.instructions.block_hash);
 
+        if self.ledger.block_height_index_enabled() {
+            // The rolled back block is always the head of the account
+            self.ledger.store.block_height.del(
+                self.txn,
+                &self.instructions.account,
+                self.instructions.old_account_info.block_count,
+            );
+        }
+
         if let Some(hash) = self.instructions.clear_successor {
             self.ledger.store.block.successor_clear(self.txn, &hash);
         }
     }
 
+    fn update_incoming_index(&mut self) {
+        if !self.ledger.incoming_index_enabled() {
+            return;
+        }
+        if let Some(pending_key) = &self.instructions.remove_pending {
+            let block = self
+                .ledger
+                .store
+                .block
+                .get(self.txn, &self.instructions.block_hash)
+                .expect("rolled back block not found");
+            self.ledger.store.incoming.del(
+                self.txn,
+                &pending_key.receiving_account,
+                block.timestamp(),
+                &pending_key.send_block_hash,
+            );
+        }
+    }
+
     fn update_account_table(&mut self) {
         self.ledger.update_account(
             self.txn,
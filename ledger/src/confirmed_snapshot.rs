@@ -0,0 +1,220 @@
+use crate::Ledger;
+use rsban_core::{
+    Account, AccountInfo, Amount, BlockHash, BlockSideband, ConfirmationHeightInfo, PendingInfo,
+    PendingKey, PublicKey,
+};
+use rsban_store_lmdb::{LmdbStore, LmdbWriteTransaction, Transaction};
+use std::collections::HashMap;
+
+/// What was written by [`ConfirmedSnapshotWriter`]
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct ConfirmedSnapshotStats {
+    pub accounts: u64,
+    pub blocks: u64,
+    pub pending: u64,
+    pub pruned: u64,
+    pub representatives: u64,
+}
+
+/// Copies the cemented part of a ledger into another store. Blocks above the
+/// confirmation height of an account are left out and the account, pending
+/// and representative weight tables are recalculated for that cut.
+/// Local tables like peers, online weight and final votes are not copied.
+pub struct ConfirmedSnapshotWriter<'a> {
+    ledger: &'a Ledger,
+    target: &'a LmdbStore,
+}
+
+impl<'a> ConfirmedSnapshotWriter<'a> {
+    pub fn new(ledger: &'a Ledger, target: &'a LmdbStore) -> Self {
+        Self { ledger, target }
+    }
+
+    pub fn write(&self) -> anyhow::Result<ConfirmedSnapshotStats> {
+        let txn = self.ledger.read_txn();
+        let mut target_txn = self.target.tx_begin_write();
+        let mut stats = ConfirmedSnapshotStats::default();
+
+        let rep_weights = self.write_accounts(&txn, &mut target_txn, &mut stats)?;
+        self.write_pending(&txn, &mut target_txn, &mut stats)?;
+        self.write_pruned(&txn, &mut target_txn, &mut stats);
+
+        for (representative, weight) in rep_weights {
+            if !weight.is_zero() {
+                self.target
+                    .rep_weight
+                    .put(&mut target_txn, representative, weight);
+                stats.representatives += 1;
+            }
+        }
+
+        target_txn.commit();
+        Ok(stats)
+    }
+
+    fn write_accounts(
+        &self,
+        txn: &dyn Transaction,
+        target_txn: &mut LmdbWriteTransaction,
+        stats: &mut ConfirmedSnapshotStats,
+    ) -> anyhow::Result<HashMap<PublicKey, Amount>> {
+        let mut rep_weights: HashMap<PublicKey, Amount> = HashMap::new();
+        let mut it = self.ledger.store.confirmation_height.begin(txn);
+        while let Some((account, conf_height)) = it.current() {
+            if conf_height.height > 0 {
+                stats.blocks += self.write_chain(txn, target_txn, conf_height)?;
+                let info = self.account_info_at(txn, account, conf_height)?;
+                self.target.account.put(target_txn, account, &info);
+                self.target
+                    .confirmation_height
+                    .put(target_txn, account, conf_height);
+                let weight = rep_weights.entry(info.representative).or_default();
+                *weight = weight.wrapping_add(info.balance);
+                stats.accounts += 1;
+            }
+            it.next();
+        }
+        Ok(rep_weights)
+    }
+
+    /// Copies all blocks up to the confirmed frontier. Blocks keep their sideband,
+    /// only the successor of the frontier is cleared
+    fn write_chain(
+        &self,
+        txn: &dyn Transaction,
+        target_txn: &mut LmdbWriteTransaction,
+        conf_height: &ConfirmationHeightInfo,
+    ) -> anyhow::Result<u64> {
+        let mut block = self
+            .ledger
+            .get_block(txn, &conf_height.frontier)
+            .ok_or_else(|| anyhow!("confirmed frontier {} not found", conf_height.frontier))?;
+        block.set_sideband(BlockSideband {
+            successor: BlockHash::zero(),
+            ..block.sideband().clone()
+        });
+
+        let mut count = 0;
+        loop {
+            self.target
+                .block
+                .raw_put(target_txn, &block.serialize_with_sideband(), &block.hash());
+            count += 1;
+            // Pruned blocks are missing. The pruned table gets copied as a whole
+            match self.ledger.get_block(txn, &block.previous()) {
+                Some(previous) => block = previous,
+                None => break,
+            }
+        }
+        Ok(count)
+    }
+
+    fn account_info_at(
+        &self,
+        txn: &dyn Transaction,
+        account: &Account,
+        conf_height: &ConfirmationHeightInfo,
+    ) -> anyhow::Result<AccountInfo> {
+        let info = self
+            .ledger
+            .account_info(txn, account)
+            .ok_or_else(|| anyhow!("account {} not found", account.encode_account()))?;
+        let frontier = self
+            .ledger
+            .get_block(txn, &conf_height.frontier)
+            .ok_or_else(|| anyhow!("confirmed frontier {} not found", conf_height.frontier))?;
+        let representative = if info.head == conf_height.frontier {
+            info.representative
+        } else {
+            self.ledger
+                .block_representative(txn, &frontier)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "representative of confirmed frontier {} not found",
+                        conf_height.frontier
+                    )
+                })?
+        };
+
+        Ok(AccountInfo {
+            head: conf_height.frontier,
+            representative,
+            open_block: info.open_block,
+            balance: frontier.balance(),
+            modified: frontier.timestamp(),
+            block_count: conf_height.height,
+            epoch: frontier.epoch(),
+        })
+    }
+
+    /// Writes all sends that are confirmed but not received by a confirmed block
+    fn write_pending(
+        &self,
+        txn: &dyn Transaction,
+        target_txn: &mut LmdbWriteTransaction,
+        stats: &mut ConfirmedSnapshotStats,
+    ) -> anyhow::Result<()> {
+        let confirmed = self.ledger.confirmed();
+        let mut it = self.ledger.store.pending.begin(txn);
+        while let Some((key, info)) = it.current() {
+            if confirmed.block_exists_or_pruned(txn, &key.send_block_hash) {
+                self.target.pending.put(target_txn, key, info);
+                stats.pending += 1;
+            }
+            it.next();
+        }
+
+        // Sends that were received by blocks which are not cemented yet
+        for (account, info) in self.ledger.store.account.iter(txn) {
+            let confirmed_height = self
+                .ledger
+                .store
+                .confirmation_height
+                .get(txn, &account)
+                .unwrap_or_default()
+                .height;
+            let mut hash = info.head;
+            while let Some(block) = self.ledger.get_block(txn, &hash) {
+                if block.height() <= confirmed_height {
+                    break;
+                }
+                if let Some(source) = block.source() {
+                    if confirmed.block_exists_or_pruned(txn, &source) {
+                        let source_account = self
+                            .ledger
+                            .any()
+                            .block_account(txn, &source)
+                            .ok_or_else(|| anyhow!("sender of pruned send {} unknown", source))?;
+                        let amount = self
+                            .ledger
+                            .any()
+                            .block_amount_for(txn, &block)
+                            .ok_or_else(|| anyhow!("amount of receive {} unknown", hash))?;
+                        self.target.pending.put(
+                            target_txn,
+                            &PendingKey::new(account, source),
+                            &PendingInfo::new(source_account, amount, block.source_epoch()),
+                        );
+                        stats.pending += 1;
+                    }
+                }
+                hash = block.previous();
+            }
+        }
+        Ok(())
+    }
+
+    fn write_pruned(
+        &self,
+        txn: &dyn Transaction,
+        target_txn: &mut LmdbWriteTransaction,
+        stats: &mut ConfirmedSnapshotStats,
+    ) {
+        let mut it = self.ledger.store.pruned.begin(txn);
+        while let Some((hash, _)) = it.current() {
+            self.target.pruned.put(target_txn, hash);
+            stats.pruned += 1;
+            it.next();
+        }
+    }
+}
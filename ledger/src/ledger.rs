@@ -3,29 +3,33 @@ use crate::{
     block_cementer::BlockCementer,
     block_insertion::{BlockInserter, BlockValidatorFactory},
     ledger_set_confirmed::LedgerSetConfirmed,
-    BlockRollbackPerformer, GenerateCacheFlags, LedgerConstants, LedgerSetAny, RepWeightCache,
-    RepWeightsUpdater, RepresentativeBlockFinder, WriteGuard, WriteQueue,
+    AccountBlockFinder, AccountHistoryPoint, BlockRollbackPerformer, GenerateCacheFlags,
+    LedgerConstants, LedgerSetAny, PruningPolicy, RepWeightCache, RepWeightsUpdater,
+    RepresentativeBlockFinder, WriteGuard, WriteQueue,
 };
 use rand::{thread_rng, Rng};
 use rsban_core::{
+    serialized_block_size,
     utils::{seconds_since_epoch, ContainerInfo},
-    Account, AccountInfo, Amount, Block, BlockHash, BlockSubType, ConfirmationHeightInfo,
-    DependentBlocks, Epoch, Link, PendingInfo, PendingKey, PublicKey, Root, SavedBlock,
+    Account, AccountInfo, Amount, Block, BlockHash, BlockSideband, BlockSubType,
+    ConfirmationHeightInfo, DependentBlocks, Epoch, Link, PendingInfo, PendingKey, PublicKey, Root,
+    SavedBlock,
 };
 use rsban_store_lmdb::{
     ConfiguredAccountDatabaseBuilder, ConfiguredBlockDatabaseBuilder,
     ConfiguredConfirmationHeightDatabaseBuilder, ConfiguredPeersDatabaseBuilder,
-    ConfiguredPendingDatabaseBuilder, ConfiguredPrunedDatabaseBuilder, LedgerCache,
-    LmdbAccountStore, LmdbBlockStore, LmdbConfirmationHeightStore, LmdbEnv, LmdbFinalVoteStore,
-    LmdbOnlineWeightStore, LmdbPeerStore, LmdbPendingStore, LmdbPrunedStore, LmdbReadTransaction,
-    LmdbRepWeightStore, LmdbStore, LmdbVersionStore, LmdbWriteTransaction, Transaction,
+    ConfiguredPendingDatabaseBuilder, ConfiguredPrunedDatabaseBuilder, LedgerCache, LedgerIndex,
+    LmdbAccountStore, LmdbBlockHeightStore, LmdbBlockStore, LmdbConfirmationHeightStore,
+    LmdbDelegatorStore, LmdbEnv, LmdbFinalVoteStore, LmdbIncomingStore, LmdbOnlineWeightStore,
+    LmdbPeerStore, LmdbPendingStore, LmdbPrunedStore, LmdbReadTransaction, LmdbRepWeightStore,
+    LmdbStore, LmdbVersionStore, LmdbWriteTransaction, Transaction,
 };
 use std::{
     collections::HashMap,
     net::SocketAddrV6,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex, RwLock,
     },
     time::{Duration, SystemTime},
 };
@@ -104,6 +108,9 @@ pub struct Ledger {
     pub constants: LedgerConstants,
     pub observer: Arc<dyn LedgerObserver>,
     pruning: AtomicBool,
+    /// Enabled indexes, in the order of `LedgerIndex::ALL`
+    indexes: [AtomicBool; LedgerIndex::ALL.len()],
+    pruning_policy: RwLock<PruningPolicy>,
     pub write_queue: Arc<WriteQueue>,
 }
 
@@ -194,6 +201,9 @@ impl NullLedgerBuilder {
             pruned: Arc::new(LmdbPrunedStore::new(env.clone()).unwrap()),
             rep_weight: Arc::new(LmdbRepWeightStore::new(env.clone()).unwrap()),
             version: Arc::new(LmdbVersionStore::new(env.clone()).unwrap()),
+            delegators: Arc::new(LmdbDelegatorStore::new(env.clone()).unwrap()),
+            block_height: Arc::new(LmdbBlockHeightStore::new(env.clone()).unwrap()),
+            incoming: Arc::new(LmdbIncomingStore::new(env.clone()).unwrap()),
         };
         Ledger::new(
             Arc::new(store),
@@ -236,11 +246,23 @@ impl Ledger {
             constants,
             observer: Arc::new(NullLedgerObserver::new()),
             pruning: AtomicBool::new(false),
+            indexes: Default::default(),
+            pruning_policy: RwLock::new(PruningPolicy::new()),
             write_queue: Arc::new(WriteQueue::new()),
         };
 
         ledger.initialize(&GenerateCacheFlags::new())?;
 
+        // Every process that writes to the ledger keeps the existing indexes up to date
+        {
+            let txn = ledger.read_txn();
+            for index in LedgerIndex::ALL {
+                if ledger.store.index_built(&txn, index) {
+                    ledger.indexes[index as usize].store(true, Ordering::SeqCst);
+                }
+            }
+        }
+
         Ok(ledger)
     }
 
@@ -258,33 +280,63 @@ impl Ledger {
 
     fn initialize(&mut self, generate_cache: &GenerateCacheFlags) -> anyhow::Result<()> {
         if self.store.account.begin(&self.read_txn()).is_end() {
+            if self.store.is_read_only() {
+                bail!("the ledger is empty and cannot be initialized in read-only mode");
+            }
             self.add_genesis_block(&mut self.rw_txn());
         }
 
+        let rep_weights = self.generate_cache(generate_cache, &self.store.cache);
+        self.rep_weights_updater.copy_from(&rep_weights);
+        Ok(())
+    }
+
+    /// Recalculates the cached counters and representative weights from the store.
+    /// This is needed when the store is opened read-only while another process writes to it
+    pub fn reload_cache(&self) {
+        let cache = LedgerCache::new();
+        let rep_weights = self.generate_cache(&GenerateCacheFlags::new(), &cache);
+        for (target, source) in [
+            (&self.store.cache.block_count, &cache.block_count),
+            (&self.store.cache.account_count, &cache.account_count),
+            (&self.store.cache.cemented_count, &cache.cemented_count),
+            (&self.store.cache.pruned_count, &cache.pruned_count),
+        ] {
+            target.store(source.load(Ordering::SeqCst), Ordering::SeqCst);
+        }
+        self.rep_weights_updater.replace_all(&rep_weights);
+    }
+
+    fn generate_cache(
+        &self,
+        generate_cache: &GenerateCacheFlags,
+        cache: &LedgerCache,
+    ) -> HashMap<PublicKey, Amount> {
+        let rep_weights: Mutex<HashMap<PublicKey, Amount>> = Mutex::new(HashMap::new());
         if generate_cache.reps || generate_cache.account_count || generate_cache.block_count {
             self.store.account.for_each_par(&|_txn, mut i, n| {
                 let mut block_count = 0;
                 let mut account_count = 0;
-                let mut rep_weights: HashMap<PublicKey, Amount> = HashMap::new();
+                let mut weights: HashMap<PublicKey, Amount> = HashMap::new();
                 while !i.eq(&n) {
                     let info = i.current().unwrap().1;
                     block_count += info.block_count;
                     account_count += 1;
                     if !info.balance.is_zero() {
-                        let total = rep_weights.entry(info.representative).or_default();
+                        let total = weights.entry(info.representative).or_default();
                         *total += info.balance;
                     }
                     i.next();
                 }
-                self.store
-                    .cache
-                    .block_count
-                    .fetch_add(block_count, Ordering::SeqCst);
-                self.store
-                    .cache
+                cache.block_count.fetch_add(block_count, Ordering::SeqCst);
+                cache
                     .account_count
                     .fetch_add(account_count, Ordering::SeqCst);
-                self.rep_weights_updater.copy_from(&rep_weights);
+                let mut rep_weights = rep_weights.lock().unwrap();
+                for (rep, weight) in weights {
+                    let total = rep_weights.entry(rep).or_default();
+                    *total = total.wrapping_add(weight);
+                }
             });
         }
 
@@ -297,20 +349,18 @@ impl Ledger {
                         cemented_count += i.current().unwrap().1.height;
                         i.next();
                     }
-                    self.store
-                        .cache
+                    cache
                         .cemented_count
                         .fetch_add(cemented_count, Ordering::SeqCst);
                 });
         }
 
         let transaction = self.store.tx_begin_read();
-        self.store
-            .cache
+        cache
             .pruned_count
             .fetch_add(self.store.pruned.count(&transaction), Ordering::SeqCst);
 
-        Ok(())
+        rep_weights.into_inner().unwrap()
     }
 
     fn add_genesis_block(&self, txn: &mut LmdbWriteTransaction) {
@@ -376,6 +426,60 @@ impl Ledger {
         self.pruning.store(true, Ordering::SeqCst);
     }
 
+    pub fn pruning_policy(&self) -> PruningPolicy {
+        self.pruning_policy.read().unwrap().clone()
+    }
+
+    pub fn set_pruning_policy(&self, policy: PruningPolicy) {
+        *self.pruning_policy.write().unwrap() = policy;
+    }
+
+    pub fn index_enabled(&self, index: LedgerIndex) -> bool {
+        self.indexes[index as usize].load(Ordering::SeqCst)
+    }
+
+    /// Keeps the index up to date from now on. The index gets built first if
+    /// it doesn't exist yet, in which case the number of indexed entries is returned.
+    pub fn enable_index(&self, index: LedgerIndex) -> Option<u64> {
+        let built = if self.store.index_built(&self.read_txn(), index) {
+            None
+        } else {
+            Some(self.store.rebuild_index(&mut self.rw_txn(), index))
+        };
+        self.indexes[index as usize].store(true, Ordering::SeqCst);
+        built
+    }
+
+    pub fn delegators_index_enabled(&self) -> bool {
+        self.index_enabled(LedgerIndex::Delegators)
+    }
+
+    /// Keeps the delegators index up to date from now on and returns the
+    /// number of indexed accounts if the index had to be built first.
+    pub fn enable_delegators_index(&self) -> Option<u64> {
+        self.enable_index(LedgerIndex::Delegators)
+    }
+
+    pub fn block_height_index_enabled(&self) -> bool {
+        self.index_enabled(LedgerIndex::BlockHeight)
+    }
+
+    /// Keeps the block height index up to date from now on and returns the
+    /// number of indexed blocks if the index had to be built first.
+    pub fn enable_block_height_index(&self) -> Option<u64> {
+        self.enable_index(LedgerIndex::BlockHeight)
+    }
+
+    pub fn incoming_index_enabled(&self) -> bool {
+        self.index_enabled(LedgerIndex::Incoming)
+    }
+
+    /// Keeps the incoming sends index up to date from now on and returns the
+    /// number of indexed sends if the index had to be built first.
+    pub fn enable_incoming_index(&self) -> Option<u64> {
+        self.enable_index(LedgerIndex::Incoming)
+    }
+
     pub fn bootstrap_weight_max_blocks(&self) -> u64 {
         self.rep_weights.bootstrap_weight_max_blocks()
     }
@@ -533,6 +637,19 @@ impl Ledger {
                 .account_count
                 .fetch_sub(1, Ordering::SeqCst);
         }
+
+        if self.delegators_index_enabled() {
+            if !old_info.head.is_zero() {
+                self.store
+                    .delegators
+                    .del(txn, &old_info.representative, account);
+            }
+            if !new_info.head.is_zero() {
+                self.store
+                    .delegators
+                    .put(txn, &new_info.representative, account, new_info.balance);
+            }
+        }
     }
 
     pub fn pruning_action(
@@ -541,8 +658,19 @@ impl Ledger {
         hash: &BlockHash,
         batch_size: u64,
     ) -> u64 {
+        self.prune_chain(txn, hash, batch_size).0
+    }
+
+    /// Like `pruning_action`, but also returns the serialized bytes of the pruned blocks
+    pub fn prune_chain(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        hash: &BlockHash,
+        batch_size: u64,
+    ) -> (u64, u64) {
         let mut pruned_count = 0;
-        let mut hash = *hash;
+        let mut bytes = 0;
+        let mut hash = self.pruning_start(txn, hash);
         let genesis_hash = self.constants.genesis_block.hash();
 
         while !hash.is_zero() && hash != genesis_hash {
@@ -552,6 +680,7 @@ impl Ledger {
                 self.store.pruned.put(txn, &hash);
                 hash = block.previous();
                 pruned_count += 1;
+                bytes += stored_size(&block);
                 self.store.cache.pruned_count.fetch_add(1, Ordering::SeqCst);
                 if pruned_count % batch_size == 0 {
                     txn.commit();
@@ -564,7 +693,62 @@ impl Ledger {
             }
         }
 
-        pruned_count
+        (pruned_count, bytes)
+    }
+
+    /// Returns the number of blocks and serialized bytes `pruning_action` would remove
+    /// for the given hash, without modifying the ledger
+    pub fn pruning_preview(&self, txn: &dyn Transaction, hash: &BlockHash) -> (u64, u64) {
+        let mut blocks = 0;
+        let mut bytes = 0;
+        let mut hash = self.pruning_start(txn, hash);
+        let genesis_hash = self.constants.genesis_block.hash();
+
+        while !hash.is_zero() && hash != genesis_hash {
+            let Some(block) = self.any().get_block(txn, &hash) else {
+                break;
+            };
+            blocks += 1;
+            bytes += stored_size(&block);
+            hash = block.previous();
+        }
+
+        (blocks, bytes)
+    }
+
+    /// Applies the pruning policy to a pruning target. Returns the first block of the
+    /// account chain that may be pruned or zero if nothing may be pruned
+    fn pruning_start(&self, txn: &dyn Transaction, hash: &BlockHash) -> BlockHash {
+        let policy = self.pruning_policy.read().unwrap();
+        if policy.is_empty() {
+            return *hash;
+        }
+        let Some(mut block) = self.any().get_block(txn, hash) else {
+            return *hash;
+        };
+        let account = block.account();
+        if policy.is_kept(&account) {
+            return BlockHash::zero();
+        }
+        let depth = match policy.depth(&account) {
+            Some(depth) if depth != 0 => depth,
+            _ => return *hash,
+        };
+
+        let confirmed_height = self
+            .store
+            .confirmation_height
+            .get(txn, &account)
+            .map(|info| info.height)
+            .unwrap_or_default();
+        let max_height = confirmed_height.saturating_sub(depth);
+        while block.height() > max_height {
+            match self.any().get_block(txn, &block.previous()) {
+                Some(previous) => block = previous,
+                None => return BlockHash::zero(),
+            }
+        }
+        block.hash()
     }
 
     pub fn dependents_confirmed(&self, txn: &dyn Transaction, block: &SavedBlock) -> bool {
@@ -605,6 +789,57 @@ impl Ledger {
         BlockRollbackPerformer::new(self, txn).roll_back(block)
     }
 
+    /// Returns the block that defines the state of the account at the given point in its history
+    pub fn account_block_at(
+        &self,
+        txn: &dyn Transaction,
+        account: &Account,
+        point: AccountHistoryPoint,
+    ) -> Option<SavedBlock> {
+        AccountBlockFinder::new(self, txn, *account)?.find(point)
+    }
+
+    /// Returns the hash of the block at the given height of the account. The
+    /// block itself may be pruned.
+    pub fn account_hash_at_height(
+        &self,
+        txn: &dyn Transaction,
+        account: &Account,
+        height: u64,
+    ) -> Option<BlockHash> {
+        AccountBlockFinder::new(self, txn, *account)?.hash_at_height(height)
+    }
+
+    pub fn account_balance_at(
+        &self,
+        txn: &dyn Transaction,
+        account: &Account,
+        point: AccountHistoryPoint,
+    ) -> Option<Amount> {
+        self.account_block_at(txn, account, point)
+            .map(|block| block.balance())
+    }
+
+    pub fn account_representative_at(
+        &self,
+        txn: &dyn Transaction,
+        account: &Account,
+        point: AccountHistoryPoint,
+    ) -> Option<PublicKey> {
+        let block = self.account_block_at(txn, account, point)?;
+        self.block_representative(txn, &block)
+    }
+
+    /// Returns the representative of the account right after the given block
+    pub fn block_representative(
+        &self,
+        txn: &dyn Transaction,
+        block: &SavedBlock,
+    ) -> Option<PublicKey> {
+        let rep_block_hash = self.representative_block_hash(txn, &block.hash());
+        self.get_block(txn, &rep_block_hash)?.representative_field()
+    }
+
     /// Returns the latest block with representative information
     pub fn representative_block_hash(&self, txn: &dyn Transaction, hash: &BlockHash) -> BlockHash {
         let hash = RepresentativeBlockFinder::new(txn, self.store.as_ref()).find_rep_block(*hash);
@@ -684,3 +919,9 @@ impl Ledger {
             .finish()
     }
 }
+
+/// Size of the serialized block including its type and sideband
+fn stored_size(block: &SavedBlock) -> u64 {
+    let block_type = block.block_type();
+    (1 + serialized_block_size(block_type) + BlockSideband::serialized_size(block_type)) as u64
+}
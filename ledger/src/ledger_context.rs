@@ -8,7 +8,7 @@ use crate::ledger_tests::helpers::AccountBlockFactory;
 
 pub struct LedgerContext {
     pub ledger: Arc<Ledger>,
-    _db_file: TestDbFile,
+    _db_file: Option<TestDbFile>,
 }
 
 impl LedgerContext {
@@ -20,12 +20,25 @@ impl LedgerContext {
         Self::with_constants(LedgerConstants::dev())
     }
 
+    /// Uses an in-memory store instead of a temporary LMDB file
+    pub fn empty_in_memory() -> Self {
+        let store = Arc::new(LmdbStore::new_in_memory());
+        Self::with_store(store, LEDGER_CONSTANTS_STUB.clone(), None)
+    }
+
     pub fn with_constants(constants: LedgerConstants) -> Self {
         let db_file = TestDbFile::random();
         let store = Arc::new(LmdbStore::open(&db_file.path).build().unwrap());
+        Self::with_store(store, constants, Some(db_file))
+    }
+
+    fn with_store(
+        store: Arc<LmdbStore>,
+        constants: LedgerConstants,
+        db_file: Option<TestDbFile>,
+    ) -> Self {
         let rep_weights = Arc::new(RepWeightCache::new());
-        let ledger =
-            Arc::new(Ledger::new(store.clone(), constants, Amount::zero(), rep_weights).unwrap());
+        let ledger = Arc::new(Ledger::new(store, constants, Amount::zero(), rep_weights).unwrap());
 
         LedgerContext {
             ledger,
@@ -0,0 +1,160 @@
+use super::LedgerContext;
+use crate::AccountHistoryPoint;
+use rsban_core::{Account, Amount, BlockHash, BlockSideband, PublicKey, SavedAccountChain};
+
+#[test]
+fn balance_at_height() {
+    let (ctx, chain) = context_with_chain();
+    let txn = ctx.ledger.read_txn();
+
+    for height in 1..=chain.height() {
+        assert_eq!(
+            ctx.ledger.account_balance_at(
+                &txn,
+                &chain.account(),
+                AccountHistoryPoint::Height(height)
+            ),
+            Some(chain.block(height).balance())
+        );
+    }
+    assert_eq!(
+        ctx.ledger
+            .account_balance_at(&txn, &chain.account(), AccountHistoryPoint::Height(0)),
+        None
+    );
+    assert_eq!(
+        ctx.ledger.account_balance_at(
+            &txn,
+            &chain.account(),
+            AccountHistoryPoint::Height(chain.height() + 1)
+        ),
+        None
+    );
+}
+
+#[test]
+fn balance_at_timestamp() {
+    let (ctx, chain) = context_with_chain();
+    assert_balances_at_timestamps(&ctx, &chain);
+}
+
+#[test]
+fn balance_at_timestamp_with_height_index() {
+    let (ctx, chain) = context_with_chain();
+    ctx.ledger.enable_block_height_index();
+    assert_balances_at_timestamps(&ctx, &chain);
+}
+
+#[test]
+fn representative_at() {
+    let (ctx, chain) = context_with_chain();
+    let txn = ctx.ledger.read_txn();
+    let account = chain.account();
+
+    assert_eq!(
+        ctx.ledger
+            .account_representative_at(&txn, &account, AccountHistoryPoint::Height(2)),
+        chain.representative_at_height(2)
+    );
+    assert_eq!(
+        ctx.ledger
+            .account_representative_at(&txn, &account, AccountHistoryPoint::Timestamp(450)),
+        Some(PublicKey::from(1000))
+    );
+}
+
+#[test]
+fn hash_at_height_of_pruned_blocks() {
+    let (ctx, chain) = context_with_chain();
+    prune_first_blocks(&ctx, &chain);
+    assert_hashes_at_heights(&ctx, &chain);
+}
+
+#[test]
+fn hash_at_height_of_pruned_blocks_with_height_index() {
+    let (ctx, chain) = context_with_chain();
+    ctx.ledger.enable_block_height_index();
+    prune_first_blocks(&ctx, &chain);
+    assert_hashes_at_heights(&ctx, &chain);
+}
+
+#[test]
+fn unknown_account() {
+    let ctx = LedgerContext::empty_in_memory();
+    let txn = ctx.ledger.read_txn();
+
+    assert_eq!(
+        ctx.ledger.account_balance_at(
+            &txn,
+            &Account::from(42),
+            AccountHistoryPoint::Timestamp(u64::MAX)
+        ),
+        None
+    );
+}
+
+fn assert_balances_at_timestamps(ctx: &LedgerContext, chain: &SavedAccountChain) {
+    let txn = ctx.ledger.read_txn();
+    let balance_at = |timestamp| {
+        ctx.ledger.account_balance_at(
+            &txn,
+            &chain.account(),
+            AccountHistoryPoint::Timestamp(timestamp),
+        )
+    };
+
+    assert_eq!(balance_at(99), None);
+    assert_eq!(balance_at(100), Some(chain.block(1).balance()));
+    assert_eq!(balance_at(250), Some(chain.block(2).balance()));
+    assert_eq!(balance_at(300), Some(chain.block(3).balance()));
+    assert_eq!(balance_at(u64::MAX), Some(chain.block(4).balance()));
+}
+
+fn prune_first_blocks(ctx: &LedgerContext, chain: &SavedAccountChain) {
+    let mut txn = ctx.ledger.rw_txn();
+    for height in 1..=2 {
+        let hash = chain.block(height).hash();
+        ctx.ledger.store.block.del(&mut txn, &hash);
+        ctx.ledger.store.pruned.put(&mut txn, &hash);
+    }
+    txn.commit();
+}
+
+fn assert_hashes_at_heights(ctx: &LedgerContext, chain: &SavedAccountChain) {
+    let txn = ctx.ledger.read_txn();
+    for height in 1..=chain.height() {
+        assert_eq!(
+            ctx.ledger
+                .account_hash_at_height(&txn, &chain.account(), height),
+            Some(chain.block(height).hash())
+        );
+    }
+}
+
+/// Saves an account chain with a block every 100 seconds
+fn context_with_chain() -> (LedgerContext, SavedAccountChain) {
+    let ctx = LedgerContext::empty_in_memory();
+    let mut chain = SavedAccountChain::new_opened_chain();
+    chain.add_legacy_send_to(Account::from(1), Amount::raw(1));
+    chain.add_legacy_change(PublicKey::from(1000));
+    chain.add_legacy_send_to(Account::from(1), Amount::raw(1));
+
+    let mut txn = ctx.ledger.rw_txn();
+    for (i, block) in chain.blocks().iter().enumerate() {
+        // the successor is set when the next block is put
+        let mut block = block.clone();
+        block.set_sideband(BlockSideband {
+            successor: BlockHash::zero(),
+            timestamp: (i as u64 + 1) * 100,
+            ..block.sideband().clone()
+        });
+        ctx.ledger.store.block.put(&mut txn, &block);
+    }
+    ctx.ledger
+        .store
+        .account
+        .put(&mut txn, &chain.account(), &chain.account_info());
+    txn.commit();
+
+    (ctx, chain)
+}
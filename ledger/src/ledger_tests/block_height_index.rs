@@ -0,0 +1,28 @@
+use super::LedgerContext;
+use crate::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+
+#[test]
+fn build_index_on_enable() {
+    let ctx = LedgerContext::empty();
+
+    assert_eq!(ctx.ledger.enable_block_height_index(), Some(1));
+    assert!(ctx.ledger.block_height_index_enabled());
+    // already built
+    assert_eq!(ctx.ledger.enable_block_height_index(), None);
+
+    let txn = ctx.ledger.read_txn();
+    assert_eq!(
+        ctx.ledger
+            .store
+            .block_height
+            .get(&txn, &DEV_GENESIS_ACCOUNT, 1),
+        Some(*DEV_GENESIS_HASH)
+    );
+    assert_eq!(
+        ctx.ledger
+            .store
+            .block_height
+            .get(&txn, &DEV_GENESIS_ACCOUNT, 2),
+        None
+    );
+}
@@ -0,0 +1,93 @@
+use super::LedgerContext;
+use crate::{ConfirmedSnapshotStats, ConfirmedSnapshotWriter, DEV_GENESIS_ACCOUNT};
+use rsban_core::{
+    Amount, BlockHash, BlockSideband, ConfirmationHeightInfo, PendingInfo, PendingKey,
+    SavedAccountChain,
+};
+use rsban_store_lmdb::LmdbStore;
+
+#[test]
+fn only_cemented_state_is_written() {
+    let ctx = LedgerContext::empty_in_memory();
+    let mut receiver = SavedAccountChain::new();
+    let mut sender = SavedAccountChain::new_opened_chain();
+    sender.add_legacy_send_to(receiver.account(), Amount::raw(10));
+    sender.add_legacy_send_to(receiver.account(), Amount::raw(20));
+    receiver.add_legacy_open_from_account_block(&sender, 2);
+    receiver.add_legacy_receive_from_account_block(&sender, 3);
+    save_chain(&ctx, &sender);
+    save_chain(&ctx, &receiver);
+    let mut txn = ctx.ledger.rw_txn();
+    ctx.ledger.store.confirmation_height.put(
+        &mut txn,
+        &sender.account(),
+        &ConfirmationHeightInfo::new(2, sender.block(2).hash()),
+    );
+    txn.commit();
+
+    let target = LmdbStore::new_in_memory();
+    let stats = ConfirmedSnapshotWriter::new(&ctx.ledger, &target)
+        .write()
+        .unwrap();
+
+    assert_eq!(
+        stats,
+        ConfirmedSnapshotStats {
+            accounts: 2,
+            blocks: 3,
+            pending: 1,
+            pruned: 0,
+            representatives: 2,
+        }
+    );
+
+    let txn = target.tx_begin_read();
+    let info = target.account.get(&txn, &sender.account()).unwrap();
+    assert_eq!(info.head, sender.block(2).hash());
+    assert_eq!(info.block_count, 2);
+    assert_eq!(info.balance, sender.block(2).balance());
+    assert!(target.account.get(&txn, &receiver.account()).is_none());
+    assert!(target.account.get(&txn, &DEV_GENESIS_ACCOUNT).is_some());
+
+    let frontier = target.block.get(&txn, &sender.block(2).hash()).unwrap();
+    assert_eq!(frontier.successor(), None);
+    assert!(!target.block.exists(&txn, &sender.block(3).hash()));
+    assert!(!target.block.exists(&txn, &receiver.open()));
+
+    // The open block of the receiver isn't cemented, so the send is still receivable
+    assert_eq!(
+        target.pending.get(
+            &txn,
+            &PendingKey::new(receiver.account(), sender.block(2).hash())
+        ),
+        Some(PendingInfo::new(
+            sender.account(),
+            Amount::raw(10),
+            sender.block(2).epoch()
+        ))
+    );
+    assert_eq!(
+        target
+            .rep_weight
+            .get(&txn, &sender.account_info().representative),
+        Some(sender.block(2).balance())
+    );
+}
+
+fn save_chain(ctx: &LedgerContext, chain: &SavedAccountChain) {
+    let mut txn = ctx.ledger.rw_txn();
+    for block in chain.blocks() {
+        // the successor is set when the next block is put
+        let mut block = block.clone();
+        block.set_sideband(BlockSideband {
+            successor: BlockHash::zero(),
+            ..block.sideband().clone()
+        });
+        ctx.ledger.store.block.put(&mut txn, &block);
+    }
+    ctx.ledger
+        .store
+        .account
+        .put(&mut txn, &chain.account(), &chain.account_info());
+    txn.commit();
+}
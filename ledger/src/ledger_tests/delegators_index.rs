@@ -0,0 +1,131 @@
+use super::LedgerContext;
+use crate::{ledger_constants::LEDGER_CONSTANTS_STUB, Ledger, RepWeightCache};
+use rsban_core::{Account, AccountInfo, Amount, BlockHash, PublicKey};
+use rsban_store_lmdb::LedgerIndex;
+use std::sync::Arc;
+
+#[test]
+fn build_index_on_enable() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.ledger.constants.genesis_account;
+
+    assert_eq!(ctx.ledger.enable_delegators_index(), Some(1));
+    assert!(ctx.ledger.delegators_index_enabled());
+    // already built
+    assert_eq!(ctx.ledger.enable_delegators_index(), None);
+
+    let txn = ctx.ledger.read_txn();
+    let delegators: Vec<_> = ctx
+        .ledger
+        .store
+        .delegators
+        .iter(&txn, &genesis.into(), &Account::zero())
+        .collect();
+    assert_eq!(delegators, vec![(genesis, Amount::MAX)]);
+}
+
+#[test]
+fn update_index_when_account_changes() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_delegators_index();
+    let mut txn = ctx.ledger.rw_txn();
+    let account = Account::from(42);
+    let rep1 = PublicKey::from(1);
+    let rep2 = PublicKey::from(2);
+    let opened = AccountInfo {
+        head: BlockHash::from(100),
+        open_block: BlockHash::from(100),
+        representative: rep1,
+        balance: Amount::raw(10),
+        block_count: 1,
+        ..Default::default()
+    };
+
+    ctx.ledger
+        .update_account(&mut txn, &account, &AccountInfo::default(), &opened);
+    assert_eq!(
+        ctx.ledger.store.delegators.get(&txn, &rep1, &account),
+        Some(Amount::raw(10))
+    );
+
+    let changed = AccountInfo {
+        head: BlockHash::from(101),
+        representative: rep2,
+        balance: Amount::raw(7),
+        block_count: 2,
+        ..opened.clone()
+    };
+    ctx.ledger
+        .update_account(&mut txn, &account, &opened, &changed);
+    assert_eq!(ctx.ledger.store.delegators.get(&txn, &rep1, &account), None);
+    assert_eq!(
+        ctx.ledger.store.delegators.get(&txn, &rep2, &account),
+        Some(Amount::raw(7))
+    );
+
+    // rollback of the open block
+    ctx.ledger
+        .update_account(&mut txn, &account, &changed, &AccountInfo::default());
+    assert_eq!(ctx.ledger.store.delegators.get(&txn, &rep2, &account), None);
+}
+
+#[test]
+fn index_is_not_maintained_when_disabled() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let account = Account::from(42);
+    let opened = AccountInfo {
+        head: BlockHash::from(100),
+        open_block: BlockHash::from(100),
+        representative: PublicKey::from(1),
+        balance: Amount::raw(10),
+        block_count: 1,
+        ..Default::default()
+    };
+
+    ctx.ledger
+        .update_account(&mut txn, &account, &AccountInfo::default(), &opened);
+
+    assert_eq!(ctx.ledger.store.delegators.count(&txn), 0);
+}
+
+#[test]
+fn enable_existing_index_when_ledger_is_opened() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_delegators_index();
+
+    let ledger = reopen(&ctx);
+
+    assert!(ledger.delegators_index_enabled());
+    assert!(!ledger.block_height_index_enabled());
+}
+
+#[test]
+fn rebuild_interrupted_index() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_delegators_index();
+    let mut txn = ctx.ledger.rw_txn();
+    ctx.ledger
+        .store
+        .version
+        .set_index_rebuilding(&mut txn, LedgerIndex::Delegators, true);
+    txn.commit();
+
+    let ledger = reopen(&ctx);
+
+    assert!(!ledger.delegators_index_enabled());
+    assert_eq!(ledger.enable_delegators_index(), Some(1));
+    assert!(ledger
+        .store
+        .index_built(&ledger.read_txn(), LedgerIndex::Delegators));
+}
+
+fn reopen(ctx: &LedgerContext) -> Ledger {
+    Ledger::new(
+        ctx.ledger.store.clone(),
+        LEDGER_CONSTANTS_STUB.clone(),
+        Amount::zero(),
+        Arc::new(RepWeightCache::new()),
+    )
+    .unwrap()
+}
@@ -0,0 +1,55 @@
+use super::LedgerContext;
+use crate::{ledger_constants::LEDGER_CONSTANTS_STUB, DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsban_core::{Account, AccountInfo, Amount, BlockHash, PublicKey};
+
+#[test]
+fn genesis_is_initialized() {
+    let ctx = LedgerContext::empty_in_memory();
+    let txn = ctx.ledger.read_txn();
+
+    assert!(ctx.ledger.store.is_in_memory());
+    assert!(ctx
+        .ledger
+        .any()
+        .get_block(&txn, &DEV_GENESIS_HASH)
+        .is_some());
+    assert_eq!(
+        ctx.ledger.any().account_balance(&txn, &DEV_GENESIS_ACCOUNT),
+        Some(LEDGER_CONSTANTS_STUB.genesis_amount)
+    );
+    assert_eq!(ctx.ledger.block_count(), 1);
+}
+
+#[test]
+fn committed_changes_are_visible_to_new_readers() {
+    let ctx = LedgerContext::empty_in_memory();
+    let reader = ctx.ledger.read_txn();
+    let account = Account::from(42);
+    let info = AccountInfo {
+        head: BlockHash::from(100),
+        open_block: BlockHash::from(100),
+        representative: PublicKey::from(1),
+        balance: Amount::raw(10),
+        block_count: 1,
+        ..Default::default()
+    };
+
+    let mut txn = ctx.ledger.rw_txn();
+    ctx.ledger
+        .update_account(&mut txn, &account, &AccountInfo::default(), &info);
+    txn.commit();
+
+    assert_eq!(ctx.ledger.account_info(&reader, &account), None);
+    assert_eq!(
+        ctx.ledger.account_info(&ctx.ledger.read_txn(), &account),
+        Some(info)
+    );
+}
+
+#[test]
+fn secondary_indexes_can_be_built() {
+    let ctx = LedgerContext::empty_in_memory();
+
+    assert_eq!(ctx.ledger.enable_delegators_index(), Some(1));
+    assert_eq!(ctx.ledger.enable_block_height_index(), Some(1));
+}
@@ -0,0 +1,55 @@
+use super::LedgerContext;
+use crate::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsban_core::{
+    Account, Amount, BlockDetails, BlockHash, BlockSideband, Epoch, SavedBlock, TestBlockBuilder,
+};
+use rsban_store_lmdb::{IncomingSend, LedgerIndex};
+
+#[test]
+fn rebuild_index_from_block_table() {
+    let ctx = LedgerContext::empty();
+    let destination = Account::from(1000);
+    let send = TestBlockBuilder::state()
+        .account(*DEV_GENESIS_ACCOUNT)
+        .previous(*DEV_GENESIS_HASH)
+        .balance(Amount::MAX - Amount::raw(100))
+        .link(destination)
+        .build();
+    let send = SavedBlock::new(
+        send,
+        BlockSideband {
+            height: 2,
+            timestamp: 1234,
+            successor: BlockHash::zero(),
+            account: *DEV_GENESIS_ACCOUNT,
+            balance: Amount::MAX - Amount::raw(100),
+            details: BlockDetails::new(Epoch::Epoch0, true, false, false),
+            source_epoch: Epoch::Epoch0,
+        },
+    );
+    let mut txn = ctx.ledger.rw_txn();
+    ctx.ledger.store.block.put(&mut txn, &send);
+
+    assert_eq!(
+        ctx.ledger
+            .store
+            .rebuild_index(&mut txn, LedgerIndex::Incoming),
+        1
+    );
+
+    let incoming: Vec<_> = ctx
+        .ledger
+        .store
+        .incoming
+        .iter(&txn, &destination, 0)
+        .collect();
+    assert_eq!(
+        incoming,
+        vec![IncomingSend {
+            hash: send.hash(),
+            timestamp: 1234,
+            source: *DEV_GENESIS_ACCOUNT,
+            amount: Amount::raw(100),
+        }]
+    );
+}
@@ -11,8 +11,15 @@ use rsban_core::{
     TestBlockBuilder, DEV_GENESIS_KEY,
 };
 
+mod account_history;
+mod block_height_index;
+mod confirmed_snapshot;
+mod delegators_index;
 mod empty_ledger;
+mod in_memory_store;
+mod incoming_index;
 mod pruning;
+mod read_only_store;
 mod receivable_iteration;
 mod rollback_legacy_change;
 mod rollback_legacy_receive;
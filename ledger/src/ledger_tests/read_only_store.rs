@@ -0,0 +1,83 @@
+use super::LedgerContext;
+use crate::{ledger_constants::LEDGER_CONSTANTS_STUB, Ledger, RepWeightCache, DEV_GENESIS_HASH};
+use rsban_core::{Account, AccountInfo, Amount, BlockHash, PublicKey};
+use rsban_store_lmdb::{EnvOptions, LmdbStore, TestDbFile};
+use std::sync::Arc;
+
+#[test]
+fn open_ledger_read_only() {
+    let file = TestDbFile::random();
+    {
+        let store = Arc::new(LmdbStore::open(&file.path).build().unwrap());
+        create_ledger(store).unwrap();
+    }
+
+    let ledger = create_ledger(open_read_only(&file)).unwrap();
+
+    assert!(ledger.store.is_read_only());
+    assert_eq!(ledger.block_count(), 1);
+    assert!(ledger
+        .any()
+        .block_exists(&ledger.read_txn(), &DEV_GENESIS_HASH));
+}
+
+#[test]
+fn empty_ledger_cannot_be_opened_read_only() {
+    let file = TestDbFile::random();
+    {
+        LmdbStore::open(&file.path).build().unwrap();
+    }
+
+    assert!(create_ledger(open_read_only(&file)).is_err());
+}
+
+#[test]
+fn reload_cache() {
+    let ctx = LedgerContext::empty_in_memory();
+    let representative = PublicKey::from(1);
+    let info = AccountInfo {
+        head: BlockHash::from(100),
+        open_block: BlockHash::from(100),
+        representative,
+        balance: Amount::raw(10),
+        block_count: 3,
+        ..Default::default()
+    };
+
+    // Simulates a write by another process, which doesn't update the caches
+    let mut txn = ctx.ledger.rw_txn();
+    ctx.ledger
+        .store
+        .account
+        .put(&mut txn, &Account::from(42), &info);
+    txn.commit();
+    assert_eq!(ctx.ledger.account_count(), 1);
+
+    ctx.ledger.reload_cache();
+
+    assert_eq!(ctx.ledger.account_count(), 2);
+    assert_eq!(ctx.ledger.block_count(), 4);
+    assert_eq!(ctx.ledger.weight(&representative), Amount::raw(10));
+}
+
+fn open_read_only(file: &TestDbFile) -> Arc<LmdbStore> {
+    let options = EnvOptions {
+        read_only: true,
+        ..Default::default()
+    };
+    Arc::new(
+        LmdbStore::open(&file.path)
+            .options(&options)
+            .build()
+            .unwrap(),
+    )
+}
+
+fn create_ledger(store: Arc<LmdbStore>) -> anyhow::Result<Ledger> {
+    Ledger::new(
+        store,
+        LEDGER_CONSTANTS_STUB.clone(),
+        Amount::zero(),
+        Arc::new(RepWeightCache::new()),
+    )
+}
@@ -4,9 +4,11 @@ extern crate anyhow;
 #[macro_use]
 extern crate num_derive;
 
+mod account_block_finder;
 mod block_cementer;
 mod block_insertion;
 mod block_rollback;
+mod confirmed_snapshot;
 mod dependent_blocks_finder;
 mod generate_cache_flags;
 mod ledger;
@@ -14,6 +16,7 @@ mod ledger_constants;
 mod ledger_context;
 mod ledger_set_any;
 mod ledger_set_confirmed;
+mod pruning_policy;
 mod rep_weight_cache;
 mod rep_weights_updater;
 mod representative_block_finder;
@@ -22,7 +25,10 @@ mod write_queue;
 #[cfg(test)]
 mod ledger_tests;
 
+pub(crate) use account_block_finder::AccountBlockFinder;
+pub use account_block_finder::AccountHistoryPoint;
 pub(crate) use block_rollback::BlockRollbackPerformer;
+pub use confirmed_snapshot::{ConfirmedSnapshotStats, ConfirmedSnapshotWriter};
 pub use dependent_blocks_finder::*;
 pub use generate_cache_flags::GenerateCacheFlags;
 pub use ledger::*;
@@ -32,6 +38,7 @@ pub use ledger_constants::{
 pub use ledger_context::LedgerContext;
 pub use ledger_set_any::*;
 pub use ledger_set_confirmed::*;
+pub use pruning_policy::PruningPolicy;
 pub use rep_weight_cache::*;
 pub use rep_weights_updater::*;
 pub(crate) use representative_block_finder::RepresentativeBlockFinder;
@@ -0,0 +1,38 @@
+use rsban_core::Account;
+use std::collections::{HashMap, HashSet};
+
+/// Per account exceptions to the global pruning depth and age
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PruningPolicy {
+    keep: HashSet<Account>,
+    depths: HashMap<Account, u64>,
+}
+
+impl PruningPolicy {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The account is never pruned
+    pub fn keep(&mut self, account: Account) {
+        self.keep.insert(account);
+    }
+
+    /// Overrides the number of blocks below the confirmed frontier that are kept for this account.
+    /// 0 means unlimited
+    pub fn set_depth(&mut self, account: Account, depth: u64) {
+        self.depths.insert(account, depth);
+    }
+
+    pub fn is_kept(&self, account: &Account) -> bool {
+        self.keep.contains(account)
+    }
+
+    pub fn depth(&self, account: &Account) -> Option<u64> {
+        self.depths.get(account).cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keep.is_empty() && self.depths.is_empty()
+    }
+}
@@ -29,6 +29,15 @@ impl RepWeightsUpdater {
         }
     }
 
+    /// Replaces all cached weights, for example after they were recalculated from the ledger
+    pub fn replace_all(&self, weights: &HashMap<PublicKey, Amount>) {
+        let mut guard = self.weight_cache.write().unwrap();
+        guard.clear();
+        for (account, amount) in weights {
+            self.put_cache(&mut guard, *account, *amount);
+        }
+    }
+
     fn get(&self, weights: &HashMap<PublicKey, Amount>, account: &PublicKey) -> Amount {
         weights.get(account).cloned().unwrap_or_default()
     }
@@ -126,6 +135,20 @@ mod tests {
         assert_eq!(rep_weights.weight(&account), Amount::from(2));
     }
 
+    #[test]
+    fn replace_all() {
+        let env = Arc::new(LmdbEnv::new_null());
+        let store = Arc::new(LmdbRepWeightStore::new(env).unwrap());
+        let rep_weights = RepWeightCache::new();
+        let rep_weights_updater = RepWeightsUpdater::new(store, Amount::zero(), &rep_weights);
+        rep_weights_updater.representation_put(PublicKey::from(1), Amount::from(1));
+
+        rep_weights_updater.replace_all(&HashMap::from([(PublicKey::from(2), Amount::from(2))]));
+
+        assert_eq!(rep_weights.len(), 1);
+        assert_eq!(rep_weights.weight(&PublicKey::from(2)), Amount::from(2));
+    }
+
     #[test]
     fn delete_rep_weight_of_zero() {
         let representative = PublicKey::from(1);
@@ -0,0 +1,82 @@
+use super::{filter::FilterArgs, rotating_writer::capture_files};
+use anyhow::Result;
+use clap::Parser;
+use rsban_network::ChannelDirection;
+use rsban_node::transport::{MessageRecordingReader, RecordedMessage};
+use serde_json::{json, Value};
+use std::{path::PathBuf, time::UNIX_EPOCH};
+
+#[derive(Parser)]
+pub(crate) struct DecodeArgs {
+    /// Capture file, or a directory with capture files which are decoded from oldest to newest
+    #[arg(long)]
+    input: PathBuf,
+    #[command(flatten)]
+    filter: FilterArgs,
+}
+
+impl DecodeArgs {
+    pub(crate) fn decode(&self) -> Result<()> {
+        let filter = self.filter.to_filter()?;
+        let files = if self.input.is_dir() {
+            capture_files(&self.input)?
+                .into_iter()
+                .map(|(_, path)| path)
+                .collect()
+        } else {
+            vec![self.input.clone()]
+        };
+
+        for file in files {
+            for recorded in MessageRecordingReader::open(&file)? {
+                let recorded = recorded?;
+                if filter.include(&recorded) {
+                    println!("{}", to_json(&recorded));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn to_json(recorded: &RecordedMessage) -> Value {
+    let message = &recorded.message.message;
+    // Most messages print their content as JSON. Everything else is kept as plain text
+    let text = message.to_string();
+    let content = serde_json::from_str::<Value>(text.trim())
+        .unwrap_or_else(|_| Value::String(text.trim().to_owned()));
+
+    json!({
+        "timestamp": recorded
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+            .to_string(),
+        "direction": match recorded.direction {
+            ChannelDirection::Inbound => "in",
+            ChannelDirection::Outbound => "out",
+        },
+        "channel_id": recorded.channel_id.to_string(),
+        "peer": recorded.peer_addr.to_string(),
+        "network": recorded.message.protocol.network.as_str(),
+        "type": message.message_type().as_str(),
+        "message": content,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_to_json() {
+        let json = to_json(&RecordedMessage::new_test_instance());
+        assert_eq!(json["type"], "telemetry_req");
+        assert_eq!(json["direction"], "in");
+        assert_eq!(json["channel_id"], "42");
+        assert_eq!(json["peer"], "[::1]:7071");
+        assert_eq!(json["timestamp"], "1700000000000");
+    }
+}
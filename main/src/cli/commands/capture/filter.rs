@@ -0,0 +1,210 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use rsban_core::Account;
+use rsban_messages::{AscPullAckType, AscPullReqType, HashType, Message, MessageType};
+use rsban_node::transport::RecordedMessage;
+use std::{
+    net::{IpAddr, Ipv6Addr, SocketAddr, SocketAddrV6},
+    str::FromStr,
+};
+
+#[derive(Args, Default)]
+pub(crate) struct FilterArgs {
+    /// Only include messages of the given type (e.g. publish, confirm_ack). Can be repeated
+    #[arg(long = "type")]
+    message_types: Vec<String>,
+    /// Only include messages from/to the given peer (IP address or IP address and port)
+    #[arg(long)]
+    peer: Option<String>,
+    /// Only include messages that reference the given account
+    #[arg(long)]
+    account: Option<String>,
+}
+
+impl FilterArgs {
+    pub(crate) fn to_filter(&self) -> Result<CaptureFilter> {
+        let types = self
+            .message_types
+            .iter()
+            .map(|t| MessageType::from_str(t).map_err(|e| anyhow!("{}: {}", e, t)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let peer = self.peer.as_ref().map(|p| parse_peer(p)).transpose()?;
+
+        let account = self
+            .account
+            .as_ref()
+            .map(|a| Account::decode_account(a).map_err(|_| anyhow!("Invalid account: {}", a)))
+            .transpose()?;
+
+        Ok(CaptureFilter {
+            types,
+            peer,
+            account,
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum PeerFilter {
+    Ip(Ipv6Addr),
+    Endpoint(SocketAddrV6),
+}
+
+fn parse_peer(peer: &str) -> Result<PeerFilter> {
+    if let Ok(addr) = SocketAddr::from_str(peer) {
+        let ip = to_ipv6(addr.ip());
+        return Ok(PeerFilter::Endpoint(SocketAddrV6::new(
+            ip,
+            addr.port(),
+            0,
+            0,
+        )));
+    }
+    let ip = IpAddr::from_str(peer).map_err(|_| anyhow!("Invalid peer: {}", peer))?;
+    Ok(PeerFilter::Ip(to_ipv6(ip)))
+}
+
+fn to_ipv6(ip: IpAddr) -> Ipv6Addr {
+    match ip {
+        IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+        IpAddr::V6(ip) => ip,
+    }
+}
+
+/// Decides which captured messages are written or printed
+#[derive(Default, Debug)]
+pub(crate) struct CaptureFilter {
+    types: Vec<MessageType>,
+    peer: Option<PeerFilter>,
+    account: Option<Account>,
+}
+
+impl CaptureFilter {
+    /// Checks everything that doesn't need the peer address, so that
+    /// messages can be dropped as early as possible
+    pub(crate) fn include_message(&self, message: &Message) -> bool {
+        self.include_message_type(message) && self.include_account(message)
+    }
+
+    pub(crate) fn include(&self, recorded: &RecordedMessage) -> bool {
+        self.include_message(&recorded.message.message) && self.include_peer(&recorded.peer_addr)
+    }
+
+    fn include_message_type(&self, message: &Message) -> bool {
+        self.types.is_empty() || self.types.contains(&message.message_type())
+    }
+
+    fn include_peer(&self, peer_addr: &SocketAddrV6) -> bool {
+        match self.peer {
+            Some(PeerFilter::Ip(ip)) => *peer_addr.ip() == ip,
+            Some(PeerFilter::Endpoint(endpoint)) => *peer_addr == endpoint,
+            None => true,
+        }
+    }
+
+    fn include_account(&self, message: &Message) -> bool {
+        let Some(account) = self.account else {
+            return true;
+        };
+
+        match message {
+            Message::Publish(i) => i.block.account_field() == Some(account),
+            Message::AscPullAck(ack) => match &ack.pull_type {
+                AscPullAckType::Blocks(i) => i
+                    .blocks()
+                    .iter()
+                    .any(|b| b.account_field() == Some(account)),
+                AscPullAckType::AccountInfo(i) => i.account == account,
+                AscPullAckType::Frontiers(i) => i.iter().any(|f| f.account == account),
+            },
+            Message::AscPullReq(req) => match &req.req_type {
+                AscPullReqType::Blocks(i) => {
+                    i.start_type == HashType::Account && account == i.start.into()
+                }
+                AscPullReqType::AccountInfo(i) => {
+                    i.target_type == HashType::Account && account == i.target.into()
+                }
+                AscPullReqType::Frontiers(i) => i.start == account,
+            },
+            Message::BulkPullAccount(i) => i.account == account,
+            Message::ConfirmAck(ack) => ack.vote().voting_account == account.into(),
+            Message::FrontierReq(i) => i.start == account,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_messages::{ConfirmAck, Publish};
+
+    #[test]
+    fn empty_filter_includes_everything() {
+        let filter = FilterArgs::default().to_filter().unwrap();
+        assert!(filter.include(&RecordedMessage::new_test_instance()));
+    }
+
+    #[test]
+    fn filter_by_type() {
+        let filter = FilterArgs {
+            message_types: vec!["publish".to_owned(), "confirm_ack".to_owned()],
+            ..Default::default()
+        }
+        .to_filter()
+        .unwrap();
+
+        assert!(filter.include_message(&Message::Publish(Publish::new_test_instance())));
+        assert!(filter.include_message(&Message::ConfirmAck(ConfirmAck::new_test_instance())));
+        assert!(!filter.include_message(&Message::TelemetryReq));
+    }
+
+    #[test]
+    fn invalid_type() {
+        let args = FilterArgs {
+            message_types: vec!["foo".to_owned()],
+            ..Default::default()
+        };
+        assert!(args.to_filter().is_err());
+    }
+
+    #[test]
+    fn filter_by_peer_ip() {
+        let filter = filter_peer("10.0.0.1");
+        assert!(filter.include_peer(&"[::ffff:10.0.0.1]:7071".parse().unwrap()));
+        assert!(filter.include_peer(&"[::ffff:10.0.0.1]:1234".parse().unwrap()));
+        assert!(!filter.include_peer(&"[::ffff:10.0.0.2]:7071".parse().unwrap()));
+    }
+
+    #[test]
+    fn filter_by_peer_endpoint() {
+        let filter = filter_peer("[::1]:7071");
+        assert!(filter.include_peer(&"[::1]:7071".parse().unwrap()));
+        assert!(!filter.include_peer(&"[::1]:7072".parse().unwrap()));
+    }
+
+    #[test]
+    fn filter_by_account() {
+        let publish = Publish::new_test_instance();
+        let account = publish.block.account_field().unwrap();
+        let filter = FilterArgs {
+            account: Some(account.encode_account()),
+            ..Default::default()
+        }
+        .to_filter()
+        .unwrap();
+
+        assert!(filter.include_message(&Message::Publish(publish)));
+        assert!(!filter.include_message(&Message::TelemetryReq));
+    }
+
+    fn filter_peer(peer: &str) -> CaptureFilter {
+        FilterArgs {
+            peer: Some(peer.to_owned()),
+            ..Default::default()
+        }
+        .to_filter()
+        .unwrap()
+    }
+}
@@ -0,0 +1,35 @@
+use anyhow::Result;
+use clap::{CommandFactory, Parser, Subcommand};
+use decode::DecodeArgs;
+use run::CaptureRunArgs;
+
+pub(crate) mod decode;
+pub(crate) mod filter;
+pub(crate) mod rotating_writer;
+pub(crate) mod run;
+
+#[derive(Subcommand)]
+pub(crate) enum CaptureSubcommands {
+    /// Runs the node and writes all sent and received messages to rotating capture files
+    Run(CaptureRunArgs),
+    /// Prints the messages of capture files as JSON, one message per line
+    Decode(DecodeArgs),
+}
+
+#[derive(Parser)]
+pub(crate) struct CaptureCommand {
+    #[command(subcommand)]
+    pub subcommand: Option<CaptureSubcommands>,
+}
+
+impl CaptureCommand {
+    pub(crate) async fn run(&self) -> Result<()> {
+        match &self.subcommand {
+            Some(CaptureSubcommands::Run(args)) => args.run().await?,
+            Some(CaptureSubcommands::Decode(args)) => args.decode()?,
+            None => CaptureCommand::command().print_long_help()?,
+        }
+
+        Ok(())
+    }
+}
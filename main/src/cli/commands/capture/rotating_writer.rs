@@ -0,0 +1,153 @@
+use anyhow::Result;
+use rsban_node::transport::{MessageRecordingWriter, RecordedMessage};
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+const FILE_PREFIX: &str = "capture_";
+const FILE_EXTENSION: &str = "rsbrec";
+
+/// Writes captured messages to a sequence of files in a directory.
+/// A new file is started as soon as the current one exceeds `max_file_size`
+/// and only the newest `max_files` files are kept.
+pub(crate) struct RotatingRecordingWriter {
+    directory: PathBuf,
+    max_file_size: usize,
+    max_files: usize,
+    index: u64,
+    current: Option<MessageRecordingWriter<BufWriter<File>>>,
+}
+
+impl RotatingRecordingWriter {
+    pub(crate) fn new(
+        directory: impl Into<PathBuf>,
+        max_file_size: usize,
+        max_files: usize,
+    ) -> Result<Self> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+        // Continue after existing captures instead of overwriting them
+        let index = capture_files(&directory)?
+            .last()
+            .map(|(i, _)| *i)
+            .unwrap_or_default();
+        Ok(Self {
+            directory,
+            max_file_size,
+            max_files: max_files.max(1),
+            index,
+            current: None,
+        })
+    }
+
+    pub(crate) fn write(&mut self, recorded: &RecordedMessage) -> Result<()> {
+        let needs_rotation = match &self.current {
+            Some(writer) => writer.bytes_written() >= self.max_file_size,
+            None => true,
+        };
+        if needs_rotation {
+            self.rotate()?;
+        }
+        self.current.as_mut().unwrap().write(recorded)
+    }
+
+    pub(crate) fn flush(&mut self) -> Result<()> {
+        match &mut self.current {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn current_file(&self) -> PathBuf {
+        file_path(&self.directory, self.index)
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        if let Some(writer) = self.current.take() {
+            writer.into_inner()?;
+        }
+        self.index += 1;
+        self.current = Some(MessageRecordingWriter::create(self.current_file())?);
+
+        let files = capture_files(&self.directory)?;
+        if files.len() > self.max_files {
+            for (_, path) in &files[..files.len() - self.max_files] {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn file_path(directory: &Path, index: u64) -> PathBuf {
+    directory.join(format!("{}{:06}.{}", FILE_PREFIX, index, FILE_EXTENSION))
+}
+
+/// All capture files in the directory, ordered from oldest to newest
+pub(crate) fn capture_files(directory: &Path) -> Result<Vec<(u64, PathBuf)>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(FILE_EXTENSION) {
+            continue;
+        }
+        let index = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|s| s.strip_prefix(FILE_PREFIX))
+            .and_then(|s| s.parse::<u64>().ok());
+        if let Some(index) = index {
+            files.push((index, path));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_node::transport::MessageRecordingReader;
+
+    #[test]
+    fn rotate_files() {
+        let dir = test_dir("rotate_files");
+        let mut writer = RotatingRecordingWriter::new(&dir, 1, 2).unwrap();
+        for _ in 0..3 {
+            writer.write(&RecordedMessage::new_test_instance()).unwrap();
+        }
+        writer.flush().unwrap();
+
+        let files = capture_files(&dir).unwrap();
+        assert_eq!(
+            files.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        let reader = MessageRecordingReader::open(&files[1].1).unwrap();
+        assert_eq!(reader.count(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn continue_after_existing_files() {
+        let dir = test_dir("continue_after_existing_files");
+        {
+            let mut writer = RotatingRecordingWriter::new(&dir, 1024, 10).unwrap();
+            writer.write(&RecordedMessage::new_test_instance()).unwrap();
+        }
+        let mut writer = RotatingRecordingWriter::new(&dir, 1024, 10).unwrap();
+        writer.write(&RecordedMessage::new_test_instance()).unwrap();
+
+        assert_eq!(writer.current_file(), file_path(&dir, 2));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("rsban_capture_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+}
@@ -0,0 +1,236 @@
+use super::{
+    filter::{CaptureFilter, FilterArgs},
+    rotating_writer::RotatingRecordingWriter,
+};
+use crate::cli::commands::node::run_daemon::{init_tracing, shutdown_signal, RunDaemonArgs};
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use rsban_messages::{DeserializedMessage, Message, ProtocolInfo};
+use rsban_network::{ChannelDirection, ChannelId};
+use rsban_node::{transport::RecordedMessage, Node, NodeCallbacks};
+use std::{
+    net::{Ipv6Addr, SocketAddrV6},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{sync_channel, Receiver, SyncSender, TryRecvError},
+        Arc, OnceLock, Weak,
+    },
+    time::SystemTime,
+};
+use tracing::{error, info, warn};
+
+/// Captured messages are buffered until the writer thread catches up.
+/// If the buffer is full, messages are dropped instead of slowing down the node.
+const BUFFER_SIZE: usize = 1024 * 64;
+
+#[derive(Parser)]
+pub(crate) struct CaptureRunArgs {
+    #[command(flatten)]
+    daemon: RunDaemonArgs,
+    /// Directory for the capture files
+    #[arg(long, default_value = "capture")]
+    output: PathBuf,
+    /// Maximum size of a single capture file in MB
+    #[arg(long, default_value_t = 64)]
+    max_file_size: usize,
+    /// Maximum number of capture files to keep. The oldest files are deleted first
+    #[arg(long, default_value_t = 16)]
+    max_files: usize,
+    #[command(flatten)]
+    filter: FilterArgs,
+}
+
+impl CaptureRunArgs {
+    pub(crate) async fn run(&self) -> Result<()> {
+        init_tracing();
+        let filter = Arc::new(self.filter.to_filter()?);
+        let writer = RotatingRecordingWriter::new(
+            &self.output,
+            self.max_file_size * 1024 * 1024,
+            self.max_files,
+        )?;
+        info!("Capturing messages to {:?}", self.output);
+
+        let (tx, rx) = sync_channel(BUFFER_SIZE);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let node = Arc::new(OnceLock::<Weak<Node>>::new());
+
+        let writer_thread = {
+            let filter = filter.clone();
+            let node = node.clone();
+            let dropped = dropped.clone();
+            std::thread::Builder::new()
+                .name("Capture writer".to_owned())
+                .spawn(move || write_captures(rx, writer, &filter, &node, &dropped))?
+        };
+
+        let node2 = node.clone();
+        let result = self
+            .daemon
+            .daemon_builder()?
+            .callbacks(capture_callbacks(
+                tx.clone(),
+                filter,
+                node.clone(),
+                dropped.clone(),
+            ))
+            .on_node_started(move |n| {
+                let _ = node2.set(Arc::downgrade(&n));
+            })
+            .run(shutdown_signal())
+            .await;
+
+        let _ = tx.send(None);
+        writer_thread
+            .join()
+            .map_err(|_| anyhow!("capture writer panicked"))??;
+
+        let dropped = dropped.load(Ordering::Relaxed);
+        if dropped > 0 {
+            warn!(
+                "{} messages could not be captured, because the buffer was full or the node did not start",
+                dropped
+            );
+        }
+        result
+    }
+}
+
+struct CapturedMessage {
+    timestamp: SystemTime,
+    channel_id: ChannelId,
+    direction: ChannelDirection,
+    peer_addr: SocketAddrV6,
+    message: Message,
+}
+
+fn capture_callbacks(
+    tx: SyncSender<Option<CapturedMessage>>,
+    filter: Arc<CaptureFilter>,
+    node: Arc<OnceLock<Weak<Node>>>,
+    dropped: Arc<AtomicU64>,
+) -> NodeCallbacks {
+    let capture = Arc::new(
+        move |channel_id: ChannelId, message: &Message, direction: ChannelDirection| {
+            if !filter.include_message(message) {
+                return;
+            }
+            // The peer address has to be resolved now, because the channel
+            // might already be closed when the message gets written
+            let captured = CapturedMessage {
+                timestamp: SystemTime::now(),
+                channel_id,
+                direction,
+                peer_addr: peer_addr(&node, channel_id),
+                message: message.clone(),
+            };
+            if tx.try_send(Some(captured)).is_err() {
+                dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        },
+    );
+
+    let capture2 = capture.clone();
+    let capture3 = capture.clone();
+    NodeCallbacks::builder()
+        .on_publish(move |channel_id, message| {
+            capture(channel_id, message, ChannelDirection::Outbound)
+        })
+        .on_inbound(move |channel_id, message| {
+            capture2(channel_id, message, ChannelDirection::Inbound)
+        })
+        .on_inbound_dropped(move |channel_id, message| {
+            capture3(channel_id, message, ChannelDirection::Inbound)
+        })
+        .finish()
+}
+
+fn peer_addr(node: &OnceLock<Weak<Node>>, channel_id: ChannelId) -> SocketAddrV6 {
+    node.get()
+        .and_then(|n| n.upgrade())
+        .and_then(|n| {
+            n.network_info
+                .read()
+                .unwrap()
+                .get(channel_id)
+                .map(|c| c.peer_addr())
+        })
+        .unwrap_or(SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0))
+}
+
+fn write_captures(
+    rx: Receiver<Option<CapturedMessage>>,
+    mut writer: RotatingRecordingWriter,
+    filter: &CaptureFilter,
+    node: &OnceLock<Weak<Node>>,
+    dropped: &AtomicU64,
+) -> Result<()> {
+    // Messages that arrive before the node is started are kept until
+    // the protocol of the node is known
+    let mut early = Vec::new();
+    loop {
+        let captured = match rx.try_recv() {
+            Ok(captured) => captured,
+            Err(TryRecvError::Empty) => {
+                // Nothing to do right now, so make sure everything captured so far is on disk
+                writer.flush()?;
+                match rx.recv() {
+                    Ok(captured) => captured,
+                    Err(_) => break,
+                }
+            }
+            Err(TryRecvError::Disconnected) => break,
+        };
+
+        let Some(captured) = captured else {
+            break;
+        };
+
+        let Some(node) = node.get().and_then(|n| n.upgrade()) else {
+            if early.len() < BUFFER_SIZE {
+                early.push(captured);
+            } else {
+                dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            continue;
+        };
+
+        let protocol = node.network_params.network.protocol_info();
+        for captured in early.drain(..).chain(std::iter::once(captured)) {
+            write_capture(&mut writer, filter, captured, protocol)?;
+        }
+    }
+
+    dropped.fetch_add(early.len() as u64, Ordering::Relaxed);
+    writer.flush()
+}
+
+fn write_capture(
+    writer: &mut RotatingRecordingWriter,
+    filter: &CaptureFilter,
+    captured: CapturedMessage,
+    protocol: ProtocolInfo,
+) -> Result<()> {
+    let recorded = RecordedMessage {
+        timestamp: captured.timestamp,
+        channel_id: captured.channel_id,
+        direction: captured.direction,
+        peer_addr: captured.peer_addr,
+        message: DeserializedMessage::new(captured.message, protocol),
+    };
+
+    if !filter.include(&recorded) {
+        return Ok(());
+    }
+
+    if let Err(e) = writer.write(&recorded) {
+        error!(
+            "Could not write capture file {:?}: {:?}",
+            writer.current_file(),
+            e
+        );
+        return Err(e);
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::cli::get_path;
+use anyhow::Result;
+use clap::{ArgGroup, Parser};
+use rsban_store_lmdb::{LmdbBlockHeightStore, LmdbEnv};
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct BlockHeightIndexArgs {
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl BlockHeightIndexArgs {
+    pub(crate) fn block_height_index(&self) -> Result<()> {
+        let path = get_path(&self.data_path, &self.network).join("data.ldb");
+
+        let env = Arc::new(LmdbEnv::new(&path)?);
+
+        let block_height_store = LmdbBlockHeightStore::new(env.clone())?;
+
+        let mut txn = env.tx_begin_write();
+
+        block_height_store.clear(&mut txn);
+
+        println!("Block height index was removed from the database");
+
+        Ok(())
+    }
+}
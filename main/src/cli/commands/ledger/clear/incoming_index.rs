@@ -0,0 +1,35 @@
+use crate::cli::get_path;
+use anyhow::Result;
+use clap::{ArgGroup, Parser};
+use rsban_store_lmdb::{LmdbEnv, LmdbIncomingStore};
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct IncomingIndexArgs {
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl IncomingIndexArgs {
+    pub(crate) fn incoming_index(&self) -> Result<()> {
+        let path = get_path(&self.data_path, &self.network).join("data.ldb");
+
+        let env = Arc::new(LmdbEnv::new(&path)?);
+
+        let incoming_store = LmdbIncomingStore::new(env.clone())?;
+
+        let mut txn = env.tx_begin_write();
+
+        incoming_store.clear(&mut txn);
+
+        println!("Incoming sends index was removed from the database");
+
+        Ok(())
+    }
+}
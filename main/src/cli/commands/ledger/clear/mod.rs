@@ -1,12 +1,18 @@
 use anyhow::Result;
+use block_height_index::BlockHeightIndexArgs;
 use clap::{CommandFactory, Parser, Subcommand};
 use confirmation_height::ConfirmationHeightArgs;
+use delegators_index::DelegatorsIndexArgs;
 use final_vote::FinalVoteArgs;
+use incoming_index::IncomingIndexArgs;
 use online_weight::OnlineWeightArgs;
 use peers::PeersArgs;
 
+pub(crate) mod block_height_index;
 pub(crate) mod confirmation_height;
+pub(crate) mod delegators_index;
 pub(crate) mod final_vote;
+pub(crate) mod incoming_index;
 pub(crate) mod online_weight;
 pub(crate) mod peers;
 
@@ -20,6 +26,12 @@ pub(crate) enum ClearSubcommands {
     Peers(PeersArgs),
     /// Clears the confirmation height of accounts
     ConfirmationHeight(ConfirmationHeightArgs),
+    /// Removes the representative to delegators index. It gets rebuilt when the node is started with --enable_delegators_index
+    DelegatorsIndex(DelegatorsIndexArgs),
+    /// Removes the account height to block index. It gets rebuilt when the node is started with --enable_block_height_index
+    BlockHeightIndex(BlockHeightIndexArgs),
+    /// Removes the incoming sends index. It gets rebuilt when the node is started with --enable_incoming_index
+    IncomingIndex(IncomingIndexArgs),
 }
 
 #[derive(Parser)]
@@ -35,6 +47,9 @@ impl ClearCommand {
             Some(ClearSubcommands::ConfirmationHeight(args)) => args.confirmation_height()?,
             Some(ClearSubcommands::OnlineWeight(args)) => args.online_weight()?,
             Some(ClearSubcommands::Peers(args)) => args.peers()?,
+            Some(ClearSubcommands::DelegatorsIndex(args)) => args.delegators_index()?,
+            Some(ClearSubcommands::BlockHeightIndex(args)) => args.block_height_index()?,
+            Some(ClearSubcommands::IncomingIndex(args)) => args.incoming_index()?,
             None => ClearCommand::command().print_long_help()?,
         }
 
@@ -0,0 +1,49 @@
+use crate::cli::get_path;
+use anyhow::Result;
+use clap::{ArgGroup, Parser};
+use rsban_core::NodeId;
+use rsban_node::{
+    config::NetworkConstants, ledger_snapshot::load_confirmed_snapshot, NetworkParams,
+};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct LoadSnapshotArgs {
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+    /// Path of a snapshot created with `ledger snapshot --confirmed_only`
+    #[arg(long)]
+    snapshot: PathBuf,
+    /// Node ID that must have signed the snapshot manifest
+    #[arg(long)]
+    trusted_node_id: NodeId,
+}
+
+impl LoadSnapshotArgs {
+    pub(crate) fn load_snapshot(&self) -> Result<()> {
+        let data_path = get_path(&self.data_path, &self.network);
+        let network_params = NetworkParams::new(NetworkConstants::active_network());
+
+        println!("Verifying snapshot {:?}", self.snapshot);
+
+        let manifest = load_confirmed_snapshot(
+            &self.snapshot,
+            &data_path,
+            &self.trusted_node_id,
+            &network_params.ledger.genesis_block.hash(),
+        )?;
+
+        println!(
+            "Loaded {} accounts, {} blocks and {} pending entries into {:?}",
+            manifest.accounts, manifest.blocks, manifest.pending, data_path
+        );
+
+        Ok(())
+    }
+}
@@ -2,11 +2,21 @@ use anyhow::Result;
 use clap::{CommandFactory, Parser, Subcommand};
 use clear::ClearCommand;
 use info::InfoCommand;
+use load_snapshot::LoadSnapshotArgs;
+use prune::PruneArgs;
+use rebuild_block_height_index::RebuildBlockHeightIndexArgs;
+use rebuild_delegators_index::RebuildDelegatorsIndexArgs;
+use rebuild_incoming_index::RebuildIncomingIndexArgs;
 use snapshot::SnapshotArgs;
 use vacuum::VacuumArgs;
 
 pub(crate) mod clear;
 pub(crate) mod info;
+pub(crate) mod load_snapshot;
+pub(crate) mod prune;
+pub(crate) mod rebuild_block_height_index;
+pub(crate) mod rebuild_delegators_index;
+pub(crate) mod rebuild_incoming_index;
 pub(crate) mod snapshot;
 pub(crate) mod vacuum;
 
@@ -20,6 +30,16 @@ pub(crate) enum LedgerSubcommands {
     Vacuum(VacuumArgs),
     /// Similar to vacuum but does not replace the existing database
     Snapshot(SnapshotArgs),
+    /// Verifies a confirmed-only snapshot and installs it as the ledger
+    LoadSnapshot(LoadSnapshotArgs),
+    /// Prunes confirmed blocks or reports what would be pruned
+    Prune(PruneArgs),
+    /// Recreates the representative to delegators index from the account table
+    RebuildDelegatorsIndex(RebuildDelegatorsIndexArgs),
+    /// Recreates the account height to block index from the block table
+    RebuildBlockHeightIndex(RebuildBlockHeightIndexArgs),
+    /// Recreates the incoming sends index from the block table
+    RebuildIncomingIndex(RebuildIncomingIndexArgs),
 }
 
 #[derive(Parser)]
@@ -35,6 +55,15 @@ impl LedgerCommand {
             Some(LedgerSubcommands::Clear(command)) => command.run()?,
             Some(LedgerSubcommands::Vacuum(args)) => args.vacuum()?,
             Some(LedgerSubcommands::Snapshot(args)) => args.snapshot()?,
+            Some(LedgerSubcommands::LoadSnapshot(args)) => args.load_snapshot()?,
+            Some(LedgerSubcommands::Prune(args)) => args.prune()?,
+            Some(LedgerSubcommands::RebuildDelegatorsIndex(args)) => {
+                args.rebuild_delegators_index()?
+            }
+            Some(LedgerSubcommands::RebuildBlockHeightIndex(args)) => {
+                args.rebuild_block_height_index()?
+            }
+            Some(LedgerSubcommands::RebuildIncomingIndex(args)) => args.rebuild_incoming_index()?,
             None => LedgerCommand::command().print_long_help()?,
         }
 
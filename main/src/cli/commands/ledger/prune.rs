@@ -0,0 +1,103 @@
+use crate::cli::get_path;
+use anyhow::{bail, Result};
+use clap::{ArgGroup, Parser};
+use rsban_core::{
+    utils::{get_cpu_count, seconds_since_epoch},
+    Amount,
+};
+use rsban_ledger::{Ledger, RepWeightCache};
+use rsban_node::{
+    config::{DaemonConfig, NetworkConstants, NodeFlags},
+    pruning::LedgerPruning,
+    utils::ThreadPoolImpl,
+    wallets::Wallets,
+    NetworkParams,
+};
+use rsban_store_lmdb::{LmdbEnv, LmdbStore};
+use std::{sync::Arc, time::Duration};
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct PruneArgs {
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+    /// Number of blocks below each account frontier that are kept. Defaults to max_pruning_depth
+    #[arg(long)]
+    depth: Option<u64>,
+    /// Blocks younger than this many seconds are kept. Defaults to max_pruning_age
+    #[arg(long)]
+    age: Option<u64>,
+    /// Only report how many blocks and bytes would be pruned
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl PruneArgs {
+    pub(crate) fn prune(&self) -> Result<()> {
+        if self.age.is_some_and(|age| age > seconds_since_epoch()) {
+            bail!("Invalid age");
+        }
+        let data_path = get_path(&self.data_path, &self.network);
+        let path = data_path.join("data.ldb");
+        let network = NetworkConstants::active_network();
+        let config = DaemonConfig::load_from_data_path(network, get_cpu_count(), &data_path)?;
+        let network_params = NetworkParams::new(network);
+
+        let ledger = Arc::new(Ledger::new(
+            Arc::new(LmdbStore::open(&path).build()?),
+            network_params.ledger,
+            Amount::zero(),
+            Arc::new(RepWeightCache::new()),
+        )?);
+
+        let pruning_keep_wallet_accounts = config.node.pruning_keep_wallet_accounts;
+        let pruning = LedgerPruning::new(
+            config.node,
+            NodeFlags::new(),
+            ledger,
+            Arc::new(ThreadPoolImpl::new_null()),
+        );
+        if pruning_keep_wallet_accounts {
+            let wallets_path = data_path.join("wallets.ldb");
+            let accounts = if wallets_path.exists() {
+                Wallets::read_accounts(&LmdbEnv::new(&wallets_path)?)?
+            } else {
+                Vec::new()
+            };
+            println!("Keeping {} wallet accounts", accounts.len());
+            pruning.set_wallet_accounts_callback(Box::new(move || accounts.clone()));
+        }
+        let mut options = pruning.configured_options(pruning.batch_size(), true);
+        if let Some(depth) = self.depth {
+            options.max_depth = depth;
+        }
+        if let Some(age) = self.age {
+            options.max_age = Some(Duration::from_secs(age));
+        }
+        options.dry_run = self.dry_run;
+
+        if !self.dry_run {
+            println!("Pruning ledger {:?}", path);
+            println!("This may take a while...");
+        }
+
+        let result = pruning.prune(&options);
+
+        if self.dry_run {
+            println!(
+                "{} blocks ({} bytes) would be pruned",
+                result.blocks, result.bytes
+            );
+        } else {
+            println!("Pruned {} blocks ({} bytes)", result.blocks, result.bytes);
+            println!("Start the node with --enable_pruning to use the pruned ledger");
+        }
+
+        Ok(())
+    }
+}
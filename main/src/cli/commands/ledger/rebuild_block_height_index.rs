@@ -0,0 +1,34 @@
+use crate::cli::get_path;
+use anyhow::Result;
+use clap::{ArgGroup, Parser};
+use rsban_store_lmdb::{LedgerIndex, LmdbStore};
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct RebuildBlockHeightIndexArgs {
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl RebuildBlockHeightIndexArgs {
+    pub(crate) fn rebuild_block_height_index(&self) -> Result<()> {
+        let path = get_path(&self.data_path, &self.network).join("data.ldb");
+
+        println!("Rebuilding block height index in {:?}", path);
+        println!("This may take a while...");
+
+        let store = LmdbStore::open(&path).build()?;
+        let mut txn = store.tx_begin_write();
+        let count = store.rebuild_index(&mut txn, LedgerIndex::BlockHeight);
+        txn.commit();
+
+        println!("Block height index rebuilt for {} blocks", count);
+
+        Ok(())
+    }
+}
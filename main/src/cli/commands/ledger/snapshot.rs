@@ -1,7 +1,15 @@
 use crate::cli::get_path;
 use anyhow::Result;
 use clap::Parser;
+use rsban_core::Amount;
+use rsban_ledger::{Ledger, RepWeightCache};
+use rsban_node::{
+    config::NetworkConstants,
+    ledger_snapshot::{manifest_path, node_id_key, write_confirmed_snapshot},
+    NetworkParams,
+};
 use rsban_store_lmdb::LmdbStore;
+use std::sync::Arc;
 
 #[derive(Parser)]
 pub(crate) struct SnapshotArgs {
@@ -11,12 +19,16 @@ pub(crate) struct SnapshotArgs {
     /// Uses the supplied network (live, test, beta or dev)
     #[arg(long, group = "input")]
     network: Option<String>,
+    /// Only writes cemented blocks and creates a manifest signed with the node ID
+    #[arg(long)]
+    confirmed_only: bool,
 }
 
 impl SnapshotArgs {
     pub(crate) fn snapshot(&self) -> Result<()> {
-        let source_path = get_path(&self.data_path, &self.network).join("data.ldb");
-        let snapshot_path = get_path(&self.data_path, &self.network).join("snapshot.ldb");
+        let data_path = get_path(&self.data_path, &self.network);
+        let source_path = data_path.join("data.ldb");
+        let snapshot_path = data_path.join("snapshot.ldb");
 
         println!(
             "Database snapshot of {:?} to {:?} in progress",
@@ -27,7 +39,28 @@ impl SnapshotArgs {
 
         let store = LmdbStore::open(&source_path).build()?;
 
-        store.copy_db(&snapshot_path)?;
+        if self.confirmed_only {
+            let network_params = NetworkParams::new(NetworkConstants::active_network());
+            let ledger = Ledger::new(
+                Arc::new(store),
+                network_params.ledger,
+                Amount::zero(),
+                Arc::new(RepWeightCache::new()),
+            )?;
+            let manifest =
+                write_confirmed_snapshot(&ledger, &node_id_key(&data_path)?, &snapshot_path)?;
+            println!(
+                "Snapshot contains {} accounts, {} blocks and {} pending entries",
+                manifest.accounts, manifest.blocks, manifest.pending
+            );
+            println!(
+                "Manifest signed by node {} written to {:?}",
+                manifest.node_id,
+                manifest_path(&snapshot_path)
+            );
+        } else {
+            store.copy_db(&snapshot_path)?;
+        }
 
         println!(
             "Snapshot completed, This can be found at {:?}",
@@ -1,3 +1,4 @@
+pub(crate) mod capture;
 pub(crate) mod config;
 pub(crate) mod ledger;
 pub(crate) mod node;
@@ -8,10 +8,12 @@ use rsban_node::{wallets::Wallets, BUILD_INFO, VERSION_STRING};
 use rsban_store_lmdb::LmdbEnv;
 use run_daemon::RunDaemonArgs;
 use std::{sync::Arc, time::Instant};
+use work_server::WorkServerArgs;
 
 pub(crate) mod generate_config;
 pub(crate) mod initialize;
 pub(crate) mod run_daemon;
+pub(crate) mod work_server;
 
 #[derive(Subcommand)]
 pub(crate) enum NodeSubcommands {
@@ -30,6 +32,10 @@ pub(crate) enum NodeSubcommands {
     /// Pass the configuration type node or rpc.
     /// See also use_defaults.
     GenerateConfig(GenerateConfigArgs),
+    /// Runs a standalone work server which answers work_generate and work_cancel requests.
+    ///
+    /// Nodes can use it by adding it to their work_peers and selecting the http work backend.
+    WorkServer(WorkServerArgs),
 }
 
 #[derive(Parser)]
@@ -44,6 +50,7 @@ impl NodeCommand {
             Some(NodeSubcommands::Run(args)) => args.run_daemon().await?,
             Some(NodeSubcommands::Initialize(args)) => args.initialize().await?,
             Some(NodeSubcommands::GenerateConfig(args)) => args.generate_config()?,
+            Some(NodeSubcommands::WorkServer(args)) => args.run_work_server().await?,
             Some(NodeSubcommands::Version) => Self::version(),
             Some(NodeSubcommands::Diagnostics) => Self::diagnostics().await?,
             None => NodeCommand::command().print_long_help()?,
@@ -64,7 +71,7 @@ impl NodeCommand {
 
         println!("Testing key derivation function");
 
-        wallets.kdf.hash_password("", &mut [0; 32]);
+        wallets.kdf.hash_password("", &[0; 32])?;
 
         println!("Testing time retrieval latency...");
 
@@ -3,7 +3,7 @@ use clap::Parser;
 use rsban_core::Networks;
 use rsban_daemon::DaemonBuilder;
 use rsban_node::config::NodeFlags;
-use std::{path::PathBuf, str::FromStr};
+use std::{path::PathBuf, str::FromStr, time::Duration};
 use tracing_subscriber::EnvFilter;
 
 #[derive(Parser)]
@@ -69,6 +69,28 @@ pub(crate) struct RunDaemonArgs {
     /// Enable experimental ledger pruning
     #[arg(long)]
     enable_pruning: bool,
+    /// Maintain a representative to delegators index for fast delegators queries. The index is built on first start
+    #[arg(long)]
+    enable_delegators_index: bool,
+    /// Maintain an account height to block index for fast history pagination. The index is built on first start
+    #[arg(long)]
+    enable_block_height_index: bool,
+    /// Maintain an index of all sends by destination account for the account_incoming RPC. The index is built on first start
+    #[arg(long)]
+    enable_incoming_index: bool,
+    /// Keep the ledger in memory instead of the data directory. Nothing is persisted when the node stops
+    #[arg(long)]
+    in_memory_ledger: bool,
+    /// Run a query node, which opens the ledger of a running node read-only and only serves ledger RPC commands.
+    /// Indexes are only available if the running node maintains them.
+    #[arg(long, verbatim_doc_comment)]
+    read_only: bool,
+    /// Seconds between reloads of the ledger caches in read-only mode, default 60
+    #[arg(long)]
+    read_only_reload_interval: Option<u64>,
+    /// Overrides the RPC port of the config file, e.g. to run a read-only node next to the node that writes the ledger
+    #[arg(long)]
+    rpc_port: Option<u16>,
     /// Increase bootstrap processor limits to allow more blocks before hitting full state and verify/write more per database call. Also disable deletion of processed unchecked blocks.
     #[arg(long)]
     fast_bootstrap: bool,
@@ -89,13 +111,20 @@ pub(crate) struct RunDaemonArgs {
 impl RunDaemonArgs {
     pub(crate) async fn run_daemon(&self) -> Result<()> {
         init_tracing();
+        self.daemon_builder()?.run(shutdown_signal()).await
+    }
+
+    pub(crate) fn daemon_builder(&self) -> Result<DaemonBuilder> {
         let network = self.get_network()?;
         let flags = self.get_flags();
         let mut daemon = DaemonBuilder::new(network).flags(flags);
         if let Some(path) = self.specified_data_path() {
             daemon = daemon.data_path(path);
         }
-        daemon.run(shutdown_signal()).await
+        if let Some(port) = self.rpc_port {
+            daemon = daemon.rpc_port(port);
+        }
+        Ok(daemon)
     }
 
     pub fn specified_data_path(&self) -> Option<PathBuf> {
@@ -133,7 +162,15 @@ impl RunDaemonArgs {
         flags.disable_block_processor_republishing = self.disable_block_processor_republishing;
         flags.allow_bootstrap_peers_duplicates = self.allow_bootstrap_peers_duplicates;
         flags.enable_pruning = self.enable_pruning;
+        flags.enable_delegators_index = self.enable_delegators_index;
+        flags.enable_block_height_index = self.enable_block_height_index;
+        flags.enable_incoming_index = self.enable_incoming_index;
+        flags.in_memory_ledger = self.in_memory_ledger;
         flags.fast_bootstrap = self.fast_bootstrap;
+        flags.read_only = self.read_only;
+        if let Some(interval) = self.read_only_reload_interval {
+            flags.read_only_reload_interval = Duration::from_secs(interval);
+        }
         if let Some(block_processor_batch_size) = self.block_processor_batch_size {
             flags.block_processor_batch_size = block_processor_batch_size;
         }
@@ -151,7 +188,7 @@ impl RunDaemonArgs {
     }
 }
 
-async fn shutdown_signal() {
+pub(crate) async fn shutdown_signal() {
     let ctrl_c = async {
         tokio::signal::ctrl_c()
             .await
@@ -175,7 +212,7 @@ async fn shutdown_signal() {
     }
 }
 
-fn init_tracing() {
+pub(crate) fn init_tracing() {
     let dirs = std::env::var(EnvFilter::DEFAULT_ENV).unwrap_or(String::from("info"));
     let filter = EnvFilter::builder().parse_lossy(dirs);
     let value = std::env::var("NANO_LOG");
@@ -0,0 +1,66 @@
+use super::run_daemon::{init_tracing, shutdown_signal};
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use rsban_core::{work::WorkPoolImpl, Networks};
+use rsban_node::NetworkParams;
+use rsban_rpc_server::run_work_server;
+use std::{
+    net::SocketAddr, str::FromStr, sync::Arc, thread::available_parallelism, time::Duration,
+};
+use tokio::net::TcpListener;
+
+#[derive(Parser)]
+pub(crate) struct WorkServerArgs {
+    /// Address on which work_generate and work_cancel requests are accepted
+    #[arg(long, default_value = "[::1]:7076")]
+    listen: SocketAddr,
+    /// Number of work generation threads. Defaults to the number of CPU cores
+    #[arg(long)]
+    threads: Option<usize>,
+    /// Uses the work thresholds of the supplied network (live, test, beta or dev)
+    #[arg(long)]
+    network: Option<String>,
+    /// Maximum difficulty multiplier that clients may request
+    #[arg(long, default_value_t = 64.0)]
+    max_multiplier: f64,
+}
+
+impl WorkServerArgs {
+    pub(crate) async fn run_work_server(&self) -> Result<()> {
+        init_tracing();
+        let network = self.get_network()?;
+        let threads = self
+            .threads
+            .unwrap_or_else(|| available_parallelism().map(|i| i.get()).unwrap_or(1));
+        let network_params = NetworkParams::new(network);
+        let work_pool = Arc::new(WorkPoolImpl::new(
+            network_params.work.clone(),
+            threads,
+            Duration::ZERO,
+        ));
+
+        println!(
+            "Starting work server for {} with {} threads",
+            network.as_str(),
+            threads
+        );
+        let listener = TcpListener::bind(self.listen).await?;
+        let result = run_work_server(
+            work_pool.clone(),
+            self.max_multiplier,
+            listener,
+            shutdown_signal(),
+        )
+        .await;
+        work_pool.stop();
+        result
+    }
+
+    fn get_network(&self) -> Result<Networks> {
+        self.network
+            .as_ref()
+            .map(|s| Networks::from_str(s).map_err(|e| anyhow!(e)))
+            .transpose()
+            .map(|n| n.unwrap_or(Networks::BananoLiveNetwork))
+    }
+}
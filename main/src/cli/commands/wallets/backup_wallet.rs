@@ -0,0 +1,60 @@
+use crate::cli::get_path;
+use anyhow::{anyhow, Result};
+use clap::{ArgGroup, Parser};
+use rsban_core::WalletId;
+use rsban_node::wallets::{Wallets, WalletsExt};
+use rsban_store_lmdb::LmdbEnv;
+use std::{
+    fs::{set_permissions, Permissions},
+    os::unix::fs::PermissionsExt,
+    sync::Arc,
+};
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct BackupWalletArgs {
+    /// The wallet to back up
+    #[arg(long)]
+    wallet: String,
+    /// Optional <password> to unlock the wallet
+    #[arg(long)]
+    password: Option<String>,
+    /// The <backup_password> that protects the backup file
+    #[arg(long)]
+    backup_password: String,
+    /// The path of the backup <file>
+    #[arg(long)]
+    file: String,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl BackupWalletArgs {
+    pub(crate) async fn backup_wallet(&self) -> Result<()> {
+        let wallet_id = WalletId::decode_hex(&self.wallet)?;
+        let path = get_path(&self.data_path, &self.network).join("wallets.ldb");
+        let env = Arc::new(LmdbEnv::new(&path)?);
+        let wallets = Arc::new(Wallets::new_null_with_env(
+            env,
+            tokio::runtime::Handle::current(),
+        ));
+
+        let password = self.password.clone().unwrap_or_default();
+
+        wallets.ensure_wallet_is_unlocked(wallet_id, &password);
+
+        let backup = wallets
+            .backup_wallet(&wallet_id, &self.backup_password)
+            .map_err(|e| anyhow!("Failed to back up wallet: {:?}", e))?;
+
+        std::fs::write(&self.file, backup.to_json())?;
+        set_permissions(&self.file, Permissions::from_mode(0o600))?;
+
+        Ok(())
+    }
+}
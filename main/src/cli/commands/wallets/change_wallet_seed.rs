@@ -1,7 +1,7 @@
 use crate::cli::get_path;
 use anyhow::{anyhow, Result};
 use clap::{ArgGroup, Parser};
-use rsban_core::{RawKey, WalletId};
+use rsban_core::{Mnemonic, RawKey, WalletId};
 use rsban_node::wallets::{Wallets, WalletsExt};
 use rsban_store_lmdb::LmdbEnv;
 use std::sync::Arc;
@@ -9,13 +9,19 @@ use std::sync::Arc;
 #[derive(Parser)]
 #[command(group = ArgGroup::new("input")
     .args(&["data_path", "network"]))]
+#[command(group = ArgGroup::new("new_seed")
+    .args(&["seed", "mnemonic"])
+    .required(true))]
 pub(crate) struct ChangeWalletSeedArgs {
     /// Changes the seed of the supplied wallet
     #[arg(long)]
     wallet: String,
     /// The new <seed> of the wallet
     #[arg(long)]
-    seed: String,
+    seed: Option<String>,
+    /// The new seed of the wallet as a 24 word BIP39 <mnemonic>
+    #[arg(long)]
+    mnemonic: Option<String>,
     /// Optional <password> to unlock the wallet
     #[arg(long)]
     password: Option<String>,
@@ -30,7 +36,11 @@ pub(crate) struct ChangeWalletSeedArgs {
 impl ChangeWalletSeedArgs {
     pub(crate) async fn change_wallet_seed(&self) -> Result<()> {
         let wallet_id = WalletId::decode_hex(&self.wallet)?;
-        let seed = RawKey::decode_hex(&self.seed)?;
+        let seed = match (&self.seed, &self.mnemonic) {
+            (Some(seed), _) => RawKey::decode_hex(seed)?,
+            (None, Some(mnemonic)) => mnemonic.parse::<Mnemonic>()?.to_seed()?,
+            (None, None) => unreachable!("clap requires a seed or mnemonic"),
+        };
         let path = get_path(&self.data_path, &self.network).join("wallets.ldb");
         let env = Arc::new(LmdbEnv::new(&path)?);
         let wallets = Arc::new(Wallets::new_null_with_env(
@@ -2,7 +2,7 @@ use crate::cli::get_path;
 use anyhow::{anyhow, Result};
 use clap::{ArgGroup, Parser};
 use rand::{thread_rng, Rng};
-use rsban_core::{RawKey, WalletId};
+use rsban_core::{Mnemonic, RawKey, WalletId};
 use rsban_node::wallets::{Wallets, WalletsExt};
 use rsban_store_lmdb::LmdbEnv;
 use std::sync::Arc;
@@ -12,8 +12,11 @@ use std::sync::Arc;
     .args(&["data_path", "network"]))]
 pub(crate) struct CreateWalletArgs {
     /// Optional seed of the new wallet
-    #[arg(long)]
+    #[arg(long, conflicts_with = "mnemonic")]
     seed: Option<String>,
+    /// Optional seed of the new wallet as a 24 word BIP39 <mnemonic>
+    #[arg(long)]
+    mnemonic: Option<String>,
     /// Optional password of the new wallet
     #[arg(long)]
     password: Option<String>,
@@ -27,6 +30,11 @@ pub(crate) struct CreateWalletArgs {
 
 impl CreateWalletArgs {
     pub(crate) async fn create_wallet(&self) -> Result<()> {
+        let seed = match (&self.seed, &self.mnemonic) {
+            (Some(seed), _) => Some(RawKey::decode_hex(seed)?),
+            (None, Some(mnemonic)) => Some(mnemonic.parse::<Mnemonic>()?.to_seed()?),
+            (None, None) => None,
+        };
         let path = get_path(&self.data_path, &self.network).join("wallets.ldb");
         let wallet_id = WalletId::from_bytes(thread_rng().gen());
         let env = Arc::new(LmdbEnv::new(&path)?);
@@ -48,11 +56,9 @@ impl CreateWalletArgs {
 
         wallets.ensure_wallet_is_unlocked(wallet_id, &password);
 
-        if let Some(seed) = &self.seed {
-            let key = RawKey::decode_hex(seed)?;
-
+        if let Some(seed) = seed {
             wallets
-                .change_seed(wallet_id, &key, 0)
+                .change_seed(wallet_id, &seed, 0)
                 .map_err(|e| anyhow!("Failed to set wallet seed: {:?}", e))?;
         }
 
@@ -1,7 +1,7 @@
 use crate::cli::get_path;
 use anyhow::{anyhow, Result};
 use clap::Parser;
-use rsban_core::WalletId;
+use rsban_core::{Mnemonic, WalletId};
 use rsban_node::wallets::{Wallets, WalletsExt};
 use rsban_store_lmdb::LmdbEnv;
 use std::sync::Arc;
@@ -16,6 +16,9 @@ pub(crate) struct DecryptWalletArgs {
     /// Optional password to unlock the wallet
     #[arg(long)]
     password: Option<String>,
+    /// Prints the seed as a BIP39 mnemonic instead of hex
+    #[arg(long)]
+    mnemonic: bool,
     /// Uses the supplied path as the data directory
     #[arg(long, group = "input")]
     data_path: Option<String>,
@@ -42,7 +45,11 @@ impl DecryptWalletArgs {
             .get_seed(wallet_id)
             .map_err(|e| anyhow!("Failed to get wallet seed: {:?}", e))?;
 
-        println!("Seed: {:?}", seed);
+        if self.mnemonic {
+            println!("Mnemonic: {}", Mnemonic::from(&seed));
+        } else {
+            println!("Seed: {:?}", seed);
+        }
 
         Ok(())
     }
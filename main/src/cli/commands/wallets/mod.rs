@@ -1,5 +1,6 @@
 use add_private_key::AddPrivateKeyArgs;
 use anyhow::Result;
+use backup_wallet::BackupWalletArgs;
 use change_wallet_seed::ChangeWalletSeedArgs;
 use clap::{CommandFactory, Parser, Subcommand};
 use clear_send_ids::ClearSendIdsArgs;
@@ -11,9 +12,12 @@ use get_wallet_representative::GetWalletRepresentativeArgs;
 use import_keys::ImportKeysArgs;
 use list_wallets::ListWalletsArgs;
 use remove_account::RemoveAccountArgs;
+use restore_wallet::RestoreWalletArgs;
 use set_wallet_representative::SetWalletRepresentativeArgs;
+use sign_block::SignBlockArgs;
 
 pub(crate) mod add_private_key;
+pub(crate) mod backup_wallet;
 pub(crate) mod change_wallet_seed;
 pub(crate) mod clear_send_ids;
 pub(crate) mod create_account;
@@ -24,7 +28,9 @@ pub(crate) mod get_wallet_representative;
 pub(crate) mod import_keys;
 pub(crate) mod list_wallets;
 pub(crate) mod remove_account;
+pub(crate) mod restore_wallet;
 pub(crate) mod set_wallet_representative;
+pub(crate) mod sign_block;
 
 #[derive(Subcommand)]
 pub(crate) enum WalletSubcommands {
@@ -48,6 +54,12 @@ pub(crate) enum WalletSubcommands {
     RemoveAccount(RemoveAccountArgs),
     /// Decrypts a wallet (WARNING: THIS WILL PRINT YOUR PRIVATE KEY TO STDOUT!)
     DecryptWallet(DecryptWalletArgs),
+    /// Writes an encrypted, password protected backup of a wallet to a file
+    BackupWallet(BackupWalletArgs),
+    /// Restores a wallet from an encrypted backup file
+    RestoreWallet(RestoreWalletArgs),
+    /// Signs an unsigned block offline with a wallet key or a private key
+    SignBlock(SignBlockArgs),
     /// List all wallets and their public keys
     ListWallets(ListWalletsArgs),
     /// Removes all send IDs from the wallets (dangerous: not intended for production use)
@@ -72,12 +84,15 @@ impl WalletsCommand {
             Some(WalletSubcommands::ImportKeys(args)) => args.import_keys().await?,
             Some(WalletSubcommands::RemoveAccount(args)) => args.remove_account().await?,
             Some(WalletSubcommands::DecryptWallet(args)) => args.decrypt_wallet().await?,
+            Some(WalletSubcommands::BackupWallet(args)) => args.backup_wallet().await?,
+            Some(WalletSubcommands::RestoreWallet(args)) => args.restore_wallet().await?,
             Some(WalletSubcommands::GetWalletRepresentative(args)) => {
                 args.get_wallet_representative().await?
             }
             Some(WalletSubcommands::SetWalletRepresentative(args)) => {
                 args.set_representative_wallet().await?
             }
+            Some(WalletSubcommands::SignBlock(args)) => args.sign_block().await?,
             Some(WalletSubcommands::ClearSendIds(args)) => args.clear_send_ids().await?,
             None => WalletsCommand::command().print_long_help()?,
         }
@@ -0,0 +1,53 @@
+use crate::cli::get_path;
+use anyhow::{Context, Result};
+use clap::{ArgGroup, Parser};
+use rsban_core::{WalletBackup, WalletId};
+use rsban_node::wallets::Wallets;
+use rsban_store_lmdb::LmdbEnv;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct RestoreWalletArgs {
+    /// The path of the backup <file>
+    #[arg(long)]
+    file: String,
+    /// The <password> that protects the backup file. The restored wallet uses the same password
+    #[arg(long)]
+    password: String,
+    /// Optional id of the restored wallet. A random id is used by default
+    #[arg(long)]
+    wallet: Option<String>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl RestoreWalletArgs {
+    pub(crate) async fn restore_wallet(&self) -> Result<()> {
+        let contents =
+            std::fs::read_to_string(&self.file).context("Unable to read <file> contents")?;
+        let backup = WalletBackup::from_json(&contents)?;
+        let wallet_id = match &self.wallet {
+            Some(wallet) => WalletId::decode_hex(wallet)?,
+            None => WalletId::random(),
+        };
+
+        let path = get_path(&self.data_path, &self.network).join("wallets.ldb");
+        let env = Arc::new(LmdbEnv::new(&path)?);
+        let wallets = Arc::new(Wallets::new_null_with_env(
+            env,
+            tokio::runtime::Handle::current(),
+        ));
+
+        wallets.restore_wallet(wallet_id, &backup, &self.password)?;
+
+        println!("{:?}", wallet_id);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::cli::get_path;
+use anyhow::{anyhow, Result};
+use clap::{ArgGroup, Parser};
+use rsban_core::{PrivateKey, UnsignedStateBlock, WalletId};
+use rsban_node::wallets::{Wallets, WalletsExt};
+use rsban_store_lmdb::LmdbEnv;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+#[command(group = ArgGroup::new("key")
+    .args(&["wallet", "private_key"])
+    .required(true))]
+pub(crate) struct SignBlockArgs {
+    /// The unsigned block <file> created by the block_prepare RPC
+    #[arg(long)]
+    unsigned: String,
+    /// The <file> to which the signed block is written
+    #[arg(long)]
+    output: String,
+    /// Signs with the key of the block account in the supplied wallet
+    #[arg(long, group = "key")]
+    wallet: Option<String>,
+    /// Signs with the supplied private key
+    #[arg(long, group = "key")]
+    private_key: Option<String>,
+    /// Optional password to unlock the wallet
+    #[arg(long)]
+    password: Option<String>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl SignBlockArgs {
+    pub(crate) async fn sign_block(&self) -> Result<()> {
+        let unsigned: UnsignedStateBlock =
+            serde_json::from_str(&std::fs::read_to_string(&self.unsigned)?)?;
+
+        let key = match &self.private_key {
+            Some(private_key) => PrivateKey::from_hex_str(private_key)?,
+            None => self.wallet_key(&unsigned)?,
+        };
+
+        let block = unsigned.sign(&key)?;
+        std::fs::write(
+            &self.output,
+            serde_json::to_string_pretty(&block.json_representation())?,
+        )?;
+
+        println!("Account: {}", unsigned.account.encode_account());
+        println!(
+            "Representative: {}",
+            unsigned.representative.encode_account()
+        );
+        println!("Balance: {}", unsigned.balance.to_string_dec());
+        println!("Link: {}", unsigned.link);
+        println!("Hash: {}", block.hash());
+
+        Ok(())
+    }
+
+    fn wallet_key(&self, unsigned: &UnsignedStateBlock) -> Result<PrivateKey> {
+        let path = get_path(&self.data_path, &self.network).join("wallets.ldb");
+        let env = Arc::new(LmdbEnv::new(&path)?);
+        let wallets = Arc::new(Wallets::new_null_with_env(
+            env,
+            tokio::runtime::Handle::current(),
+        ));
+
+        let wallet_id = WalletId::decode_hex(self.wallet.as_ref().unwrap())?;
+        let password = self.password.clone().unwrap_or_default();
+
+        wallets.ensure_wallet_is_unlocked(wallet_id, &password);
+
+        let raw_key = wallets
+            .fetch(&wallet_id, &unsigned.account.into())
+            .map_err(|e| anyhow!("Failed to get the account key: {:?}", e))?;
+        Ok(PrivateKey::from(raw_key))
+    }
+}
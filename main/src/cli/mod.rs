@@ -1,8 +1,8 @@
 use anyhow::Result;
 use clap::{CommandFactory, Parser, Subcommand};
 use commands::{
-    config::ConfigCommand, ledger::LedgerCommand, node::NodeCommand, utils::UtilsCommand,
-    wallets::WalletsCommand,
+    capture::CaptureCommand, config::ConfigCommand, ledger::LedgerCommand, node::NodeCommand,
+    utils::UtilsCommand, wallets::WalletsCommand,
 };
 use rsban_core::{Networks, PrivateKeyFactory};
 use rsban_node::{config::NetworkConstants, working_path};
@@ -25,6 +25,7 @@ impl Cli {
             Some(Commands::Node(command)) => command.run().await?,
             Some(Commands::Ledger(command)) => command.run()?,
             Some(Commands::Config(command)) => command.run()?,
+            Some(Commands::Capture(command)) => command.run().await?,
             None => Cli::command().print_long_help()?,
         }
         Ok(())
@@ -33,6 +34,8 @@ impl Cli {
 
 #[derive(Subcommand)]
 pub(crate) enum Commands {
+    /// Commands to capture and decode the messages of a running node
+    Capture(CaptureCommand),
     /// Commands related to configs
     Config(ConfigCommand),
     /// Commands related to the ledger
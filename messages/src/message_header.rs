@@ -8,6 +8,7 @@ use rsban_core::{
 use std::{
     fmt::{Debug, Display},
     mem::size_of,
+    str::FromStr,
 };
 
 use super::*;
@@ -57,6 +58,17 @@ impl MessageType {
     }
 }
 
+impl FromStr for MessageType {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        (0..=u8::MAX)
+            .filter_map(MessageType::from_u8)
+            .find(|t| t.as_str() == s)
+            .ok_or("Invalid message type")
+    }
+}
+
 impl Debug for MessageType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(self.as_str())
@@ -274,6 +286,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_message_type() {
+        assert_eq!(
+            MessageType::from_str("confirm_ack"),
+            Ok(MessageType::ConfirmAck)
+        );
+        assert!(MessageType::from_str("foo").is_err());
+    }
+
     #[test]
     fn serialize_and_deserialize() {
         let original = test_header();
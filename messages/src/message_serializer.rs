@@ -23,6 +23,10 @@ impl MessageSerializer {
         }
     }
 
+    pub fn protocol(&self) -> ProtocolInfo {
+        self.protocol
+    }
+
     pub fn serialize(&'_ mut self, message: &Message) -> &'_ [u8] {
         let payload_len;
         {
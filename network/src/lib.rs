@@ -7,6 +7,7 @@ mod network;
 mod network_info;
 mod network_observer;
 mod peer_connector;
+mod peer_event;
 pub mod peer_exclusion;
 mod response_server_spawner;
 mod tcp_listener;
@@ -23,6 +24,7 @@ pub use network_info::*;
 pub use network_observer::*;
 use num_derive::FromPrimitive;
 pub use peer_connector::*;
+pub use peer_event::*;
 pub use response_server_spawner::*;
 use std::fmt::{Debug, Display};
 pub use tcp_listener::*;
@@ -3,7 +3,8 @@ use crate::{
     attempt_container::AttemptContainer,
     peer_exclusion::PeerExclusion,
     utils::{is_ipv4_mapped, map_address_to_subnetwork, reserved_address},
-    ChannelId, ChannelInfo, ChannelMode, TrafficType,
+    ChannelId, ChannelInfo, ChannelMode, DisconnectReason, PeerEvent, PeerEventObserver,
+    TrafficType,
 };
 use rand::{seq::SliceRandom, thread_rng};
 use rsban_core::{utils::ContainerInfo, Networks, NodeId};
@@ -82,6 +83,9 @@ pub struct NetworkInfo {
     channels: HashMap<ChannelId, Arc<ChannelInfo>>,
     stopped: bool,
     new_realtime_channel_observers: Vec<Arc<dyn Fn(Arc<ChannelInfo>) + Send + Sync>>,
+    peer_event_observers: Vec<PeerEventObserver>,
+    /// Channels that were closed for a reason that can't be derived from the channel itself
+    close_reasons: HashMap<ChannelId, DisconnectReason>,
     attempts: AttemptContainer,
     network_config: NetworkConfig,
     excluded_peers: PeerExclusion,
@@ -94,6 +98,8 @@ impl NetworkInfo {
             channels: HashMap::new(),
             stopped: false,
             new_realtime_channel_observers: Vec::new(),
+            peer_event_observers: Vec::new(),
+            close_reasons: HashMap::new(),
             attempts: Default::default(),
             network_config,
             excluded_peers: PeerExclusion::new(),
@@ -118,6 +124,16 @@ impl NetworkInfo {
         self.new_realtime_channel_observers.clone()
     }
 
+    pub fn on_peer_event(&mut self, observer: PeerEventObserver) {
+        self.peer_event_observers.push(observer);
+    }
+
+    fn notify(&self, event: PeerEvent) {
+        for observer in &self.peer_event_observers {
+            observer(&event);
+        }
+    }
+
     pub fn is_inbound_slot_available(&self) -> bool {
         self.count_by_direction(ChannelDirection::Inbound)
             < self.network_config.max_inbound_connections
@@ -126,6 +142,14 @@ impl NetworkInfo {
     /// Perma bans are used for prohibiting a node to connect to itself.
     pub fn perma_ban(&mut self, peer_addr: SocketAddrV6) {
         self.excluded_peers.perma_ban(peer_addr);
+        for channel in self.find_channels_by_peering_addr(&peer_addr) {
+            self.close_reasons
+                .insert(channel.channel_id(), DisconnectReason::Excluded);
+        }
+        self.notify(PeerEvent::Excluded {
+            peer_addr,
+            permanent: true,
+        });
     }
 
     pub fn is_excluded(&mut self, peer_addr: &SocketAddrV6, now: Timestamp) -> bool {
@@ -166,6 +190,7 @@ impl NetworkInfo {
             now,
         ));
         self.channels.insert(channel_id, channel_info.clone());
+        self.notify(PeerEvent::Connected(channel_info.clone()));
         Ok(channel_info)
     }
 
@@ -188,7 +213,18 @@ impl NetworkInfo {
     }
 
     pub fn remove(&mut self, channel_id: ChannelId) {
-        self.channels.remove(&channel_id);
+        if let Some(channel) = self.channels.remove(&channel_id) {
+            self.notify_disconnected(channel);
+        }
+    }
+
+    fn notify_disconnected(&mut self, channel: Arc<ChannelInfo>) {
+        let reason = match self.close_reasons.remove(&channel.channel_id()) {
+            Some(reason) => reason,
+            None if channel.timed_out() => DisconnectReason::Timeout,
+            None => DisconnectReason::Purge,
+        };
+        self.notify(PeerEvent::Disconnected { channel, reason });
     }
 
     pub fn set_node_id(&self, channel_id: ChannelId, node_id: NodeId) {
@@ -300,6 +336,7 @@ impl NetworkInfo {
         for entry in self.channels.values() {
             if now - entry.last_activity() >= cutoff_period {
                 debug!(remote_addr = ?entry.peer_addr(), channel_id = %entry.channel_id(), mode = ?entry.mode(), "Closing idle channel");
+                entry.set_timed_out(true);
                 entry.close();
             }
         }
@@ -328,6 +365,7 @@ impl NetworkInfo {
         for channel in &dead_channels {
             debug!("Removing dead channel: {}", channel.peer_addr());
             self.channels.remove(&channel.channel_id());
+            self.notify_disconnected(channel.clone());
         }
 
         dead_channels
@@ -546,6 +584,15 @@ impl NetworkInfo {
 
         self.excluded_peers
             .peer_misbehaved(&channel.peer_addr(), now);
+        self.close_reasons
+            .insert(channel_id, DisconnectReason::Misbehaved);
+        // A peer only gets excluded after it misbehaved repeatedly
+        if self.excluded_peers.is_excluded(&channel.peer_addr(), now) {
+            self.notify(PeerEvent::Excluded {
+                peer_addr: channel.peer_addr(),
+                permanent: false,
+            });
+        }
 
         let peer_addr = channel.peer_addr();
         let mode = channel.mode();
@@ -565,6 +612,7 @@ impl NetworkInfo {
                 channel.close();
             }
             self.channels.clear();
+            self.close_reasons.clear();
             self.stopped = true;
             true
         }
@@ -620,8 +668,15 @@ impl NetworkInfo {
             }
         }
 
+        self.notify(PeerEvent::HandshakeCompleted {
+            channel: channel.clone(),
+            node_id,
+            protocol_version: channel.protocol_version(),
+        });
+
         channel.set_node_id(node_id);
         channel.set_mode(ChannelMode::Realtime);
+        self.notify(PeerEvent::RealtimeUpgrade(channel.clone()));
 
         let observers = self.new_realtime_channel_observers();
         let channel = channel.clone();
@@ -814,6 +869,141 @@ mod tests {
         );
     }
 
+    mod peer_events {
+        use super::*;
+        use std::sync::Mutex;
+
+        #[test]
+        fn connect_handshake_and_upgrade() {
+            let (mut network, events) = network_with_event_tracker();
+            let channel = add_channel(&mut network);
+            network.upgrade_to_realtime_connection(channel.channel_id(), NodeId::from(42));
+
+            assert_eq!(
+                *events.lock().unwrap(),
+                ["connected", "handshake_completed", "realtime_upgrade"]
+            );
+        }
+
+        #[test]
+        fn disconnect_after_timeout() {
+            let (mut network, events) = network_with_event_tracker();
+            let now = Timestamp::new_test_instance();
+            let channel = add_channel(&mut network);
+            channel.set_last_activity(now - Duration::from_secs(300));
+
+            network.purge(now, Duration::from_secs(1));
+
+            assert_eq!(
+                events.lock().unwrap().last().unwrap(),
+                "disconnected:timeout"
+            );
+        }
+
+        #[test]
+        fn disconnect_closed_channel() {
+            let (mut network, events) = network_with_event_tracker();
+            let channel = add_channel(&mut network);
+            channel.close();
+
+            network.purge(Timestamp::new_test_instance(), Duration::from_secs(1));
+
+            assert_eq!(events.lock().unwrap().last().unwrap(), "disconnected:purge");
+        }
+
+        #[test]
+        fn disconnect_misbehaving_peer() {
+            let (mut network, events) = network_with_event_tracker();
+            let channel = add_channel(&mut network);
+
+            network.peer_misbehaved(channel.channel_id(), Timestamp::new_test_instance());
+            network.purge(Timestamp::new_test_instance(), Duration::from_secs(1));
+
+            assert_eq!(
+                *events.lock().unwrap(),
+                ["connected", "disconnected:misbehaved"]
+            );
+        }
+
+        #[test]
+        fn exclude_peer_that_misbehaves_repeatedly() {
+            let (mut network, events) = network_with_event_tracker();
+            let now = Timestamp::new_test_instance();
+            let channel = add_channel(&mut network);
+            network.peer_misbehaved(channel.channel_id(), now);
+            network.purge(now, Duration::from_secs(1));
+            let channel = add_channel(&mut network);
+
+            network.peer_misbehaved(channel.channel_id(), now);
+
+            assert_eq!(events.lock().unwrap().last().unwrap(), "excluded");
+        }
+
+        #[test]
+        fn no_handshake_event_for_duplicate_node_id() {
+            let (mut network, events) = network_with_event_tracker();
+            let channel = add_channel(&mut network);
+            network.upgrade_to_realtime_connection(channel.channel_id(), NodeId::from(42));
+            let duplicate = network
+                .add(
+                    TEST_ENDPOINT_1,
+                    SocketAddrV6::new(*TEST_ENDPOINT_2.ip(), TEST_ENDPOINT_2.port() + 1, 0, 0),
+                    ChannelDirection::Outbound,
+                    ChannelMode::Realtime,
+                    Timestamp::new_test_instance(),
+                )
+                .unwrap();
+            events.lock().unwrap().clear();
+
+            network.upgrade_to_realtime_connection(duplicate.channel_id(), NodeId::from(42));
+
+            assert!(events.lock().unwrap().is_empty());
+        }
+
+        #[test]
+        fn disconnect_banned_peer() {
+            let (mut network, events) = network_with_event_tracker();
+            let channel = add_channel(&mut network);
+
+            network.perma_ban(TEST_ENDPOINT_2);
+            channel.close();
+            network.remove(channel.channel_id());
+
+            assert_eq!(
+                *events.lock().unwrap(),
+                ["connected", "excluded", "disconnected:excluded"]
+            );
+        }
+
+        fn add_channel(network: &mut NetworkInfo) -> Arc<ChannelInfo> {
+            network
+                .add(
+                    TEST_ENDPOINT_1,
+                    TEST_ENDPOINT_2,
+                    ChannelDirection::Outbound,
+                    ChannelMode::Realtime,
+                    Timestamp::new_test_instance(),
+                )
+                .unwrap()
+        }
+
+        fn network_with_event_tracker() -> (NetworkInfo, Arc<Mutex<Vec<String>>>) {
+            let mut network = NetworkInfo::new_test_instance();
+            let events = Arc::new(Mutex::new(Vec::new()));
+            let events2 = events.clone();
+            network.on_peer_event(Arc::new(move |event| {
+                let name = match event {
+                    PeerEvent::Disconnected { reason, .. } => {
+                        format!("disconnected:{}", reason.as_str())
+                    }
+                    _ => event.as_str().to_owned(),
+                };
+                events2.lock().unwrap().push(name);
+            }));
+            (network, events)
+        }
+    }
+
     mod purging {
         use super::*;
 
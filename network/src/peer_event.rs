@@ -0,0 +1,63 @@
+use crate::ChannelInfo;
+use rsban_core::NodeId;
+use std::{net::SocketAddrV6, sync::Arc};
+
+/// Why a channel was removed from the network
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisconnectReason {
+    /// The channel was idle for too long
+    Timeout,
+    /// The channel was closed for any other reason (socket closed, old protocol version...)
+    Purge,
+    /// The peer sent invalid data and was added to the exclusion list
+    Misbehaved,
+    /// The peer was permanently banned
+    Excluded,
+}
+
+impl DisconnectReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisconnectReason::Timeout => "timeout",
+            DisconnectReason::Purge => "purge",
+            DisconnectReason::Misbehaved => "misbehaved",
+            DisconnectReason::Excluded => "excluded",
+        }
+    }
+}
+
+/// A step in the lifecycle of a peer connection
+#[derive(Clone)]
+pub enum PeerEvent {
+    Connected(Arc<ChannelInfo>),
+    HandshakeCompleted {
+        channel: Arc<ChannelInfo>,
+        node_id: NodeId,
+        protocol_version: u8,
+    },
+    RealtimeUpgrade(Arc<ChannelInfo>),
+    Disconnected {
+        channel: Arc<ChannelInfo>,
+        reason: DisconnectReason,
+    },
+    /// The peer was added to the exclusion list
+    Excluded {
+        peer_addr: SocketAddrV6,
+        permanent: bool,
+    },
+}
+
+impl PeerEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PeerEvent::Connected(_) => "connected",
+            PeerEvent::HandshakeCompleted { .. } => "handshake_completed",
+            PeerEvent::RealtimeUpgrade(_) => "realtime_upgrade",
+            PeerEvent::Disconnected { .. } => "disconnected",
+            PeerEvent::Excluded { .. } => "excluded",
+        }
+    }
+}
+
+/// Observers are called while the network info is locked, so they must not access it
+pub type PeerEventObserver = Arc<dyn Fn(&PeerEvent) + Send + Sync>;
@@ -6,21 +6,24 @@ use crate::{
     bootstrap::{BootstrapAscendingConfig, BootstrapInitiatorConfig, BootstrapServerConfig},
     cementation::ConfirmingSetConfig,
     consensus::{
-        ActiveElectionsConfig, HintedSchedulerConfig, OptimisticSchedulerConfig,
-        PriorityBucketConfig, RequestAggregatorConfig, VoteCacheConfig, VoteProcessorConfig,
+        ActiveElectionsConfig, EquivocationDetectorConfig, HintedSchedulerConfig,
+        OptimisticSchedulerConfig, PriorityBucketConfig, RequestAggregatorConfig, VoteCacheConfig,
+        VoteProcessorConfig,
     },
+    ledger_analytics::LedgerAnalyticsConfig,
     stats::StatsConfig,
     transport::{MessageProcessorConfig, TcpConfig},
+    work::WorkBackendConfig,
     IpcConfig, NetworkParams, DEV_NETWORK_PARAMS,
 };
 use once_cell::sync::Lazy;
 use rand::{thread_rng, Rng};
 use rsban_core::{
     utils::{get_env_or_default_string, is_sanitizer_build, Peer},
-    Account, Amount, PublicKey,
+    Account, Amount, KdfVariant, KeyDerivationFunction, PublicKey,
 };
 use rsban_store_lmdb::LmdbConfig;
-use std::{cmp::max, net::Ipv6Addr, time::Duration};
+use std::{cmp::max, collections::HashMap, net::Ipv6Addr, time::Duration};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct NodeConfig {
@@ -81,6 +84,12 @@ pub struct NodeConfig {
     pub preconfigured_representatives: Vec<PublicKey>,
     pub max_pruning_age_s: i64,
     pub max_pruning_depth: u64,
+    /// Accounts that are never pruned
+    pub pruning_keep_accounts: Vec<Account>,
+    /// Per account overrides of max_pruning_depth
+    pub pruning_account_depths: HashMap<Account, u64>,
+    /// Never prune the accounts of the local wallets
+    pub pruning_keep_wallet_accounts: bool,
     pub callback_address: String,
     pub callback_port: u16,
     pub callback_target: String,
@@ -90,6 +99,7 @@ pub struct NodeConfig {
     pub stat_config: StatsConfig,
     pub lmdb_config: LmdbConfig,
     pub vote_cache: VoteCacheConfig,
+    pub equivocation_detector: EquivocationDetectorConfig,
     pub rep_crawler_query_timeout: Duration,
     pub block_processor: BlockProcessorConfig,
     pub active_elections: ActiveElectionsConfig,
@@ -101,6 +111,9 @@ pub struct NodeConfig {
     pub local_block_broadcaster: LocalBlockBroadcasterConfig,
     pub confirming_set: ConfirmingSetConfig,
     pub monitor: MonitorConfig,
+    pub ledger_analytics: LedgerAnalyticsConfig,
+    pub wallet_kdf: WalletKdfConfig,
+    pub work_backend: WorkBackendConfig,
     pub backlog: BacklogPopulationConfig,
     pub network_duplicate_filter_cutoff: u64,
 }
@@ -294,6 +307,9 @@ impl NodeConfig {
                 5 * 60
             }, // 1 day; 5 minutes for beta network
             max_pruning_depth: 0,
+            pruning_keep_accounts: Vec::new(),
+            pruning_account_depths: HashMap::new(),
+            pruning_keep_wallet_accounts: false,
             callback_address: String::new(),
             callback_port: 0,
             callback_target: String::new(),
@@ -310,6 +326,7 @@ impl NodeConfig {
             },
             priority_bucket: Default::default(),
             vote_cache: Default::default(),
+            equivocation_detector: Default::default(),
             active_elections: Default::default(),
             rep_crawler_query_timeout: if network_params.network.is_dev_network() {
                 Duration::from_secs(1)
@@ -331,6 +348,9 @@ impl NodeConfig {
             ),
             confirming_set: Default::default(),
             monitor: Default::default(),
+            ledger_analytics: Default::default(),
+            wallet_kdf: WalletKdfConfig::new(network_params.kdf_work),
+            work_backend: Default::default(),
             backlog: Default::default(),
             network_duplicate_filter_cutoff: 60,
         }
@@ -360,3 +380,33 @@ impl Default for MonitorConfig {
         }
     }
 }
+
+/// KDF parameters for newly created wallets. Existing wallets keep the
+/// parameters they were created with until they are upgraded explicitly.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WalletKdfConfig {
+    pub variant: KdfVariant,
+    pub memory_cost: u32,
+    pub time_cost: u32,
+    pub lanes: u32,
+}
+
+impl WalletKdfConfig {
+    pub fn new(kdf_work: u32) -> Self {
+        Self {
+            variant: KdfVariant::Argon2d,
+            memory_cost: kdf_work,
+            time_cost: 1,
+            lanes: 1,
+        }
+    }
+
+    pub fn kdf(&self) -> anyhow::Result<KeyDerivationFunction> {
+        KeyDerivationFunction::with_params(
+            self.variant,
+            self.memory_cost,
+            self.time_cost,
+            self.lanes,
+        )
+    }
+}
@@ -1,5 +1,6 @@
 use crate::block_processing::BlockProcessorConfig;
 use rsban_ledger::GenerateCacheFlags;
+use std::time::Duration;
 
 #[derive(Clone)]
 pub struct NodeFlags {
@@ -26,8 +27,14 @@ pub struct NodeFlags {
     pub disable_max_peers_per_subnetwork: bool, // For testing only
     pub disable_search_pending: bool,           // For testing only
     pub enable_pruning: bool,
+    pub enable_delegators_index: bool,
+    pub enable_block_height_index: bool,
+    pub enable_incoming_index: bool,
+    pub in_memory_ledger: bool,
     pub fast_bootstrap: bool,
     pub read_only: bool,
+    /// How often the ledger caches are reloaded in read-only mode
+    pub read_only_reload_interval: Duration,
     pub disable_connection_cleanup: bool,
     pub generate_cache: GenerateCacheFlags,
     pub inactive_node: bool,
@@ -64,8 +71,13 @@ impl NodeFlags {
             disable_max_peers_per_subnetwork: false,
             disable_search_pending: false,
             enable_pruning: false,
+            enable_delegators_index: false,
+            enable_block_height_index: false,
+            enable_incoming_index: false,
+            in_memory_ledger: false,
             fast_bootstrap: false,
             read_only: false,
+            read_only_reload_interval: Duration::from_secs(60),
             disable_connection_cleanup: false,
             generate_cache: GenerateCacheFlags::new(),
             inactive_node: false,
@@ -62,8 +62,12 @@ impl From<&DaemonConfig> for OpenclToml {
 
 #[cfg(test)]
 mod tests {
-    use crate::config::{DaemonConfig, DaemonToml};
+    use crate::{
+        config::{DaemonConfig, DaemonToml},
+        transport::RateLimit,
+    };
     use rsban_core::Networks;
+    use rsban_messages::MessageType;
     use std::path::PathBuf;
 
     static CUSTOM_TOML_STR: &str = r#"[node]
@@ -154,6 +158,24 @@ mod tests {
         enable = false
         interval = 999
 
+        [node.ledger_analytics]
+        enable = true
+        interval = 999
+        top_accounts = 999
+
+        [node.wallet_kdf]
+        variant = "argon2id"
+        memory_cost = 999
+        time_cost = 999
+        lanes = 999
+
+        [node.work_backend]
+        kind = "process"
+        local_fallback = false
+        command = "/usr/bin/work-generator"
+        args = ["--gpu"]
+        timeout = 999
+
         [node.ipc.local]
         allow_unsafe = true
         enable = true
@@ -208,6 +230,9 @@ mod tests {
         secondary_work_peers = ["dev.org:998"]
         max_pruning_age = 999
         max_pruning_depth = 999
+        pruning_keep_accounts = ["ban_3arg3asgtigae3xckabaaewkx3bzsh7nwz7jkmjos79ihyaxwphhm6qgjps4"]
+        pruning_keep_wallet_accounts = true
+        pruning_account_depths = { ban_3arg3asgtigae3xckabaaewkx3bzsh7nwz7jkmjos79ihyaxwphhm6qgjps4 = 999 }
 
         [node.vote_cache]
         age_cutoff = 999
@@ -253,6 +278,14 @@ mod tests {
         [node.message_processor]
         threads = 999
         max_queue = 999
+        max_rate_limit_violations = 999
+
+        [node.message_processor.peer_rate_limit]
+        rate = 999
+        burst = 999
+
+        [node.message_processor.type_rate_limits]
+        confirm_req = { rate = 999, burst = 999 }
 
         [opencl]
         device = 999
@@ -590,6 +623,52 @@ mod tests {
             default_cfg.node.monitor.interval
         );
 
+        // Ledger Analytics section
+        assert_ne!(
+            deserialized.node.ledger_analytics,
+            default_cfg.node.ledger_analytics
+        );
+
+        // Wallet KDF section
+        assert_ne!(
+            deserialized.node.wallet_kdf.variant,
+            default_cfg.node.wallet_kdf.variant
+        );
+        assert_ne!(
+            deserialized.node.wallet_kdf.memory_cost,
+            default_cfg.node.wallet_kdf.memory_cost
+        );
+        assert_ne!(
+            deserialized.node.wallet_kdf.time_cost,
+            default_cfg.node.wallet_kdf.time_cost
+        );
+        assert_ne!(
+            deserialized.node.wallet_kdf.lanes,
+            default_cfg.node.wallet_kdf.lanes
+        );
+
+        // Work backend section
+        assert_ne!(
+            deserialized.node.work_backend.kind,
+            default_cfg.node.work_backend.kind
+        );
+        assert_ne!(
+            deserialized.node.work_backend.local_fallback,
+            default_cfg.node.work_backend.local_fallback
+        );
+        assert_ne!(
+            deserialized.node.work_backend.command,
+            default_cfg.node.work_backend.command
+        );
+        assert_ne!(
+            deserialized.node.work_backend.args,
+            default_cfg.node.work_backend.args
+        );
+        assert_ne!(
+            deserialized.node.work_backend.timeout,
+            default_cfg.node.work_backend.timeout
+        );
+
         // IPC Local section
         assert_ne!(
             deserialized
@@ -946,6 +1025,24 @@ mod tests {
             default_cfg.node.request_aggregator.batch_size
         );
 
+        // Experimental section
+        assert_ne!(
+            deserialized.node.max_pruning_depth,
+            default_cfg.node.max_pruning_depth
+        );
+        assert_ne!(
+            deserialized.node.pruning_keep_accounts,
+            default_cfg.node.pruning_keep_accounts
+        );
+        assert_ne!(
+            deserialized.node.pruning_keep_wallet_accounts,
+            default_cfg.node.pruning_keep_wallet_accounts
+        );
+        assert_ne!(
+            deserialized.node.pruning_account_depths,
+            default_cfg.node.pruning_account_depths
+        );
+
         // Message Processor section
         assert_ne!(
             deserialized.node.message_processor.threads,
@@ -955,6 +1052,14 @@ mod tests {
             deserialized.node.message_processor.max_queue,
             default_cfg.node.message_processor.max_queue
         );
+        assert_ne!(
+            deserialized.node.message_processor.rate_limiter,
+            default_cfg.node.message_processor.rate_limiter
+        );
+        assert_eq!(
+            deserialized.node.message_processor.rate_limiter.type_limits[&MessageType::ConfirmReq],
+            RateLimit::new(999, 999)
+        );
 
         // OpenCL section
         assert_ne!(deserialized.opencl.device, default_cfg.opencl.device);
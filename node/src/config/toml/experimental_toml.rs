@@ -1,12 +1,15 @@
 use crate::config::NodeConfig;
-use rsban_core::utils::Peer;
+use rsban_core::{utils::Peer, Account};
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
+use std::{collections::BTreeMap, str::FromStr};
 
 #[derive(Deserialize, Serialize)]
 pub struct ExperimentalToml {
     pub max_pruning_age: Option<u64>,
     pub max_pruning_depth: Option<u64>,
+    pub pruning_keep_accounts: Option<Vec<String>>,
+    pub pruning_keep_wallet_accounts: Option<bool>,
+    pub pruning_account_depths: Option<BTreeMap<String, u64>>,
     pub secondary_work_peers: Option<Vec<String>>,
 }
 
@@ -18,6 +21,27 @@ impl NodeConfig {
         if let Some(max_pruning_depth) = toml.max_pruning_depth {
             self.max_pruning_depth = max_pruning_depth;
         }
+        if let Some(keep_accounts) = &toml.pruning_keep_accounts {
+            self.pruning_keep_accounts = keep_accounts
+                .iter()
+                .map(|string| {
+                    Account::decode_account(string).expect("Invalid pruning keep account")
+                })
+                .collect();
+        }
+        if let Some(keep_wallet_accounts) = toml.pruning_keep_wallet_accounts {
+            self.pruning_keep_wallet_accounts = keep_wallet_accounts;
+        }
+        if let Some(account_depths) = &toml.pruning_account_depths {
+            self.pruning_account_depths = account_depths
+                .iter()
+                .map(|(string, depth)| {
+                    let account =
+                        Account::decode_account(string).expect("Invalid pruning depth account");
+                    (account, *depth)
+                })
+                .collect();
+        }
         if let Some(secondary_work_peers) = &toml.secondary_work_peers {
             self.secondary_work_peers = secondary_work_peers
                 .iter()
@@ -39,6 +63,21 @@ impl From<&NodeConfig> for ExperimentalToml {
             ),
             max_pruning_age: Some(config.max_pruning_age_s as u64),
             max_pruning_depth: Some(config.max_pruning_depth),
+            pruning_keep_accounts: Some(
+                config
+                    .pruning_keep_accounts
+                    .iter()
+                    .map(|account| account.encode_account())
+                    .collect(),
+            ),
+            pruning_keep_wallet_accounts: Some(config.pruning_keep_wallet_accounts),
+            pruning_account_depths: Some(
+                config
+                    .pruning_account_depths
+                    .iter()
+                    .map(|(account, depth)| (account.encode_account(), *depth))
+                    .collect(),
+            ),
         }
     }
 }
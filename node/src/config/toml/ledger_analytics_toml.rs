@@ -0,0 +1,44 @@
+use crate::ledger_analytics::LedgerAnalyticsConfig;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Deserialize, Serialize)]
+pub struct LedgerAnalyticsToml {
+    pub enable: Option<bool>,
+    pub interval: Option<u64>,
+    pub top_accounts: Option<usize>,
+}
+
+impl Default for LedgerAnalyticsToml {
+    fn default() -> Self {
+        let config = LedgerAnalyticsConfig::default();
+        (&config).into()
+    }
+}
+
+impl From<&LedgerAnalyticsToml> for LedgerAnalyticsConfig {
+    fn from(toml: &LedgerAnalyticsToml) -> Self {
+        let mut config = LedgerAnalyticsConfig::default();
+
+        if let Some(enabled) = toml.enable {
+            config.enabled = enabled;
+        }
+        if let Some(interval) = toml.interval {
+            config.interval = Duration::from_secs(interval);
+        }
+        if let Some(top_accounts) = toml.top_accounts {
+            config.top_accounts = top_accounts;
+        }
+        config
+    }
+}
+
+impl From<&LedgerAnalyticsConfig> for LedgerAnalyticsToml {
+    fn from(config: &LedgerAnalyticsConfig) -> Self {
+        Self {
+            enable: Some(config.enabled),
+            interval: Some(config.interval.as_secs()),
+            top_accounts: Some(config.top_accounts),
+        }
+    }
+}
@@ -1,10 +1,42 @@
-use crate::transport::MessageProcessorConfig;
+use crate::transport::{MessageProcessorConfig, RateLimit};
+use rsban_messages::MessageType;
 use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, str::FromStr};
+use tracing::warn;
 
 #[derive(Deserialize, Serialize)]
 pub struct MessageProcessorToml {
     pub max_queue: Option<usize>,
     pub threads: Option<usize>,
+    pub max_rate_limit_violations: Option<usize>,
+    pub peer_rate_limit: Option<RateLimitToml>,
+    pub type_rate_limits: Option<BTreeMap<String, RateLimitToml>>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct RateLimitToml {
+    pub rate: Option<usize>,
+    pub burst: Option<usize>,
+}
+
+impl RateLimitToml {
+    fn merge_into(&self, limit: &mut RateLimit) {
+        if let Some(rate) = self.rate {
+            limit.rate = rate;
+        }
+        if let Some(burst) = self.burst {
+            limit.burst = burst;
+        }
+    }
+}
+
+impl From<&RateLimit> for RateLimitToml {
+    fn from(limit: &RateLimit) -> Self {
+        Self {
+            rate: Some(limit.rate),
+            burst: Some(limit.burst),
+        }
+    }
 }
 
 impl MessageProcessorConfig {
@@ -15,6 +47,26 @@ impl MessageProcessorConfig {
         if let Some(max_queue) = toml.max_queue {
             self.max_queue = max_queue;
         }
+        if let Some(max_violations) = toml.max_rate_limit_violations {
+            self.rate_limiter.max_violations = max_violations;
+        }
+        if let Some(peer_limit) = &toml.peer_rate_limit {
+            peer_limit.merge_into(&mut self.rate_limiter.peer_limit);
+        }
+        if let Some(type_limits) = &toml.type_rate_limits {
+            for (name, limit_toml) in type_limits {
+                let Ok(message_type) = MessageType::from_str(name) else {
+                    warn!("Ignoring rate limit for unknown message type: {}", name);
+                    continue;
+                };
+                let limit = self
+                    .rate_limiter
+                    .type_limits
+                    .entry(message_type)
+                    .or_insert(RateLimit::UNLIMITED);
+                limit_toml.merge_into(limit);
+            }
+        }
     }
 }
 
@@ -23,6 +75,16 @@ impl From<&MessageProcessorConfig> for MessageProcessorToml {
         Self {
             threads: Some(config.threads),
             max_queue: Some(config.max_queue),
+            max_rate_limit_violations: Some(config.rate_limiter.max_violations),
+            peer_rate_limit: Some((&config.rate_limiter.peer_limit).into()),
+            type_rate_limits: Some(
+                config
+                    .rate_limiter
+                    .type_limits
+                    .iter()
+                    .map(|(message_type, limit)| (message_type.as_str().to_owned(), limit.into()))
+                    .collect(),
+            ),
         }
     }
 }
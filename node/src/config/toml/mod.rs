@@ -9,6 +9,7 @@ mod experimental_toml;
 mod hinted_scheduler_toml;
 mod httpcallback_toml;
 mod ipc_toml;
+mod ledger_analytics_toml;
 mod lmdb_toml;
 mod message_processor_toml;
 mod monitor_toml;
@@ -22,7 +23,9 @@ mod request_aggregator_toml;
 mod stats_toml;
 mod vote_cache_toml;
 mod vote_processor_toml;
+mod wallet_kdf_toml;
 mod websocket_toml;
+mod work_backend_toml;
 
 pub use active_elections_toml::*;
 pub use backlog_population_toml::*;
@@ -35,6 +38,7 @@ pub use experimental_toml::*;
 pub use hinted_scheduler_toml::*;
 pub use httpcallback_toml::*;
 pub use ipc_toml::*;
+pub use ledger_analytics_toml::*;
 pub use lmdb_toml::*;
 pub use message_processor_toml::*;
 pub use monitor_toml::*;
@@ -48,4 +52,6 @@ pub use request_aggregator_toml::*;
 pub use stats_toml::*;
 pub use vote_cache_toml::*;
 pub use vote_processor_toml::*;
+pub use wallet_kdf_toml::*;
 pub use websocket_toml::*;
+pub use work_backend_toml::*;
@@ -59,6 +59,7 @@ pub struct NodeToml {
     pub experimental: Option<ExperimentalToml>,
     pub httpcallback: Option<HttpcallbackToml>,
     pub ipc: Option<IpcToml>,
+    pub ledger_analytics: Option<LedgerAnalyticsToml>,
     pub lmdb: Option<LmdbToml>,
     pub message_processor: Option<MessageProcessorToml>,
     pub monitor: Option<MonitorToml>,
@@ -70,6 +71,8 @@ pub struct NodeToml {
     pub statistics: Option<StatsToml>,
     pub vote_cache: Option<VoteCacheToml>,
     pub vote_processor: Option<VoteProcessorToml>,
+    pub wallet_kdf: Option<WalletKdfToml>,
+    pub work_backend: Option<WorkBackendToml>,
     pub websocket: Option<WebsocketToml>,
     pub backlog_population: Option<BacklogPopulationToml>,
 }
@@ -315,9 +318,21 @@ impl NodeConfig {
         if let Some(message_processor_toml) = &toml.message_processor {
             self.message_processor.merge_toml(message_processor_toml);
         }
+        if let Some(experimental_toml) = &toml.experimental {
+            self.merge_experimental_toml(experimental_toml);
+        }
+        if let Some(ledger_analytics_toml) = &toml.ledger_analytics {
+            self.ledger_analytics = ledger_analytics_toml.into();
+        }
         if let Some(monitor_toml) = &toml.monitor {
             self.monitor = monitor_toml.into();
         }
+        if let Some(wallet_kdf_toml) = &toml.wallet_kdf {
+            self.wallet_kdf.merge_toml(wallet_kdf_toml);
+        }
+        if let Some(work_backend_toml) = &toml.work_backend {
+            self.work_backend.merge_toml(work_backend_toml);
+        }
         if let Some(rep_crawler_weight_minimum) = &toml.rep_crawler_weight_minimum {
             self.rep_crawler_weight_minimum = Amount::decode_dec(&rep_crawler_weight_minimum)
                 .expect("Invalid rep crawler weight minimum");
@@ -423,7 +438,10 @@ impl From<&NodeConfig> for NodeToml {
             vote_processor: Some((&config.vote_processor).into()),
             request_aggregator: Some((&config.request_aggregator).into()),
             message_processor: Some((&config.message_processor).into()),
+            ledger_analytics: Some((&config.ledger_analytics).into()),
             monitor: Some((&config.monitor).into()),
+            wallet_kdf: Some((&config.wallet_kdf).into()),
+            work_backend: Some((&config.work_backend).into()),
             httpcallback: Some(config.into()),
             rep_crawler: Some(config.into()),
             experimental: Some(config.into()),
@@ -0,0 +1,38 @@
+use crate::config::WalletKdfConfig;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct WalletKdfToml {
+    pub variant: Option<String>,
+    pub memory_cost: Option<u32>,
+    pub time_cost: Option<u32>,
+    pub lanes: Option<u32>,
+}
+
+impl WalletKdfConfig {
+    pub fn merge_toml(&mut self, toml: &WalletKdfToml) {
+        if let Some(variant) = &toml.variant {
+            self.variant = variant.parse().expect("Invalid wallet kdf variant");
+        }
+        if let Some(memory_cost) = toml.memory_cost {
+            self.memory_cost = memory_cost;
+        }
+        if let Some(time_cost) = toml.time_cost {
+            self.time_cost = time_cost;
+        }
+        if let Some(lanes) = toml.lanes {
+            self.lanes = lanes;
+        }
+    }
+}
+
+impl From<&WalletKdfConfig> for WalletKdfToml {
+    fn from(config: &WalletKdfConfig) -> Self {
+        Self {
+            variant: Some(config.variant.to_string()),
+            memory_cost: Some(config.memory_cost),
+            time_cost: Some(config.time_cost),
+            lanes: Some(config.lanes),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::work::WorkBackendConfig;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Deserialize, Serialize)]
+pub struct WorkBackendToml {
+    pub kind: Option<String>,
+    pub local_fallback: Option<bool>,
+    pub command: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub timeout: Option<u64>,
+}
+
+impl WorkBackendConfig {
+    pub fn merge_toml(&mut self, toml: &WorkBackendToml) {
+        if let Some(kind) = &toml.kind {
+            self.kind = kind.parse().expect("Invalid work backend");
+        }
+        if let Some(local_fallback) = toml.local_fallback {
+            self.local_fallback = local_fallback;
+        }
+        if let Some(command) = &toml.command {
+            self.command = command.clone();
+        }
+        if let Some(args) = &toml.args {
+            self.args = args.clone();
+        }
+        if let Some(timeout) = toml.timeout {
+            self.timeout = Duration::from_secs(timeout);
+        }
+    }
+}
+
+impl From<&WorkBackendConfig> for WorkBackendToml {
+    fn from(config: &WorkBackendConfig) -> Self {
+        Self {
+            kind: Some(config.kind.to_string()),
+            local_fallback: Some(config.local_fallback),
+            command: Some(config.command.clone()),
+            args: Some(config.args.clone()),
+            timeout: Some(config.timeout.as_secs()),
+        }
+    }
+}
@@ -0,0 +1,382 @@
+use crate::stats::{DetailType, StatType, Stats};
+use rsban_core::{utils::ContainerInfo, BlockHash, PublicKey, QualifiedRoot, Vote};
+use std::{
+    collections::{HashMap, VecDeque},
+    mem::size_of,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+use tracing::warn;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EquivocationDetectorConfig {
+    /// Maximum number of (representative, root) final votes that are remembered
+    pub max_votes: usize,
+    /// Maximum number of detected equivocations that are kept as evidence
+    pub max_equivocations: usize,
+}
+
+impl Default for EquivocationDetectorConfig {
+    fn default() -> Self {
+        Self {
+            max_votes: 1024 * 256,
+            max_equivocations: 1024,
+        }
+    }
+}
+
+/// Two final votes signed by the same representative for different blocks under the same root.
+/// Both votes are kept, so that anyone can verify the signatures independently.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Equivocation {
+    pub representative: PublicKey,
+    pub root: QualifiedRoot,
+    pub first_hash: BlockHash,
+    pub first_vote: Arc<Vote>,
+    pub second_hash: BlockHash,
+    pub second_vote: Arc<Vote>,
+    pub detected: SystemTime,
+}
+
+pub type EquivocationCallback = Box<dyn Fn(&Equivocation) + Send + Sync>;
+
+/// Keeps a bounded index of final votes per (representative, root) and flags representatives
+/// that sign final votes for conflicting blocks
+pub struct EquivocationDetector {
+    config: EquivocationDetectorConfig,
+    stats: Arc<Stats>,
+    mutex: Mutex<DetectorState>,
+    observers: Mutex<Vec<EquivocationCallback>>,
+}
+
+impl EquivocationDetector {
+    pub fn new(config: EquivocationDetectorConfig, stats: Arc<Stats>) -> Self {
+        Self {
+            config,
+            stats,
+            mutex: Mutex::new(DetectorState::default()),
+            observers: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn on_equivocation(&self, callback: EquivocationCallback) {
+        self.observers.lock().unwrap().push(callback);
+    }
+
+    /// Records a final vote for `hash` which belongs to `root`.
+    /// Returns the equivocation if the representative already signed a final vote
+    /// for a different block with the same root
+    pub fn vote(
+        &self,
+        vote: &Arc<Vote>,
+        hash: BlockHash,
+        root: QualifiedRoot,
+    ) -> Option<Equivocation> {
+        if !vote.is_final() {
+            return None;
+        }
+
+        let equivocation = {
+            let mut guard = self.mutex.lock().unwrap();
+            let key = (vote.voting_account, root);
+            match guard.votes.get(&key) {
+                Some(existing) if existing.hash == hash => return None,
+                Some(existing) => {
+                    let equivocation = Equivocation {
+                        representative: vote.voting_account,
+                        root: key.1.clone(),
+                        first_hash: existing.hash,
+                        first_vote: existing.vote.clone(),
+                        second_hash: hash,
+                        second_vote: vote.clone(),
+                        detected: SystemTime::now(),
+                    };
+                    if guard.already_reported(&equivocation) {
+                        return None;
+                    }
+                    guard.equivocations.push_back(equivocation.clone());
+                    if guard.equivocations.len() > self.config.max_equivocations {
+                        guard.equivocations.pop_front();
+                    }
+                    equivocation
+                }
+                None => {
+                    guard.sequential.push_back(key.clone());
+                    guard.votes.insert(
+                        key,
+                        FinalVoteEntry {
+                            hash,
+                            vote: vote.clone(),
+                        },
+                    );
+                    if guard.sequential.len() > self.config.max_votes {
+                        if let Some(oldest) = guard.sequential.pop_front() {
+                            guard.votes.remove(&oldest);
+                        }
+                    }
+                    return None;
+                }
+            }
+        };
+
+        self.stats
+            .inc(StatType::EquivocationDetector, DetailType::Equivocation);
+        warn!(
+            representative = %equivocation.representative.as_account().encode_account(),
+            first = %equivocation.first_hash,
+            second = %equivocation.second_hash,
+            "Representative signed conflicting final votes"
+        );
+
+        let observers = self.observers.lock().unwrap();
+        for observer in observers.iter() {
+            observer(&equivocation);
+        }
+
+        Some(equivocation)
+    }
+
+    /// Returns the detected equivocations, newest first
+    pub fn equivocations(&self, max_count: usize) -> Vec<Equivocation> {
+        let guard = self.mutex.lock().unwrap();
+        guard
+            .equivocations
+            .iter()
+            .rev()
+            .take(max_count)
+            .cloned()
+            .collect()
+    }
+
+    pub fn equivocations_by(&self, representative: &PublicKey) -> Vec<Equivocation> {
+        let guard = self.mutex.lock().unwrap();
+        guard
+            .equivocations
+            .iter()
+            .rev()
+            .filter(|e| e.representative == *representative)
+            .cloned()
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.mutex.lock().unwrap().votes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn container_info(&self) -> ContainerInfo {
+        let guard = self.mutex.lock().unwrap();
+        [
+            (
+                "votes",
+                guard.votes.len(),
+                size_of::<PublicKey>() + size_of::<QualifiedRoot>() + size_of::<FinalVoteEntry>(),
+            ),
+            (
+                "equivocations",
+                guard.equivocations.len(),
+                size_of::<Equivocation>(),
+            ),
+        ]
+        .into()
+    }
+}
+
+struct FinalVoteEntry {
+    hash: BlockHash,
+    vote: Arc<Vote>,
+}
+
+#[derive(Default)]
+struct DetectorState {
+    votes: HashMap<(PublicKey, QualifiedRoot), FinalVoteEntry>,
+    sequential: VecDeque<(PublicKey, QualifiedRoot)>,
+    equivocations: VecDeque<Equivocation>,
+}
+
+impl DetectorState {
+    fn already_reported(&self, equivocation: &Equivocation) -> bool {
+        self.equivocations.iter().any(|e| {
+            e.representative == equivocation.representative
+                && e.root == equivocation.root
+                && e.second_hash == equivocation.second_hash
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::Direction;
+    use rsban_core::{PrivateKey, Root};
+
+    #[test]
+    fn empty() {
+        let detector = create_detector();
+        assert_eq!(detector.len(), 0);
+        assert!(detector.equivocations(usize::MAX).is_empty());
+    }
+
+    #[test]
+    fn same_hash_is_not_an_equivocation() {
+        let detector = create_detector();
+        let key = PrivateKey::from(1);
+        let hash = BlockHash::from(2);
+        let root = QualifiedRoot::new_test_instance();
+
+        assert!(detector
+            .vote(&final_vote(&key, hash), hash, root.clone())
+            .is_none());
+        assert!(detector.vote(&final_vote(&key, hash), hash, root).is_none());
+        assert_eq!(detector.len(), 1);
+    }
+
+    #[test]
+    fn ignore_non_final_votes() {
+        let detector = create_detector();
+        let key = PrivateKey::from(1);
+        let root = QualifiedRoot::new_test_instance();
+        let vote1 = Arc::new(Vote::new(&key, 1, 0, vec![BlockHash::from(2)]));
+        let vote2 = Arc::new(Vote::new(&key, 1, 0, vec![BlockHash::from(3)]));
+
+        detector.vote(&vote1, BlockHash::from(2), root.clone());
+        assert!(detector.vote(&vote2, BlockHash::from(3), root).is_none());
+        assert_eq!(detector.len(), 0);
+    }
+
+    #[test]
+    fn detect_conflicting_final_votes() {
+        let detector = create_detector();
+        let key = PrivateKey::from(1);
+        let root = QualifiedRoot::new_test_instance();
+        let vote1 = final_vote(&key, BlockHash::from(2));
+        let vote2 = final_vote(&key, BlockHash::from(3));
+
+        detector.vote(&vote1, BlockHash::from(2), root.clone());
+        let equivocation = detector
+            .vote(&vote2, BlockHash::from(3), root.clone())
+            .unwrap();
+
+        assert_eq!(equivocation.representative, key.public_key());
+        assert_eq!(equivocation.root, root);
+        assert_eq!(equivocation.first_hash, BlockHash::from(2));
+        assert_eq!(equivocation.first_vote, vote1);
+        assert_eq!(equivocation.second_hash, BlockHash::from(3));
+        assert_eq!(equivocation.second_vote, vote2);
+        assert_eq!(detector.equivocations(usize::MAX), vec![equivocation]);
+        assert_eq!(
+            detector.stats.count(
+                StatType::EquivocationDetector,
+                DetailType::Equivocation,
+                Direction::In
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn different_representatives_dont_conflict() {
+        let detector = create_detector();
+        let root = QualifiedRoot::new_test_instance();
+        let hash1 = BlockHash::from(2);
+        let hash2 = BlockHash::from(3);
+
+        detector.vote(
+            &final_vote(&PrivateKey::from(1), hash1),
+            hash1,
+            root.clone(),
+        );
+        let result = detector.vote(&final_vote(&PrivateKey::from(2), hash2), hash2, root);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn report_equivocation_only_once() {
+        let detector = create_detector();
+        let key = PrivateKey::from(1);
+        let root = QualifiedRoot::new_test_instance();
+        let hash1 = BlockHash::from(2);
+        let hash2 = BlockHash::from(3);
+
+        detector.vote(&final_vote(&key, hash1), hash1, root.clone());
+        detector.vote(&final_vote(&key, hash2), hash2, root.clone());
+        let result = detector.vote(&final_vote(&key, hash2), hash2, root);
+
+        assert!(result.is_none());
+        assert_eq!(detector.equivocations(usize::MAX).len(), 1);
+    }
+
+    #[test]
+    fn notify_observers() {
+        let detector = create_detector();
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let notified2 = notified.clone();
+        detector.on_equivocation(Box::new(move |e| {
+            notified2.lock().unwrap().push(e.clone());
+        }));
+        let key = PrivateKey::from(1);
+        let root = QualifiedRoot::new_test_instance();
+        let hash1 = BlockHash::from(2);
+        let hash2 = BlockHash::from(3);
+
+        detector.vote(&final_vote(&key, hash1), hash1, root.clone());
+        detector.vote(&final_vote(&key, hash2), hash2, root);
+
+        assert_eq!(notified.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn evict_oldest_votes() {
+        let detector = EquivocationDetector::new(
+            EquivocationDetectorConfig {
+                max_votes: 2,
+                max_equivocations: 1,
+            },
+            Arc::new(Stats::default()),
+        );
+        let key = PrivateKey::from(1);
+        for i in 0..3 {
+            let hash = BlockHash::from(i + 100);
+            let root = QualifiedRoot::new(Root::from(i), BlockHash::from(i));
+            detector.vote(&final_vote(&key, hash), hash, root);
+        }
+        assert_eq!(detector.len(), 2);
+
+        // The first vote was evicted, so a conflicting vote for its root can't be detected anymore
+        let hash = BlockHash::from(999);
+        let result = detector.vote(
+            &final_vote(&key, hash),
+            hash,
+            QualifiedRoot::new(Root::from(0), BlockHash::from(0)),
+        );
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn filter_by_representative() {
+        let detector = create_detector();
+        let key1 = PrivateKey::from(1);
+        let key2 = PrivateKey::from(2);
+        let root = QualifiedRoot::new_test_instance();
+        let hash1 = BlockHash::from(2);
+        let hash2 = BlockHash::from(3);
+
+        detector.vote(&final_vote(&key1, hash1), hash1, root.clone());
+        detector.vote(&final_vote(&key1, hash2), hash2, root);
+
+        assert_eq!(detector.equivocations_by(&key1.public_key()).len(), 1);
+        assert!(detector.equivocations_by(&key2.public_key()).is_empty());
+    }
+
+    fn create_detector() -> EquivocationDetector {
+        EquivocationDetector::new(Default::default(), Arc::new(Stats::default()))
+    }
+
+    fn final_vote(key: &PrivateKey, hash: BlockHash) -> Arc<Vote> {
+        Arc::new(Vote::new_final(key, vec![hash]))
+    }
+}
@@ -56,6 +56,10 @@ impl RecentlyConfirmedCache {
         self.mutex.lock().unwrap().by_hash.contains_key(hash)
     }
 
+    pub fn root_of(&self, hash: &BlockHash) -> Option<QualifiedRoot> {
+        self.mutex.lock().unwrap().by_hash.get(hash).cloned()
+    }
+
     pub fn clear(&self) {
         let mut guard = self.mutex.lock().unwrap();
         guard.sequential.clear();
@@ -0,0 +1,438 @@
+use crate::{
+    representatives::OnlineReps,
+    utils::{CancellationToken, Runnable},
+};
+use primitive_types::U256;
+use rsban_core::{utils::seconds_since_epoch, Account, AccountInfo, Amount, Epoch};
+use rsban_ledger::Ledger;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tracing::debug;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LedgerAnalyticsConfig {
+    pub enabled: bool,
+    pub interval: Duration,
+    /// Number of accounts in the rich list
+    pub top_accounts: usize,
+}
+
+impl Default for LedgerAnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(60 * 60),
+            top_accounts: 100,
+        }
+    }
+}
+
+/// Time windows for which the accounts with a recent change are counted
+pub const ACTIVE_ACCOUNT_WINDOWS: [Duration; 4] = [
+    Duration::from_secs(24 * 60 * 60),
+    Duration::from_secs(7 * 24 * 60 * 60),
+    Duration::from_secs(30 * 24 * 60 * 60),
+    Duration::from_secs(365 * 24 * 60 * 60),
+];
+
+/// Accounts with a balance in the range `min..max`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BalanceBucket {
+    pub min: Amount,
+    pub max: Amount,
+    pub accounts: u64,
+    pub balance: Amount,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LedgerAnalyticsReport {
+    /// Seconds since the UNIX epoch
+    pub created: u64,
+    pub scan_duration: Duration,
+    pub accounts: u64,
+    /// Non empty balance buckets in ascending order. Each bucket spans one decimal order of magnitude in raw
+    pub balance_distribution: Vec<BalanceBucket>,
+    /// Accounts with the highest balance in descending order
+    pub top_accounts: Vec<(Account, Amount)>,
+    /// Number of accounts that were modified within each of the `ACTIVE_ACCOUNT_WINDOWS`
+    pub active_accounts: Vec<(Duration, u64)>,
+    pub epochs: Vec<(Epoch, u64)>,
+    pub online_weight: Amount,
+    /// Minimum number of online representatives that together reach the quorum of the
+    /// online weight. `None` if the online representatives don't reach it
+    pub nakamoto_coefficient: Option<u64>,
+}
+
+/// Periodically scans all accounts and caches distribution statistics of the ledger
+pub struct LedgerAnalytics {
+    config: LedgerAnalyticsConfig,
+    ledger: Arc<Ledger>,
+    online_reps: Arc<Mutex<OnlineReps>>,
+    report: Mutex<Option<Arc<LedgerAnalyticsReport>>>,
+}
+
+impl LedgerAnalytics {
+    pub fn new(
+        config: LedgerAnalyticsConfig,
+        ledger: Arc<Ledger>,
+        online_reps: Arc<Mutex<OnlineReps>>,
+    ) -> Self {
+        Self {
+            config,
+            ledger,
+            online_reps,
+            report: Mutex::new(None),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// The result of the last scan
+    pub fn report(&self) -> Option<Arc<LedgerAnalyticsReport>> {
+        self.report.lock().unwrap().clone()
+    }
+
+    /// Scans the ledger and replaces the cached report
+    pub fn refresh(&self) -> Arc<LedgerAnalyticsReport> {
+        let started = Instant::now();
+        let now = seconds_since_epoch();
+        let stats = Mutex::new(AccountStats::new(self.config.top_accounts));
+        self.ledger.store.account.for_each_par(&|_txn, mut i, n| {
+            let mut partial = AccountStats::new(self.config.top_accounts);
+            while !i.eq(&n) {
+                if let Some((account, info)) = i.current() {
+                    partial.add(account, info, now);
+                }
+                i.next();
+            }
+            stats.lock().unwrap().merge(partial);
+        });
+        let stats = stats.into_inner().unwrap();
+
+        let (online_weight, nakamoto_coefficient) = self.representative_concentration();
+        let report = Arc::new(LedgerAnalyticsReport {
+            created: now,
+            scan_duration: started.elapsed(),
+            accounts: stats.accounts,
+            balance_distribution: stats.balance_distribution(),
+            top_accounts: stats.top_accounts(),
+            active_accounts: ACTIVE_ACCOUNT_WINDOWS
+                .iter()
+                .cloned()
+                .zip(stats.active_accounts)
+                .collect(),
+            epochs: stats.epochs(),
+            online_weight,
+            nakamoto_coefficient,
+        });
+
+        debug!(
+            "Ledger analytics: scanned {} accounts in {:?}",
+            report.accounts, report.scan_duration
+        );
+        *self.report.lock().unwrap() = Some(report.clone());
+        report
+    }
+
+    fn representative_concentration(&self) -> (Amount, Option<u64>) {
+        let (online_weight, quorum_percent, online_reps) = {
+            let online_reps = self.online_reps.lock().unwrap();
+            (
+                online_reps.online_weight(),
+                online_reps.quorum_percent(),
+                online_reps.online_reps().cloned().collect::<Vec<_>>(),
+            )
+        };
+        let weights = online_reps
+            .iter()
+            .map(|rep| self.ledger.rep_weights.weight(rep))
+            .collect();
+        let quorum =
+            U256::from(online_weight.number()) * U256::from(quorum_percent) / U256::from(100);
+        (
+            online_weight,
+            nakamoto_coefficient(weights, Amount::raw(quorum.as_u128())),
+        )
+    }
+}
+
+/// Minimum number of the given weights that add up to at least the quorum
+pub fn nakamoto_coefficient(mut weights: Vec<Amount>, quorum: Amount) -> Option<u64> {
+    if quorum.is_zero() {
+        return None;
+    }
+    weights.sort_by(|a, b| b.cmp(a));
+    let mut total = Amount::zero();
+    for (i, weight) in weights.iter().enumerate() {
+        total = total.saturating_add(*weight);
+        if total >= quorum {
+            return Some(i as u64 + 1);
+        }
+    }
+    None
+}
+
+const BALANCE_BUCKETS: usize = 40;
+
+struct AccountStats {
+    top_count: usize,
+    accounts: u64,
+    /// Index is the number of decimal digits of the raw balance
+    buckets: [(u64, Amount); BALANCE_BUCKETS],
+    top: BinaryHeap<Reverse<(Amount, Account)>>,
+    active_accounts: [u64; ACTIVE_ACCOUNT_WINDOWS.len()],
+    epochs: HashMap<Epoch, u64>,
+}
+
+impl AccountStats {
+    fn new(top_count: usize) -> Self {
+        Self {
+            top_count,
+            accounts: 0,
+            buckets: [(0, Amount::zero()); BALANCE_BUCKETS],
+            top: BinaryHeap::new(),
+            active_accounts: [0; ACTIVE_ACCOUNT_WINDOWS.len()],
+            epochs: HashMap::new(),
+        }
+    }
+
+    fn add(&mut self, account: &Account, info: &AccountInfo, now: u64) {
+        self.accounts += 1;
+
+        let bucket = &mut self.buckets[Self::bucket_index(info.balance)];
+        bucket.0 += 1;
+        bucket.1 = bucket.1.saturating_add(info.balance);
+
+        self.add_top(*account, info.balance);
+
+        let age = now.saturating_sub(info.modified);
+        for (i, window) in ACTIVE_ACCOUNT_WINDOWS.iter().enumerate() {
+            if age <= window.as_secs() {
+                self.active_accounts[i] += 1;
+            }
+        }
+
+        *self.epochs.entry(info.epoch).or_default() += 1;
+    }
+
+    fn add_top(&mut self, account: Account, balance: Amount) {
+        if self.top_count == 0 {
+            return;
+        }
+        self.top.push(Reverse((balance, account)));
+        if self.top.len() > self.top_count {
+            self.top.pop();
+        }
+    }
+
+    fn merge(&mut self, other: AccountStats) {
+        self.accounts += other.accounts;
+        for (bucket, (accounts, balance)) in self.buckets.iter_mut().zip(other.buckets) {
+            bucket.0 += accounts;
+            bucket.1 = bucket.1.saturating_add(balance);
+        }
+        for Reverse((balance, account)) in other.top {
+            self.add_top(account, balance);
+        }
+        for (i, count) in other.active_accounts.iter().enumerate() {
+            self.active_accounts[i] += count;
+        }
+        for (epoch, count) in other.epochs {
+            *self.epochs.entry(epoch).or_default() += count;
+        }
+    }
+
+    fn bucket_index(balance: Amount) -> usize {
+        balance
+            .number()
+            .checked_ilog10()
+            .map(|log| log as usize + 1)
+            .unwrap_or_default()
+    }
+
+    fn balance_distribution(&self) -> Vec<BalanceBucket> {
+        self.buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, (accounts, _))| *accounts > 0)
+            .map(|(i, (accounts, balance))| {
+                let (min, max) = match i {
+                    0 => (0, 1),
+                    _ => (
+                        10u128.pow(i as u32 - 1),
+                        10u128.checked_pow(i as u32).unwrap_or(u128::MAX),
+                    ),
+                };
+                BalanceBucket {
+                    min: Amount::raw(min),
+                    max: Amount::raw(max),
+                    accounts: *accounts,
+                    balance: *balance,
+                }
+            })
+            .collect()
+    }
+
+    fn top_accounts(&self) -> Vec<(Account, Amount)> {
+        let mut top: Vec<_> = self
+            .top
+            .iter()
+            .map(|Reverse((balance, account))| (*account, *balance))
+            .collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        top
+    }
+
+    fn epochs(&self) -> Vec<(Epoch, u64)> {
+        let mut epochs: Vec<_> = self
+            .epochs
+            .iter()
+            .map(|(epoch, count)| (*epoch, *count))
+            .collect();
+        epochs.sort_by_key(|(epoch, _)| *epoch as u8);
+        epochs
+    }
+}
+
+pub struct LedgerAnalyticsUpdater {
+    analytics: Arc<LedgerAnalytics>,
+}
+
+impl LedgerAnalyticsUpdater {
+    pub fn new(analytics: Arc<LedgerAnalytics>) -> Self {
+        Self { analytics }
+    }
+}
+
+impl Runnable for LedgerAnalyticsUpdater {
+    fn run(&mut self, _cancel_token: &CancellationToken) {
+        self.analytics.refresh();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_core::PublicKey;
+    use rsban_ledger::{LedgerConstants, RepWeightCache};
+    use rsban_store_lmdb::LmdbStore;
+
+    #[test]
+    fn scan_accounts() {
+        let ledger = ledger_with_accounts(&[
+            (Account::from(1), Amount::zero(), 0, Epoch::Epoch0),
+            (
+                Account::from(2),
+                Amount::raw(5),
+                seconds_since_epoch(),
+                Epoch::Epoch1,
+            ),
+            (Account::from(3), Amount::raw(50), 0, Epoch::Epoch2),
+            (
+                Account::from(4),
+                Amount::raw(70),
+                seconds_since_epoch(),
+                Epoch::Epoch2,
+            ),
+        ]);
+        let analytics = create_analytics(ledger, 2);
+
+        let report = analytics.refresh();
+
+        assert_eq!(analytics.report(), Some(report.clone()));
+        assert_eq!(report.accounts, 5); // including genesis
+        assert_eq!(
+            report.top_accounts[1..],
+            [(Account::from(4), Amount::raw(70))]
+        );
+        assert_eq!(report.top_accounts.len(), 2);
+        assert_eq!(
+            report.balance_distribution[..3],
+            [
+                BalanceBucket {
+                    min: Amount::zero(),
+                    max: Amount::raw(1),
+                    accounts: 1,
+                    balance: Amount::zero(),
+                },
+                BalanceBucket {
+                    min: Amount::raw(1),
+                    max: Amount::raw(10),
+                    accounts: 1,
+                    balance: Amount::raw(5),
+                },
+                BalanceBucket {
+                    min: Amount::raw(10),
+                    max: Amount::raw(100),
+                    accounts: 2,
+                    balance: Amount::raw(120),
+                },
+            ]
+        );
+        assert_eq!(report.active_accounts[0], (ACTIVE_ACCOUNT_WINDOWS[0], 3)); // including genesis
+        assert!(report.epochs.contains(&(Epoch::Epoch2, 2)));
+    }
+
+    #[test]
+    fn no_report_before_first_scan() {
+        let analytics = create_analytics(ledger_with_accounts(&[]), 10);
+        assert_eq!(analytics.report(), None);
+    }
+
+    #[test]
+    fn nakamoto_coefficient_of_weights() {
+        let weights = vec![Amount::raw(10), Amount::raw(50), Amount::raw(40)];
+        assert_eq!(
+            nakamoto_coefficient(weights.clone(), Amount::raw(67)),
+            Some(2)
+        );
+        assert_eq!(
+            nakamoto_coefficient(weights.clone(), Amount::raw(50)),
+            Some(1)
+        );
+        assert_eq!(
+            nakamoto_coefficient(weights.clone(), Amount::raw(101)),
+            None
+        );
+        assert_eq!(nakamoto_coefficient(weights, Amount::zero()), None);
+    }
+
+    fn create_analytics(ledger: Arc<Ledger>, top_accounts: usize) -> LedgerAnalytics {
+        let config = LedgerAnalyticsConfig {
+            enabled: true,
+            top_accounts,
+            ..Default::default()
+        };
+        LedgerAnalytics::new(config, ledger, Arc::new(Mutex::new(OnlineReps::default())))
+    }
+
+    fn ledger_with_accounts(accounts: &[(Account, Amount, u64, Epoch)]) -> Arc<Ledger> {
+        let ledger = Ledger::new(
+            Arc::new(LmdbStore::new_in_memory()),
+            LedgerConstants::unit_test(),
+            Amount::zero(),
+            Arc::new(RepWeightCache::new()),
+        )
+        .unwrap();
+        let mut txn = ledger.rw_txn();
+        for (account, balance, modified, epoch) in accounts {
+            let info = AccountInfo {
+                balance: *balance,
+                modified: *modified,
+                epoch: *epoch,
+                representative: PublicKey::from(1),
+                ..AccountInfo::new_test_instance()
+            };
+            ledger.store.account.put(&mut txn, account, &info);
+        }
+        txn.commit();
+        drop(txn);
+        Arc::new(ledger)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::utils::{CancellationToken, Runnable};
+use rsban_ledger::Ledger;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Keeps the in-memory ledger caches of a read-only node up to date,
+/// because the ledger is written by another process. Reloading scans the
+/// whole ledger, so it is skipped while nothing was committed.
+pub struct LedgerCacheReloader {
+    ledger: Arc<Ledger>,
+    last_txn_id: Option<u64>,
+}
+
+impl LedgerCacheReloader {
+    pub fn new(ledger: Arc<Ledger>) -> Self {
+        Self {
+            ledger,
+            last_txn_id: None,
+        }
+    }
+}
+
+impl Runnable for LedgerCacheReloader {
+    fn run(&mut self, _cancel_token: &CancellationToken) {
+        let txn_id = self.ledger.store.last_txn_id().ok();
+        if txn_id.is_some() && txn_id == self.last_txn_id {
+            return;
+        }
+        self.last_txn_id = txn_id;
+        self.ledger.reload_cache();
+        debug!(
+            blocks = self.ledger.block_count(),
+            cemented = self.ledger.cemented_count(),
+            "Ledger cache reloaded"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_core::{Account, AccountInfo, Amount};
+    use rsban_ledger::{LedgerConstants, RepWeightCache};
+    use rsban_store_lmdb::LmdbStore;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn reload_only_after_commits() {
+        let ledger = Arc::new(
+            Ledger::new(
+                Arc::new(LmdbStore::new_in_memory()),
+                LedgerConstants::unit_test(),
+                Amount::zero(),
+                Arc::new(RepWeightCache::new()),
+            )
+            .unwrap(),
+        );
+        let mut reloader = LedgerCacheReloader::new(ledger.clone());
+        let cancel_token = CancellationToken::new();
+        reloader.run(&cancel_token);
+
+        // A stale counter stays as long as nothing was committed
+        ledger.store.cache.account_count.store(0, Ordering::SeqCst);
+        reloader.run(&cancel_token);
+        assert_eq!(ledger.account_count(), 0);
+
+        let mut txn = ledger.rw_txn();
+        ledger
+            .store
+            .account
+            .put(&mut txn, &Account::from(42), &AccountInfo::default());
+        txn.commit();
+        reloader.run(&cancel_token);
+        assert_eq!(ledger.account_count(), 2);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::node_id_key_file::NodeIdKeyFile;
+use anyhow::Context;
+use rsban_core::{
+    utils::seconds_since_epoch, BlockHash, BlockHashBuilder, NodeId, PrivateKey, PublicKey,
+    Signature,
+};
+use rsban_ledger::{ConfirmedSnapshotWriter, Ledger};
+use rsban_store_lmdb::LmdbStore;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// Describes a snapshot that only contains the cemented part of a ledger.
+/// It is signed with the node ID key of the node that created the snapshot,
+/// so that new nodes can check that it comes from a trusted source.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub genesis: BlockHash,
+    /// Seconds since the UNIX epoch
+    pub created: u64,
+    pub accounts: u64,
+    pub blocks: u64,
+    pub pending: u64,
+    pub pruned: u64,
+    /// Blake2b hash of the snapshot file
+    pub file_hash: BlockHash,
+    pub node_id: NodeId,
+    pub signature: Signature,
+}
+
+impl SnapshotManifest {
+    fn signed_hash(&self) -> BlockHash {
+        BlockHashBuilder::new()
+            .update(self.genesis.as_bytes())
+            .update(self.created.to_be_bytes())
+            .update(self.accounts.to_be_bytes())
+            .update(self.blocks.to_be_bytes())
+            .update(self.pending.to_be_bytes())
+            .update(self.pruned.to_be_bytes())
+            .update(self.file_hash.as_bytes())
+            .update(PublicKey::from(self.node_id).as_bytes())
+            .build()
+    }
+
+    fn sign(&mut self, key: &PrivateKey) {
+        self.node_id = key.into();
+        self.signature = key.sign(self.signed_hash().as_bytes());
+    }
+
+    /// Checks that the manifest was signed by the trusted node and belongs to the given network
+    pub fn verify(&self, trusted_node_id: &NodeId, genesis: &BlockHash) -> anyhow::Result<()> {
+        if self.node_id != *trusted_node_id {
+            bail!("snapshot was created by untrusted node {}", self.node_id);
+        }
+        PublicKey::from(self.node_id)
+            .verify(self.signed_hash().as_bytes(), &self.signature)
+            .context("invalid snapshot manifest signature")?;
+        if self.genesis != *genesis {
+            bail!("snapshot belongs to a different network");
+        }
+        Ok(())
+    }
+}
+
+/// The manifest is stored next to the snapshot file
+pub fn manifest_path(snapshot_path: &Path) -> PathBuf {
+    snapshot_path.with_extension("manifest.json")
+}
+
+/// Loads the node ID key of the node in the given data path
+pub fn node_id_key(data_path: &Path) -> anyhow::Result<PrivateKey> {
+    NodeIdKeyFile::default().initialize(data_path)
+}
+
+/// Writes the cemented part of the ledger to a new database file and a signed manifest next to it
+pub fn write_confirmed_snapshot(
+    ledger: &Ledger,
+    node_key: &PrivateKey,
+    snapshot_path: &Path,
+) -> anyhow::Result<SnapshotManifest> {
+    if snapshot_path.exists() {
+        bail!("snapshot {:?} already exists", snapshot_path);
+    }
+
+    let stats = {
+        let target = LmdbStore::open(snapshot_path).build()?;
+        ConfirmedSnapshotWriter::new(ledger, &target).write()?
+    };
+
+    let mut manifest = SnapshotManifest {
+        genesis: ledger.constants.genesis_block.hash(),
+        created: seconds_since_epoch(),
+        accounts: stats.accounts,
+        blocks: stats.blocks,
+        pending: stats.pending,
+        pruned: stats.pruned,
+        file_hash: hash_file(snapshot_path)?,
+        node_id: NodeId::default(),
+        signature: Signature::new(),
+    };
+    manifest.sign(node_key);
+
+    let manifest_path = manifest_path(snapshot_path);
+    std::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)
+        .context(format!("Could not write manifest {:?}", manifest_path))?;
+    Ok(manifest)
+}
+
+/// Verifies a snapshot against its manifest and installs it as the ledger of the given data path
+pub fn load_confirmed_snapshot(
+    snapshot_path: &Path,
+    data_path: &Path,
+    trusted_node_id: &NodeId,
+    genesis: &BlockHash,
+) -> anyhow::Result<SnapshotManifest> {
+    let manifest_path = manifest_path(snapshot_path);
+    let manifest_json = std::fs::read_to_string(&manifest_path)
+        .context(format!("Could not read manifest {:?}", manifest_path))?;
+    let manifest: SnapshotManifest = serde_json::from_str(&manifest_json)?;
+    manifest.verify(trusted_node_id, genesis)?;
+
+    if hash_file(snapshot_path)? != manifest.file_hash {
+        bail!("snapshot file does not match the manifest");
+    }
+
+    let db_path = data_path.join("data.ldb");
+    if db_path.exists() {
+        bail!("ledger {:?} already exists", db_path);
+    }
+    std::fs::create_dir_all(data_path)?;
+    std::fs::copy(snapshot_path, &db_path)
+        .context(format!("Could not copy snapshot to {:?}", db_path))?;
+    Ok(manifest)
+}
+
+fn hash_file(path: &Path) -> anyhow::Result<BlockHash> {
+    let mut file = File::open(path).context(format!("Could not open {:?}", path))?;
+    let mut builder = BlockHashBuilder::new();
+    let mut buffer = vec![0; 1024 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        builder = builder.update(&buffer[..read]);
+    }
+    Ok(builder.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unique_path;
+    use rsban_core::Amount;
+    use rsban_ledger::{LedgerConstants, RepWeightCache};
+    use std::sync::Arc;
+
+    #[test]
+    fn write_and_load() {
+        let dir = unique_path().unwrap();
+        let snapshot_path = dir.join("snapshot.ldb");
+        let key = PrivateKey::from(1);
+        let ledger = create_ledger();
+
+        let written = write_confirmed_snapshot(&ledger, &key, &snapshot_path).unwrap();
+        let loaded = load_confirmed_snapshot(
+            &snapshot_path,
+            &dir.join("node"),
+            &NodeId::from(&key),
+            &ledger.constants.genesis_block.hash(),
+        )
+        .unwrap();
+
+        assert_eq!(loaded, written);
+        assert_eq!(loaded.accounts, 1);
+        assert_eq!(loaded.blocks, 1);
+        assert!(dir.join("node").join("data.ldb").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reject_untrusted_node() {
+        let dir = unique_path().unwrap();
+        let snapshot_path = dir.join("snapshot.ldb");
+        let ledger = create_ledger();
+        write_confirmed_snapshot(&ledger, &PrivateKey::from(1), &snapshot_path).unwrap();
+
+        let result = load_confirmed_snapshot(
+            &snapshot_path,
+            &dir.join("node"),
+            &NodeId::from(&PrivateKey::from(2)),
+            &ledger.constants.genesis_block.hash(),
+        );
+
+        assert!(result.is_err());
+        assert!(!dir.join("node").join("data.ldb").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reject_modified_manifest() {
+        let key = PrivateKey::from(1);
+        let mut manifest = SnapshotManifest {
+            genesis: BlockHash::from(1),
+            created: 1000,
+            accounts: 1,
+            blocks: 2,
+            pending: 3,
+            pruned: 4,
+            file_hash: BlockHash::from(5),
+            node_id: NodeId::default(),
+            signature: Signature::new(),
+        };
+        manifest.sign(&key);
+        assert!(manifest
+            .verify(&NodeId::from(&key), &BlockHash::from(1))
+            .is_ok());
+
+        manifest.blocks = 3;
+
+        assert!(manifest
+            .verify(&NodeId::from(&key), &BlockHash::from(1))
+            .is_err());
+    }
+
+    fn create_ledger() -> Ledger {
+        Ledger::new(
+            Arc::new(LmdbStore::new_in_memory()),
+            LedgerConstants::unit_test(),
+            Amount::zero(),
+            Arc::new(RepWeightCache::new()),
+        )
+        .unwrap()
+    }
+}
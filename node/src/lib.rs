@@ -13,6 +13,9 @@ pub mod cementation;
 pub mod config;
 pub mod consensus;
 mod ipc;
+pub mod ledger_analytics;
+mod ledger_cache_reloader;
+pub mod ledger_snapshot;
 mod monitor;
 mod node;
 mod node_builder;
@@ -11,12 +11,14 @@ use crate::{
     config::{GlobalConfig, NodeConfig, NodeFlags},
     consensus::{
         election_schedulers::ElectionSchedulers, get_bootstrap_weights, log_bootstrap_weights,
-        ActiveElections, ActiveElectionsExt, ElectionStatusType, LocalVoteHistory,
-        ProcessLiveDispatcher, ProcessLiveDispatcherExt, RecentlyConfirmedCache, RepTiers,
-        RequestAggregator, RequestAggregatorCleanup, VoteApplier, VoteBroadcaster, VoteCache,
-        VoteCacheProcessor, VoteGenerators, VoteProcessor, VoteProcessorExt, VoteProcessorQueue,
-        VoteProcessorQueueCleanup, VoteRouter,
+        ActiveElections, ActiveElectionsExt, ElectionStatusType, EquivocationDetector,
+        LocalVoteHistory, ProcessLiveDispatcher, ProcessLiveDispatcherExt, RecentlyConfirmedCache,
+        RepTiers, RequestAggregator, RequestAggregatorCleanup, VoteApplier, VoteBroadcaster,
+        VoteCache, VoteCacheProcessor, VoteGenerators, VoteProcessor, VoteProcessorExt,
+        VoteProcessorQueue, VoteProcessorQueueCleanup, VoteRouter,
     },
+    ledger_analytics::{LedgerAnalytics, LedgerAnalyticsUpdater},
+    ledger_cache_reloader::LedgerCacheReloader,
     monitor::Monitor,
     node_id_key_file::NodeIdKeyFile,
     pruning::{LedgerPruning, LedgerPruningExt},
@@ -26,16 +28,16 @@ use crate::{
         DetailType, Direction, StatType, Stats,
     },
     transport::{
-        InboundMessageQueue, InboundMessageQueueCleanup, KeepaliveFactory, LatestKeepalives,
-        LatestKeepalivesCleanup, MessageProcessor, MessagePublisher, NanoResponseServerSpawner,
-        NetworkFilter, NetworkThreads, PeerCacheConnector, PeerCacheUpdater,
-        RealtimeMessageHandler, SynCookies,
+        InboundMessageQueue, InboundMessageQueueCleanup, InboundRateLimiter,
+        InboundRateLimiterCleanup, KeepaliveFactory, LatestKeepalives, LatestKeepalivesCleanup,
+        MessageProcessor, MessagePublisher, NanoResponseServerSpawner, NetworkFilter,
+        NetworkThreads, PeerCacheConnector, PeerCacheUpdater, RealtimeMessageHandler, SynCookies,
     },
     utils::{
         LongRunningTransactionLogger, ThreadPool, ThreadPoolImpl, TimerThread, TxnTrackingConfig,
     },
     wallets::{Wallets, WalletsExt},
-    work::DistributedWorkFactory,
+    work::{create_work_backend, DistributedWorkFactory},
     NetworkParams, NodeCallbacks, OnlineWeightSampler, TelementryConfig, TelementryExt, Telemetry,
     BUILD_INFO, VERSION_STRING,
 };
@@ -107,6 +109,7 @@ pub struct Node {
     pub active: Arc<ActiveElections>,
     pub vote_router: Arc<VoteRouter>,
     pub vote_processor: Arc<VoteProcessor>,
+    pub equivocation_detector: Arc<EquivocationDetector>,
     vote_cache_processor: Arc<VoteCacheProcessor>,
     pub bootstrap_initiator: Arc<BootstrapInitiator>,
     pub rep_crawler: Arc<RepCrawler>,
@@ -119,12 +122,16 @@ pub struct Node {
     pub process_live_dispatcher: Arc<ProcessLiveDispatcher>,
     message_processor: Mutex<MessageProcessor>,
     network_threads: Arc<Mutex<NetworkThreads>>,
-    ledger_pruning: Arc<LedgerPruning>,
+    pub ledger_pruning: Arc<LedgerPruning>,
+    pub ledger_analytics: Arc<LedgerAnalytics>,
+    ledger_analytics_updater: TimerThread<LedgerAnalyticsUpdater>,
+    ledger_cache_reloader: TimerThread<LedgerCacheReloader>,
     pub peer_connector: Arc<PeerConnector>,
     ongoing_bootstrap: Arc<OngoingBootstrap>,
     peer_cache_updater: TimerThread<PeerCacheUpdater>,
     peer_cache_connector: TimerThread<PeerCacheConnector>,
     pub inbound_message_queue: Arc<InboundMessageQueue>,
+    pub inbound_rate_limiter: Arc<InboundRateLimiter>,
     monitor: TimerThread<Monitor>,
     stopped: AtomicBool,
     pub network_filter: Arc<NetworkFilter>,
@@ -204,6 +211,8 @@ impl Node {
 
         let store = if is_nulled {
             Arc::new(LmdbStore::new_null())
+        } else if flags.in_memory_ledger {
+            Arc::new(LmdbStore::new_in_memory())
         } else {
             make_store(
                 &application_path,
@@ -212,6 +221,7 @@ impl Node {
                 Duration::from_millis(config.block_processor_batch_max_time_ms as u64),
                 config.lmdb_config.clone(),
                 config.backup_before_upgrade,
+                flags.read_only,
             )
             .expect("Could not create LMDB store")
         };
@@ -313,6 +323,14 @@ impl Node {
             inbound_message_queue.clone(),
         ));
 
+        let inbound_rate_limiter = Arc::new(InboundRateLimiter::new(
+            config.message_processor.rate_limiter.clone(),
+            stats.clone(),
+            network_info.clone(),
+            steady_clock.clone(),
+        ));
+        dead_channel_cleanup.add_step(InboundRateLimiterCleanup::new(inbound_rate_limiter.clone()));
+
         let telemetry_config = TelementryConfig {
             enable_ongoing_requests: false,
             enable_ongoing_broadcasts: !flags.disable_providing_telemetry_metrics,
@@ -414,7 +432,17 @@ impl Node {
             block_processor.processor_loop.clone(),
         ));
 
-        let distributed_work = Arc::new(DistributedWorkFactory::new(work.clone(), runtime.clone()));
+        let work_backend = create_work_backend(
+            &config.work_backend,
+            &config.work_peers,
+            work.clone(),
+            network_params.work.clone(),
+            runtime.clone(),
+        );
+        let distributed_work = Arc::new(DistributedWorkFactory::with_backend(
+            work_backend,
+            runtime.clone(),
+        ));
 
         let mut wallets_path = application_path.clone();
         wallets_path.push("wallets.ldb");
@@ -425,9 +453,13 @@ impl Node {
         let wallets_options = EnvOptions {
             config: wallets_lmdb_config,
             use_no_mem_init: false,
+            read_only: false,
         };
         let wallets_env = if is_nulled {
             Arc::new(LmdbEnv::new_null())
+        } else if flags.read_only {
+            // The wallets belong to the node that writes the ledger
+            Arc::new(LmdbEnv::new_in_memory())
         } else {
             Arc::new(LmdbEnv::new_with_options(wallets_path, &wallets_options).unwrap())
         };
@@ -504,6 +536,11 @@ impl Node {
             on_vote,
         ));
 
+        let equivocation_detector = Arc::new(EquivocationDetector::new(
+            config.equivocation_detector.clone(),
+            stats.clone(),
+        ));
+
         let vote_cache_processor = Arc::new(VoteCacheProcessor::new(
             stats.clone(),
             vote_cache.clone(),
@@ -531,7 +568,7 @@ impl Node {
             on_election_end,
             online_reps.clone(),
             flags.clone(),
-            recently_confirmed,
+            recently_confirmed.clone(),
             vote_applier.clone(),
             vote_router.clone(),
             vote_cache_processor.clone(),
@@ -603,6 +640,7 @@ impl Node {
             bootstrap_initiator: bootstrap_initiator.clone(),
             network: network_info.clone(),
             inbound_queue: inbound_message_queue.clone(),
+            rate_limiter: inbound_rate_limiter.clone(),
             node_flags: flags.clone(),
             network_params: network_params.clone(),
             syn_cookies: syn_cookies.clone(),
@@ -822,6 +860,34 @@ impl Node {
             }
         }));
 
+        let vote_router_w = Arc::downgrade(&vote_router);
+        let detector_l = equivocation_detector.clone();
+        let recently_confirmed_l = recently_confirmed.clone();
+        vote_router.add_vote_processed_observer(Box::new(move |vote, _source, results| {
+            if !vote.is_final() {
+                return;
+            }
+            let Some(vote_router) = vote_router_w.upgrade() else {
+                return;
+            };
+
+            // Conflicting blocks share an election, so the election is the primary source for the root.
+            // Blocks whose election is already gone are resolved via the recently confirmed cache.
+            // Other hashes are unknown to this node and are skipped, so that no ledger lookup is needed
+            for (hash, code) in results {
+                let root = match code {
+                    VoteCode::Vote | VoteCode::Replay | VoteCode::Ignored => vote_router
+                        .election(hash)
+                        .map(|election| election.qualified_root.clone())
+                        .or_else(|| recently_confirmed_l.root_of(hash)),
+                    VoteCode::Invalid | VoteCode::Indeterminate => None,
+                };
+                if let Some(root) = root {
+                    detector_l.vote(vote, *hash, root);
+                }
+            }
+        }));
+
         let keepalive_factory_w = Arc::downgrade(&keepalive_factory);
         let message_publisher_l = Arc::new(Mutex::new(message_publisher.clone()));
         let message_publisher_w = Arc::downgrade(&message_publisher_l);
@@ -898,7 +964,32 @@ impl Node {
             }
         }
 
-        if ledger.pruning_enabled() {
+        // Indexes that exist already were enabled when the ledger was opened
+        if flags.enable_delegators_index && !flags.read_only && !ledger.delegators_index_enabled() {
+            info!("Building delegators index. This may take a while...");
+            if let Some(count) = ledger.enable_delegators_index() {
+                info!("Delegators index built for {} accounts", count);
+            }
+        }
+
+        if flags.enable_block_height_index
+            && !flags.read_only
+            && !ledger.block_height_index_enabled()
+        {
+            info!("Building block height index. This may take a while...");
+            if let Some(count) = ledger.enable_block_height_index() {
+                info!("Block height index built for {} blocks", count);
+            }
+        }
+
+        if flags.enable_incoming_index && !flags.read_only && !ledger.incoming_index_enabled() {
+            info!("Building incoming sends index. This may take a while...");
+            if let Some(count) = ledger.enable_incoming_index() {
+                info!("Incoming sends index built for {} sends", count);
+            }
+        }
+
+        if ledger.pruning_enabled() && !flags.read_only {
             if config.enable_voting && !flags.inactive_node {
                 let msg = "Incompatibility detected between config node.enable_voting and existing pruned blocks";
                 error!(msg);
@@ -921,6 +1012,12 @@ impl Node {
                 return;
             };
 
+            if wallets.is_watched(&block.account()) {
+                let block = block.clone();
+                let wallets = wallets.clone();
+                workers.push_task(Box::new(move || wallets.watch_cemented(&block)));
+            }
+
             // TODO: Is it neccessary to call this for all blocks?
             if block.is_send() {
                 let block = block.clone();
@@ -939,6 +1036,24 @@ impl Node {
             )
             .parse()
             .unwrap();
+
+            let tokio_l = tokio.clone();
+            let stats_l = stats.clone();
+            let url_l = url.clone();
+            wallets.on_balance_alert(Box::new(move |alert| {
+                let message = BalanceAlertCallbackMessage {
+                    alert: "balance",
+                    wallet: alert.wallet.encode_hex(),
+                    account: alert.account.encode_account(),
+                    hash: alert.hash.encode_hex(),
+                    threshold: alert.threshold.to_string_dec(),
+                    previous_balance: alert.previous_balance.to_string_dec(),
+                    balance: alert.balance.to_string_dec(),
+                    direction: alert.direction.as_str(),
+                };
+                tokio_l.spawn(post_callback(url_l.clone(), stats_l.clone(), message));
+            }));
+
             active_elections.on_election_ended(Box::new(
                 move |status, _weights, account, amount, is_state_send, is_state_epoch| {
                     let block = status.winner.as_ref().unwrap().clone();
@@ -969,38 +1084,7 @@ impl Node {
                                 },
                                 is_send: if is_state_send { Some("true") } else { None },
                             };
-
-                            let http_client = HttpClient::new();
-                            match http_client.post_json(url.clone(), &message).await {
-                                Ok(response) => {
-                                    if response.status().is_success() {
-                                        stats.inc_dir(
-                                            StatType::HttpCallback,
-                                            DetailType::Initiate,
-                                            Direction::Out,
-                                        );
-                                    } else {
-                                        error!(
-                                            "Callback to {} failed [status: {:?}]",
-                                            url,
-                                            response.status()
-                                        );
-                                        stats.inc_dir(
-                                            StatType::Error,
-                                            DetailType::HttpCallback,
-                                            Direction::Out,
-                                        );
-                                    }
-                                }
-                                Err(e) => {
-                                    error!("Unable to send callback: {} ({})", url, e);
-                                    stats.inc_dir(
-                                        StatType::Error,
-                                        DetailType::HttpCallback,
-                                        Direction::Out,
-                                    );
-                                }
-                            }
+                            post_callback(url, stats, message).await;
                         });
                     }
                 },
@@ -1035,6 +1119,27 @@ impl Node {
             workers.clone(),
         ));
 
+        let wallets_w = Arc::downgrade(&wallets);
+        ledger_pruning.set_wallet_accounts_callback(Box::new(move || {
+            wallets_w
+                .upgrade()
+                .map(|wallets| wallets.get_accounts(usize::MAX))
+                .unwrap_or_default()
+        }));
+
+        let ledger_analytics = Arc::new(LedgerAnalytics::new(
+            config.ledger_analytics.clone(),
+            ledger.clone(),
+            online_reps.clone(),
+        ));
+        let ledger_analytics_updater = TimerThread::new_run_immedately(
+            "Ledger analytics",
+            LedgerAnalyticsUpdater::new(ledger_analytics.clone()),
+        );
+
+        let ledger_cache_reloader =
+            TimerThread::new("Ledger reload", LedgerCacheReloader::new(ledger.clone()));
+
         let monitor = TimerThread::new(
             "Monitor",
             Monitor::new(
@@ -1089,6 +1194,7 @@ impl Node {
             vote_generators,
             active: active_elections,
             vote_processor,
+            equivocation_detector,
             vote_cache_processor,
             bootstrap_initiator,
             rep_crawler,
@@ -1100,9 +1206,13 @@ impl Node {
             local_block_broadcaster,
             process_live_dispatcher, // needs to stay alive
             ledger_pruning,
+            ledger_analytics,
+            ledger_analytics_updater,
+            ledger_cache_reloader,
             network_threads,
             message_processor,
             inbound_message_queue,
+            inbound_rate_limiter,
             monitor,
             message_publisher: message_publisher_l,
             network_filter,
@@ -1152,6 +1262,10 @@ impl Node {
             )
             .node("vote_cache", vote_cache)
             .node("vote_router", self.vote_router.container_info())
+            .node(
+                "equivocation_detector",
+                self.equivocation_detector.container_info(),
+            )
             .node("vote_generators", self.vote_generators.container_info())
             .node("bootstrap_ascending", self.ascendboot.container_info())
             .node("unchecked", self.unchecked.container_info())
@@ -1164,6 +1278,10 @@ impl Node {
                 "message_processor",
                 self.inbound_message_queue.container_info(),
             )
+            .node(
+                "inbound_rate_limiter",
+                self.inbound_rate_limiter.container_info(),
+            )
             .finish()
     }
 
@@ -1360,6 +1478,14 @@ impl NodeExt for Arc<Node> {
             panic!("Genesis block not found!");
         }
 
+        if self.flags.read_only {
+            // Another node writes the ledger, so only its caches have to be kept up to date
+            info!("Node is running in read-only mode");
+            self.ledger_cache_reloader
+                .start(self.flags.read_only_reload_interval);
+            return;
+        }
+
         self.long_inactivity_cleanup();
         self.network_threads.lock().unwrap().start();
         self.message_processor.lock().unwrap().start();
@@ -1447,6 +1573,11 @@ impl NodeExt for Arc<Node> {
         if self.config.monitor.enabled {
             self.monitor.start(self.config.monitor.interval);
         }
+
+        if self.config.ledger_analytics.enabled {
+            self.ledger_analytics_updater
+                .start(self.config.ledger_analytics.interval);
+        }
     }
 
     fn stop(&self) {
@@ -1461,6 +1592,12 @@ impl NodeExt for Arc<Node> {
         }
         info!("Node stopping...");
 
+        if self.flags.read_only {
+            self.ledger_cache_reloader.stop();
+            self.workers.stop();
+            return;
+        }
+
         self.tcp_listener.stop();
         self.bootstrap_workers.stop();
         self.wallet_workers.stop();
@@ -1498,6 +1635,7 @@ impl NodeExt for Arc<Node> {
         self.message_processor.lock().unwrap().stop();
         self.network_threads.lock().unwrap().stop(); // Stop network last to avoid killing in-use sockets
         self.monitor.stop();
+        self.ledger_analytics_updater.stop();
 
         // work pool is not stopped on purpose due to testing setup
     }
@@ -1598,6 +1736,7 @@ fn make_store(
     block_processor_batch_max_time: Duration,
     lmdb_config: LmdbConfig,
     backup_before_upgrade: bool,
+    read_only: bool,
 ) -> anyhow::Result<Arc<LmdbStore>> {
     let mut path = PathBuf::from(path);
     if add_db_postfix {
@@ -1616,6 +1755,7 @@ fn make_store(
     let options = EnvOptions {
         config: lmdb_config,
         use_no_mem_init: true,
+        read_only,
     };
 
     let store = LmdbStore::open(&path)
@@ -1626,6 +1766,40 @@ fn make_store(
     Ok(Arc::new(store))
 }
 
+async fn post_callback(url: Url, stats: Arc<Stats>, message: impl Serialize) {
+    let http_client = HttpClient::new();
+    match http_client.post_json(url.clone(), &message).await {
+        Ok(response) => {
+            if response.status().is_success() {
+                stats.inc_dir(StatType::HttpCallback, DetailType::Initiate, Direction::Out);
+            } else {
+                error!(
+                    "Callback to {} failed [status: {:?}]",
+                    url,
+                    response.status()
+                );
+                stats.inc_dir(StatType::Error, DetailType::HttpCallback, Direction::Out);
+            }
+        }
+        Err(e) => {
+            error!("Unable to send callback: {} ({})", url, e);
+            stats.inc_dir(StatType::Error, DetailType::HttpCallback, Direction::Out);
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct BalanceAlertCallbackMessage {
+    alert: &'static str,
+    wallet: String,
+    account: String,
+    hash: String,
+    threshold: String,
+    previous_balance: String,
+    balance: String,
+    direction: &'static str,
+}
+
 #[derive(Serialize)]
 struct RpcCallbackMessage {
     account: String,
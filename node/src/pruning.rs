@@ -2,25 +2,61 @@ use crate::{
     config::{NodeConfig, NodeFlags},
     utils::ThreadPool,
 };
-use rsban_core::{Account, BlockHash};
-use rsban_ledger::{Ledger, Writer};
+use rsban_core::{utils::seconds_since_epoch, Account, BlockHash};
+use rsban_ledger::{Ledger, PruningPolicy, Writer};
 use rsban_store_lmdb::Transaction;
 use std::{
     collections::VecDeque,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex,
     },
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::Duration,
 };
 use tracing::debug;
 
+pub type WalletAccountsCallback = Box<dyn Fn() -> Vec<Account> + Send + Sync>;
+
+/// Settings for a single pruning run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PruningOptions {
+    /// Number of blocks below the frontier of an account that are always kept. 0 means unlimited
+    pub max_depth: u64,
+    /// Blocks younger than this are kept. `None` disables the age check
+    pub max_age: Option<Duration>,
+    pub batch_size: u64,
+    /// Only count the blocks that would be pruned, without modifying the ledger
+    pub dry_run: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PruningResult {
+    pub blocks: u64,
+    /// Size of the serialized blocks including their sidebands
+    pub bytes: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PruningStatus {
+    pub running: bool,
+    pub paused: bool,
+    pub dry_run: bool,
+    /// Progress of the current run or the result of the last one
+    pub progress: PruningResult,
+    /// Start of the current or last run in seconds since the UNIX epoch. 0 if pruning never ran
+    pub started: u64,
+}
+
 pub struct LedgerPruning {
     config: NodeConfig,
     flags: NodeFlags,
     ledger: Arc<Ledger>,
     stopped: AtomicBool,
+    paused: AtomicBool,
     workers: Arc<dyn ThreadPool>,
+    status: Mutex<PruningStatus>,
+    run_mutex: Mutex<()>,
+    wallet_accounts_callback: Mutex<Option<WalletAccountsCallback>>,
 }
 
 impl LedgerPruning {
@@ -36,71 +72,192 @@ impl LedgerPruning {
             ledger,
             workers,
             stopped: AtomicBool::new(false),
+            paused: AtomicBool::new(false),
+            status: Mutex::new(PruningStatus::default()),
+            run_mutex: Mutex::new(()),
+            wallet_accounts_callback: Mutex::new(None),
+        }
+    }
+
+    /// Provides the accounts of the local wallets, which are kept if
+    /// `pruning_keep_wallet_accounts` is enabled
+    pub fn set_wallet_accounts_callback(&self, callback: WalletAccountsCallback) {
+        *self.wallet_accounts_callback.lock().unwrap() = Some(callback);
+    }
+
+    /// The pruning policy as set in the node config
+    pub fn policy(&self) -> PruningPolicy {
+        let mut policy = PruningPolicy::new();
+        for account in &self.config.pruning_keep_accounts {
+            policy.keep(*account);
+        }
+        for (account, depth) in &self.config.pruning_account_depths {
+            policy.set_depth(*account, *depth);
+        }
+        if self.config.pruning_keep_wallet_accounts {
+            if let Some(callback) = self.wallet_accounts_callback.lock().unwrap().as_ref() {
+                for account in callback() {
+                    policy.keep(account);
+                }
+            }
         }
+        policy
     }
 
     pub fn stop(&self) {
         self.stopped.store(true, Ordering::SeqCst);
     }
 
-    pub fn ledger_pruning(&self, batch_size_a: u64, bootstrap_weight_reached_a: bool) {
-        let max_depth = if self.config.max_pruning_depth != 0 {
-            self.config.max_pruning_depth
+    /// Stops the current run and skips further runs until `resume` is called
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    fn should_stop(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst) || self.is_paused()
+    }
+
+    pub fn status(&self) -> PruningStatus {
+        let mut status = self.status.lock().unwrap().clone();
+        status.paused = self.is_paused();
+        status
+    }
+
+    pub fn batch_size(&self) -> u64 {
+        if self.flags.block_processor_batch_size != 0 {
+            self.flags.block_processor_batch_size as u64
         } else {
-            u64::MAX
+            2 * 1024
+        }
+    }
+
+    /// The options of the periodic pruning as set in the node config
+    pub fn configured_options(
+        &self,
+        batch_size: u64,
+        bootstrap_weight_reached: bool,
+    ) -> PruningOptions {
+        PruningOptions {
+            max_depth: self.config.max_pruning_depth,
+            max_age: bootstrap_weight_reached
+                .then(|| Duration::from_secs(self.config.max_pruning_age_s as u64)),
+            batch_size,
+            dry_run: false,
+        }
+    }
+
+    pub fn ledger_pruning(&self, batch_size_a: u64, bootstrap_weight_reached_a: bool) {
+        self.prune(&self.configured_options(batch_size_a, bootstrap_weight_reached_a));
+    }
+
+    pub fn prune(&self, options: &PruningOptions) -> PruningResult {
+        let _run_guard = self.run_mutex.lock().unwrap();
+        if self.is_paused() {
+            return PruningResult::default();
+        }
+        *self.status.lock().unwrap() = PruningStatus {
+            running: true,
+            paused: false,
+            dry_run: options.dry_run,
+            progress: PruningResult::default(),
+            started: seconds_since_epoch(),
         };
-        let cutoff_time = if bootstrap_weight_reached_a {
-            (SystemTime::now() - Duration::from_secs(self.config.max_pruning_age_s as u64))
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs()
+        self.ledger.set_pruning_policy(self.policy());
+
+        let max_depth = if options.max_depth != 0 {
+            options.max_depth
         } else {
             u64::MAX
         };
-        let mut pruned_count = 0;
+        let cutoff_time = match options.max_age {
+            Some(max_age) => seconds_since_epoch().saturating_sub(max_age.as_secs()),
+            None => u64::MAX,
+        };
+        let batch_size = options.batch_size;
+        let mut result = PruningResult::default();
         let mut transaction_write_count = 0;
         let mut last_account = Account::from(1); // 0 Burn account is never opened. So it can be used to break loop
         let mut pruning_targets = VecDeque::new();
         let mut target_finished = false;
-        while (transaction_write_count != 0 || !target_finished)
-            && !self.stopped.load(Ordering::SeqCst)
-        {
+        while (transaction_write_count != 0 || !target_finished) && !self.should_stop() {
             // Search pruning targets
-            while pruning_targets.len() < batch_size_a as usize
+            while pruning_targets.len() < batch_size as usize
                 && !target_finished
-                && !self.stopped.load(Ordering::SeqCst)
+                && !self.should_stop()
             {
                 target_finished = self.collect_ledger_pruning_targets(
                     &mut pruning_targets,
                     &mut last_account,
-                    batch_size_a * 2,
+                    batch_size * 2,
                     max_depth,
                     cutoff_time,
                 );
             }
             // Pruning write operation
             transaction_write_count = 0;
-            if !pruning_targets.is_empty() && !self.stopped.load(Ordering::SeqCst) {
-                let _scoped_write_guard = self.ledger.write_queue.wait(Writer::Pruning);
-                let mut tx = self.ledger.rw_txn();
-                while !pruning_targets.is_empty()
-                    && transaction_write_count < batch_size_a
-                    && !self.stopped.load(Ordering::SeqCst)
-                {
-                    let pruning_hash = pruning_targets.front().unwrap();
-                    let account_pruned_count =
-                        self.ledger
-                            .pruning_action(&mut tx, pruning_hash, batch_size_a);
-                    transaction_write_count += account_pruned_count;
-                    pruning_targets.pop_front();
-                }
-                pruned_count += transaction_write_count;
+            if !pruning_targets.is_empty() && !self.should_stop() {
+                let batch = if options.dry_run {
+                    self.count_targets(&mut pruning_targets, batch_size)
+                } else {
+                    self.prune_targets(&mut pruning_targets, batch_size)
+                };
+                transaction_write_count = batch.blocks;
+                result.blocks += batch.blocks;
+                result.bytes += batch.bytes;
+                self.status.lock().unwrap().progress = result.clone();
 
-                debug!("Pruned blocks: {}", pruned_count);
+                debug!("Pruned blocks: {}", result.blocks);
             }
         }
 
-        debug!("Total recently pruned block count: {}", pruned_count);
+        self.status.lock().unwrap().running = false;
+        debug!("Total recently pruned block count: {}", result.blocks);
+        result
+    }
+
+    fn prune_targets(
+        &self,
+        pruning_targets: &mut VecDeque<BlockHash>,
+        batch_size: u64,
+    ) -> PruningResult {
+        let mut batch = PruningResult::default();
+        let _scoped_write_guard = self.ledger.write_queue.wait(Writer::Pruning);
+        let mut tx = self.ledger.rw_txn();
+        while batch.blocks < batch_size && !self.should_stop() {
+            let Some(pruning_hash) = pruning_targets.pop_front() else {
+                break;
+            };
+            let (blocks, bytes) = self.ledger.prune_chain(&mut tx, &pruning_hash, batch_size);
+            batch.blocks += blocks;
+            batch.bytes += bytes;
+        }
+        batch
+    }
+
+    fn count_targets(
+        &self,
+        pruning_targets: &mut VecDeque<BlockHash>,
+        batch_size: u64,
+    ) -> PruningResult {
+        let mut batch = PruningResult::default();
+        let tx = self.ledger.read_txn();
+        while batch.blocks < batch_size && !self.should_stop() {
+            let Some(pruning_hash) = pruning_targets.pop_front() else {
+                break;
+            };
+            let (blocks, bytes) = self.ledger.pruning_preview(&tx, &pruning_hash);
+            batch.blocks += blocks;
+            batch.bytes += bytes;
+        }
+        batch
     }
 
     pub fn collect_ledger_pruning_targets(
@@ -111,6 +268,7 @@ impl LedgerPruning {
         max_depth_a: u64,
         cutoff_time_a: u64,
     ) -> bool {
+        let policy = self.ledger.pruning_policy();
         let mut read_operations = 0;
         let mut finish_transaction = false;
         let mut tx = self.ledger.read_txn();
@@ -124,9 +282,18 @@ impl LedgerPruning {
                 break;
             }
             read_operations += 1;
-            let mut hash = info.frontier;
+            let mut hash = if policy.is_kept(&account) {
+                BlockHash::zero()
+            } else {
+                info.frontier
+            };
+            let max_depth = match policy.depth(&account) {
+                Some(0) => u64::MAX,
+                Some(depth) => depth,
+                None => max_depth_a,
+            };
             let mut depth = 0;
-            while !hash.is_zero() && depth < max_depth_a {
+            while !hash.is_zero() && depth < max_depth {
                 if let Some(block) = self.ledger.any().get_block(&tx, &hash) {
                     if block.timestamp() > cutoff_time_a || depth == 0 {
                         hash = block.previous();
@@ -182,14 +349,7 @@ impl LedgerPruningExt for Arc<LedgerPruning> {
     fn ongoing_ledger_pruning(&self) {
         let bootstrap_weight_reached =
             self.ledger.block_count() >= self.ledger.bootstrap_weight_max_blocks();
-        self.ledger_pruning(
-            if self.flags.block_processor_batch_size != 0 {
-                self.flags.block_processor_batch_size as u64
-            } else {
-                2 * 1024
-            },
-            bootstrap_weight_reached,
-        );
+        self.ledger_pruning(self.batch_size(), bootstrap_weight_reached);
         let ledger_pruning_interval = if bootstrap_weight_reached {
             Duration::from_secs(self.config.max_pruning_age_s as u64)
         } else {
@@ -206,3 +366,203 @@ impl LedgerPruningExt for Arc<LedgerPruning> {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ThreadPoolImpl;
+    use rsban_core::{Amount, BlockSideband, ConfirmationHeightInfo, SavedAccountChain};
+    use rsban_ledger::{LedgerConstants, RepWeightCache};
+    use rsban_store_lmdb::LmdbStore;
+
+    #[test]
+    fn dry_run_does_not_modify_the_ledger() {
+        let (ledger, chain) = ledger_with_confirmed_chain();
+        let pruning = create_pruning(ledger.clone());
+
+        let result = pruning.prune(&options(true));
+
+        assert_eq!(result.blocks, 2);
+        let expected_bytes: usize = (1..=2)
+            .map(|height| chain.block(height).serialize_with_sideband().len())
+            .sum();
+        assert_eq!(result.bytes, expected_bytes as u64);
+        let txn = ledger.read_txn();
+        assert_eq!(ledger.store.pruned.count(&txn), 0);
+        assert!(ledger.any().block_exists(&txn, &chain.open()));
+
+        let status = pruning.status();
+        assert!(!status.running);
+        assert!(status.dry_run);
+        assert_eq!(status.progress, result);
+        assert!(status.started > 0);
+    }
+
+    #[test]
+    fn prune_confirmed_blocks() {
+        let (ledger, chain) = ledger_with_confirmed_chain();
+        let pruning = create_pruning(ledger.clone());
+        let expected = pruning.prune(&options(true));
+
+        let result = pruning.prune(&options(false));
+
+        assert_eq!(result, expected);
+        let txn = ledger.read_txn();
+        assert_eq!(ledger.store.pruned.count(&txn), 2);
+        assert!(ledger.store.pruned.exists(&txn, &chain.open()));
+        assert!(ledger.any().block_exists(&txn, &chain.frontier()));
+        assert!(!pruning.status().dry_run);
+    }
+
+    #[test]
+    fn keep_listed_account_is_not_pruned() {
+        let (ledger, chain) = ledger_with_confirmed_chain();
+        let mut config = NodeConfig::new_test_instance();
+        config.pruning_keep_accounts = vec![chain.account()];
+        let pruning = create_pruning_with(config, ledger.clone());
+
+        let result = pruning.prune(&options(false));
+
+        assert_eq!(result, PruningResult::default());
+        assert_eq!(ledger.store.pruned.count(&ledger.read_txn()), 0);
+    }
+
+    #[test]
+    fn account_depth_overrides_global_depth() {
+        let (ledger, chain) = ledger_with_confirmed_chain();
+        let mut config = NodeConfig::new_test_instance();
+        config.pruning_account_depths = [(chain.account(), 2)].into();
+        let pruning = create_pruning_with(config, ledger.clone());
+
+        let result = pruning.prune(&options(false));
+
+        assert_eq!(result.blocks, 1);
+        let txn = ledger.read_txn();
+        assert!(ledger.store.pruned.exists(&txn, &chain.open()));
+        assert_eq!(ledger.store.pruned.count(&txn), 1);
+    }
+
+    #[test]
+    fn wallet_accounts_are_kept() {
+        let (ledger, chain) = ledger_with_confirmed_chain();
+        let mut config = NodeConfig::new_test_instance();
+        config.pruning_keep_wallet_accounts = true;
+        let pruning = create_pruning_with(config, ledger.clone());
+        let account = chain.account();
+        pruning.set_wallet_accounts_callback(Box::new(move || vec![account]));
+
+        let result = pruning.prune(&options(false));
+
+        assert_eq!(result.blocks, 0);
+    }
+
+    #[test]
+    fn paused_pruning_does_nothing() {
+        let (ledger, _) = ledger_with_confirmed_chain();
+        let pruning = create_pruning(ledger.clone());
+        pruning.pause();
+
+        assert_eq!(pruning.prune(&options(false)), PruningResult::default());
+        assert_eq!(ledger.store.pruned.count(&ledger.read_txn()), 0);
+        assert!(pruning.status().paused);
+
+        pruning.resume();
+        assert_eq!(pruning.prune(&options(false)).blocks, 2);
+        assert!(!pruning.status().paused);
+    }
+
+    #[test]
+    fn age_beyond_epoch_keeps_all_blocks() {
+        let (ledger, _) = ledger_with_confirmed_chain();
+        let pruning = create_pruning(ledger.clone());
+        let options = PruningOptions {
+            max_age: Some(Duration::from_secs(u64::MAX)),
+            ..options(false)
+        };
+
+        assert_eq!(pruning.prune(&options), PruningResult::default());
+    }
+
+    #[test]
+    fn pruning_action_applies_policy() {
+        let (ledger, chain) = ledger_with_confirmed_chain();
+        let mut policy = PruningPolicy::new();
+        policy.set_depth(chain.account(), 2);
+        ledger.set_pruning_policy(policy);
+
+        let mut txn = ledger.rw_txn();
+        assert_eq!(
+            ledger.pruning_action(&mut txn, &chain.block(2).hash(), 1),
+            1
+        );
+
+        let mut policy = PruningPolicy::new();
+        policy.keep(chain.account());
+        ledger.set_pruning_policy(policy);
+        assert_eq!(ledger.pruning_action(&mut txn, &chain.frontier(), 1), 0);
+    }
+
+    fn options(dry_run: bool) -> PruningOptions {
+        PruningOptions {
+            max_depth: 0,
+            max_age: None,
+            batch_size: 16,
+            dry_run,
+        }
+    }
+
+    fn ledger_with_confirmed_chain() -> (Arc<Ledger>, SavedAccountChain) {
+        let ledger = Arc::new(
+            Ledger::new(
+                Arc::new(LmdbStore::new_in_memory()),
+                LedgerConstants::unit_test(),
+                Amount::zero(),
+                Arc::new(RepWeightCache::new()),
+            )
+            .unwrap(),
+        );
+        let mut chain = SavedAccountChain::new_opened_chain();
+        chain.add_legacy_send();
+        chain.add_legacy_send();
+
+        let mut txn = ledger.rw_txn();
+        for block in chain.blocks() {
+            // the successor is set when the next block is put
+            let mut block = block.clone();
+            block.set_sideband(BlockSideband {
+                successor: BlockHash::zero(),
+                ..block.sideband().clone()
+            });
+            ledger.store.block.put(&mut txn, &block);
+        }
+        ledger
+            .store
+            .account
+            .put(&mut txn, &chain.account(), &chain.account_info());
+        ledger.store.confirmation_height.put(
+            &mut txn,
+            &chain.account(),
+            &ConfirmationHeightInfo {
+                height: chain.height(),
+                frontier: chain.frontier(),
+            },
+        );
+        txn.commit();
+        drop(txn);
+
+        (ledger, chain)
+    }
+
+    fn create_pruning(ledger: Arc<Ledger>) -> LedgerPruning {
+        create_pruning_with(NodeConfig::new_test_instance(), ledger)
+    }
+
+    fn create_pruning_with(config: NodeConfig, ledger: Arc<Ledger>) -> LedgerPruning {
+        LedgerPruning::new(
+            config,
+            NodeFlags::new(),
+            ledger,
+            Arc::new(ThreadPoolImpl::new_null()),
+        )
+    }
+}
@@ -86,6 +86,9 @@ pub enum StatType {
     MessageProcessorOverfill,
     MessageProcessorType,
     ProcessConfirmed,
+    EquivocationDetector,
+    InboundRateLimit,
+    InboundRateLimitType,
 }
 
 impl StatType {
@@ -250,6 +253,14 @@ pub enum DetailType {
     ConfirmOnce,
     ConfirmOnceFailed,
 
+    // equivocation detector
+    Equivocation,
+
+    // inbound rate limiter
+    PeerLimit,
+    TypeLimit,
+    Misbehaving,
+
     // election types
     Manual,
     Priority,
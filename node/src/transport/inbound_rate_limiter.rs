@@ -0,0 +1,436 @@
+use crate::stats::{DetailType, StatType, Stats};
+use rsban_core::utils::ContainerInfo;
+use rsban_messages::MessageType;
+use rsban_network::{
+    token_bucket::TokenBucket, ChannelId, ChannelInfo, DeadChannelCleanupStep, NetworkInfo,
+};
+use rsban_nullable_clock::{SteadyClock, Timestamp};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
+};
+use tracing::debug;
+
+/// Violations are only counted within this window. A peer that exceeds its limits
+/// now and then is not treated as misbehaving
+const VIOLATION_WINDOW: Duration = Duration::from_secs(60);
+
+/// A token bucket limit of `rate` messages per second that allows bursts of up to `burst`
+/// messages. A rate of 0 disables the limit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub rate: usize,
+    pub burst: usize,
+}
+
+impl RateLimit {
+    pub const UNLIMITED: Self = Self { rate: 0, burst: 0 };
+
+    pub fn new(rate: usize, burst: usize) -> Self {
+        Self { rate, burst }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.rate == 0
+    }
+
+    fn create_bucket(&self) -> TokenBucket {
+        TokenBucket::new(self.burst.max(self.rate), self.rate)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InboundRateLimiterConfig {
+    /// Limit for all realtime messages of a single channel
+    pub peer_limit: RateLimit,
+    /// Limits for specific message types of a single channel
+    pub type_limits: HashMap<MessageType, RateLimit>,
+    /// Number of violations within a minute after which the peer is treated as misbehaving.
+    /// 0 disables the escalation
+    pub max_violations: usize,
+}
+
+/// Rate limiting is opt-in, so that busy but honest peers are not dropped
+/// or excluded after an upgrade
+impl Default for InboundRateLimiterConfig {
+    fn default() -> Self {
+        Self {
+            peer_limit: RateLimit::UNLIMITED,
+            type_limits: HashMap::new(),
+            max_violations: 0,
+        }
+    }
+}
+
+/// Limits the rate of inbound realtime messages per channel and per message type
+pub struct InboundRateLimiter {
+    config: InboundRateLimiterConfig,
+    stats: Arc<Stats>,
+    network_info: Arc<RwLock<NetworkInfo>>,
+    clock: Arc<SteadyClock>,
+    peers: Mutex<HashMap<ChannelId, PeerLimits>>,
+}
+
+impl InboundRateLimiter {
+    pub fn new(
+        config: InboundRateLimiterConfig,
+        stats: Arc<Stats>,
+        network_info: Arc<RwLock<NetworkInfo>>,
+        clock: Arc<SteadyClock>,
+    ) -> Self {
+        Self {
+            config,
+            stats,
+            network_info,
+            clock,
+            peers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn new_null() -> Self {
+        Self::new(
+            InboundRateLimiterConfig::default(),
+            Arc::new(Stats::default()),
+            Arc::new(RwLock::new(NetworkInfo::new_test_instance())),
+            Arc::new(SteadyClock::new_null()),
+        )
+    }
+
+    /// Returns false if the message exceeds a limit and should be dropped.
+    /// Peers that exceed their limits repeatedly are reported as misbehaving
+    pub fn should_pass(&self, channel: &ChannelInfo, message_type: MessageType) -> bool {
+        let now = self.clock.now();
+        let violation = {
+            let mut peers = self.peers.lock().unwrap();
+            let peer = peers
+                .entry(channel.channel_id())
+                .or_insert_with(|| PeerLimits::new(&self.config, now));
+
+            let violation = peer.check(&self.config, message_type);
+            match violation {
+                Some(detail) => {
+                    self.stats.inc(StatType::InboundRateLimit, detail);
+                    self.stats
+                        .inc(StatType::InboundRateLimitType, message_type.into());
+                    Some(peer.add_violation(now))
+                }
+                None => None,
+            }
+        };
+
+        match violation {
+            None => true,
+            Some(violations) => {
+                if self.config.max_violations > 0 && violations == self.config.max_violations {
+                    self.stats
+                        .inc(StatType::InboundRateLimit, DetailType::Misbehaving);
+                    debug!(
+                        channel_id = %channel.channel_id(),
+                        peer_addr = %channel.peer_addr(),
+                        violations,
+                        "Peer exceeded inbound rate limits repeatedly"
+                    );
+                    self.network_info
+                        .write()
+                        .unwrap()
+                        .peer_misbehaved(channel.channel_id(), now);
+                }
+                false
+            }
+        }
+    }
+
+    pub fn peer_count(&self) -> usize {
+        self.peers.lock().unwrap().len()
+    }
+
+    pub fn container_info(&self) -> ContainerInfo {
+        [(
+            "peers",
+            self.peer_count(),
+            size_of::<ChannelId>() + size_of::<PeerLimits>(),
+        )]
+        .into()
+    }
+}
+
+struct PeerLimits {
+    peer_bucket: Option<TokenBucket>,
+    type_buckets: HashMap<MessageType, TokenBucket>,
+    violations: usize,
+    window_start: Timestamp,
+}
+
+impl PeerLimits {
+    fn new(config: &InboundRateLimiterConfig, now: Timestamp) -> Self {
+        let peer_bucket = if config.peer_limit.is_unlimited() {
+            None
+        } else {
+            Some(config.peer_limit.create_bucket())
+        };
+
+        Self {
+            peer_bucket,
+            type_buckets: HashMap::new(),
+            violations: 0,
+            window_start: now,
+        }
+    }
+
+    /// Returns the violated limit, if any
+    fn check(
+        &mut self,
+        config: &InboundRateLimiterConfig,
+        message_type: MessageType,
+    ) -> Option<DetailType> {
+        if let Some(limit) = config.type_limits.get(&message_type) {
+            if !limit.is_unlimited() {
+                let bucket = self
+                    .type_buckets
+                    .entry(message_type)
+                    .or_insert_with(|| limit.create_bucket());
+                if !bucket.try_consume(1) {
+                    return Some(DetailType::TypeLimit);
+                }
+            }
+        }
+
+        if let Some(bucket) = &mut self.peer_bucket {
+            if !bucket.try_consume(1) {
+                return Some(DetailType::PeerLimit);
+            }
+        }
+
+        None
+    }
+
+    /// Returns the number of violations in the current window
+    fn add_violation(&mut self, now: Timestamp) -> usize {
+        if self.window_start.elapsed(now) >= VIOLATION_WINDOW {
+            self.window_start = now;
+            self.violations = 0;
+        }
+        self.violations += 1;
+        self.violations
+    }
+}
+
+pub struct InboundRateLimiterCleanup(Arc<InboundRateLimiter>);
+
+impl InboundRateLimiterCleanup {
+    pub fn new(limiter: Arc<InboundRateLimiter>) -> Self {
+        Self(limiter)
+    }
+}
+
+impl DeadChannelCleanupStep for InboundRateLimiterCleanup {
+    fn clean_up_dead_channels(&self, dead_channel_ids: &[ChannelId]) {
+        let mut peers = self.0.peers.lock().unwrap();
+        for channel_id in dead_channel_ids {
+            peers.remove(channel_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::Direction;
+    use rsban_core::utils::{TEST_ENDPOINT_1, TEST_ENDPOINT_2};
+    use rsban_network::{ChannelDirection, ChannelMode};
+
+    #[test]
+    fn no_limits_by_default() {
+        let limiter = InboundRateLimiter::new_null();
+        let channel = ChannelInfo::new_test_instance();
+        for _ in 0..10_000 {
+            assert!(limiter.should_pass(&channel, MessageType::Publish));
+            assert!(limiter.should_pass(&channel, MessageType::ConfirmReq));
+        }
+        assert_eq!(limiter.peer_count(), 1);
+    }
+
+    #[test]
+    fn pass_messages_within_limits() {
+        let fixture = Fixture::new(config(RateLimit::new(1, 3), 0));
+        let channel = fixture.add_channel();
+        for _ in 0..3 {
+            assert!(fixture
+                .limiter
+                .should_pass(&channel, MessageType::ConfirmReq));
+        }
+        assert_eq!(
+            fixture.stats.count(
+                StatType::InboundRateLimit,
+                DetailType::TypeLimit,
+                Direction::In
+            ),
+            0
+        );
+    }
+
+    #[test]
+    fn drop_messages_above_type_limit() {
+        let fixture = Fixture::new(config(RateLimit::new(1, 2), 0));
+        let channel = fixture.add_channel();
+
+        fixture
+            .limiter
+            .should_pass(&channel, MessageType::ConfirmReq);
+        fixture
+            .limiter
+            .should_pass(&channel, MessageType::ConfirmReq);
+        assert!(!fixture
+            .limiter
+            .should_pass(&channel, MessageType::ConfirmReq));
+        // Other message types are not affected
+        assert!(fixture
+            .limiter
+            .should_pass(&channel, MessageType::Keepalive));
+
+        assert_eq!(
+            fixture.stats.count(
+                StatType::InboundRateLimit,
+                DetailType::TypeLimit,
+                Direction::In
+            ),
+            1
+        );
+        assert_eq!(
+            fixture.stats.count(
+                StatType::InboundRateLimitType,
+                DetailType::ConfirmReq,
+                Direction::In
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn drop_messages_above_peer_limit() {
+        let fixture = Fixture::new(InboundRateLimiterConfig {
+            peer_limit: RateLimit::new(1, 2),
+            type_limits: HashMap::new(),
+            max_violations: 0,
+        });
+        let channel = fixture.add_channel();
+
+        assert!(fixture
+            .limiter
+            .should_pass(&channel, MessageType::Keepalive));
+        assert!(fixture.limiter.should_pass(&channel, MessageType::Publish));
+        assert!(!fixture
+            .limiter
+            .should_pass(&channel, MessageType::ConfirmAck));
+        assert_eq!(
+            fixture.stats.count(
+                StatType::InboundRateLimit,
+                DetailType::PeerLimit,
+                Direction::In
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn limits_are_per_channel() {
+        let fixture = Fixture::new(config(RateLimit::new(1, 1), 0));
+        let channel1 = fixture.add_channel();
+        let channel2 = fixture.add_channel();
+
+        assert!(fixture
+            .limiter
+            .should_pass(&channel1, MessageType::ConfirmReq));
+        assert!(fixture
+            .limiter
+            .should_pass(&channel2, MessageType::ConfirmReq));
+        assert_eq!(fixture.limiter.peer_count(), 2);
+    }
+
+    #[test]
+    fn repeated_violations_mark_peer_as_misbehaving() {
+        let fixture = Fixture::new(config(RateLimit::new(1, 1), 3));
+        let channel = fixture.add_channel();
+
+        for _ in 0..3 {
+            fixture
+                .limiter
+                .should_pass(&channel, MessageType::ConfirmReq);
+        }
+        assert!(channel.is_alive());
+
+        fixture
+            .limiter
+            .should_pass(&channel, MessageType::ConfirmReq);
+        assert!(!channel.is_alive());
+        assert_eq!(
+            fixture.stats.count(
+                StatType::InboundRateLimit,
+                DetailType::Misbehaving,
+                Direction::In
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn clean_up_dead_channels() {
+        let fixture = Fixture::new(config(RateLimit::new(1, 1), 0));
+        let channel = fixture.add_channel();
+        fixture
+            .limiter
+            .should_pass(&channel, MessageType::ConfirmReq);
+
+        InboundRateLimiterCleanup::new(fixture.limiter.clone())
+            .clean_up_dead_channels(&[channel.channel_id()]);
+
+        assert_eq!(fixture.limiter.peer_count(), 0);
+    }
+
+    fn config(confirm_req_limit: RateLimit, max_violations: usize) -> InboundRateLimiterConfig {
+        InboundRateLimiterConfig {
+            peer_limit: RateLimit::UNLIMITED,
+            type_limits: HashMap::from([(MessageType::ConfirmReq, confirm_req_limit)]),
+            max_violations,
+        }
+    }
+
+    struct Fixture {
+        limiter: Arc<InboundRateLimiter>,
+        stats: Arc<Stats>,
+        network_info: Arc<RwLock<NetworkInfo>>,
+    }
+
+    impl Fixture {
+        fn new(config: InboundRateLimiterConfig) -> Self {
+            let stats = Arc::new(Stats::default());
+            let network_info = Arc::new(RwLock::new(NetworkInfo::new_test_instance()));
+            let limiter = Arc::new(InboundRateLimiter::new(
+                config,
+                stats.clone(),
+                network_info.clone(),
+                Arc::new(SteadyClock::new_null()),
+            ));
+            Self {
+                limiter,
+                stats,
+                network_info,
+            }
+        }
+
+        fn add_channel(&self) -> Arc<ChannelInfo> {
+            self.network_info
+                .write()
+                .unwrap()
+                .add(
+                    TEST_ENDPOINT_1,
+                    TEST_ENDPOINT_2,
+                    ChannelDirection::Inbound,
+                    ChannelMode::Realtime,
+                    Timestamp::new_test_instance(),
+                )
+                .unwrap()
+        }
+    }
+}
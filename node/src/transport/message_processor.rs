@@ -1,4 +1,4 @@
-use super::{InboundMessageQueue, RealtimeMessageHandler};
+use super::{InboundMessageQueue, InboundRateLimiterConfig, RealtimeMessageHandler};
 use crate::config::{NodeConfig, NodeFlags};
 use rsban_messages::Message;
 use rsban_network::{ChannelId, ChannelInfo};
@@ -18,6 +18,7 @@ use tracing::debug;
 pub struct MessageProcessorConfig {
     pub threads: usize,
     pub max_queue: usize,
+    pub rate_limiter: InboundRateLimiterConfig,
 }
 
 impl MessageProcessorConfig {
@@ -25,6 +26,7 @@ impl MessageProcessorConfig {
         Self {
             threads: min(2, max(parallelism / 4, 1)),
             max_queue: 64,
+            rate_limiter: InboundRateLimiterConfig::default(),
         }
     }
 }
@@ -0,0 +1,319 @@
+use num::FromPrimitive;
+use rsban_messages::{
+    deserialize_message, DeserializedMessage, Message, MessageSerializer, ProtocolInfo,
+};
+use rsban_network::{ChannelDirection, ChannelId};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    net::{Ipv6Addr, SocketAddrV6},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/*
+ * Recording file format:
+ * [8 bytes] Magic "RSBREC02"
+ * followed by any number of frames:
+ * [8 bytes] Timestamp in milliseconds since the UNIX epoch (big endian)
+ * [8 bytes] Channel id (big endian)
+ * [1 byte]  Direction (0 = inbound, 1 = outbound)
+ * [16 bytes] Peer IPv6 address (unspecified if unknown)
+ * [2 bytes] Peer port (big endian)
+ * [4 bytes] Length of the serialized message (big endian)
+ * [n bytes] Serialized message (header + payload)
+ *
+ * Version 1 recordings ("RSBREC01") have no peer address in the frame header.
+ * They can still be read, the peer address is unspecified for all their messages.
+ */
+const MAGIC: &[u8; 8] = b"RSBREC02";
+const MAGIC_V1: &[u8; 8] = b"RSBREC01";
+const FRAME_HEADER_SIZE: usize = 8 + 8 + 1 + 16 + 2 + 4;
+const FRAME_HEADER_SIZE_V1: usize = 8 + 8 + 1 + 4;
+const MAX_FRAME_SIZE: usize = 1024 * 128;
+
+/// A message that was sent or received by a node at a specific point in time
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordedMessage {
+    pub timestamp: SystemTime,
+    pub channel_id: ChannelId,
+    pub direction: ChannelDirection,
+    pub peer_addr: SocketAddrV6,
+    pub message: DeserializedMessage,
+}
+
+impl RecordedMessage {
+    pub fn new_test_instance() -> Self {
+        Self {
+            timestamp: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            channel_id: 42.into(),
+            direction: ChannelDirection::Inbound,
+            peer_addr: SocketAddrV6::new(Ipv6Addr::LOCALHOST, 7071, 0, 0),
+            message: DeserializedMessage::new(Message::TelemetryReq, ProtocolInfo::default()),
+        }
+    }
+}
+
+/// Writes recorded messages in the framed recording format
+pub struct MessageRecordingWriter<W: Write> {
+    writer: W,
+    serializers: Vec<MessageSerializer>,
+    bytes_written: usize,
+}
+
+impl MessageRecordingWriter<BufWriter<File>> {
+    pub fn create(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> MessageRecordingWriter<W> {
+    pub fn new(mut writer: W) -> anyhow::Result<Self> {
+        writer.write_all(MAGIC)?;
+        Ok(Self {
+            writer,
+            serializers: Vec::new(),
+            bytes_written: MAGIC.len(),
+        })
+    }
+
+    pub fn write(&mut self, recorded: &RecordedMessage) -> anyhow::Result<()> {
+        let serializer = serializer_for(&mut self.serializers, recorded.message.protocol);
+        let bytes = serializer.serialize(&recorded.message.message);
+
+        let millis = recorded
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        let mut frame_header = [0; FRAME_HEADER_SIZE];
+        frame_header[..8].copy_from_slice(&millis.to_be_bytes());
+        frame_header[8..16].copy_from_slice(&(recorded.channel_id.as_usize() as u64).to_be_bytes());
+        frame_header[16] = recorded.direction as u8;
+        frame_header[17..33].copy_from_slice(&recorded.peer_addr.ip().octets());
+        frame_header[33..35].copy_from_slice(&recorded.peer_addr.port().to_be_bytes());
+        frame_header[35..].copy_from_slice(&(bytes.len() as u32).to_be_bytes());
+
+        self.writer.write_all(&frame_header)?;
+        self.writer.write_all(bytes)?;
+        self.bytes_written += frame_header.len() + bytes.len();
+        Ok(())
+    }
+
+    pub fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+
+    pub fn flush(&mut self) -> anyhow::Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    pub fn into_inner(mut self) -> anyhow::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// The header of each message contains the protocol info, so a serializer is needed
+/// for every protocol that occurs in the recording
+fn serializer_for(
+    serializers: &mut Vec<MessageSerializer>,
+    protocol: ProtocolInfo,
+) -> &mut MessageSerializer {
+    let index = match serializers.iter().position(|s| s.protocol() == protocol) {
+        Some(i) => i,
+        None => {
+            serializers.push(MessageSerializer::new(protocol));
+            serializers.len() - 1
+        }
+    };
+    &mut serializers[index]
+}
+
+/// Reads recorded messages from the framed recording format
+pub struct MessageRecordingReader<R: Read> {
+    reader: R,
+    buffer: Vec<u8>,
+    has_peer_addr: bool,
+}
+
+impl MessageRecordingReader<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> MessageRecordingReader<R> {
+    pub fn new(mut reader: R) -> anyhow::Result<Self> {
+        let mut magic = [0; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        let has_peer_addr = if &magic == MAGIC {
+            true
+        } else if &magic == MAGIC_V1 {
+            false
+        } else {
+            bail!("not a message recording");
+        };
+        Ok(Self {
+            reader,
+            buffer: Vec::new(),
+            has_peer_addr,
+        })
+    }
+
+    /// Reads the next recorded message. Returns `None` at the end of the recording
+    pub fn read(&mut self) -> anyhow::Result<Option<RecordedMessage>> {
+        let mut frame_header = [0; FRAME_HEADER_SIZE];
+        let header_size = if self.has_peer_addr {
+            FRAME_HEADER_SIZE
+        } else {
+            FRAME_HEADER_SIZE_V1
+        };
+        let frame_header = &mut frame_header[..header_size];
+        match self.reader.read_exact(&mut frame_header[..1]) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+        self.reader.read_exact(&mut frame_header[1..])?;
+
+        let millis = u64::from_be_bytes(frame_header[..8].try_into().unwrap());
+        let channel_id = u64::from_be_bytes(frame_header[8..16].try_into().unwrap());
+        let direction = ChannelDirection::from_u8(frame_header[16])
+            .ok_or_else(|| anyhow!("invalid channel direction"))?;
+        let peer_addr = if self.has_peer_addr {
+            let ip: [u8; 16] = frame_header[17..33].try_into().unwrap();
+            let port = u16::from_be_bytes(frame_header[33..35].try_into().unwrap());
+            SocketAddrV6::new(Ipv6Addr::from(ip), port, 0, 0)
+        } else {
+            SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0)
+        };
+        let len = u32::from_be_bytes(frame_header[header_size - 4..].try_into().unwrap()) as usize;
+        if len > MAX_FRAME_SIZE {
+            bail!("recorded message too big: {} bytes", len);
+        }
+
+        self.buffer.resize(len, 0);
+        self.reader.read_exact(&mut self.buffer)?;
+        let (header, message) = deserialize_message(&self.buffer)?;
+
+        Ok(Some(RecordedMessage {
+            timestamp: UNIX_EPOCH + Duration::from_millis(millis),
+            channel_id: (channel_id as usize).into(),
+            direction,
+            peer_addr,
+            message: DeserializedMessage::new(message, header.protocol),
+        }))
+    }
+}
+
+impl<R: Read> Iterator for MessageRecordingReader<R> {
+    type Item = anyhow::Result<RecordedMessage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_core::Networks;
+    use rsban_messages::{ConfirmAck, Keepalive, Publish};
+    use std::io::Cursor;
+
+    #[test]
+    fn empty_recording() {
+        let writer = MessageRecordingWriter::new(Vec::new()).unwrap();
+        let bytes = writer.into_inner().unwrap();
+        let mut reader = MessageRecordingReader::new(Cursor::new(bytes)).unwrap();
+        assert!(reader.read().unwrap().is_none());
+    }
+
+    #[test]
+    fn roundtrip() {
+        let messages = vec![
+            RecordedMessage::new_test_instance(),
+            RecordedMessage {
+                timestamp: UNIX_EPOCH + Duration::from_millis(1_700_000_000_123),
+                channel_id: 7.into(),
+                direction: ChannelDirection::Outbound,
+                peer_addr: "[::ffff:10.0.0.1]:7071".parse().unwrap(),
+                message: DeserializedMessage::new(
+                    Message::Keepalive(Keepalive::default()),
+                    ProtocolInfo::default(),
+                ),
+            },
+            RecordedMessage {
+                message: DeserializedMessage::new(
+                    Message::Publish(Publish::new_test_instance()),
+                    ProtocolInfo::default(),
+                ),
+                ..RecordedMessage::new_test_instance()
+            },
+            RecordedMessage {
+                message: DeserializedMessage::new(
+                    Message::ConfirmAck(ConfirmAck::new_test_instance()),
+                    ProtocolInfo::default_for(Networks::BananoDevNetwork),
+                ),
+                ..RecordedMessage::new_test_instance()
+            },
+        ];
+
+        let mut writer = MessageRecordingWriter::new(Vec::new()).unwrap();
+        for message in &messages {
+            writer.write(message).unwrap();
+        }
+        let bytes_written = writer.bytes_written();
+        let bytes = writer.into_inner().unwrap();
+        assert_eq!(bytes_written, bytes.len());
+
+        let reader = MessageRecordingReader::new(Cursor::new(bytes)).unwrap();
+        let read: Vec<_> = reader.map(|i| i.unwrap()).collect();
+        assert_eq!(read, messages);
+    }
+
+    #[test]
+    fn invalid_magic() {
+        assert!(MessageRecordingReader::new(Cursor::new(b"INVALID!".to_vec())).is_err());
+    }
+
+    #[test]
+    fn read_version_1_recording() {
+        let message = RecordedMessage::new_test_instance();
+        let mut serializer = MessageSerializer::new(message.message.protocol);
+        let bytes = serializer.serialize(&message.message.message);
+        let mut recording = MAGIC_V1.to_vec();
+        recording.extend_from_slice(&1_700_000_000_000u64.to_be_bytes());
+        recording.extend_from_slice(&42u64.to_be_bytes());
+        recording.push(ChannelDirection::Inbound as u8);
+        recording.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        recording.extend_from_slice(bytes);
+
+        let read: Vec<_> = MessageRecordingReader::new(Cursor::new(recording))
+            .unwrap()
+            .map(|i| i.unwrap())
+            .collect();
+
+        assert_eq!(
+            read,
+            vec![RecordedMessage {
+                peer_addr: SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0),
+                ..message
+            }]
+        );
+    }
+
+    #[test]
+    fn truncated_frame() {
+        let mut writer = MessageRecordingWriter::new(Vec::new()).unwrap();
+        writer.write(&RecordedMessage::new_test_instance()).unwrap();
+        let mut bytes = writer.into_inner().unwrap();
+        bytes.pop();
+
+        let mut reader = MessageRecordingReader::new(Cursor::new(bytes)).unwrap();
+        assert!(reader.read().is_err());
+    }
+}
@@ -0,0 +1,266 @@
+use super::{InboundMessageQueue, RecordedMessage};
+use rsban_network::{ChannelDirection, ChannelId, ChannelInfo};
+use rsban_nullable_clock::SteadyClock;
+use std::{
+    collections::HashMap,
+    net::{Ipv6Addr, SocketAddrV6},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReplaySpeed {
+    /// Keep the original gaps between the recorded messages
+    Original,
+    /// Shorten the original gaps between the recorded messages by the given factor
+    Accelerated(f64),
+    /// Enqueue all messages as fast as possible
+    Unthrottled,
+}
+
+impl ReplaySpeed {
+    /// The time after the start of the replay at which a message with the given
+    /// offset into the recording should be enqueued
+    pub fn scale(&self, offset: Duration) -> Duration {
+        match self {
+            ReplaySpeed::Original => offset,
+            ReplaySpeed::Accelerated(factor) if *factor > 0.0 => offset.div_f64(*factor),
+            ReplaySpeed::Accelerated(_) | ReplaySpeed::Unthrottled => Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReplayResult {
+    /// Messages that were put into the inbound queue
+    pub replayed: usize,
+    /// Messages that were dropped because the inbound queue was full
+    pub dropped: usize,
+    /// Outbound messages, which were sent by the recording node and are not replayed
+    pub skipped: usize,
+}
+
+/// Feeds a recorded message stream into the inbound message queue of a node, without
+/// using any sockets. Every recorded channel is represented by a fake channel, so that
+/// the fair queue treats the peers like in the recording.
+pub struct MessageReplayer {
+    queue: Arc<InboundMessageQueue>,
+    clock: Arc<SteadyClock>,
+    speed: ReplaySpeed,
+    channels: HashMap<ChannelId, Arc<ChannelInfo>>,
+    sleep: Box<dyn FnMut(Duration) + Send>,
+}
+
+impl MessageReplayer {
+    pub fn new(
+        queue: Arc<InboundMessageQueue>,
+        clock: Arc<SteadyClock>,
+        speed: ReplaySpeed,
+    ) -> Self {
+        Self {
+            queue,
+            clock,
+            speed,
+            channels: HashMap::new(),
+            sleep: Box::new(std::thread::sleep),
+        }
+    }
+
+    /// Replaces the function that waits until the next message is due
+    pub fn set_sleep(&mut self, sleep: impl FnMut(Duration) + Send + 'static) {
+        self.sleep = Box::new(sleep);
+    }
+
+    pub fn replay(&mut self, messages: impl IntoIterator<Item = RecordedMessage>) -> ReplayResult {
+        let mut result = ReplayResult::default();
+        let started = self.clock.now();
+        let mut first_timestamp: Option<SystemTime> = None;
+
+        for recorded in messages {
+            if recorded.direction == ChannelDirection::Outbound {
+                result.skipped += 1;
+                continue;
+            }
+
+            let first = *first_timestamp.get_or_insert(recorded.timestamp);
+            let offset = recorded.timestamp.duration_since(first).unwrap_or_default();
+            let due = started + self.speed.scale(offset);
+            let now = self.clock.now();
+            if due > now {
+                (self.sleep)(due - now);
+            }
+
+            let channel = self.channel(&recorded);
+            if self.queue.put(recorded.message.message, channel) {
+                result.replayed += 1;
+            } else {
+                result.dropped += 1;
+            }
+        }
+
+        result
+    }
+
+    fn channel(&mut self, recorded: &RecordedMessage) -> Arc<ChannelInfo> {
+        self.channels
+            .entry(recorded.channel_id)
+            .or_insert_with(|| {
+                Arc::new(ChannelInfo::new(
+                    recorded.channel_id,
+                    SocketAddrV6::new(Ipv6Addr::LOCALHOST, 7075, 0, 0),
+                    peer_addr(recorded),
+                    ChannelDirection::Inbound,
+                    recorded.message.protocol.version_using,
+                    self.clock.now(),
+                ))
+            })
+            .clone()
+    }
+}
+
+/// The recorded peer address or, if it is unknown, a unique local address (fd00::/8)
+/// that encodes the recorded channel id
+fn peer_addr(recorded: &RecordedMessage) -> SocketAddrV6 {
+    if !recorded.peer_addr.ip().is_unspecified() {
+        return recorded.peer_addr;
+    }
+    let ip = Ipv6Addr::from((0xfd_u128 << 120) | recorded.channel_id.as_usize() as u128);
+    SocketAddrV6::new(ip, 7075, 0, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_messages::{DeserializedMessage, Message, ProtocolInfo};
+    use std::sync::Mutex;
+
+    #[test]
+    fn scale_offsets() {
+        let offset = Duration::from_secs(10);
+        assert_eq!(ReplaySpeed::Original.scale(offset), offset);
+        assert_eq!(
+            ReplaySpeed::Accelerated(4.0).scale(offset),
+            Duration::from_millis(2500)
+        );
+        assert_eq!(ReplaySpeed::Unthrottled.scale(offset), Duration::ZERO);
+        assert_eq!(ReplaySpeed::Accelerated(0.0).scale(offset), Duration::ZERO);
+    }
+
+    #[test]
+    fn replay_inbound_messages() {
+        let queue = Arc::new(InboundMessageQueue::default());
+        let mut replayer = create_replayer(queue.clone());
+
+        let result = replayer.replay([
+            RecordedMessage::new_test_instance(),
+            RecordedMessage::new_test_instance(),
+        ]);
+
+        assert_eq!(
+            result,
+            ReplayResult {
+                replayed: 2,
+                dropped: 0,
+                skipped: 0
+            }
+        );
+        assert_eq!(queue.size(), 2);
+    }
+
+    #[test]
+    fn skip_outbound_messages() {
+        let queue = Arc::new(InboundMessageQueue::default());
+        let mut replayer = create_replayer(queue.clone());
+
+        let result = replayer.replay([RecordedMessage {
+            direction: ChannelDirection::Outbound,
+            ..RecordedMessage::new_test_instance()
+        }]);
+
+        assert_eq!(result.skipped, 1);
+        assert_eq!(queue.size(), 0);
+    }
+
+    #[test]
+    fn keep_recorded_channel_ids() {
+        let queue = Arc::new(InboundMessageQueue::default());
+        let mut replayer = create_replayer(queue.clone());
+
+        replayer.replay([
+            recorded_on_channel(1),
+            recorded_on_channel(2),
+            recorded_on_channel(1),
+        ]);
+
+        let batch = queue.next_batch(10);
+        let mut channel_ids: Vec<_> = batch.iter().map(|(id, _)| *id).collect();
+        channel_ids.sort_by_key(|id| id.as_usize());
+        assert_eq!(channel_ids, vec![1.into(), 1.into(), 2.into()]);
+        let (_, (_, channel)) = batch.iter().find(|(id, _)| *id == 2.into()).unwrap();
+        assert_eq!(channel.peer_addr(), recorded_on_channel(2).peer_addr);
+    }
+
+    #[test]
+    fn fake_peer_address_when_unknown() {
+        let recorded = RecordedMessage {
+            peer_addr: SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0),
+            ..recorded_on_channel(3)
+        };
+        assert_eq!(
+            peer_addr(&recorded),
+            "[fd00::3]:7075".parse::<SocketAddrV6>().unwrap()
+        );
+    }
+
+    #[test]
+    fn drop_messages_when_queue_is_full() {
+        let queue = Arc::new(InboundMessageQueue::new(1, Default::default()));
+        let mut replayer = create_replayer(queue.clone());
+
+        let result = replayer.replay([recorded_on_channel(1), recorded_on_channel(1)]);
+
+        assert_eq!(result.replayed, 1);
+        assert_eq!(result.dropped, 1);
+    }
+
+    #[test]
+    fn wait_for_recorded_gaps() {
+        let queue = Arc::new(InboundMessageQueue::default());
+        let mut replayer = MessageReplayer::new(
+            queue,
+            Arc::new(SteadyClock::new_null()),
+            ReplaySpeed::Accelerated(2.0),
+        );
+        let sleeps = Arc::new(Mutex::new(Vec::new()));
+        let sleeps_clone = sleeps.clone();
+        replayer.set_sleep(move |duration| sleeps_clone.lock().unwrap().push(duration));
+        let first = RecordedMessage::new_test_instance();
+
+        replayer.replay([
+            first.clone(),
+            RecordedMessage {
+                timestamp: first.timestamp + Duration::from_secs(4),
+                ..first.clone()
+            },
+        ]);
+
+        assert_eq!(*sleeps.lock().unwrap(), vec![Duration::from_secs(2)]);
+    }
+
+    fn create_replayer(queue: Arc<InboundMessageQueue>) -> MessageReplayer {
+        MessageReplayer::new(
+            queue,
+            Arc::new(SteadyClock::new_null()),
+            ReplaySpeed::Unthrottled,
+        )
+    }
+
+    fn recorded_on_channel(channel_id: usize) -> RecordedMessage {
+        RecordedMessage {
+            channel_id: channel_id.into(),
+            peer_addr: SocketAddrV6::new(Ipv6Addr::LOCALHOST, 1000 + channel_id as u16, 0, 0),
+            message: DeserializedMessage::new(Message::TelemetryReq, ProtocolInfo::default()),
+            ..RecordedMessage::new_test_instance()
+        }
+    }
+}
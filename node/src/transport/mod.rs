@@ -2,10 +2,13 @@ mod block_deserializer;
 mod fair_queue;
 mod handshake_process;
 mod inbound_message_queue;
+mod inbound_rate_limiter;
 mod latest_keepalives;
 mod message_deserializer;
 mod message_processor;
 mod message_publisher;
+mod message_recording;
+mod message_replayer;
 mod network_filter;
 mod network_threads;
 mod peer_cache_connector;
@@ -20,10 +23,13 @@ pub use block_deserializer::read_block;
 pub use fair_queue::*;
 pub(crate) use handshake_process::*;
 pub use inbound_message_queue::*;
+pub use inbound_rate_limiter::*;
 pub use latest_keepalives::*;
 pub use message_deserializer::MessageDeserializer;
 pub use message_processor::*;
 pub use message_publisher::*;
+pub use message_recording::*;
+pub use message_replayer::*;
 pub use network_filter::NetworkFilter;
 pub(crate) use network_threads::*;
 pub use peer_cache_connector::*;
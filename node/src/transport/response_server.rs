@@ -1,6 +1,6 @@
 use super::{
-    HandshakeProcess, HandshakeStatus, InboundMessageQueue, LatestKeepalives, MessageDeserializer,
-    NetworkFilter, SynCookies,
+    HandshakeProcess, HandshakeStatus, InboundMessageQueue, InboundRateLimiter, LatestKeepalives,
+    MessageDeserializer, NetworkFilter, SynCookies,
 };
 use crate::{
     block_processing::BlockProcessor,
@@ -78,6 +78,7 @@ pub struct ResponseServer {
     allow_bootstrap: bool,
     network_info: Arc<RwLock<NetworkInfo>>,
     inbound_queue: Arc<InboundMessageQueue>,
+    rate_limiter: Arc<InboundRateLimiter>,
     handshake_process: HandshakeProcess,
     initiate_handshake_listener: OutputListenerMt<()>,
     network_filter: Arc<NetworkFilter>,
@@ -96,6 +97,7 @@ impl ResponseServer {
     pub fn new(
         network_info: Arc<RwLock<NetworkInfo>>,
         inbound_queue: Arc<InboundMessageQueue>,
+        rate_limiter: Arc<InboundRateLimiter>,
         channel: Arc<Channel>,
         network_filter: Arc<NetworkFilter>,
         network_params: Arc<NetworkParams>,
@@ -116,6 +118,7 @@ impl ResponseServer {
         Self {
             network_info,
             inbound_queue,
+            rate_limiter,
             channel,
             disable_bootstrap_listener: false,
             connections_max: 64,
@@ -222,6 +225,12 @@ impl ResponseServer {
     }
 
     fn queue_realtime(&self, message: Message) {
+        if !self
+            .rate_limiter
+            .should_pass(&self.channel.info, message.message_type())
+        {
+            return;
+        }
         self.inbound_queue.put(message, self.channel.info.clone());
         // TODO: Throttle if not added
     }
@@ -1,6 +1,6 @@
 use super::{
-    InboundMessageQueue, LatestKeepalives, MessagePublisher, NetworkFilter, ResponseServer,
-    ResponseServerExt, SynCookies,
+    InboundMessageQueue, InboundRateLimiter, LatestKeepalives, MessagePublisher, NetworkFilter,
+    ResponseServer, ResponseServerExt, SynCookies,
 };
 use crate::{
     block_processing::BlockProcessor,
@@ -29,6 +29,7 @@ pub struct NanoResponseServerSpawner {
     pub(crate) network: Arc<RwLock<NetworkInfo>>,
     pub(crate) network_filter: Arc<NetworkFilter>,
     pub(crate) inbound_queue: Arc<InboundMessageQueue>,
+    pub(crate) rate_limiter: Arc<InboundRateLimiter>,
     pub(crate) node_flags: NodeFlags,
     pub(crate) network_params: NetworkParams,
     pub(crate) syn_cookies: Arc<SynCookies>,
@@ -72,6 +73,7 @@ impl NanoResponseServerSpawner {
             )),
             network: network_info,
             inbound_queue: Arc::new(InboundMessageQueue::default()),
+            rate_limiter: Arc::new(InboundRateLimiter::new_null()),
             node_flags: flags,
             network_params,
             syn_cookies: Arc::new(SynCookies::new(1)),
@@ -91,6 +93,7 @@ impl NanoResponseServerSpawner {
         let server = Arc::new(ResponseServer::new(
             self.network.clone(),
             self.inbound_queue.clone(),
+            self.rate_limiter.clone(),
             channel,
             self.network_filter.clone(),
             Arc::new(self.network_params.clone()),
@@ -0,0 +1,206 @@
+use rsban_core::{Account, Amount, BlockHash};
+use std::collections::HashSet;
+
+/// A single payment of a batch send
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BatchSendItem {
+    pub destination: Account,
+    pub amount: Amount,
+    /// Unique send id. An item whose id was already used is not sent again,
+    /// which makes it possible to resume a partially failed batch.
+    pub id: String,
+}
+
+impl BatchSendItem {
+    pub fn new(destination: Account, amount: Amount, id: impl Into<String>) -> Self {
+        Self {
+            destination,
+            amount,
+            id: id.into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BatchSendStatus {
+    /// A new block was created for this item
+    Sent,
+    /// The id was used by an earlier send, the existing block is returned
+    AlreadySent,
+    Failed,
+    /// Not attempted, because an earlier item of the batch failed
+    Pending,
+}
+
+impl BatchSendStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BatchSendStatus::Sent => "sent",
+            BatchSendStatus::AlreadySent => "already_sent",
+            BatchSendStatus::Failed => "failed",
+            BatchSendStatus::Pending => "pending",
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        matches!(self, BatchSendStatus::Sent | BatchSendStatus::AlreadySent)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BatchSendResult {
+    pub id: String,
+    pub status: BatchSendStatus,
+    pub block: Option<BlockHash>,
+    pub error: Option<String>,
+}
+
+impl BatchSendResult {
+    pub fn new(id: String, status: BatchSendStatus) -> Self {
+        Self {
+            id,
+            status,
+            block: None,
+            error: None,
+        }
+    }
+}
+
+/// Progress of a batch send. The items are sent one after another, each by its
+/// own wallet action, and the batch stops at the first failure.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BatchSendProgress {
+    pub items: Vec<BatchSendItem>,
+    pub results: Vec<BatchSendResult>,
+}
+
+impl BatchSendProgress {
+    pub fn new(items: Vec<BatchSendItem>, results: Vec<BatchSendResult>) -> Self {
+        Self { items, results }
+    }
+
+    /// The next item to send, or None if all items are done or an item failed
+    pub fn next_pending(&self) -> Option<usize> {
+        self.results
+            .iter()
+            .position(|r| !r.status.is_done())
+            .filter(|i| self.results[*i].status == BatchSendStatus::Pending)
+    }
+
+    pub fn item_sent(&mut self, index: usize, result: anyhow::Result<BlockHash>) {
+        let item = &mut self.results[index];
+        match result {
+            Ok(hash) => {
+                item.status = BatchSendStatus::Sent;
+                item.block = Some(hash);
+            }
+            Err(e) => {
+                item.status = BatchSendStatus::Failed;
+                item.error = Some(e.to_string());
+            }
+        }
+    }
+}
+
+/// Maximum number of items of a single batch send
+pub const MAX_BATCH_SEND_ITEMS: usize = 1000;
+
+pub(crate) fn validate_batch(items: &[BatchSendItem]) -> anyhow::Result<()> {
+    if items.is_empty() {
+        bail!("batch is empty");
+    }
+    if items.len() > MAX_BATCH_SEND_ITEMS {
+        bail!("batch contains more than {} sends", MAX_BATCH_SEND_ITEMS);
+    }
+    let mut ids = HashSet::new();
+    for item in items {
+        if item.id.is_empty() {
+            bail!("send id must not be empty");
+        }
+        if item.amount.is_zero() {
+            bail!("amount must not be zero");
+        }
+        if !ids.insert(item.id.as_str()) {
+            bail!("duplicate send id: {}", item.id);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_batch() {
+        let items = vec![
+            BatchSendItem::new(Account::from(1), Amount::raw(1), "a"),
+            BatchSendItem::new(Account::from(1), Amount::raw(1), "b"),
+        ];
+        assert!(validate_batch(&items).is_ok());
+    }
+
+    #[test]
+    fn reject_invalid_batches() {
+        assert!(validate_batch(&[]).is_err());
+        assert!(
+            validate_batch(&[BatchSendItem::new(Account::from(1), Amount::raw(1), "")]).is_err()
+        );
+        assert!(
+            validate_batch(&[BatchSendItem::new(Account::from(1), Amount::zero(), "a")]).is_err()
+        );
+        let too_large: Vec<_> = (0..=MAX_BATCH_SEND_ITEMS)
+            .map(|i| BatchSendItem::new(Account::from(1), Amount::raw(1), i.to_string()))
+            .collect();
+        assert!(validate_batch(&too_large).is_err());
+        let duplicates = vec![
+            BatchSendItem::new(Account::from(1), Amount::raw(1), "a"),
+            BatchSendItem::new(Account::from(2), Amount::raw(2), "a"),
+        ];
+        assert_eq!(
+            validate_batch(&duplicates).unwrap_err().to_string(),
+            "duplicate send id: a"
+        );
+    }
+
+    #[test]
+    fn track_progress() {
+        let items = vec![
+            BatchSendItem::new(Account::from(1), Amount::raw(1), "a"),
+            BatchSendItem::new(Account::from(2), Amount::raw(2), "b"),
+            BatchSendItem::new(Account::from(3), Amount::raw(3), "c"),
+        ];
+        let results = vec![
+            BatchSendResult::new("a".to_owned(), BatchSendStatus::AlreadySent),
+            BatchSendResult::new("b".to_owned(), BatchSendStatus::Pending),
+            BatchSendResult::new("c".to_owned(), BatchSendStatus::Pending),
+        ];
+        let mut progress = BatchSendProgress::new(items, results);
+        assert_eq!(progress.next_pending(), Some(1));
+
+        progress.item_sent(1, Ok(BlockHash::from(42)));
+        assert_eq!(progress.results[1].status, BatchSendStatus::Sent);
+        assert_eq!(progress.results[1].block, Some(BlockHash::from(42)));
+        assert_eq!(progress.next_pending(), Some(2));
+
+        progress.item_sent(2, Err(anyhow!("insufficient balance")));
+        assert_eq!(progress.results[2].status, BatchSendStatus::Failed);
+        assert_eq!(progress.next_pending(), None);
+    }
+
+    #[test]
+    fn stop_at_first_failure() {
+        let items = vec![
+            BatchSendItem::new(Account::from(1), Amount::raw(1), "a"),
+            BatchSendItem::new(Account::from(2), Amount::raw(2), "b"),
+        ];
+        let results = vec![
+            BatchSendResult::new("a".to_owned(), BatchSendStatus::Pending),
+            BatchSendResult::new("b".to_owned(), BatchSendStatus::Pending),
+        ];
+        let mut progress = BatchSendProgress::new(items, results);
+        progress.item_sent(0, Err(anyhow!("failed")));
+        assert_eq!(progress.next_pending(), None);
+        assert_eq!(progress.results[1].status, BatchSendStatus::Pending);
+    }
+}
@@ -0,0 +1,265 @@
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+use std::{fmt::Display, str::FromStr};
+
+/// A cron-like cadence with the five classic fields
+/// `minute hour day-of-month month day-of-week`, evaluated in UTC.
+///
+/// Every field accepts `*`, single values, ranges (`1-5`), steps (`*/15`, `0-30/10`)
+/// and comma separated lists. The shorthands `@hourly`, `@daily`, `@weekly`,
+/// `@monthly` and `@yearly` are supported as well. As in cron, a date matches if
+/// either the day of month or the day of week matches when both are restricted.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CronExpression {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronExpression {
+    /// Upper bound for the search of the next occurrence. Expressions like
+    /// `0 0 31 2 *` never match and must not loop forever.
+    const MAX_SEARCH_DAYS: i64 = 366 * 5;
+
+    /// The first occurrence strictly after the given time
+    pub fn next_after(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = time.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = start + Duration::days(Self::MAX_SEARCH_DAYS);
+        let mut current = start;
+        while current < limit {
+            if !contains(self.months, current.month()) {
+                current = first_of_next_month(current)?;
+                continue;
+            }
+            if !self.day_matches(current) {
+                current = midnight(current)? + Duration::days(1);
+                continue;
+            }
+            if !contains(self.hours, current.hour()) {
+                current = current.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !contains(self.minutes, current.minute()) {
+                current += Duration::minutes(1);
+                continue;
+            }
+            return Some(current);
+        }
+        None
+    }
+
+    fn day_matches(&self, time: DateTime<Utc>) -> bool {
+        let dom = contains(self.days_of_month, time.day());
+        let dow = contains(self.days_of_week, time.weekday().num_days_from_sunday());
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+}
+
+impl FromStr for CronExpression {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = s.trim();
+        let expanded = match expression {
+            "@hourly" => "0 * * * *",
+            "@daily" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            bail!("cron expression must have 5 fields: {}", expression);
+        };
+
+        let mut days_of_week = parse_field(day_of_week, 0, 7)?;
+        // Both 0 and 7 mean sunday
+        if contains(days_of_week, 7) {
+            days_of_week |= 1;
+        }
+
+        Ok(Self {
+            expression: expression.to_owned(),
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days_of_month: parse_field(day_of_month, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            days_of_week,
+            day_of_month_restricted: day_of_month != "*",
+            day_of_week_restricted: day_of_week != "*",
+        })
+    }
+}
+
+impl Display for CronExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> anyhow::Result<u64> {
+    let mut result = 0u64;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, parse_number(step)?),
+            None => (item, 1),
+        };
+        if step == 0 {
+            bail!("invalid cron step: {}", item);
+        }
+
+        let (first, last) = if range == "*" {
+            (min, max)
+        } else if let Some((first, last)) = range.split_once('-') {
+            (parse_number(first)?, parse_number(last)?)
+        } else {
+            let value = parse_number(range)?;
+            // "5/10" means "from 5 to the end in steps of 10"
+            (value, if item.contains('/') { max } else { value })
+        };
+
+        if first < min || last > max || first > last {
+            bail!("cron value out of range: {}", item);
+        }
+        for value in (first..=last).step_by(step as usize) {
+            result |= 1 << value;
+        }
+    }
+    Ok(result)
+}
+
+fn parse_number(s: &str) -> anyhow::Result<u32> {
+    s.parse().map_err(|_| anyhow!("invalid cron value: {}", s))
+}
+
+fn contains(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+fn midnight(time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    time.with_hour(0)?.with_minute(0)
+}
+
+fn first_of_next_month(time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let (year, month) = if time.month() == 12 {
+        (time.year() + 1, 1)
+    } else {
+        (time.year(), time.month() + 1)
+    };
+    Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_minute() {
+        let cron: CronExpression = "* * * * *".parse().unwrap();
+        assert_eq!(
+            cron.next_after(time(2024, 1, 1, 10, 0, 30)),
+            Some(time(2024, 1, 1, 10, 1, 0))
+        );
+    }
+
+    #[test]
+    fn daily() {
+        let cron: CronExpression = "@daily".parse().unwrap();
+        assert_eq!(
+            cron.next_after(time(2024, 1, 1, 0, 0, 0)),
+            Some(time(2024, 1, 2, 0, 0, 0))
+        );
+        assert_eq!(cron.to_string(), "@daily");
+    }
+
+    #[test]
+    fn monthly_on_first_at_noon() {
+        let cron: CronExpression = "0 12 1 * *".parse().unwrap();
+        assert_eq!(
+            cron.next_after(time(2024, 12, 1, 12, 0, 0)),
+            Some(time(2025, 1, 1, 12, 0, 0))
+        );
+    }
+
+    #[test]
+    fn steps_and_lists() {
+        let cron: CronExpression = "*/15 8,17 * * *".parse().unwrap();
+        assert_eq!(
+            cron.next_after(time(2024, 1, 1, 8, 46, 0)),
+            Some(time(2024, 1, 1, 17, 0, 0))
+        );
+        assert_eq!(
+            cron.next_after(time(2024, 1, 1, 17, 0, 0)),
+            Some(time(2024, 1, 1, 17, 15, 0))
+        );
+    }
+
+    #[test]
+    fn weekdays() {
+        // 2024-01-06 is a saturday
+        let cron: CronExpression = "30 9 * * 1-5".parse().unwrap();
+        assert_eq!(
+            cron.next_after(time(2024, 1, 6, 0, 0, 0)),
+            Some(time(2024, 1, 8, 9, 30, 0))
+        );
+    }
+
+    #[test]
+    fn sunday_as_seven() {
+        let cron: CronExpression = "0 0 * * 7".parse().unwrap();
+        assert_eq!(
+            cron.next_after(time(2024, 1, 1, 0, 0, 0)),
+            Some(time(2024, 1, 7, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn day_of_month_or_day_of_week() {
+        // the 15th or any monday
+        let cron: CronExpression = "0 0 15 * 1".parse().unwrap();
+        assert_eq!(
+            cron.next_after(time(2024, 1, 9, 0, 0, 0)),
+            Some(time(2024, 1, 15, 0, 0, 0))
+        );
+        assert_eq!(
+            cron.next_after(time(2024, 1, 10, 0, 0, 0)),
+            Some(time(2024, 1, 15, 0, 0, 0))
+        );
+        assert_eq!(
+            cron.next_after(time(2024, 1, 15, 0, 0, 0)),
+            Some(time(2024, 1, 22, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn never_matching() {
+        let cron: CronExpression = "0 0 31 2 *".parse().unwrap();
+        assert_eq!(cron.next_after(time(2024, 1, 1, 0, 0, 0)), None);
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        assert!("* * * *".parse::<CronExpression>().is_err());
+        assert!("60 * * * *".parse::<CronExpression>().is_err());
+        assert!("* * 0 * *".parse::<CronExpression>().is_err());
+        assert!("*/0 * * * *".parse::<CronExpression>().is_err());
+        assert!("5-1 * * * *".parse::<CronExpression>().is_err());
+        assert!("a * * * *".parse::<CronExpression>().is_err());
+    }
+
+    fn time(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, min, sec)
+            .unwrap()
+    }
+}
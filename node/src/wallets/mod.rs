@@ -1,9 +1,21 @@
+mod batch_send;
+mod cron;
+mod payment_schedules;
+mod sweep;
 mod wallet;
 mod wallet_action_thread;
 mod wallet_representatives;
 mod wallets;
+mod watch;
+mod work_cache;
 
+pub use batch_send::*;
+pub use cron::*;
+pub use payment_schedules::*;
+pub use sweep::*;
 pub use wallet::*;
 pub use wallet_action_thread::*;
 pub use wallet_representatives::*;
 pub use wallets::*;
+pub use watch::*;
+pub use work_cache::*;
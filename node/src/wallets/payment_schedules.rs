@@ -0,0 +1,411 @@
+use super::CronExpression;
+use chrono::{DateTime, Utc};
+use rand::{thread_rng, Rng};
+use rsban_core::{
+    utils::{BufferReader, BufferWriter, Deserialize, MemoryStream, Serialize, Stream, StreamExt},
+    Account, Amount, BlockHash, WalletId,
+};
+use rsban_nullable_lmdb::{DatabaseFlags, LmdbDatabase, WriteFlags};
+use rsban_store_lmdb::{BinaryDbIterator, LmdbIteratorImpl, LmdbWriteTransaction, Transaction};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PaymentScheduleStatus {
+    Active,
+    Completed,
+    Cancelled,
+    /// The payment could not be sent after too many attempts
+    Failed,
+}
+
+impl PaymentScheduleStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentScheduleStatus::Active => "active",
+            PaymentScheduleStatus::Completed => "completed",
+            PaymentScheduleStatus::Cancelled => "cancelled",
+            PaymentScheduleStatus::Failed => "failed",
+        }
+    }
+
+    fn from_u8(value: u8) -> anyhow::Result<Self> {
+        match value {
+            0 => Ok(PaymentScheduleStatus::Active),
+            1 => Ok(PaymentScheduleStatus::Completed),
+            2 => Ok(PaymentScheduleStatus::Cancelled),
+            3 => Ok(PaymentScheduleStatus::Failed),
+            _ => Err(anyhow!("invalid payment schedule status: {}", value)),
+        }
+    }
+
+    fn as_u8(&self) -> u8 {
+        match self {
+            PaymentScheduleStatus::Active => 0,
+            PaymentScheduleStatus::Completed => 1,
+            PaymentScheduleStatus::Cancelled => 2,
+            PaymentScheduleStatus::Failed => 3,
+        }
+    }
+}
+
+/// Number of failed attempts after which a schedule gives up
+const MAX_FAILURES: u32 = 10;
+/// The delay before a failed payment is retried doubles with every failure up to this limit
+const MAX_RETRY_DELAY: u64 = 60 * 60;
+const FIRST_RETRY_DELAY: u64 = 15;
+
+/// The parameters of a payment schedule that is about to be created
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NewPaymentSchedule {
+    pub wallet: WalletId,
+    pub source: Account,
+    pub destination: Account,
+    pub amount: Amount,
+    pub cadence: CronExpression,
+    /// Unix timestamp in seconds. The schedule starts right away if it's not set
+    pub start: Option<u64>,
+    pub end: Option<u64>,
+}
+
+/// A recurring send from a wallet account. All times are unix timestamps in seconds.
+/// Occurrences that were missed while the node was offline are not skipped,
+/// they are paid one after another as soon as the node is running again.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PaymentSchedule {
+    pub id: u64,
+    pub wallet: WalletId,
+    pub source: Account,
+    pub destination: Account,
+    pub amount: Amount,
+    pub cadence: CronExpression,
+    pub end: Option<u64>,
+    /// The occurrence that will be paid next
+    pub next: u64,
+    pub occurrences: u64,
+    pub last_block: Option<BlockHash>,
+    pub status: PaymentScheduleStatus,
+    /// Failed attempts to pay the current occurrence
+    pub failures: u32,
+    /// A failed payment is not retried before this time
+    pub retry_at: u64,
+}
+
+impl PaymentSchedule {
+    /// Creates a schedule whose first occurrence is the first match of the
+    /// cadence at or after the start of the new schedule or `now`
+    pub fn new(id: u64, schedule: NewPaymentSchedule, now: u64) -> anyhow::Result<Self> {
+        let NewPaymentSchedule {
+            wallet,
+            source,
+            destination,
+            amount,
+            cadence,
+            start,
+            end,
+        } = schedule;
+        let start = start.unwrap_or(now);
+        let first = cadence
+            .next_after(to_datetime(start.saturating_sub(1)))
+            .map(|t| t.timestamp() as u64)
+            .filter(|t| end.map(|end| *t <= end).unwrap_or(true))
+            .ok_or_else(|| anyhow!("payment schedule has no occurrences"))?;
+
+        Ok(Self {
+            id,
+            wallet,
+            source,
+            destination,
+            amount,
+            cadence,
+            end,
+            next: first,
+            occurrences: 0,
+            last_block: None,
+            status: PaymentScheduleStatus::Active,
+            failures: 0,
+            retry_at: 0,
+        })
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        self.status == PaymentScheduleStatus::Active && self.next <= now && self.retry_at <= now
+    }
+
+    /// Unique send id of the next occurrence. Sends with the same id are only
+    /// executed once, so an occurrence can safely be retried.
+    pub fn send_id(&self) -> String {
+        format!("schedule-{}-{}", self.id, self.next)
+    }
+
+    /// Records the payment of the current occurrence and moves on to the next one
+    pub fn advance(&mut self, block: BlockHash) {
+        self.occurrences += 1;
+        self.last_block = Some(block);
+        self.failures = 0;
+        self.retry_at = 0;
+        match self.cadence.next_after(to_datetime(self.next)) {
+            Some(next) if self.before_end(next.timestamp() as u64) => {
+                self.next = next.timestamp() as u64;
+            }
+            _ => self.status = PaymentScheduleStatus::Completed,
+        }
+    }
+
+    /// Records a failed payment attempt. The payment is retried with an
+    /// increasing delay, until the schedule gives up after too many failures
+    pub fn payment_failed(&mut self, now: u64) {
+        self.failures += 1;
+        if self.failures >= MAX_FAILURES {
+            self.status = PaymentScheduleStatus::Failed;
+        } else {
+            let delay = FIRST_RETRY_DELAY
+                .saturating_mul(1 << (self.failures - 1))
+                .min(MAX_RETRY_DELAY);
+            self.retry_at = now.saturating_add(delay);
+        }
+    }
+
+    fn before_end(&self, timestamp: u64) -> bool {
+        self.end.map(|end| timestamp <= end).unwrap_or(true)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut stream = MemoryStream::new();
+        self.serialize(&mut stream);
+        stream.to_vec()
+    }
+}
+
+impl Serialize for PaymentSchedule {
+    fn serialize(&self, writer: &mut dyn BufferWriter) {
+        writer.write_u64_be_safe(self.id);
+        self.wallet.serialize(writer);
+        self.source.serialize(writer);
+        self.destination.serialize(writer);
+        self.amount.serialize(writer);
+        writer.write_u64_be_safe(self.end.unwrap_or_default());
+        writer.write_u64_be_safe(self.next);
+        writer.write_u64_be_safe(self.occurrences);
+        self.last_block.unwrap_or_default().serialize(writer);
+        writer.write_u8_safe(self.status.as_u8());
+        let cadence = self.cadence.to_string();
+        writer.write_u8_safe(cadence.len() as u8);
+        writer.write_bytes_safe(cadence.as_bytes());
+        writer.write_u32_be_safe(self.failures);
+        writer.write_u64_be_safe(self.retry_at);
+    }
+}
+
+impl Deserialize for PaymentSchedule {
+    type Target = Self;
+
+    fn deserialize(stream: &mut dyn Stream) -> anyhow::Result<Self> {
+        let id = stream.read_u64_be()?;
+        let wallet = WalletId::deserialize(stream)?;
+        let source = Account::deserialize(stream)?;
+        let destination = Account::deserialize(stream)?;
+        let amount = Amount::deserialize(stream)?;
+        let end = stream.read_u64_be()?;
+        let next = stream.read_u64_be()?;
+        let occurrences = stream.read_u64_be()?;
+        let last_block = BlockHash::deserialize(stream)?;
+        let status = PaymentScheduleStatus::from_u8(stream.read_u8()?)?;
+        let len = stream.read_u8()? as usize;
+        let mut cadence = vec![0; len];
+        stream.read_bytes(&mut cadence, len)?;
+        let cadence = String::from_utf8(cadence)?.parse()?;
+        let failures = stream.read_u32_be()?;
+        let retry_at = stream.read_u64_be()?;
+        Ok(Self {
+            id,
+            wallet,
+            source,
+            destination,
+            amount,
+            cadence,
+            end: (end != 0).then_some(end),
+            next,
+            occurrences,
+            last_block: (!last_block.is_zero()).then_some(last_block),
+            status,
+            failures,
+            retry_at,
+        })
+    }
+}
+
+/// Persists payment schedules in the wallets LMDB environment
+pub struct PaymentScheduleStore {
+    database: LmdbDatabase,
+}
+
+impl PaymentScheduleStore {
+    pub fn new(txn: &mut LmdbWriteTransaction) -> anyhow::Result<Self> {
+        let database = unsafe {
+            txn.rw_txn_mut()
+                .create_db(Some("payment_schedules"), DatabaseFlags::empty())?
+        };
+        Ok(Self { database })
+    }
+
+    pub fn put(&self, txn: &mut LmdbWriteTransaction, schedule: &PaymentSchedule) {
+        txn.put(
+            self.database,
+            &schedule.id.to_be_bytes(),
+            &schedule.to_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn get(&self, txn: &dyn Transaction, id: u64) -> Option<PaymentSchedule> {
+        let bytes = txn.get(self.database, &id.to_be_bytes()).ok()?;
+        PaymentSchedule::deserialize(&mut BufferReader::new(bytes)).ok()
+    }
+
+    pub fn del(&self, txn: &mut LmdbWriteTransaction, id: u64) {
+        txn.delete(self.database, &id.to_be_bytes(), None).unwrap();
+    }
+
+    pub fn all(&self, txn: &dyn Transaction) -> Vec<PaymentSchedule> {
+        let mut result = Vec::new();
+        let mut it: BinaryDbIterator<u64, PaymentSchedule> =
+            BinaryDbIterator::new(LmdbIteratorImpl::new(txn, self.database, None, true));
+        while let Some((_, schedule)) = it.current() {
+            result.push(schedule.clone());
+            it.next();
+        }
+        result
+    }
+
+    /// A random unused id. Ids are never reused, because they are part of the
+    /// send ids of the scheduled payments.
+    pub fn new_id(&self, txn: &dyn Transaction) -> u64 {
+        loop {
+            let id = thread_rng().gen_range(1..u64::MAX);
+            if txn.get(self.database, &id.to_be_bytes()).is_err() {
+                return id;
+            }
+        }
+    }
+}
+
+pub fn to_datetime(timestamp: u64) -> DateTime<Utc> {
+    DateTime::from_timestamp(timestamp as i64, 0).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn first_occurrence_at_or_after_start() {
+        let start = timestamp(2024, 1, 1, 0, 0);
+        let schedule = test_schedule("0 0 * * *", start, None).unwrap();
+        assert_eq!(schedule.next, start);
+        assert!(schedule.is_due(start));
+        assert!(!schedule.is_due(start - 1));
+    }
+
+    #[test]
+    fn advance() {
+        let mut schedule = test_schedule("@daily", timestamp(2024, 1, 1, 0, 0), None).unwrap();
+        let first_id = schedule.send_id();
+        schedule.advance(BlockHash::from(7));
+        assert_eq!(schedule.next, timestamp(2024, 1, 2, 0, 0));
+        assert_eq!(schedule.occurrences, 1);
+        assert_eq!(schedule.last_block, Some(BlockHash::from(7)));
+        assert_ne!(schedule.send_id(), first_id);
+    }
+
+    #[test]
+    fn completes_at_end_date() {
+        let mut schedule = test_schedule(
+            "@daily",
+            timestamp(2024, 1, 1, 0, 0),
+            Some(timestamp(2024, 1, 2, 12, 0)),
+        )
+        .unwrap();
+        schedule.advance(BlockHash::from(1));
+        assert_eq!(schedule.status, PaymentScheduleStatus::Active);
+        schedule.advance(BlockHash::from(2));
+        assert_eq!(schedule.status, PaymentScheduleStatus::Completed);
+        assert!(!schedule.is_due(u64::MAX));
+    }
+
+    #[test]
+    fn retry_failed_payments_with_backoff() {
+        let start = timestamp(2024, 1, 1, 0, 0);
+        let mut schedule = test_schedule("@daily", start, None).unwrap();
+
+        schedule.payment_failed(start);
+        assert!(!schedule.is_due(start + 14));
+        assert!(schedule.is_due(start + 15));
+
+        schedule.payment_failed(start);
+        assert!(!schedule.is_due(start + 29));
+        assert!(schedule.is_due(start + 30));
+
+        schedule.advance(BlockHash::from(1));
+        assert_eq!(schedule.failures, 0);
+        assert!(schedule.is_due(schedule.next));
+    }
+
+    #[test]
+    fn give_up_after_too_many_failures() {
+        let start = timestamp(2024, 1, 1, 0, 0);
+        let mut schedule = test_schedule("@daily", start, None).unwrap();
+        for _ in 0..MAX_FAILURES {
+            schedule.payment_failed(start);
+        }
+        assert_eq!(schedule.status, PaymentScheduleStatus::Failed);
+        assert!(!schedule.is_due(u64::MAX));
+    }
+
+    #[test]
+    fn no_occurrence_before_end() {
+        let result = test_schedule(
+            "0 0 1 * *",
+            timestamp(2024, 1, 2, 0, 0),
+            Some(timestamp(2024, 1, 31, 0, 0)),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        let mut schedule =
+            test_schedule("*/5 * * * 1-5", 1_700_000_000, Some(1_800_000_000)).unwrap();
+        schedule.advance(BlockHash::from(3));
+        schedule.payment_failed(1_700_000_100);
+        let bytes = schedule.to_bytes();
+        let deserialized = PaymentSchedule::deserialize(&mut BufferReader::new(&bytes)).unwrap();
+        assert_eq!(deserialized, schedule);
+    }
+
+    fn test_schedule(
+        cadence: &str,
+        start: u64,
+        end: Option<u64>,
+    ) -> anyhow::Result<PaymentSchedule> {
+        PaymentSchedule::new(
+            1,
+            NewPaymentSchedule {
+                wallet: WalletId::from(2),
+                source: Account::from(3),
+                destination: Account::from(4),
+                amount: Amount::raw(5),
+                cadence: cadence.parse().unwrap(),
+                start: Some(start),
+                end,
+            },
+            0,
+        )
+    }
+
+    fn timestamp(year: i32, month: u32, day: u32, hour: u32, min: u32) -> u64 {
+        Utc.with_ymd_and_hms(year, month, day, hour, min, 0)
+            .unwrap()
+            .timestamp() as u64
+    }
+}
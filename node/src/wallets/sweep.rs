@@ -0,0 +1,118 @@
+use rsban_core::{Account, Amount, BlockHash};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SweepStatus {
+    /// Not processed yet
+    Pending,
+    /// The balance was sent to the destination
+    Swept,
+    /// Nothing to receive and no balance to send
+    Empty,
+    Failed,
+}
+
+impl SweepStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SweepStatus::Pending => "pending",
+            SweepStatus::Swept => "swept",
+            SweepStatus::Empty => "empty",
+            SweepStatus::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SweepAccount {
+    pub account: Account,
+    pub status: SweepStatus,
+    /// Receive blocks that were created for this account
+    pub received: Vec<BlockHash>,
+    /// The send block to the destination
+    pub sent: Option<BlockHash>,
+    /// The amount that was sent to the destination
+    pub amount: Amount,
+    pub error: Option<String>,
+}
+
+impl SweepAccount {
+    pub fn new(account: Account) -> Self {
+        Self {
+            account,
+            status: SweepStatus::Pending,
+            received: Vec::new(),
+            sent: None,
+            amount: Amount::zero(),
+            error: None,
+        }
+    }
+}
+
+/// Progress of a sweep, which moves the funds of many wallet accounts to a single
+/// destination account. Every account is processed by its own wallet action.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SweepProgress {
+    pub destination: Account,
+    /// Receivable blocks below this amount are left alone
+    pub threshold: Amount,
+    pub accounts: Vec<SweepAccount>,
+}
+
+impl SweepProgress {
+    pub fn new(destination: Account, threshold: Amount, accounts: Vec<Account>) -> Self {
+        Self {
+            destination,
+            threshold,
+            accounts: accounts.into_iter().map(SweepAccount::new).collect(),
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next_pending().is_none()
+    }
+
+    pub fn next_pending(&self) -> Option<usize> {
+        self.accounts
+            .iter()
+            .position(|a| a.status == SweepStatus::Pending)
+    }
+
+    pub fn processed(&self) -> usize {
+        self.accounts
+            .iter()
+            .filter(|a| a.status != SweepStatus::Pending)
+            .count()
+    }
+
+    pub fn total_swept(&self) -> Amount {
+        self.accounts
+            .iter()
+            .fold(Amount::zero(), |total, a| total + a.amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn track_progress() {
+        let mut progress = SweepProgress::new(
+            Account::from(100),
+            Amount::raw(1),
+            vec![Account::from(1), Account::from(2)],
+        );
+        assert_eq!(progress.next_pending(), Some(0));
+        assert_eq!(progress.processed(), 0);
+        assert!(!progress.is_done());
+
+        progress.accounts[0].status = SweepStatus::Swept;
+        progress.accounts[0].amount = Amount::raw(5);
+        assert_eq!(progress.next_pending(), Some(1));
+
+        progress.accounts[1].status = SweepStatus::Empty;
+        assert!(progress.is_done());
+        assert_eq!(progress.processed(), 2);
+        assert_eq!(progress.total_swept(), Amount::raw(5));
+    }
+}
@@ -1,4 +1,10 @@
-use super::{Wallet, WalletActionThread, WalletRepresentatives};
+use super::{
+    sort_by_priority, validate_batch, BalanceAlert, BalanceAlertDirection, BatchSendItem,
+    BatchSendProgress, BatchSendResult, BatchSendStatus, NewPaymentSchedule, PaymentSchedule,
+    PaymentScheduleStatus, PaymentScheduleStore, SweepAccount, SweepProgress, SweepStatus, Wallet,
+    WalletActionThread, WalletRepresentatives, WatchActivity, WatchStore, WorkActivityStore,
+    WorkCacheEntry,
+};
 use crate::{
     block_processing::{BlockProcessor, BlockSource},
     cementation::{ConfirmingSet, ConfirmingSetConfig},
@@ -14,8 +20,9 @@ use rand::{thread_rng, Rng};
 use rsban_core::{
     utils::{get_env_or_default_string, ContainerInfo},
     work::{WorkPoolImpl, WorkThresholds},
-    Account, Amount, Block, BlockDetails, BlockHash, Epoch, KeyDerivationFunction, Link, NoValue,
-    PendingKey, PrivateKey, PublicKey, RawKey, Root, SavedBlock, StateBlockArgs, WalletId,
+    Account, Amount, Block, BlockDetails, BlockHash, Epoch, KdfParams, KeyDerivationFunction, Link,
+    NoValue, PendingKey, PrivateKey, PublicKey, RawKey, Root, SavedBlock, StateBlockArgs,
+    WalletBackup, WalletBackupContents, WalletId,
 };
 use rsban_ledger::{Ledger, RepWeightCache};
 use rsban_messages::{Message, Publish};
@@ -23,7 +30,7 @@ use rsban_network::DropPolicy;
 use rsban_nullable_lmdb::{DatabaseFlags, LmdbDatabase, WriteFlags};
 use rsban_store_lmdb::{
     create_backup_file, BinaryDbIterator, KeyType, LmdbEnv, LmdbIteratorImpl, LmdbWalletStore,
-    LmdbWriteTransaction, Transaction,
+    LmdbWriteTransaction, Transaction, WalletValue,
 };
 use serde::{Deserialize, Serialize};
 use std::{
@@ -34,7 +41,7 @@ use std::{
     os::unix::fs::PermissionsExt,
     path::{Path, PathBuf},
     sync::{Arc, Condvar, Mutex},
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tracing::{info, warn};
 
@@ -71,6 +78,8 @@ impl fmt::Display for WalletsError {
 
 impl std::error::Error for WalletsError {}
 
+pub type BalanceAlertCallback = Box<dyn Fn(&BalanceAlert) + Send + Sync>;
+
 pub type WalletsIterator<'txn> = BinaryDbIterator<'txn, [u8; 64], NoValue>;
 
 pub enum PreparedSend {
@@ -78,9 +87,60 @@ pub enum PreparedSend {
     New(Block, BlockDetails),
 }
 
+/// Marks a batch send as running until the last wallet action of the batch is dropped
+pub struct BatchSendGuard {
+    wallets: Arc<Wallets>,
+    source: Account,
+}
+
+impl Drop for BatchSendGuard {
+    fn drop(&mut self) {
+        if let Some(entry) = self
+            .wallets
+            .batch_sends
+            .lock()
+            .unwrap()
+            .get_mut(&self.source)
+        {
+            entry.finished = Some(Instant::now());
+        }
+        self.wallets
+            .batch_sends_in_flight
+            .lock()
+            .unwrap()
+            .remove(&self.source);
+    }
+}
+
+/// The latest batch send of a source account
+struct BatchSendEntry {
+    progress: BatchSendProgress,
+    /// When the batch stopped. A finished batch is dropped once its status was
+    /// read or after `BATCH_SEND_STATUS_TTL`
+    finished: Option<Instant>,
+}
+
 pub struct Wallets {
     db: Option<LmdbDatabase>,
     send_action_ids_handle: Option<LmdbDatabase>,
+    payment_schedules: Option<PaymentScheduleStore>,
+    /// Payment schedules whose current occurrence is queued for sending
+    schedules_in_flight: Mutex<HashSet<u64>>,
+    /// Source accounts that currently run a batch send
+    batch_sends_in_flight: Mutex<HashSet<Account>>,
+    /// The latest batch send of each source account
+    batch_sends: Mutex<HashMap<Account, BatchSendEntry>>,
+    /// The latest sweep of each wallet
+    sweeps: Mutex<HashMap<WalletId, SweepProgress>>,
+    watch_store: Option<WatchStore>,
+    /// Watch-only accounts and the wallets that contain them
+    watched_accounts: Mutex<HashMap<Account, Vec<WalletId>>>,
+    /// Balance alert thresholds by account
+    balance_alerts: Mutex<HashMap<Account, Vec<(WalletId, Amount)>>>,
+    balance_alert_observers: Mutex<Vec<BalanceAlertCallback>>,
+    work_activity: Option<WorkActivityStore>,
+    /// Accounts whose work is regenerated by the background precaching
+    work_regenerating: Mutex<HashSet<Account>>,
     env: Arc<LmdbEnv>,
     pub mutex: Mutex<HashMap<WalletId, Arc<Wallet>>>,
     node_config: NodeConfig,
@@ -96,6 +156,8 @@ pub struct Wallets {
     pub representative_wallets: Mutex<WalletRepresentatives>,
     online_reps: Arc<Mutex<OnlineReps>>,
     pub kdf: KeyDerivationFunction,
+    /// KDF for newly created wallets
+    wallet_kdf: KeyDerivationFunction,
     start_election: Mutex<Option<Box<dyn Fn(SavedBlock) + Send + Sync>>>,
     confirming_set: Arc<ConfirmingSet>,
     message_publisher: Mutex<MessagePublisher>,
@@ -145,9 +207,24 @@ impl Wallets {
         message_publisher: MessagePublisher,
     ) -> Self {
         let kdf = KeyDerivationFunction::new(kdf_work);
+        let wallet_kdf = node_config
+            .wallet_kdf
+            .kdf()
+            .expect("Invalid wallet kdf configuration");
         Self {
             db: None,
             send_action_ids_handle: None,
+            payment_schedules: None,
+            schedules_in_flight: Mutex::new(HashSet::new()),
+            batch_sends_in_flight: Mutex::new(HashSet::new()),
+            batch_sends: Mutex::new(HashMap::new()),
+            sweeps: Mutex::new(HashMap::new()),
+            watch_store: None,
+            watched_accounts: Mutex::new(HashMap::new()),
+            balance_alerts: Mutex::new(HashMap::new()),
+            balance_alert_observers: Mutex::new(Vec::new()),
+            work_activity: None,
+            work_regenerating: Mutex::new(HashSet::new()),
             mutex: Mutex::new(HashMap::new()),
             env,
             node_config: node_config.clone(),
@@ -166,6 +243,7 @@ impl Wallets {
             )),
             online_reps,
             kdf: kdf.clone(),
+            wallet_kdf,
             start_election: Mutex::new(None),
             confirming_set,
             message_publisher: Mutex::new(message_publisher),
@@ -191,6 +269,11 @@ impl Wallets {
             txn.rw_txn_mut()
                 .create_db(Some("send_action_ids"), DatabaseFlags::empty())?
         });
+        self.payment_schedules = Some(PaymentScheduleStore::new(&mut txn)?);
+        let watch_store = WatchStore::new(&mut txn)?;
+        *self.balance_alerts.lock().unwrap() = watch_store.alerts(&txn);
+        self.watch_store = Some(watch_store);
+        self.work_activity = Some(WorkActivityStore::new(&mut txn)?);
         {
             let mut guard = self.mutex.lock().unwrap();
             let wallet_ids = self.get_wallet_ids(&txn);
@@ -225,7 +308,12 @@ impl Wallets {
             if backup_required {
                 create_backup_file(&self.env)?;
             }
+            for wallet in guard.values() {
+                wallet.store.upgrade(&mut txn)?;
+            }
         }
+        txn.commit();
+        self.update_watched_accounts();
         Ok(())
     }
 
@@ -264,6 +352,41 @@ impl Wallets {
         wallet_ids
     }
 
+    /// Reads the accounts of all wallets in `env` without loading or upgrading
+    /// the wallets, so that it can be used while the node is running
+    pub fn read_accounts(env: &LmdbEnv) -> anyhow::Result<Vec<Account>> {
+        let txn = env.tx_begin_read();
+        let wallets_db = env.environment.open_db(None)?;
+        let mut wallet_names = Vec::new();
+        let beginning = RawKey::from(0).encode_hex();
+        let mut it = WalletsIterator::new(LmdbIteratorImpl::new(
+            &txn,
+            wallets_db,
+            Some(beginning.as_bytes()),
+            true,
+        ));
+        while let Some((k, _)) = it.current() {
+            wallet_names.push(std::str::from_utf8(k)?.to_owned());
+            it.next();
+        }
+
+        let mut accounts = Vec::new();
+        for name in wallet_names {
+            let db = env.environment.open_db(Some(&name))?;
+            let mut it: BinaryDbIterator<PublicKey, WalletValue> = LmdbIteratorImpl::new_iterator(
+                &txn,
+                db,
+                Some(LmdbWalletStore::special_count().as_bytes()),
+                true,
+            );
+            while let Some((k, _)) = it.current() {
+                accounts.push(k.into());
+                it.next();
+            }
+        }
+        Ok(accounts)
+    }
+
     pub fn get_block_hash(
         &self,
         txn: &dyn Transaction,
@@ -398,7 +521,10 @@ impl Wallets {
                 return Err(WalletsError::BadPublicKey);
             }
         }
+        tx.commit();
+        drop(guard);
 
+        self.update_watched_accounts();
         Ok(())
     }
 
@@ -528,6 +654,24 @@ impl Wallets {
         let _action_guard = self.wallet_actions.lock_safe();
         let wallet = guard.remove(id).unwrap();
         wallet.store.destroy(&mut tx);
+        if let Some(schedules) = &self.payment_schedules {
+            for schedule in schedules.all(&tx) {
+                if schedule.wallet == *id {
+                    schedules.del(&mut tx, schedule.id);
+                }
+            }
+        }
+        self.sweeps.lock().unwrap().remove(id);
+        if let Some(watch_store) = &self.watch_store {
+            watch_store.clear_activity(&mut tx, id, None);
+            watch_store.clear_alerts(&mut tx, id, None);
+            *self.balance_alerts.lock().unwrap() = watch_store.alerts(&tx);
+            self.watched_accounts
+                .lock()
+                .unwrap()
+                .values_mut()
+                .for_each(|wallets| wallets.retain(|w| w != id));
+        }
     }
 
     pub fn remove_key(
@@ -545,6 +689,18 @@ impl Wallets {
             return Err(WalletsError::AccountNotFound);
         }
         wallet.store.erase(&mut tx, pub_key);
+        if let Some(watch_store) = &self.watch_store {
+            watch_store.clear_activity(&mut tx, wallet_id, Some(&pub_key.into()));
+            watch_store.clear_alerts(&mut tx, wallet_id, Some(&pub_key.into()));
+            *self.balance_alerts.lock().unwrap() = watch_store.alerts(&tx);
+        }
+        if let Some(activity) = &self.work_activity {
+            activity.del(&mut tx, &pub_key.into());
+        }
+        tx.commit();
+        drop(guard);
+
+        self.update_watched_accounts();
         Ok(())
     }
 
@@ -581,7 +737,17 @@ impl Wallets {
         target
             .store
             .move_keys(&mut tx, &source.store, accounts)
-            .map_err(|_| WalletsError::AccountNotFound)
+            .map_err(|_| WalletsError::AccountNotFound)?;
+        if let Some(watch_store) = &self.watch_store {
+            for account in accounts {
+                watch_store.clear_activity(&mut tx, source_id, Some(&account.into()));
+            }
+        }
+        tx.commit();
+        drop(guard);
+
+        self.update_watched_accounts();
+        Ok(())
     }
 
     pub fn backup(&self, path: &Path) -> anyhow::Result<()> {
@@ -787,7 +953,8 @@ impl Wallets {
     pub fn import(&self, wallet_id: WalletId, json: &str) -> anyhow::Result<()> {
         let _guard = self.mutex.lock().unwrap();
         let mut tx = self.env.tx_begin_write();
-        let _wallet = Wallet::new_from_json(
+        // Exports of wallets without a KDF record were encrypted with the legacy KDF
+        let wallet = Wallet::new_from_json(
             Arc::clone(&self.ledger),
             self.work_thresholds.clone(),
             &mut tx,
@@ -796,6 +963,12 @@ impl Wallets {
             &PathBuf::from(wallet_id.to_string()),
             json,
         )?;
+        // Like a created wallet, an imported wallet gets the configured KDF. A wallet
+        // without a password can switch right away, otherwise its wallet key is bound
+        // to the exported KDF until `wallet_kdf_upgrade` is called with the password.
+        if wallet.store.valid_password(&tx) {
+            wallet.store.kdf_put(&mut tx, &self.wallet_kdf);
+        }
         Ok(())
     }
 
@@ -811,6 +984,8 @@ impl Wallets {
             .ok_or_else(|| anyhow!("wallet not found"))?;
         let mut tx = self.env.tx_begin_write();
         let id = WalletId::from_bytes(thread_rng().gen());
+        // The temporary store only decrypts the export, the keys are moved into the
+        // existing wallet which keeps its own KDF
         let temp = LmdbWalletStore::new_from_json(
             1,
             self.kdf.clone(),
@@ -885,6 +1060,520 @@ impl Wallets {
         Ok(wallet.store.serialize_json(&tx))
     }
 
+    /// Creates an encrypted backup of the wallet, protected by the given password
+    pub fn backup_wallet(
+        &self,
+        wallet_id: &WalletId,
+        password: &str,
+    ) -> Result<WalletBackup, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        if !wallet.store.valid_password(&tx) {
+            return Err(WalletsError::WalletLocked);
+        }
+
+        let mut adhoc_keys = Vec::new();
+        let mut watch_accounts = Vec::new();
+        let mut it = wallet.store.begin(&tx);
+        while let Some((pub_key, value)) = it.current() {
+            match LmdbWalletStore::key_type(value) {
+                KeyType::Adhoc => adhoc_keys.push(
+                    wallet
+                        .store
+                        .fetch(&tx, pub_key)
+                        .map_err(|_| WalletsError::Generic)?,
+                ),
+                KeyType::Unknown if value.key.is_zero() => watch_accounts.push(pub_key.into()),
+                _ => {}
+            }
+            it.next();
+        }
+
+        let contents = WalletBackupContents {
+            seed: wallet.store.seed(&tx),
+            deterministic_index: wallet.store.deterministic_index_get(&tx),
+            adhoc_keys,
+            watch_accounts,
+            representative: wallet.store.representative(&tx).into(),
+        };
+        WalletBackup::encrypt(&contents, password, &self.wallet_kdf)
+            .map_err(|_| WalletsError::Generic)
+    }
+
+    /// Creates a new wallet from an encrypted backup. The backup is verified before
+    /// anything is written, and the restored wallet is protected by the backup password.
+    pub fn restore_wallet(
+        &self,
+        wallet_id: WalletId,
+        backup: &WalletBackup,
+        password: &str,
+    ) -> anyhow::Result<()> {
+        let contents = backup.decrypt(password)?;
+
+        {
+            let mut guard = self.mutex.lock().unwrap();
+            if guard.contains_key(&wallet_id) {
+                bail!("wallet already exists");
+            }
+
+            let mut tx = self.env.tx_begin_write();
+            let wallet = Wallet::new(
+                Arc::clone(&self.ledger),
+                self.work_thresholds.clone(),
+                &mut tx,
+                self.node_config.password_fanout as usize,
+                self.wallet_kdf.clone(),
+                contents.representative.into(),
+                &PathBuf::from(wallet_id.to_string()),
+            )?;
+
+            let store = &wallet.store;
+            let result = (|| {
+                store.set_seed(&mut tx, &contents.seed);
+                for index in 0..contents.deterministic_index {
+                    store.deterministic_insert_at(&mut tx, index);
+                }
+                store.deterministic_index_set(&mut tx, contents.deterministic_index);
+                for key in &contents.adhoc_keys {
+                    store.insert_adhoc(&mut tx, key);
+                }
+                for account in &contents.watch_accounts {
+                    store.insert_watch(&mut tx, &account.into())?;
+                }
+                store.rekey(&mut tx, password)
+            })();
+            if let Err(e) = result {
+                store.destroy(&mut tx);
+                return Err(e);
+            }
+            tx.commit();
+
+            guard.insert(wallet_id, Arc::new(wallet));
+        }
+
+        self.compute_reps();
+        self.update_watched_accounts();
+        Ok(())
+    }
+
+    /// The KDF that new wallets are created with
+    pub fn wallet_kdf(&self) -> &KeyDerivationFunction {
+        &self.wallet_kdf
+    }
+
+    /// Re-encrypts the wallet key with a key derived by the given KDF. The seed
+    /// and all accounts of the wallet stay the same.
+    pub fn kdf_upgrade(
+        &self,
+        wallet_id: &WalletId,
+        password: &str,
+        kdf: &KeyDerivationFunction,
+    ) -> Result<(), WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, wallet_id)?;
+        let mut tx = self.env.tx_begin_write();
+        wallet
+            .store
+            .upgrade_kdf(&mut tx, password, kdf)
+            .map_err(|_| WalletsError::InvalidPassword)
+    }
+
+    pub fn wallet_kdf_params(&self, wallet_id: &WalletId) -> Result<KdfParams, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        wallet
+            .store
+            .kdf(&tx)
+            .map(|kdf| kdf.params())
+            .map_err(|_| WalletsError::Generic)
+    }
+
+    /// Creates a recurring send from the source to the destination of the schedule
+    pub fn schedule_create(&self, schedule: NewPaymentSchedule) -> anyhow::Result<PaymentSchedule> {
+        if schedule.cadence.to_string().len() > u8::MAX as usize {
+            bail!("cadence too long");
+        }
+        if schedule.amount.is_zero() {
+            bail!("amount must not be zero");
+        }
+
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, &schedule.wallet)?;
+        let mut tx = self.env.tx_begin_write();
+        if wallet.store.find(&tx, &schedule.source.into()).is_end() {
+            return Err(WalletsError::AccountNotFound.into());
+        }
+
+        let schedules = self
+            .payment_schedules
+            .as_ref()
+            .ok_or_else(|| anyhow!("payment schedules are not available"))?;
+        let schedule = PaymentSchedule::new(schedules.new_id(&tx), schedule, unix_now())?;
+        schedules.put(&mut tx, &schedule);
+        Ok(schedule)
+    }
+
+    pub fn schedule_list(
+        &self,
+        wallet_id: &WalletId,
+    ) -> Result<Vec<PaymentSchedule>, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        Self::get_wallet(&guard, wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        Ok(self
+            .payment_schedules
+            .as_ref()
+            .map(|schedules| schedules.all(&tx))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|s| s.wallet == *wallet_id)
+            .collect())
+    }
+
+    pub fn schedule_cancel(&self, wallet_id: &WalletId, id: u64) -> anyhow::Result<()> {
+        let guard = self.mutex.lock().unwrap();
+        Self::get_wallet(&guard, wallet_id)?;
+        let mut tx = self.env.tx_begin_write();
+        let schedules = self
+            .payment_schedules
+            .as_ref()
+            .ok_or_else(|| anyhow!("payment schedule not found"))?;
+        let mut schedule = schedules
+            .get(&tx, id)
+            .filter(|s| s.wallet == *wallet_id)
+            .ok_or_else(|| anyhow!("payment schedule not found"))?;
+        if schedule.status != PaymentScheduleStatus::Active {
+            bail!("payment schedule is not active");
+        }
+        schedule.status = PaymentScheduleStatus::Cancelled;
+        schedules.put(&mut tx, &schedule);
+        Ok(())
+    }
+
+    /// Moves the schedule on to its next occurrence, unless the occurrence
+    /// was already recorded or the schedule was cancelled in the meantime
+    fn payment_schedule_executed(&self, id: u64, occurrence: u64, block: BlockHash) {
+        let Some(schedules) = &self.payment_schedules else {
+            return;
+        };
+        let mut tx = self.env.tx_begin_write();
+        if let Some(mut schedule) = schedules.get(&tx, id) {
+            if schedule.status == PaymentScheduleStatus::Active && schedule.next == occurrence {
+                schedule.advance(block);
+                schedules.put(&mut tx, &schedule);
+            }
+        }
+    }
+
+    /// Delays the next attempt of a failed payment, or gives up on the schedule
+    /// after too many failures
+    fn payment_schedule_failed(
+        &self,
+        id: u64,
+        occurrence: u64,
+        now: u64,
+        error: impl std::fmt::Debug,
+    ) {
+        let Some(schedules) = &self.payment_schedules else {
+            return;
+        };
+        let mut tx = self.env.tx_begin_write();
+        let Some(mut schedule) = schedules.get(&tx, id) else {
+            return;
+        };
+        if schedule.status != PaymentScheduleStatus::Active || schedule.next != occurrence {
+            return;
+        }
+        schedule.payment_failed(now);
+        schedules.put(&mut tx, &schedule);
+        if schedule.status == PaymentScheduleStatus::Failed {
+            warn!(
+                "Payment schedule {} failed {} times and was stopped: {:?}",
+                id, schedule.failures, error
+            );
+        } else {
+            warn!(
+                "Payment schedule {} could not send, will retry at {}: {:?}",
+                id, schedule.retry_at, error
+            );
+        }
+    }
+
+    /// The cached work of all accounts of the wallet that have a private key
+    pub fn work_cache_status(
+        &self,
+        wallet_id: &WalletId,
+    ) -> Result<Vec<WorkCacheEntry>, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        Ok(self.work_cache_entries(&tx, wallet))
+    }
+
+    fn work_cache_entries(&self, tx: &dyn Transaction, wallet: &Wallet) -> Vec<WorkCacheEntry> {
+        let watch_accounts = wallet.store.watch_accounts(tx);
+        let activity = self.work_activity.as_ref();
+        let regenerating = self.work_regenerating.lock().unwrap();
+        let block_tx = self.ledger.read_txn();
+        wallet
+            .store
+            .accounts(tx)
+            .into_iter()
+            .filter(|account| !watch_accounts.contains(account))
+            .map(|account| {
+                let info = self.ledger.account_info(&block_tx, &account);
+                let root = info
+                    .as_ref()
+                    .map(|i| i.head.into())
+                    .unwrap_or_else(|| account.into());
+                let work = wallet
+                    .store
+                    .work_get(tx, &account.into())
+                    .unwrap_or_default();
+                let mut entry = WorkCacheEntry::new(
+                    &self.work_thresholds,
+                    account,
+                    root,
+                    info.map(|i| i.epoch),
+                    work,
+                );
+                entry.last_active = activity.and_then(|a| a.get(tx, &account));
+                entry.regenerating = regenerating.contains(&account);
+                entry
+            })
+            .collect()
+    }
+
+    /// Remembers that a block was created for the account, which gives
+    /// the account priority when its work is precached
+    fn record_work_activity(&self, account: &Account) {
+        let Some(activity) = &self.work_activity else {
+            return;
+        };
+        let mut tx = self.env.tx_begin_write();
+        activity.put(&mut tx, account, unix_now());
+    }
+
+    /// Progress of the latest batch send from `source` and whether it is still running.
+    /// The progress of a finished batch can only be read once.
+    pub fn batch_send_progress(&self, source: &Account) -> Option<(BatchSendProgress, bool)> {
+        let mut batch_sends = self.batch_sends.lock().unwrap();
+        remove_expired_batch_sends(&mut batch_sends);
+        let entry = batch_sends.get(source)?;
+        if entry.finished.is_none() {
+            return Some((entry.progress.clone(), true));
+        }
+        batch_sends
+            .remove(source)
+            .map(|entry| (entry.progress, false))
+    }
+
+    pub fn batch_send_in_progress(&self, source: &Account) -> bool {
+        self.batch_sends_in_flight.lock().unwrap().contains(source)
+    }
+
+    /// Progress of the latest sweep of the wallet
+    pub fn sweep_progress(
+        &self,
+        wallet_id: &WalletId,
+    ) -> Result<Option<SweepProgress>, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        Self::get_wallet(&guard, wallet_id)?;
+        Ok(self.sweeps.lock().unwrap().get(wallet_id).cloned())
+    }
+
+    /// A page of the confirmed activity of all watch-only accounts of the wallet, newest first
+    pub fn wallet_activity(
+        &self,
+        wallet_id: &WalletId,
+        offset: usize,
+        count: usize,
+    ) -> Result<Vec<WatchActivity>, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        Self::get_wallet(&guard, wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        Ok(self
+            .watch_store
+            .as_ref()
+            .map(|store| store.activity(&tx, wallet_id, offset, count))
+            .unwrap_or_default())
+    }
+
+    pub fn balance_alert_set(
+        &self,
+        wallet_id: &WalletId,
+        account: &Account,
+        threshold: Amount,
+    ) -> anyhow::Result<()> {
+        if threshold.is_zero() {
+            bail!("threshold must not be zero");
+        }
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, wallet_id)?;
+        let mut tx = self.env.tx_begin_write();
+        if wallet.store.find(&tx, &account.into()).is_end() {
+            return Err(WalletsError::AccountNotFound.into());
+        }
+        let watch_store = self
+            .watch_store
+            .as_ref()
+            .ok_or_else(|| anyhow!("balance alerts are not available"))?;
+        watch_store.put_alert(&mut tx, wallet_id, account, threshold);
+        *self.balance_alerts.lock().unwrap() = watch_store.alerts(&tx);
+        Ok(())
+    }
+
+    pub fn balance_alert_remove(
+        &self,
+        wallet_id: &WalletId,
+        account: &Account,
+    ) -> anyhow::Result<()> {
+        let guard = self.mutex.lock().unwrap();
+        Self::get_wallet(&guard, wallet_id)?;
+        let mut tx = self.env.tx_begin_write();
+        let Some(watch_store) = &self.watch_store else {
+            bail!("balance alert not found");
+        };
+        if !watch_store.del_alert(&mut tx, wallet_id, account) {
+            bail!("balance alert not found");
+        }
+        *self.balance_alerts.lock().unwrap() = watch_store.alerts(&tx);
+        Ok(())
+    }
+
+    pub fn balance_alerts(
+        &self,
+        wallet_id: &WalletId,
+    ) -> Result<Vec<(Account, Amount)>, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        Self::get_wallet(&guard, wallet_id)?;
+        let mut result: Vec<_> = self
+            .balance_alerts
+            .lock()
+            .unwrap()
+            .iter()
+            .flat_map(|(account, thresholds)| {
+                thresholds
+                    .iter()
+                    .filter(|(wallet, _)| wallet == wallet_id)
+                    .map(|(_, threshold)| (*account, *threshold))
+            })
+            .collect();
+        result.sort_by_key(|(account, _)| *account);
+        Ok(result)
+    }
+
+    pub fn on_balance_alert(&self, callback: BalanceAlertCallback) {
+        self.balance_alert_observers.lock().unwrap().push(callback);
+    }
+
+    /// Whether confirmed blocks of this account need to be passed to `watch_cemented`
+    pub fn is_watched(&self, account: &Account) -> bool {
+        self.watched_accounts.lock().unwrap().contains_key(account)
+            || self.balance_alerts.lock().unwrap().contains_key(account)
+    }
+
+    /// Records the activity of a newly cemented block of a watched account
+    /// and raises the balance alerts whose threshold it crossed
+    pub fn watch_cemented(&self, block: &SavedBlock) {
+        let account = block.account();
+        let wallets = self.watched_accounts.lock().unwrap().get(&account).cloned();
+        for wallet_id in wallets.unwrap_or_default() {
+            self.index_watch_activity(&wallet_id, &account);
+        }
+
+        let thresholds = self.balance_alerts.lock().unwrap().get(&account).cloned();
+        let Some(thresholds) = thresholds else {
+            return;
+        };
+        let previous_balance = self
+            .ledger
+            .any()
+            .block_balance(&self.ledger.read_txn(), &block.previous())
+            .unwrap_or_default();
+        for (wallet_id, threshold) in thresholds {
+            if let Some(direction) =
+                BalanceAlertDirection::crossing(threshold, previous_balance, block.balance())
+            {
+                let alert = BalanceAlert {
+                    wallet: wallet_id,
+                    account,
+                    hash: block.hash(),
+                    threshold,
+                    previous_balance,
+                    balance: block.balance(),
+                    direction,
+                };
+                for observer in self.balance_alert_observers.lock().unwrap().iter() {
+                    observer(&alert);
+                }
+            }
+        }
+    }
+
+    /// Rebuilds the index of watch-only accounts and records the confirmed
+    /// activity that was not recorded yet
+    fn update_watched_accounts(&self) {
+        let mut watched: HashMap<Account, Vec<WalletId>> = HashMap::new();
+        {
+            let guard = self.mutex.lock().unwrap();
+            let tx = self.env.tx_begin_read();
+            for (wallet_id, wallet) in guard.iter() {
+                for account in wallet.store.watch_accounts(&tx) {
+                    watched.entry(account).or_default().push(*wallet_id);
+                }
+            }
+        }
+
+        for (account, wallets) in &watched {
+            for wallet_id in wallets {
+                self.index_watch_activity(wallet_id, account);
+            }
+        }
+        *self.watched_accounts.lock().unwrap() = watched;
+    }
+
+    /// Walks the confirmed chain of the account back to the last recorded block.
+    /// The activity is committed in batches, so indexing a long chain doesn't
+    /// block other wallet writes. Recording a block twice is harmless.
+    fn index_watch_activity(&self, wallet_id: &WalletId, account: &Account) {
+        let Some(watch_store) = &self.watch_store else {
+            return;
+        };
+        let mut tx = self.env.tx_begin_write();
+        let block_tx = self.ledger.read_txn();
+        let Some(head) = self.ledger.confirmed().account_head(&block_tx, account) else {
+            return;
+        };
+        let indexed = watch_store.indexed_height(&tx, wallet_id, account);
+        let mut block = self.ledger.any().get_block(&block_tx, &head);
+        let Some(confirmed_height) = block.as_ref().map(|b| b.height()) else {
+            return;
+        };
+
+        let mut count = 0;
+        while let Some(current) = block {
+            if current.height() <= indexed {
+                break;
+            }
+            let previous = self.ledger.any().get_block(&block_tx, &current.previous());
+            let previous_balance = previous.as_ref().map(|b| b.balance()).unwrap_or_default();
+            watch_store.put_activity(
+                &mut tx,
+                wallet_id,
+                &WatchActivity::new(&current, previous_balance),
+            );
+            block = previous;
+            count += 1;
+            if count % WATCH_INDEX_BATCH_SIZE == 0 {
+                tx.refresh();
+            }
+        }
+        watch_store.set_indexed_height(&mut tx, wallet_id, account, confirmed_height);
+    }
+
     pub fn should_republish_vote(&self, voting_account: Account) -> bool {
         let guard = self.representative_wallets.lock().unwrap();
         !guard.have_half_rep() && !guard.exists(&voting_account)
@@ -910,6 +1599,8 @@ impl Drop for Wallets {
 }
 
 const GENERATE_PRIORITY: Amount = Amount::MAX;
+/// Number of blocks that are indexed for a watched account per wallets write transaction
+const WATCH_INDEX_BATCH_SIZE: usize = 1000;
 const HIGH_PRIORITY: Amount = Amount::raw(u128::MAX - 1);
 
 pub trait WalletsExt {
@@ -1160,6 +1851,40 @@ pub trait WalletsExt {
     fn ensure_wallet_is_unlocked(&self, wallet_id: WalletId, password: &str) -> bool;
 
     fn initialize2(&self);
+
+    /// Queues the sends of all payment schedules that are due at `now`
+    fn process_payment_schedules(&self, now: SystemTime);
+
+    fn ongoing_payment_schedules(&self);
+
+    /// Starts to send all items from `source`, chaining the blocks back-to-back.
+    /// The batch stops at the first failure. Items that were sent already
+    /// are skipped, so the same batch can be submitted again to resume it.
+    /// The progress can be queried with `batch_send_progress`.
+    fn send_batch(
+        &self,
+        wallet_id: &WalletId,
+        source: Account,
+        items: &[BatchSendItem],
+    ) -> anyhow::Result<BatchSendProgress>;
+
+    /// Starts to move the funds of the given accounts, or of all accounts of the
+    /// wallet, to `destination`. Confirmed receivable blocks of at least
+    /// `threshold` are received first, then the whole balance is sent.
+    /// Each account is handled by its own wallet action.
+    fn wallet_sweep(
+        &self,
+        wallet_id: &WalletId,
+        destination: Account,
+        accounts: Option<Vec<Account>>,
+        threshold: Amount,
+    ) -> anyhow::Result<()>;
+
+    /// Queues the regeneration of missing or invalid cached work. Recently
+    /// active accounts are handled first.
+    fn precache_work(&self);
+
+    fn ongoing_work_precache(&self);
 }
 
 impl WalletsExt for Arc<Wallets> {
@@ -1330,6 +2055,7 @@ impl WalletsExt for Arc<Wallets> {
     ) -> anyhow::Result<SavedBlock> {
         // Unschedule any work caching for this account
         self.delayed_work.lock().unwrap().remove(&account);
+        self.record_work_activity(&account);
         let hash = block.hash();
         let required_difficulty = self.network_params.work.threshold(details);
         if self.network_params.work.difficulty_block(&block) < required_difficulty {
@@ -1996,7 +2722,7 @@ impl WalletsExt for Arc<Wallets> {
                 self.work_thresholds.clone(),
                 &mut tx,
                 self.node_config.password_fanout as usize,
-                self.kdf.clone(),
+                self.wallet_kdf.clone(),
                 self.node_config.random_representative(),
                 &PathBuf::from(wallet_id.to_string()),
             ) else {
@@ -2155,7 +2881,447 @@ impl WalletsExt for Arc<Wallets> {
         if self.node_config.enable_voting {
             self.ongoing_compute_reps();
         }
+        self.ongoing_payment_schedules();
+        self.ongoing_work_precache();
+    }
+
+    fn process_payment_schedules(&self, now: SystemTime) {
+        let Some(schedules) = &self.payment_schedules else {
+            return;
+        };
+        let now = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let due: Vec<_> = {
+            let tx = self.env.tx_begin_read();
+            schedules
+                .all(&tx)
+                .into_iter()
+                .filter(|s| s.is_due(now))
+                .collect()
+        };
+
+        for schedule in due {
+            let Some(wallet) = self.mutex.lock().unwrap().get(&schedule.wallet).cloned() else {
+                continue;
+            };
+            if !self.schedules_in_flight.lock().unwrap().insert(schedule.id) {
+                continue;
+            }
+
+            let self_l = Arc::clone(self);
+            self.wallet_actions.queue_wallet_action(
+                HIGH_PRIORITY,
+                wallet,
+                Box::new(move |wallet| {
+                    let result = self_l.send_action(
+                        &wallet,
+                        schedule.source,
+                        schedule.destination,
+                        schedule.amount,
+                        0,
+                        true,
+                        Some(schedule.send_id()),
+                    );
+                    match result {
+                        Ok(block) => {
+                            self_l.payment_schedule_executed(
+                                schedule.id,
+                                schedule.next,
+                                block.hash(),
+                            );
+                        }
+                        Err(e) => {
+                            self_l.payment_schedule_failed(schedule.id, schedule.next, now, e);
+                        }
+                    }
+                    self_l
+                        .schedules_in_flight
+                        .lock()
+                        .unwrap()
+                        .remove(&schedule.id);
+                }),
+            );
+        }
+    }
+
+    fn ongoing_payment_schedules(&self) {
+        self.process_payment_schedules(SystemTime::now());
+        let self_w = Arc::downgrade(self);
+        self.workers.add_delayed_task(
+            PAYMENT_SCHEDULE_INTERVAL,
+            Box::new(move || {
+                if let Some(self_l) = self_w.upgrade() {
+                    self_l.ongoing_payment_schedules();
+                }
+            }),
+        );
+    }
+
+    fn send_batch(
+        &self,
+        wallet_id: &WalletId,
+        source: Account,
+        items: &[BatchSendItem],
+    ) -> anyhow::Result<BatchSendProgress> {
+        validate_batch(items)?;
+        let wallet = Wallets::get_wallet(&self.mutex.lock().unwrap(), wallet_id)?.clone();
+
+        let mut results = Vec::with_capacity(items.len());
+        {
+            let tx = self.env.tx_begin_read();
+            if wallet.store.find(&tx, &source.into()).is_end() {
+                return Err(WalletsError::AccountNotFound.into());
+            }
+            if !wallet.store.valid_password(&tx) {
+                return Err(WalletsError::WalletLocked.into());
+            }
+
+            let mut total = Amount::zero();
+            for item in items {
+                let mut result = BatchSendResult::new(item.id.clone(), BatchSendStatus::Pending);
+                match self.get_block_hash(&tx, &item.id)? {
+                    Some(hash) => {
+                        result.status = BatchSendStatus::AlreadySent;
+                        result.block = Some(hash);
+                    }
+                    None => {
+                        total = total
+                            .number()
+                            .checked_add(item.amount.number())
+                            .map(Amount::raw)
+                            .ok_or_else(|| anyhow!("total amount of the batch is too large"))?;
+                    }
+                }
+                results.push(result);
+            }
+
+            let balance = self
+                .ledger
+                .any()
+                .account_balance(&self.ledger.read_txn(), &source)
+                .unwrap_or_default();
+            if balance < total {
+                bail!("insufficient balance");
+            }
+        }
+
+        if !self.batch_sends_in_flight.lock().unwrap().insert(source) {
+            bail!("a batch send from this account is already in progress");
+        }
+        let guard = Arc::new(BatchSendGuard {
+            wallets: Arc::clone(self),
+            source,
+        });
+
+        let progress = BatchSendProgress::new(items.to_vec(), results);
+        {
+            let mut batch_sends = self.batch_sends.lock().unwrap();
+            remove_expired_batch_sends(&mut batch_sends);
+            batch_sends.insert(
+                source,
+                BatchSendEntry {
+                    progress: progress.clone(),
+                    finished: None,
+                },
+            );
+        }
+        send_batch_next(self, wallet, guard);
+        Ok(progress)
     }
+
+    fn wallet_sweep(
+        &self,
+        wallet_id: &WalletId,
+        destination: Account,
+        accounts: Option<Vec<Account>>,
+        threshold: Amount,
+    ) -> anyhow::Result<()> {
+        let wallet = Wallets::get_wallet(&self.mutex.lock().unwrap(), wallet_id)?.clone();
+        let accounts = {
+            let tx = self.env.tx_begin_read();
+            if !wallet.store.valid_password(&tx) {
+                return Err(WalletsError::WalletLocked.into());
+            }
+            let watch_accounts = wallet.store.watch_accounts(&tx);
+            let accounts = match accounts {
+                Some(accounts) => {
+                    for account in &accounts {
+                        if wallet.store.find(&tx, &(*account).into()).is_end() {
+                            return Err(WalletsError::AccountNotFound.into());
+                        }
+                        if watch_accounts.contains(account) {
+                            bail!("cannot sweep watch-only account");
+                        }
+                    }
+                    accounts
+                }
+                None => wallet
+                    .store
+                    .accounts(&tx)
+                    .into_iter()
+                    .filter(|a| !watch_accounts.contains(a))
+                    .collect(),
+            };
+            accounts
+                .into_iter()
+                .filter(|a| *a != destination)
+                .collect::<Vec<_>>()
+        };
+
+        {
+            let mut sweeps = self.sweeps.lock().unwrap();
+            if sweeps.get(wallet_id).is_some_and(|p| !p.is_done()) {
+                bail!("a sweep of this wallet is already in progress");
+            }
+            // Blocks below the receive minimum would not be received anyway
+            let threshold = std::cmp::max(threshold, self.node_config.receive_minimum);
+            sweeps.insert(
+                *wallet_id,
+                SweepProgress::new(destination, threshold, accounts),
+            );
+        }
+
+        info!(
+            "Starting sweep of wallet {} to {}",
+            wallet_id,
+            destination.encode_account()
+        );
+        sweep_next(self, wallet, *wallet_id);
+        Ok(())
+    }
+
+    fn precache_work(&self) {
+        if !self.distributed_work.work_generation_enabled() {
+            return;
+        }
+
+        let mut entries = Vec::new();
+        let mut wallets = HashMap::new();
+        {
+            let guard = self.mutex.lock().unwrap();
+            let tx = self.env.tx_begin_read();
+            let delayed = self.delayed_work.lock().unwrap();
+            for wallet in guard.values() {
+                for entry in self.work_cache_entries(&tx, wallet) {
+                    if entry.should_precache() && !delayed.contains_key(&entry.account) {
+                        wallets.insert(entry.account, wallet.clone());
+                        entries.push(entry);
+                    }
+                }
+            }
+        }
+        sort_by_priority(&mut entries);
+
+        let mut regenerating = self.work_regenerating.lock().unwrap();
+        let free = WORK_PRECACHE_BATCH.saturating_sub(regenerating.len());
+        for entry in entries.into_iter().take(free) {
+            let Some(wallet) = wallets.remove(&entry.account) else {
+                continue;
+            };
+            regenerating.insert(entry.account);
+            let self_l = Arc::clone(self);
+            self.wallet_actions.queue_wallet_action(
+                GENERATE_PRIORITY,
+                wallet,
+                Box::new(move |wallet| {
+                    self_l.work_cache_blocking(&wallet, &entry.account.into(), &entry.root);
+                    self_l
+                        .work_regenerating
+                        .lock()
+                        .unwrap()
+                        .remove(&entry.account);
+                }),
+            );
+        }
+    }
+
+    fn ongoing_work_precache(&self) {
+        self.precache_work();
+        let self_w = Arc::downgrade(self);
+        self.workers.add_delayed_task(
+            WORK_PRECACHE_INTERVAL,
+            Box::new(move || {
+                if let Some(self_l) = self_w.upgrade() {
+                    self_l.ongoing_work_precache();
+                }
+            }),
+        );
+    }
+}
+
+/// Sends the next pending item of the batch
+fn send_batch_next(wallets: &Arc<Wallets>, wallet: Arc<Wallet>, guard: Arc<BatchSendGuard>) {
+    let source = guard.source;
+    let next = wallets
+        .batch_sends
+        .lock()
+        .unwrap()
+        .get(&source)
+        .map(|entry| &entry.progress)
+        .and_then(|p| p.next_pending().map(|i| (i, p.items[i].clone())));
+    // Dropping the last reference to the guard ends the batch
+    let Some((index, item)) = next else {
+        return;
+    };
+
+    let self_l = Arc::clone(wallets);
+    wallets.wallet_actions.queue_wallet_action(
+        HIGH_PRIORITY,
+        wallet,
+        Box::new(move |wallet| {
+            // Every block is processed before the next one is created, so the
+            // next block builds on the new frontier. The work for it is
+            // precached by `work_ensure` after each block.
+            let result = self_l
+                .send_action(
+                    &wallet,
+                    source,
+                    item.destination,
+                    item.amount,
+                    0,
+                    true,
+                    Some(item.id.clone()),
+                )
+                .map(|block| block.hash());
+            if let Err(e) = &result {
+                warn!(
+                    "Batch send {} from {} failed: {:?}",
+                    item.id,
+                    source.encode_account(),
+                    e
+                );
+            }
+
+            if let Some(entry) = self_l.batch_sends.lock().unwrap().get_mut(&source) {
+                entry.progress.item_sent(index, result);
+            }
+            send_batch_next(&self_l, wallet, Arc::clone(&guard));
+        }),
+    );
+}
+
+/// Sweeps the next account that wasn't handled yet
+fn sweep_next(wallets: &Arc<Wallets>, wallet: Arc<Wallet>, wallet_id: WalletId) {
+    let self_l = Arc::clone(wallets);
+    wallets.wallet_actions.queue_wallet_action(
+        HIGH_PRIORITY,
+        wallet,
+        Box::new(move |wallet| {
+            let next = self_l.sweeps.lock().unwrap().get(&wallet_id).and_then(|p| {
+                p.next_pending()
+                    .map(|i| (i, p.accounts[i].account, p.destination, p.threshold))
+            });
+            let Some((index, account, destination, threshold)) = next else {
+                return;
+            };
+
+            let result = sweep_account(&self_l, &wallet, account, destination, threshold);
+            if let Some(error) = &result.error {
+                warn!(
+                    "Could not sweep account {}: {}",
+                    account.encode_account(),
+                    error
+                );
+            }
+
+            let done = match self_l.sweeps.lock().unwrap().get_mut(&wallet_id) {
+                Some(progress) => {
+                    progress.accounts[index] = result;
+                    progress.is_done()
+                }
+                // The wallet was destroyed
+                None => true,
+            };
+            if done {
+                info!("Sweep of wallet {} completed", wallet_id);
+            } else {
+                sweep_next(&self_l, wallet, wallet_id);
+            }
+        }),
+    );
+}
+
+fn remove_expired_batch_sends(batch_sends: &mut HashMap<Account, BatchSendEntry>) {
+    batch_sends.retain(|_, entry| {
+        entry
+            .finished
+            .map_or(true, |finished| finished.elapsed() < BATCH_SEND_STATUS_TTL)
+    });
+}
+
+/// Receives the receivable blocks of a single account and sends its whole balance to `destination`
+fn sweep_account(
+    wallets: &Arc<Wallets>,
+    wallet: &Arc<Wallet>,
+    account: Account,
+    destination: Account,
+    threshold: Amount,
+) -> SweepAccount {
+    let mut result = SweepAccount::new(account);
+    let receivable: Vec<(BlockHash, Amount)> = {
+        let tx = wallets.ledger.read_txn();
+        wallets
+            .ledger
+            .any()
+            .account_receivable_upper_bound(&tx, account, BlockHash::zero())
+            .filter(|(key, info)| {
+                info.amount >= threshold
+                    && wallets
+                        .ledger
+                        .confirmed()
+                        .block_exists_or_pruned(&tx, &key.send_block_hash)
+            })
+            .map(|(key, info)| (key.send_block_hash, info.amount))
+            .collect()
+    };
+
+    let representative = wallet.store.representative(&wallets.env.tx_begin_read());
+    for (hash, amount) in receivable {
+        match wallets.receive_action(wallet, hash, representative, amount, account, 0, true) {
+            Some(block) => result.received.push(block.hash()),
+            None => {
+                result.status = SweepStatus::Failed;
+                result.error = Some(format!("could not receive block {}", hash));
+                return result;
+            }
+        }
+    }
+
+    let balance = wallets
+        .ledger
+        .any()
+        .account_balance(&wallets.ledger.read_txn(), &account)
+        .unwrap_or_default();
+    if balance.is_zero() {
+        result.status = SweepStatus::Empty;
+        return result;
+    }
+
+    match wallets.send_action(wallet, account, destination, balance, 0, true, None) {
+        Ok(block) => {
+            result.status = SweepStatus::Swept;
+            result.sent = Some(block.hash());
+            result.amount = balance;
+        }
+        Err(e) => {
+            result.status = SweepStatus::Failed;
+            result.error = Some(e.to_string());
+        }
+    }
+    result
+}
+
+const PAYMENT_SCHEDULE_INTERVAL: Duration = Duration::from_secs(15);
+/// How long the status of a finished batch send is kept if nobody reads it
+const BATCH_SEND_STATUS_TTL: Duration = Duration::from_secs(60 * 60);
+const WORK_PRECACHE_INTERVAL: Duration = Duration::from_secs(30);
+/// Maximum number of accounts whose work is regenerated at the same time
+const WORK_PRECACHE_BATCH: usize = 16;
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
 }
 
 fn test_scan_wallet_reps_delay() -> Duration {
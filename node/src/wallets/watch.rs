@@ -0,0 +1,408 @@
+use rsban_core::{
+    utils::{BufferReader, Deserialize, Stream},
+    Account, Amount, BlockHash, BlockSubType, SavedBlock, WalletId,
+};
+use rsban_nullable_lmdb::{DatabaseFlags, LmdbDatabase, WriteFlags};
+use rsban_store_lmdb::{LmdbIteratorImpl, LmdbWriteTransaction, Transaction};
+use std::collections::HashMap;
+
+/// A confirmed block of a watched account, as it is listed in the activity
+/// history of a wallet
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WatchActivity {
+    pub account: Account,
+    pub hash: BlockHash,
+    pub subtype: BlockSubType,
+    pub amount: Amount,
+    pub balance: Amount,
+    pub height: u64,
+    pub local_timestamp: u64,
+}
+
+impl WatchActivity {
+    pub fn new(block: &SavedBlock, previous_balance: Amount) -> Self {
+        let balance = block.balance();
+        let amount = if balance > previous_balance {
+            balance - previous_balance
+        } else {
+            previous_balance - balance
+        };
+        Self {
+            account: block.account(),
+            hash: block.hash(),
+            subtype: block.subtype(),
+            amount,
+            balance,
+            height: block.height(),
+            local_timestamp: block.timestamp(),
+        }
+    }
+
+    /// Newest entries come first. Entries of the same second are ordered by
+    /// account and descending height.
+    fn key(&self, wallet: &WalletId) -> [u8; 80] {
+        let mut key = [0; 80];
+        key[..32].copy_from_slice(wallet.as_bytes());
+        key[32..40].copy_from_slice(&(u64::MAX - self.local_timestamp).to_be_bytes());
+        key[40..72].copy_from_slice(self.account.as_bytes());
+        key[72..].copy_from_slice(&(u64::MAX - self.height).to_be_bytes());
+        key
+    }
+
+    fn value(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(65);
+        bytes.extend_from_slice(self.hash.as_bytes());
+        bytes.push(subtype_to_u8(self.subtype));
+        bytes.extend_from_slice(&self.amount.to_be_bytes());
+        bytes.extend_from_slice(&self.balance.to_be_bytes());
+        bytes
+    }
+
+    fn from_db(key: &[u8], value: &[u8]) -> anyhow::Result<Self> {
+        let mut stream = BufferReader::new(value);
+        let hash = BlockHash::deserialize(&mut stream)?;
+        let subtype = subtype_from_u8(stream.read_u8()?)?;
+        let amount = Amount::deserialize(&mut stream)?;
+        let balance = Amount::deserialize(&mut stream)?;
+        Ok(Self {
+            account: Account::from_slice(&key[40..72])
+                .ok_or_else(|| anyhow!("invalid activity key"))?,
+            hash,
+            subtype,
+            amount,
+            balance,
+            height: u64::MAX - u64::from_be_bytes(key[72..80].try_into()?),
+            local_timestamp: u64::MAX - u64::from_be_bytes(key[32..40].try_into()?),
+        })
+    }
+}
+
+fn subtype_to_u8(subtype: BlockSubType) -> u8 {
+    match subtype {
+        BlockSubType::Send => 0,
+        BlockSubType::Receive => 1,
+        BlockSubType::Open => 2,
+        BlockSubType::Change => 3,
+        BlockSubType::Epoch => 4,
+    }
+}
+
+fn subtype_from_u8(value: u8) -> anyhow::Result<BlockSubType> {
+    match value {
+        0 => Ok(BlockSubType::Send),
+        1 => Ok(BlockSubType::Receive),
+        2 => Ok(BlockSubType::Open),
+        3 => Ok(BlockSubType::Change),
+        4 => Ok(BlockSubType::Epoch),
+        _ => Err(anyhow!("invalid block subtype: {}", value)),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BalanceAlertDirection {
+    /// The balance rose to or above the threshold
+    Above,
+    /// The balance fell below the threshold
+    Below,
+}
+
+impl BalanceAlertDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BalanceAlertDirection::Above => "above",
+            BalanceAlertDirection::Below => "below",
+        }
+    }
+
+    /// The direction in which the balance crossed the threshold, if it did
+    pub fn crossing(threshold: Amount, previous: Amount, current: Amount) -> Option<Self> {
+        if previous < threshold && current >= threshold {
+            Some(BalanceAlertDirection::Above)
+        } else if previous >= threshold && current < threshold {
+            Some(BalanceAlertDirection::Below)
+        } else {
+            None
+        }
+    }
+}
+
+/// Raised when a confirmed block moves the balance of an account across the
+/// threshold that was configured for it
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BalanceAlert {
+    pub wallet: WalletId,
+    pub account: Account,
+    pub hash: BlockHash,
+    pub threshold: Amount,
+    pub previous_balance: Amount,
+    pub balance: Amount,
+    pub direction: BalanceAlertDirection,
+}
+
+/// Persists the activity history and balance alert thresholds of watched
+/// accounts in the wallets LMDB environment
+pub struct WatchStore {
+    activity: LmdbDatabase,
+    /// Height up to which the activity of a watched account is recorded
+    indexed: LmdbDatabase,
+    alerts: LmdbDatabase,
+}
+
+impl WatchStore {
+    pub fn new(txn: &mut LmdbWriteTransaction) -> anyhow::Result<Self> {
+        let activity = unsafe {
+            txn.rw_txn_mut()
+                .create_db(Some("watch_activity"), DatabaseFlags::empty())?
+        };
+        let indexed = unsafe {
+            txn.rw_txn_mut()
+                .create_db(Some("watch_indexed"), DatabaseFlags::empty())?
+        };
+        let alerts = unsafe {
+            txn.rw_txn_mut()
+                .create_db(Some("watch_alerts"), DatabaseFlags::empty())?
+        };
+        Ok(Self {
+            activity,
+            indexed,
+            alerts,
+        })
+    }
+
+    pub fn put_activity(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        wallet: &WalletId,
+        activity: &WatchActivity,
+    ) {
+        txn.put(
+            self.activity,
+            &activity.key(wallet),
+            &activity.value(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    /// A page of the activity history of a wallet, newest first
+    pub fn activity(
+        &self,
+        txn: &dyn Transaction,
+        wallet: &WalletId,
+        offset: usize,
+        count: usize,
+    ) -> Vec<WatchActivity> {
+        let mut result = Vec::new();
+        let mut it = LmdbIteratorImpl::new(txn, self.activity, Some(wallet.as_bytes()), true);
+        let mut skipped = 0;
+        while let Some((key, value)) = it.current() {
+            if result.len() >= count || !key.starts_with(wallet.as_bytes()) {
+                break;
+            }
+            if skipped < offset {
+                skipped += 1;
+            } else if let Ok(activity) = WatchActivity::from_db(key, value) {
+                result.push(activity);
+            }
+            it.next();
+        }
+        result
+    }
+
+    pub fn indexed_height(
+        &self,
+        txn: &dyn Transaction,
+        wallet: &WalletId,
+        account: &Account,
+    ) -> u64 {
+        match txn.get(self.indexed, &account_key(wallet, account)) {
+            Ok(bytes) => u64::from_be_bytes(bytes.try_into().unwrap()),
+            Err(_) => 0,
+        }
+    }
+
+    pub fn set_indexed_height(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        wallet: &WalletId,
+        account: &Account,
+        height: u64,
+    ) {
+        txn.put(
+            self.indexed,
+            &account_key(wallet, account),
+            &height.to_be_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    /// Deletes the activity of the given account, or of all accounts of the
+    /// wallet if no account is given
+    pub fn clear_activity(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        wallet: &WalletId,
+        account: Option<&Account>,
+    ) {
+        let keys = self.keys_with_prefix(txn, self.activity, wallet, |key| {
+            account
+                .map(|a| &key[40..72] == a.as_bytes())
+                .unwrap_or(true)
+        });
+        for key in keys {
+            txn.delete(self.activity, &key, None).unwrap();
+        }
+        let keys = self.keys_with_prefix(txn, self.indexed, wallet, |key| {
+            account.map(|a| &key[32..] == a.as_bytes()).unwrap_or(true)
+        });
+        for key in keys {
+            txn.delete(self.indexed, &key, None).unwrap();
+        }
+    }
+
+    pub fn put_alert(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        wallet: &WalletId,
+        account: &Account,
+        threshold: Amount,
+    ) {
+        txn.put(
+            self.alerts,
+            &account_key(wallet, account),
+            &threshold.to_be_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn del_alert(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        wallet: &WalletId,
+        account: &Account,
+    ) -> bool {
+        txn.delete(self.alerts, &account_key(wallet, account), None)
+            .is_ok()
+    }
+
+    pub fn clear_alerts(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        wallet: &WalletId,
+        account: Option<&Account>,
+    ) {
+        let keys = self.keys_with_prefix(txn, self.alerts, wallet, |key| {
+            account.map(|a| &key[32..] == a.as_bytes()).unwrap_or(true)
+        });
+        for key in keys {
+            txn.delete(self.alerts, &key, None).unwrap();
+        }
+    }
+
+    /// All thresholds, grouped by account
+    pub fn alerts(&self, txn: &dyn Transaction) -> HashMap<Account, Vec<(WalletId, Amount)>> {
+        let mut result: HashMap<Account, Vec<(WalletId, Amount)>> = HashMap::new();
+        let mut it = LmdbIteratorImpl::new(txn, self.alerts, None, true);
+        while let Some((key, value)) = it.current() {
+            let wallet = WalletId::from_bytes(key[..32].try_into().unwrap());
+            let account = Account::from_slice(&key[32..]).unwrap();
+            let threshold = Amount::from_be_bytes(value.try_into().unwrap());
+            result.entry(account).or_default().push((wallet, threshold));
+            it.next();
+        }
+        result
+    }
+
+    fn keys_with_prefix(
+        &self,
+        txn: &dyn Transaction,
+        database: LmdbDatabase,
+        wallet: &WalletId,
+        filter: impl Fn(&[u8]) -> bool,
+    ) -> Vec<Vec<u8>> {
+        let mut keys = Vec::new();
+        let mut it = LmdbIteratorImpl::new(txn, database, Some(wallet.as_bytes()), true);
+        while let Some((key, _)) = it.current() {
+            if !key.starts_with(wallet.as_bytes()) {
+                break;
+            }
+            if filter(key) {
+                keys.push(key.to_vec());
+            }
+            it.next();
+        }
+        keys
+    }
+}
+
+fn account_key(wallet: &WalletId, account: &Account) -> [u8; 64] {
+    let mut key = [0; 64];
+    key[..32].copy_from_slice(wallet.as_bytes());
+    key[32..].copy_from_slice(account.as_bytes());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crossing() {
+        let threshold = Amount::raw(100);
+        assert_eq!(
+            BalanceAlertDirection::crossing(threshold, Amount::raw(99), Amount::raw(100)),
+            Some(BalanceAlertDirection::Above)
+        );
+        assert_eq!(
+            BalanceAlertDirection::crossing(threshold, Amount::raw(100), Amount::raw(99)),
+            Some(BalanceAlertDirection::Below)
+        );
+        assert_eq!(
+            BalanceAlertDirection::crossing(threshold, Amount::raw(150), Amount::raw(120)),
+            None
+        );
+        assert_eq!(
+            BalanceAlertDirection::crossing(threshold, Amount::raw(10), Amount::raw(20)),
+            None
+        );
+    }
+
+    #[test]
+    fn activity_db_roundtrip() {
+        let activity = WatchActivity {
+            account: Account::from(1),
+            hash: BlockHash::from(2),
+            subtype: BlockSubType::Receive,
+            amount: Amount::raw(3),
+            balance: Amount::raw(4),
+            height: 5,
+            local_timestamp: 6,
+        };
+        let wallet = WalletId::from(7);
+        let restored = WatchActivity::from_db(&activity.key(&wallet), &activity.value()).unwrap();
+        assert_eq!(restored, activity);
+    }
+
+    #[test]
+    fn newer_activity_sorts_first() {
+        let wallet = WalletId::from(1);
+        let mut older = WatchActivity {
+            account: Account::from(1),
+            hash: BlockHash::from(2),
+            subtype: BlockSubType::Send,
+            amount: Amount::raw(3),
+            balance: Amount::raw(4),
+            height: 2,
+            local_timestamp: 100,
+        };
+        let mut newer = older.clone();
+        newer.local_timestamp = 101;
+        assert!(newer.key(&wallet) < older.key(&wallet));
+
+        older.local_timestamp = 101;
+        newer.height = 3;
+        assert!(newer.key(&wallet) < older.key(&wallet));
+    }
+}
@@ -0,0 +1,236 @@
+use rsban_core::{work::WorkThresholds, Account, BlockDetails, Epoch, Root};
+use rsban_nullable_lmdb::{DatabaseFlags, LmdbDatabase, WriteFlags};
+use rsban_store_lmdb::{LmdbWriteTransaction, Transaction};
+use std::cmp::Reverse;
+
+/// Validity of the cached work of a wallet account at the current work thresholds
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WorkCacheState {
+    /// The work is valid for any next block
+    Valid,
+    /// The work is only valid for a receive block
+    ReceiveOnly,
+    /// The work is too low for the current thresholds or was created for an old root
+    Invalid,
+    /// No work is cached
+    Missing,
+}
+
+impl WorkCacheState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkCacheState::Valid => "valid",
+            WorkCacheState::ReceiveOnly => "receive_only",
+            WorkCacheState::Invalid => "invalid",
+            WorkCacheState::Missing => "missing",
+        }
+    }
+}
+
+/// The cached work of a wallet account
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WorkCacheEntry {
+    pub account: Account,
+    /// Root of the next block of the account
+    pub root: Root,
+    pub work: u64,
+    /// Difficulty of the cached work for the current root
+    pub difficulty: u64,
+    pub state: WorkCacheState,
+    /// Unix timestamp in seconds of the last block the wallet created for this account
+    pub last_active: Option<u64>,
+    /// Work for this account is being regenerated in the background
+    pub regenerating: bool,
+}
+
+impl WorkCacheEntry {
+    /// Validates the cached work against the thresholds of the next block. The
+    /// next block of an unopened account (epoch None) can only be a receive.
+    pub fn new(
+        thresholds: &WorkThresholds,
+        account: Account,
+        root: Root,
+        epoch: Option<Epoch>,
+        work: u64,
+    ) -> Self {
+        let difficulty = if work == 0 {
+            0
+        } else {
+            thresholds.difficulty(&root, work)
+        };
+        let receive_threshold = thresholds.threshold(&BlockDetails::new(
+            epoch.unwrap_or(Epoch::Epoch2),
+            false,
+            true,
+            false,
+        ));
+        let send_threshold = match epoch {
+            Some(epoch) => thresholds.threshold(&BlockDetails::new(epoch, true, false, false)),
+            None => receive_threshold,
+        };
+
+        let state = if work == 0 {
+            WorkCacheState::Missing
+        } else if difficulty >= send_threshold {
+            WorkCacheState::Valid
+        } else if difficulty >= receive_threshold {
+            WorkCacheState::ReceiveOnly
+        } else {
+            WorkCacheState::Invalid
+        };
+
+        Self {
+            account,
+            root,
+            work,
+            difficulty,
+            state,
+            last_active: None,
+            regenerating: false,
+        }
+    }
+
+    pub fn needs_regeneration(&self) -> bool {
+        self.state != WorkCacheState::Valid
+    }
+
+    /// Work is only precached in the background for accounts the wallet created
+    /// blocks for. Other accounts get their work when they are used.
+    pub fn should_precache(&self) -> bool {
+        self.needs_regeneration() && self.last_active.is_some() && !self.regenerating
+    }
+}
+
+/// Sorts the entries in the order in which their work should be regenerated.
+/// Recently active accounts come first, accounts without activity last.
+pub fn sort_by_priority(entries: &mut [WorkCacheEntry]) {
+    entries.sort_by_key(|e| Reverse(e.last_active));
+}
+
+/// Persists when the wallets last created a block for an account
+pub struct WorkActivityStore {
+    database: LmdbDatabase,
+}
+
+impl WorkActivityStore {
+    pub fn new(txn: &mut LmdbWriteTransaction) -> anyhow::Result<Self> {
+        let database = unsafe {
+            txn.rw_txn_mut()
+                .create_db(Some("work_cache_activity"), DatabaseFlags::empty())?
+        };
+        Ok(Self { database })
+    }
+
+    pub fn put(&self, txn: &mut LmdbWriteTransaction, account: &Account, timestamp: u64) {
+        txn.put(
+            self.database,
+            account.as_bytes(),
+            &timestamp.to_be_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn get(&self, txn: &dyn Transaction, account: &Account) -> Option<u64> {
+        let bytes = txn.get(self.database, account.as_bytes()).ok()?;
+        Some(u64::from_be_bytes(bytes.try_into().ok()?))
+    }
+
+    pub fn del(&self, txn: &mut LmdbWriteTransaction, account: &Account) {
+        let _ = txn.delete(self.database, account.as_bytes(), None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_work() {
+        let entry = WorkCacheEntry::new(
+            WorkThresholds::publish_dev(),
+            Account::from(1),
+            Root::from(1),
+            Some(Epoch::Epoch2),
+            0,
+        );
+        assert_eq!(entry.state, WorkCacheState::Missing);
+        assert!(entry.needs_regeneration());
+    }
+
+    #[test]
+    fn classify_work_by_block_subtype() {
+        // Epoch 2 receives require less work than sends
+        let thresholds =
+            WorkThresholds::new(0xff00000000000000, 0xfff0000000000000, 0xf000000000000000);
+        let root = Root::from(1);
+        let receive_work = find_work(&thresholds, &root, 0xf000000000000000, 0xfff0000000000000);
+        let low_work = find_work(&thresholds, &root, 0, 0xf000000000000000);
+
+        let entry = |epoch, work| {
+            WorkCacheEntry::new(&thresholds, Account::from(1), root, epoch, work).state
+        };
+
+        assert_eq!(
+            entry(Some(Epoch::Epoch2), receive_work),
+            WorkCacheState::ReceiveOnly
+        );
+        assert_eq!(entry(None, receive_work), WorkCacheState::Valid);
+        assert_eq!(
+            entry(Some(Epoch::Epoch2), low_work),
+            WorkCacheState::Invalid
+        );
+    }
+
+    #[test]
+    fn recently_active_accounts_first() {
+        let mut entries: Vec<_> = [None, Some(10), Some(20)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, last_active)| {
+                let mut entry = WorkCacheEntry::new(
+                    WorkThresholds::publish_dev(),
+                    Account::from(i as u64),
+                    Root::from(1),
+                    Some(Epoch::Epoch2),
+                    0,
+                );
+                entry.last_active = last_active;
+                entry
+            })
+            .collect();
+
+        sort_by_priority(&mut entries);
+
+        let order: Vec<_> = entries.iter().map(|e| e.last_active).collect();
+        assert_eq!(order, vec![Some(20), Some(10), None]);
+    }
+
+    #[test]
+    fn precache_only_active_accounts() {
+        let mut entry = WorkCacheEntry::new(
+            WorkThresholds::publish_dev(),
+            Account::from(1),
+            Root::from(1),
+            None,
+            0,
+        );
+        assert!(!entry.should_precache());
+
+        entry.last_active = Some(10);
+        assert!(entry.should_precache());
+
+        entry.regenerating = true;
+        assert!(!entry.should_precache());
+    }
+
+    /// Finds work whose difficulty is in the range [min, max)
+    fn find_work(thresholds: &WorkThresholds, root: &Root, min: u64, max: u64) -> u64 {
+        (1..)
+            .find(|work| {
+                let difficulty = thresholds.difficulty(root, *work);
+                difficulty >= min && difficulty < max
+            })
+            .unwrap()
+    }
+}
@@ -1,11 +1,7 @@
-use rsban_core::{
-    to_hex_string,
-    work::{WorkPool, WorkPoolImpl},
-    Account, Block, Root,
-};
+use super::{CpuWorkBackend, WorkBackend};
+use rsban_core::{to_hex_string, work::WorkPoolImpl, Account, Block, Root};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tokio::sync::oneshot;
 
 #[derive(Serialize)]
 pub struct HttpWorkRequest {
@@ -52,13 +48,17 @@ impl WorkRequest {
 }
 
 pub struct DistributedWorkFactory {
-    work_pool: Arc<WorkPoolImpl>,
+    backend: Arc<dyn WorkBackend>,
     pub tokio: tokio::runtime::Handle,
 }
 
 impl DistributedWorkFactory {
     pub fn new(work_pool: Arc<WorkPoolImpl>, tokio: tokio::runtime::Handle) -> Self {
-        Self { work_pool, tokio }
+        Self::with_backend(Arc::new(CpuWorkBackend::new(work_pool)), tokio)
+    }
+
+    pub fn with_backend(backend: Arc<dyn WorkBackend>, tokio: tokio::runtime::Handle) -> Self {
+        Self { backend, tokio }
     }
 
     pub fn make_blocking_block(&self, block: &mut Block, difficulty: u64) -> Option<u64> {
@@ -101,32 +101,21 @@ impl DistributedWorkFactory {
     }
 
     async fn generate_work(&self, request: WorkRequest) -> Option<u64> {
-        self.generate_in_local_work_pool(request.root, request.difficulty)
+        self.backend
+            .generate(request.root, request.difficulty, request.account)
             .await
     }
 
-    async fn generate_in_local_work_pool(&self, root: Root, difficulty: u64) -> Option<u64> {
-        let (tx, rx) = oneshot::channel::<Option<u64>>();
-        self.work_pool.generate_async(
-            root,
-            difficulty,
-            Some(Box::new(move |work| {
-                tx.send(work).unwrap();
-            })),
-        );
-        rx.await.ok()?
-    }
-
     pub fn cancel(&self, root: Root) {
-        self.work_pool.cancel(&root);
+        self.backend.cancel(&root);
     }
 
     pub fn work_generation_enabled(&self) -> bool {
-        self.work_pool.work_generation_enabled()
+        self.backend.is_enabled()
     }
 
     pub fn stop(&self) {
-        //TODO
+        self.backend.stop();
     }
 }
 
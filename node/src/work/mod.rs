@@ -1,2 +1,5 @@
 mod distributed_work_factory;
+mod work_backend;
+
 pub use distributed_work_factory::*;
+pub use work_backend::*;
@@ -0,0 +1,792 @@
+use super::HttpWorkRequest;
+use anyhow::Context;
+use async_trait::async_trait;
+use rsban_core::{
+    utils::Peer,
+    work::{WorkPool, WorkPoolImpl, WorkThresholds},
+    Account, Root,
+};
+use rsban_nullable_http_client::{HttpClient, Url};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+use tokio::sync::oneshot;
+use tracing::{debug, warn};
+
+/// A source of proof of work. The node uses exactly one backend, which is
+/// selected in the `[node.work_backend]` config section.
+#[async_trait]
+pub trait WorkBackend: Send + Sync {
+    /// Returns work for the root with at least the given difficulty,
+    /// or None if the work generation failed or was cancelled
+    async fn generate(&self, root: Root, difficulty: u64, account: Option<Account>) -> Option<u64>;
+    fn cancel(&self, root: &Root);
+    fn is_enabled(&self) -> bool;
+    fn stop(&self) {}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum WorkBackendKind {
+    /// Generate work with the threads of the local work pool
+    #[default]
+    Cpu,
+    /// Request work from the configured work peers
+    Http,
+    /// Request work from an external process via stdin/stdout
+    Process,
+}
+
+impl WorkBackendKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkBackendKind::Cpu => "cpu",
+            WorkBackendKind::Http => "http",
+            WorkBackendKind::Process => "process",
+        }
+    }
+}
+
+impl FromStr for WorkBackendKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cpu" => Ok(WorkBackendKind::Cpu),
+            "http" => Ok(WorkBackendKind::Http),
+            "process" => Ok(WorkBackendKind::Process),
+            _ => Err(anyhow!("unsupported work backend: {}", s)),
+        }
+    }
+}
+
+impl Display for WorkBackendKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkBackendConfig {
+    pub kind: WorkBackendKind,
+    /// Generate work in the local work pool if the http or process backend fails
+    pub local_fallback: bool,
+    /// Executable of the process backend
+    pub command: String,
+    pub args: Vec<String>,
+    /// Maximum time to wait for a single work peer or the work process
+    pub timeout: Duration,
+}
+
+impl Default for WorkBackendConfig {
+    fn default() -> Self {
+        Self {
+            kind: WorkBackendKind::Cpu,
+            local_fallback: true,
+            command: String::new(),
+            args: Vec::new(),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+pub fn create_work_backend(
+    config: &WorkBackendConfig,
+    work_peers: &[Peer],
+    work_pool: Arc<WorkPoolImpl>,
+    thresholds: WorkThresholds,
+    tokio: tokio::runtime::Handle,
+) -> Arc<dyn WorkBackend> {
+    let fallback = config.local_fallback.then(|| work_pool.clone());
+    match config.kind {
+        WorkBackendKind::Cpu => Arc::new(CpuWorkBackend::new(work_pool)),
+        WorkBackendKind::Http => Arc::new(HttpWorkBackend::new(
+            Arc::new(HttpClient::new()),
+            work_peers,
+            thresholds,
+            config.timeout,
+            fallback,
+            tokio,
+        )),
+        WorkBackendKind::Process => {
+            match ProcessWorkBackend::spawn(
+                &config.command,
+                &config.args,
+                thresholds,
+                config.timeout,
+                fallback,
+            ) {
+                Ok(backend) => Arc::new(backend),
+                Err(e) if config.local_fallback => {
+                    warn!(
+                        "Could not start work process, using local work pool instead: {:?}",
+                        e
+                    );
+                    Arc::new(CpuWorkBackend::new(work_pool))
+                }
+                Err(e) => {
+                    warn!(
+                        "Could not start work process, work generation is disabled: {:?}",
+                        e
+                    );
+                    Arc::new(DisabledWorkBackend)
+                }
+            }
+        }
+    }
+}
+
+pub struct CpuWorkBackend {
+    work_pool: Arc<WorkPoolImpl>,
+}
+
+impl CpuWorkBackend {
+    pub fn new(work_pool: Arc<WorkPoolImpl>) -> Self {
+        Self { work_pool }
+    }
+}
+
+#[async_trait]
+impl WorkBackend for CpuWorkBackend {
+    async fn generate(
+        &self,
+        root: Root,
+        difficulty: u64,
+        _account: Option<Account>,
+    ) -> Option<u64> {
+        let (tx, rx) = oneshot::channel::<Option<u64>>();
+        self.work_pool.generate_async(
+            root,
+            difficulty,
+            Some(Box::new(move |work| {
+                let _ = tx.send(work);
+            })),
+        );
+        rx.await.ok()?
+    }
+
+    fn cancel(&self, root: &Root) {
+        self.work_pool.cancel(root);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.work_pool.work_generation_enabled()
+    }
+}
+
+/// Used when the configured backend can't be started and no local fallback is allowed
+pub struct DisabledWorkBackend;
+
+#[async_trait]
+impl WorkBackend for DisabledWorkBackend {
+    async fn generate(
+        &self,
+        _root: Root,
+        _difficulty: u64,
+        _account: Option<Account>,
+    ) -> Option<u64> {
+        None
+    }
+
+    fn cancel(&self, _root: &Root) {}
+
+    fn is_enabled(&self) -> bool {
+        false
+    }
+}
+
+/// Requests work from other nodes or work servers via the `work_generate` RPC.
+/// The peers are asked one after the other until one returns valid work.
+pub struct HttpWorkBackend {
+    http_client: Arc<HttpClient>,
+    peers: Vec<Url>,
+    thresholds: WorkThresholds,
+    timeout: Duration,
+    fallback: Option<CpuWorkBackend>,
+    running: RunningRequests,
+    tokio: tokio::runtime::Handle,
+}
+
+impl HttpWorkBackend {
+    pub fn new(
+        http_client: Arc<HttpClient>,
+        peers: &[Peer],
+        thresholds: WorkThresholds,
+        timeout: Duration,
+        fallback: Option<Arc<WorkPoolImpl>>,
+        tokio: tokio::runtime::Handle,
+    ) -> Self {
+        Self {
+            http_client,
+            peers: peers.iter().filter_map(peer_url).collect(),
+            thresholds,
+            timeout,
+            fallback: fallback.map(CpuWorkBackend::new),
+            running: RunningRequests::default(),
+            tokio,
+        }
+    }
+
+    async fn request_work(
+        &self,
+        url: &Url,
+        root: Root,
+        difficulty: u64,
+        account: Option<Account>,
+    ) -> anyhow::Result<u64> {
+        let request = HttpWorkRequest::new(root, difficulty, account);
+        let response = tokio::time::timeout(
+            self.timeout,
+            self.http_client.post_json(url.clone(), &request),
+        )
+        .await
+        .map_err(|_| anyhow!("request timed out"))??;
+        let response: WorkResponse = response.json().await?;
+        response.validate(&self.thresholds, &root, difficulty)
+    }
+}
+
+#[async_trait]
+impl WorkBackend for HttpWorkBackend {
+    async fn generate(&self, root: Root, difficulty: u64, account: Option<Account>) -> Option<u64> {
+        self.running.start(root);
+        let mut work = None;
+        for url in &self.peers {
+            match self.request_work(url, root, difficulty, account).await {
+                Ok(result) => {
+                    work = Some(result);
+                    break;
+                }
+                Err(e) => {
+                    debug!("Work peer {} failed to generate work: {:?}", url, e);
+                    if self.running.is_cancelled(&root) {
+                        break;
+                    }
+                }
+            }
+        }
+        let cancelled = self.running.finish(&root);
+
+        match &self.fallback {
+            Some(fallback) if work.is_none() && !cancelled => {
+                fallback.generate(root, difficulty, account).await
+            }
+            _ => work,
+        }
+    }
+
+    fn cancel(&self, root: &Root) {
+        if let Some(fallback) = &self.fallback {
+            fallback.cancel(root);
+        }
+
+        if !self.running.cancel(root) {
+            return;
+        }
+
+        let http_client = self.http_client.clone();
+        let peers = self.peers.clone();
+        let request = WorkCancelRequest::new(root);
+        self.tokio.spawn(async move {
+            for url in peers {
+                if let Err(e) = http_client.post_json(url.clone(), &request).await {
+                    debug!("Could not cancel work on peer {}: {:?}", url, e);
+                }
+            }
+        });
+    }
+
+    fn is_enabled(&self) -> bool {
+        !self.peers.is_empty()
+            || self
+                .fallback
+                .as_ref()
+                .map(|f| f.is_enabled())
+                .unwrap_or_default()
+    }
+}
+
+/// Requests work from an external process. Every request and every response is a
+/// single JSON line: the process reads `work_generate` and `work_cancel` requests
+/// from stdin and writes `{"hash": ..., "work": ...}` or `{"hash": ..., "error": ...}`
+/// to stdout.
+pub struct ProcessWorkBackend {
+    child: Mutex<Child>,
+    /// Lines for stdin. They are written by a separate thread, because the
+    /// writes block when the process doesn't read its input.
+    requests: mpsc::Sender<String>,
+    pending: Arc<Mutex<HashMap<Root, Vec<PendingWork>>>>,
+    alive: Arc<AtomicBool>,
+    timeout: Duration,
+    fallback: Option<CpuWorkBackend>,
+    running: RunningRequests,
+}
+
+struct PendingWork {
+    difficulty: u64,
+    tx: oneshot::Sender<Option<u64>>,
+}
+
+impl ProcessWorkBackend {
+    pub fn spawn(
+        command: &str,
+        args: &[String],
+        thresholds: WorkThresholds,
+        timeout: Duration,
+        fallback: Option<Arc<WorkPoolImpl>>,
+    ) -> anyhow::Result<Self> {
+        if command.is_empty() {
+            bail!("no command configured for the process work backend");
+        }
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("could not start {}", command))?;
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let pending = Arc::new(Mutex::new(HashMap::new()));
+        let alive = Arc::new(AtomicBool::new(true));
+
+        let reader_pending = pending.clone();
+        let reader_alive = alive.clone();
+        thread::Builder::new()
+            .name("Work process".to_string())
+            .spawn(move || read_responses(stdout, thresholds, reader_pending, reader_alive))?;
+
+        let (requests, requests_rx) = mpsc::channel();
+        thread::Builder::new()
+            .name("Work proc input".to_string())
+            .spawn(move || write_requests(stdin, requests_rx))?;
+
+        Ok(Self {
+            child: Mutex::new(child),
+            requests,
+            pending,
+            alive,
+            timeout,
+            fallback: fallback.map(CpuWorkBackend::new),
+            running: RunningRequests::default(),
+        })
+    }
+
+    fn write_line(&self, request: &impl Serialize) -> anyhow::Result<()> {
+        let line = serde_json::to_string(request)?;
+        self.requests
+            .send(line)
+            .map_err(|_| anyhow!("work process input is closed"))
+    }
+
+    /// Drops the requests of callers that stopped waiting for the work
+    fn remove_abandoned(&self, root: &Root) {
+        let mut pending = self.pending.lock().unwrap();
+        if let Some(requests) = pending.get_mut(root) {
+            requests.retain(|r| !r.tx.is_closed());
+            if requests.is_empty() {
+                pending.remove(root);
+            }
+        }
+    }
+
+    async fn request_work(
+        &self,
+        root: Root,
+        difficulty: u64,
+        account: Option<Account>,
+    ) -> Option<u64> {
+        let (tx, rx) = oneshot::channel();
+        {
+            let mut pending = self.pending.lock().unwrap();
+            if !self.alive.load(Ordering::SeqCst) {
+                return None;
+            }
+            pending
+                .entry(root)
+                .or_default()
+                .push(PendingWork { difficulty, tx });
+        }
+
+        if let Err(e) = self.write_line(&HttpWorkRequest::new(root, difficulty, account)) {
+            warn!("Could not send work request to work process: {:?}", e);
+            self.pending.lock().unwrap().remove(&root);
+            return None;
+        }
+
+        match tokio::time::timeout(self.timeout, rx).await {
+            Ok(work) => work.ok()?,
+            Err(_) => {
+                warn!("Work process did not respond in time for root {}", root);
+                self.remove_abandoned(&root);
+                let _ = self.write_line(&WorkCancelRequest::new(&root));
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl WorkBackend for ProcessWorkBackend {
+    async fn generate(&self, root: Root, difficulty: u64, account: Option<Account>) -> Option<u64> {
+        self.running.start(root);
+        let work = self.request_work(root, difficulty, account).await;
+        let cancelled = self.running.finish(&root);
+
+        match &self.fallback {
+            Some(fallback) if work.is_none() && !cancelled => {
+                fallback.generate(root, difficulty, account).await
+            }
+            _ => work,
+        }
+    }
+
+    fn cancel(&self, root: &Root) {
+        if let Some(fallback) = &self.fallback {
+            fallback.cancel(root);
+        }
+
+        if self.running.cancel(root) && self.alive.load(Ordering::SeqCst) {
+            if let Err(e) = self.write_line(&WorkCancelRequest::new(root)) {
+                debug!("Could not cancel work in work process: {:?}", e);
+            }
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.alive.load(Ordering::SeqCst)
+            || self
+                .fallback
+                .as_ref()
+                .map(|f| f.is_enabled())
+                .unwrap_or_default()
+    }
+
+    fn stop(&self) {
+        let mut child = self.child.lock().unwrap();
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+impl Drop for ProcessWorkBackend {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn write_requests(mut stdin: ChildStdin, requests: mpsc::Receiver<String>) {
+    for line in requests {
+        if let Err(e) = writeln!(stdin, "{}", line).and_then(|_| stdin.flush()) {
+            warn!("Could not write to work process: {:?}", e);
+            break;
+        }
+    }
+}
+
+fn read_responses(
+    stdout: ChildStdout,
+    thresholds: WorkThresholds,
+    pending: Arc<Mutex<HashMap<Root, Vec<PendingWork>>>>,
+    alive: Arc<AtomicBool>,
+) {
+    for line in BufReader::new(stdout).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let response = match serde_json::from_str::<WorkResponse>(&line) {
+            Ok(response) => response,
+            Err(e) => {
+                warn!("Invalid response from work process: {:?}", e);
+                continue;
+            }
+        };
+        let Some(root) = response
+            .hash
+            .as_ref()
+            .and_then(|hash| Root::decode_hex(hash).ok())
+        else {
+            warn!("Work process response contains no valid hash");
+            continue;
+        };
+
+        let requests = pending.lock().unwrap().remove(&root).unwrap_or_default();
+        for request in requests {
+            let work = response.validate(&thresholds, &root, request.difficulty);
+            if let Err(e) = &work {
+                debug!("Work process failed to generate work: {:?}", e);
+            }
+            let _ = request.tx.send(work.ok());
+        }
+    }
+
+    warn!("Work process terminated");
+    alive.store(false, Ordering::SeqCst);
+    for (_, requests) in pending.lock().unwrap().drain() {
+        for request in requests {
+            let _ = request.tx.send(None);
+        }
+    }
+}
+
+fn peer_url(peer: &Peer) -> Option<Url> {
+    let host = if peer.address.contains(':') {
+        format!("[{}]", peer.address)
+    } else {
+        peer.address.clone()
+    };
+    match Url::parse(&format!("http://{}:{}/", host, peer.port)) {
+        Ok(url) => Some(url),
+        Err(e) => {
+            warn!("Invalid work peer {}: {:?}", peer, e);
+            None
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct WorkCancelRequest {
+    action: &'static str,
+    hash: String,
+}
+
+impl WorkCancelRequest {
+    fn new(root: &Root) -> Self {
+        Self {
+            action: "work_cancel",
+            hash: root.to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct WorkResponse {
+    hash: Option<String>,
+    work: Option<String>,
+    error: Option<String>,
+}
+
+impl WorkResponse {
+    fn validate(
+        &self,
+        thresholds: &WorkThresholds,
+        root: &Root,
+        difficulty: u64,
+    ) -> anyhow::Result<u64> {
+        if let Some(error) = &self.error {
+            bail!("{}", error);
+        }
+        let Some(work) = &self.work else {
+            bail!("response contains no work");
+        };
+        let work = u64::from_str_radix(work, 16)?;
+        if thresholds.difficulty(root, work) < difficulty {
+            bail!("work does not meet the requested difficulty");
+        }
+        Ok(work)
+    }
+}
+
+/// Remembers which remote requests were cancelled, so that no
+/// local fallback is started for them
+#[derive(Default)]
+struct RunningRequests(Mutex<HashMap<Root, bool>>);
+
+impl RunningRequests {
+    fn start(&self, root: Root) {
+        self.0.lock().unwrap().insert(root, false);
+    }
+
+    /// Returns true if a request for this root was running
+    fn cancel(&self, root: &Root) -> bool {
+        match self.0.lock().unwrap().get_mut(root) {
+            Some(cancelled) => {
+                *cancelled = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn is_cancelled(&self, root: &Root) -> bool {
+        self.0
+            .lock()
+            .unwrap()
+            .get(root)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the request was cancelled
+    fn finish(&self, root: &Root) -> bool {
+        self.0.lock().unwrap().remove(root).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_core::{to_hex_string, work::WORK_THRESHOLDS_STUB};
+    use rsban_nullable_http_client::{ConfiguredResponse, StatusCode};
+
+    #[tokio::test]
+    async fn cpu_backend_uses_work_pool() {
+        let backend = CpuWorkBackend::new(Arc::new(WorkPoolImpl::new_null(12345)));
+        assert!(backend.is_enabled());
+        let work = backend.generate(Root::from(1), 42, None).await;
+        assert_eq!(work, Some(12345));
+    }
+
+    #[tokio::test]
+    async fn http_backend_returns_valid_work_of_peer() {
+        let root = Root::from(1);
+        let work = 0x1234;
+        let difficulty = WORK_THRESHOLDS_STUB.difficulty(&root, work);
+        let http_client = HttpClient::null_builder().respond(ConfiguredResponse::new(
+            StatusCode::OK,
+            serde_json::json!({ "work": to_hex_string(work) }),
+        ));
+        let backend = HttpWorkBackend::new(
+            Arc::new(http_client),
+            &[Peer::new("127.0.0.1", 7076)],
+            WORK_THRESHOLDS_STUB.clone(),
+            Duration::from_secs(5),
+            None,
+            tokio::runtime::Handle::current(),
+        );
+
+        assert_eq!(backend.generate(root, difficulty, None).await, Some(work));
+    }
+
+    #[tokio::test]
+    async fn http_backend_falls_back_to_local_pool_when_work_is_invalid() {
+        let root = Root::from(1);
+        let http_client = HttpClient::null_builder().respond(ConfiguredResponse::new(
+            StatusCode::OK,
+            serde_json::json!({ "work": "0000000000000001" }),
+        ));
+        let backend = HttpWorkBackend::new(
+            Arc::new(http_client),
+            &[Peer::new("127.0.0.1", 7076)],
+            WORK_THRESHOLDS_STUB.clone(),
+            Duration::from_secs(5),
+            Some(Arc::new(WorkPoolImpl::new_null(12345))),
+            tokio::runtime::Handle::current(),
+        );
+
+        let work = backend.generate(root, u64::MAX, None).await;
+
+        assert_eq!(work, Some(12345));
+    }
+
+    #[tokio::test]
+    async fn http_backend_without_peers_and_fallback_is_disabled() {
+        let backend = HttpWorkBackend::new(
+            Arc::new(HttpClient::new_null()),
+            &[],
+            WORK_THRESHOLDS_STUB.clone(),
+            Duration::from_secs(5),
+            None,
+            tokio::runtime::Handle::current(),
+        );
+        assert!(!backend.is_enabled());
+        assert_eq!(backend.generate(Root::from(1), 1, None).await, None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn process_backend_reads_work_from_stdout() {
+        // Answers every request with the work 0000000000000001
+        let script =
+            r#"sed -u 's/.*"hash":"\([0-9A-F]*\)".*/{"hash":"\1","work":"0000000000000001"}/'"#;
+        let backend = ProcessWorkBackend::spawn(
+            "sh",
+            &["-c".to_string(), script.to_string()],
+            WORK_THRESHOLDS_STUB.clone(),
+            Duration::from_secs(5),
+            None,
+        )
+        .unwrap();
+        let root = Root::from(1);
+        let difficulty = WORK_THRESHOLDS_STUB.difficulty(&root, 1);
+
+        assert_eq!(backend.generate(root, difficulty, None).await, Some(1));
+        assert_eq!(backend.generate(root, u64::MAX, None).await, None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn process_backend_falls_back_to_local_pool_on_timeout() {
+        // Reads the requests but never answers
+        let backend = ProcessWorkBackend::spawn(
+            "sh",
+            &["-c".to_string(), "cat > /dev/null".to_string()],
+            WORK_THRESHOLDS_STUB.clone(),
+            Duration::from_millis(100),
+            Some(Arc::new(WorkPoolImpl::new_null(12345))),
+        )
+        .unwrap();
+        let root = Root::from(1);
+
+        assert_eq!(backend.generate(root, 1, None).await, Some(12345));
+        assert!(backend.pending.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn process_backend_requires_command() {
+        assert!(ProcessWorkBackend::spawn(
+            "",
+            &[],
+            WORK_THRESHOLDS_STUB.clone(),
+            Duration::from_secs(5),
+            None
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn failed_work_process_uses_local_pool_only_with_fallback() {
+        let mut config = WorkBackendConfig {
+            kind: WorkBackendKind::Process,
+            ..Default::default()
+        };
+        let create = |config: &WorkBackendConfig| {
+            create_work_backend(
+                config,
+                &[],
+                Arc::new(WorkPoolImpl::new_null(12345)),
+                WORK_THRESHOLDS_STUB.clone(),
+                tokio::runtime::Handle::current(),
+            )
+        };
+
+        let backend = create(&config);
+        assert!(backend.is_enabled());
+        assert_eq!(backend.generate(Root::from(1), 1, None).await, Some(12345));
+
+        config.local_fallback = false;
+        let backend = create(&config);
+        assert!(!backend.is_enabled());
+        assert_eq!(backend.generate(Root::from(1), 1, None).await, None);
+    }
+
+    #[test]
+    fn parse_backend_kind() {
+        assert_eq!(
+            "http".parse::<WorkBackendKind>().unwrap(),
+            WorkBackendKind::Http
+        );
+        assert_eq!(WorkBackendKind::Process.to_string(), "process");
+        assert!("gpu".parse::<WorkBackendKind>().is_err());
+    }
+}
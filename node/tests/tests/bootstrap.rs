@@ -2309,6 +2309,7 @@ fn create_response_server(node: &Node) -> Arc<ResponseServer> {
     Arc::new(ResponseServer::new(
         node.network_info.clone(),
         node.inbound_message_queue.clone(),
+        node.inbound_rate_limiter.clone(),
         channel,
         node.network_filter.clone(),
         Arc::new(node.network_params.clone()),
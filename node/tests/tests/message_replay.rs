@@ -0,0 +1,53 @@
+use rsban_core::{Account, UnsavedBlockLatticeBuilder};
+use rsban_messages::{DeserializedMessage, Message, ProtocolInfo, Publish};
+use rsban_network::ChannelDirection;
+use rsban_node::transport::{
+    MessageRecordingReader, MessageRecordingWriter, MessageReplayer, RecordedMessage, ReplaySpeed,
+};
+use std::{
+    io::Cursor,
+    time::{Duration, SystemTime},
+};
+use test_helpers::{assert_timely, System};
+
+#[test]
+fn replay_recorded_publish() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let mut lattice = UnsavedBlockLatticeBuilder::new();
+    let send1 = lattice.genesis().send(Account::from(1), 1);
+    let send2 = lattice.genesis().send(Account::from(2), 1);
+    let protocol = ProtocolInfo::default_for(node.network_params.network.current_network);
+    let now = SystemTime::now();
+
+    let mut writer = MessageRecordingWriter::new(Vec::new()).unwrap();
+    for (i, block) in [send1.clone(), send2.clone()].into_iter().enumerate() {
+        writer
+            .write(&RecordedMessage {
+                timestamp: now + Duration::from_millis(100 * i as u64),
+                channel_id: 1.into(),
+                direction: ChannelDirection::Inbound,
+                peer_addr: "[::1]:7071".parse().unwrap(),
+                message: DeserializedMessage::new(
+                    Message::Publish(Publish::new_forward(block)),
+                    protocol,
+                ),
+            })
+            .unwrap();
+    }
+    let recording = writer.into_inner().unwrap();
+
+    let reader = MessageRecordingReader::new(Cursor::new(recording)).unwrap();
+    let mut replayer = MessageReplayer::new(
+        node.inbound_message_queue.clone(),
+        node.steady_clock.clone(),
+        ReplaySpeed::Accelerated(10.0),
+    );
+    let result = replayer.replay(reader.map(|i| i.unwrap()));
+
+    assert_eq!(result.replayed, 2);
+    assert_timely(Duration::from_secs(5), || {
+        node.block_exists(&send1.hash()) && node.block_exists(&send2.hash())
+    });
+}
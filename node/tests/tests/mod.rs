@@ -10,6 +10,7 @@ mod election;
 mod election_scheduler;
 mod ledger;
 mod ledger_confirm;
+mod message_replay;
 mod network;
 mod node;
 mod optimistic_scheduler;
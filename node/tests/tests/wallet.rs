@@ -1,6 +1,6 @@
 use rsban_core::{
-    deterministic_key, Account, Amount, Block, BlockHash, Epoch, EpochBlockArgs,
-    KeyDerivationFunction, PrivateKey, PublicKey, RawKey, UnsavedBlockLatticeBuilder,
+    deterministic_key, Account, Amount, Block, BlockHash, Epoch, EpochBlockArgs, KdfVariant,
+    KeyDerivationFunction, PrivateKey, PublicKey, RawKey, UnsavedBlockLatticeBuilder, WalletId,
     DEV_GENESIS_KEY,
 };
 use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
@@ -8,10 +8,10 @@ use rsban_node::{
     config::{NodeConfig, NodeFlags},
     consensus::ActiveElectionsExt,
     unique_path,
-    wallets::{WalletsError, WalletsExt},
+    wallets::{Wallets, WalletsError, WalletsExt},
     Node, DEV_NETWORK_PARAMS,
 };
-use rsban_store_lmdb::{LmdbEnv, LmdbWalletStore};
+use rsban_store_lmdb::{LmdbEnv, LmdbWalletStore, WalletValue};
 use std::{
     collections::HashSet,
     path::PathBuf,
@@ -395,7 +395,7 @@ fn rekey() {
     );
     wallet.rekey(&mut tx, "1").unwrap();
     let password = wallet.password();
-    let password1 = wallet.derive_key(&tx, "1");
+    let password1 = wallet.derive_key(&tx, "1").unwrap();
     assert_eq!(password1, password);
     let prv2 = wallet.fetch(&tx, &key1.public_key()).unwrap();
     assert_eq!(prv2, key1.raw_key());
@@ -412,13 +412,124 @@ fn hash_password() {
     let kdf = KeyDerivationFunction::new(DEV_NETWORK_PARAMS.kdf_work);
     let wallet =
         LmdbWalletStore::new(0, kdf, &mut tx, &DEV_GENESIS_PUB_KEY, &PathBuf::from("0")).unwrap();
-    let hash1 = wallet.derive_key(&tx, "");
-    let hash2 = wallet.derive_key(&tx, "");
+    let hash1 = wallet.derive_key(&tx, "").unwrap();
+    let hash2 = wallet.derive_key(&tx, "").unwrap();
     assert_eq!(hash1, hash2);
-    let hash3 = wallet.derive_key(&tx, "a");
+    let hash3 = wallet.derive_key(&tx, "a").unwrap();
     assert_ne!(hash1, hash3);
 }
 
+#[test]
+fn kdf_upgrade() {
+    let mut test_file = unique_path().unwrap();
+    test_file.push("wallet.ldb");
+    let env = LmdbEnv::new(test_file).unwrap();
+    let mut tx = env.tx_begin_write();
+    let kdf = KeyDerivationFunction::new(DEV_NETWORK_PARAMS.kdf_work);
+    let wallet = LmdbWalletStore::new(
+        0,
+        kdf.clone(),
+        &mut tx,
+        &DEV_GENESIS_PUB_KEY,
+        &PathBuf::from("0"),
+    )
+    .unwrap();
+    assert_eq!(wallet.kdf(&tx).unwrap(), kdf);
+    wallet.rekey(&mut tx, "1").unwrap();
+    let key = PrivateKey::new();
+    wallet.insert_adhoc(&mut tx, &key.raw_key());
+    let seed = wallet.seed(&tx);
+
+    let upgraded = KeyDerivationFunction::with_params(KdfVariant::Argon2id, 16, 2, 1).unwrap();
+    assert!(wallet.upgrade_kdf(&mut tx, "2", &upgraded).is_err());
+    assert_eq!(wallet.kdf(&tx).unwrap(), kdf);
+    wallet.upgrade_kdf(&mut tx, "1", &upgraded).unwrap();
+
+    assert_eq!(wallet.kdf(&tx).unwrap(), upgraded);
+    assert!(wallet.valid_password(&tx));
+    assert_eq!(wallet.seed(&tx), seed);
+    assert_eq!(wallet.fetch(&tx, &key.public_key()).unwrap(), key.raw_key());
+    wallet.lock();
+    assert!(wallet.attempt_password(&tx, "1"));
+    assert_eq!(wallet.seed(&tx), seed);
+}
+
+#[test]
+fn kdf_upgrade_keeps_locked_wallet_locked() {
+    let mut test_file = unique_path().unwrap();
+    test_file.push("wallet.ldb");
+    let env = LmdbEnv::new(test_file).unwrap();
+    let mut tx = env.tx_begin_write();
+    let kdf = KeyDerivationFunction::new(DEV_NETWORK_PARAMS.kdf_work);
+    let wallet =
+        LmdbWalletStore::new(0, kdf, &mut tx, &DEV_GENESIS_PUB_KEY, &PathBuf::from("0")).unwrap();
+    wallet.rekey(&mut tx, "1").unwrap();
+    wallet.lock();
+
+    let upgraded = KeyDerivationFunction::with_params(KdfVariant::Argon2i, 16, 1, 1).unwrap();
+    wallet.upgrade_kdf(&mut tx, "1", &upgraded).unwrap();
+    assert!(!wallet.valid_password(&tx));
+    assert!(wallet.attempt_password(&tx, "1"));
+}
+
+#[test]
+fn legacy_wallet_without_kdf_key() {
+    let mut test_file = unique_path().unwrap();
+    test_file.push("wallet.ldb");
+    let env = LmdbEnv::new(test_file).unwrap();
+    let mut tx = env.tx_begin_write();
+    let kdf = KeyDerivationFunction::new(DEV_NETWORK_PARAMS.kdf_work);
+    {
+        let wallet = LmdbWalletStore::new(
+            0,
+            kdf.clone(),
+            &mut tx,
+            &DEV_GENESIS_PUB_KEY,
+            &PathBuf::from("0"),
+        )
+        .unwrap();
+        wallet.rekey(&mut tx, "1").unwrap();
+        // Wallets created by older node versions don't store their KDF
+        wallet.erase(&mut tx, &LmdbWalletStore::kdf_special());
+        wallet.version_put(&mut tx, 4);
+    }
+    let wallet = LmdbWalletStore::new(
+        0,
+        kdf.clone(),
+        &mut tx,
+        &DEV_GENESIS_PUB_KEY,
+        &PathBuf::from("0"),
+    )
+    .unwrap();
+    assert_eq!(wallet.kdf(&tx).unwrap(), kdf);
+    assert_eq!(wallet.version(&tx), 4);
+    assert_eq!(wallet.kdf(&tx).unwrap(), kdf);
+    wallet.upgrade(&mut tx).unwrap();
+    assert_eq!(wallet.version(&tx), LmdbWalletStore::VERSION_CURRENT);
+    assert_eq!(wallet.kdf(&tx).unwrap(), kdf);
+    assert!(wallet.attempt_password(&tx, "1"));
+}
+
+#[test]
+fn malformed_kdf_record() {
+    let mut test_file = unique_path().unwrap();
+    test_file.push("wallet.ldb");
+    let env = LmdbEnv::new(test_file).unwrap();
+    let mut tx = env.tx_begin_write();
+    let kdf = KeyDerivationFunction::new(DEV_NETWORK_PARAMS.kdf_work);
+    let wallet =
+        LmdbWalletStore::new(0, kdf, &mut tx, &DEV_GENESIS_PUB_KEY, &PathBuf::from("0")).unwrap();
+    wallet.entry_put_raw(
+        &mut tx,
+        &LmdbWalletStore::kdf_special(),
+        &WalletValue::new(RawKey::from(42), 0),
+    );
+
+    assert!(wallet.kdf(&tx).is_err());
+    assert!(!wallet.attempt_password(&tx, ""));
+    assert!(wallet.rekey(&mut tx, "1").is_err());
+}
+
 #[test]
 fn reopen_default_password() {
     let mut test_file = unique_path().unwrap();
@@ -626,6 +737,33 @@ fn wallet_store_move() {
     assert_eq!(wallet2.exists(&tx, &key2.public_key()), false);
 }
 
+#[test]
+fn read_accounts_of_all_wallets() {
+    let mut test_file = unique_path().unwrap();
+    test_file.push("wallet.ldb");
+    let env = LmdbEnv::new(test_file).unwrap();
+    let kdf = KeyDerivationFunction::new(DEV_NETWORK_PARAMS.kdf_work);
+    let mut accounts = Vec::new();
+    let mut tx = env.tx_begin_write();
+    for _ in 0..2 {
+        let wallet = LmdbWalletStore::new(
+            0,
+            kdf.clone(),
+            &mut tx,
+            &DEV_GENESIS_PUB_KEY,
+            &PathBuf::from(WalletId::random().encode_hex()),
+        )
+        .unwrap();
+        accounts.push(Account::from(wallet.deterministic_insert(&mut tx)));
+    }
+    tx.commit();
+
+    let mut read = Wallets::read_accounts(&env).unwrap();
+    read.sort();
+    accounts.sort();
+    assert_eq!(read, accounts);
+}
+
 #[test]
 fn wallet_store_import() {
     let mut system = System::new();
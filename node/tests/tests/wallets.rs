@@ -1,12 +1,20 @@
-use rsban_core::{Amount, PrivateKey, UnsavedBlockLatticeBuilder, WalletId, DEV_GENESIS_KEY};
-use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_PUB_KEY};
+use rsban_core::{
+    Account, Amount, PrivateKey, RawKey, UnsavedBlockLatticeBuilder, WalletId, DEV_GENESIS_KEY,
+};
+use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_BLOCK, DEV_GENESIS_PUB_KEY};
 use rsban_node::{
     config::{NodeConfig, NodeFlags},
     consensus::ActiveElectionsExt,
-    wallets::WalletsExt,
+    wallets::{
+        BalanceAlertDirection, NewPaymentSchedule, PaymentScheduleStatus, SweepStatus, WalletsExt,
+        WorkCacheState,
+    },
+};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
 };
-use std::time::Duration;
-use test_helpers::{assert_timely, assert_timely_eq, System};
+use test_helpers::{assert_never, assert_timely, assert_timely_eq, System};
 
 #[test]
 fn open_create() {
@@ -189,3 +197,282 @@ fn search_receivable() {
         assert_eq!(send.hash(), receive.source().unwrap());
     }
 }
+
+#[test]
+fn backup_and_restore() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let seed = RawKey::from(42);
+    node.wallets.change_seed(wallet_id, &seed, 2).unwrap();
+    let adhoc = PrivateKey::new();
+    node.wallets
+        .insert_adhoc2(&wallet_id, &adhoc.raw_key(), false)
+        .unwrap();
+    let watched = Account::from(1000);
+    node.wallets.insert_watch(&wallet_id, &[watched]).unwrap();
+
+    let backup = node.wallets.backup_wallet(&wallet_id, "secret").unwrap();
+
+    let restored_id = WalletId::random();
+    node.wallets
+        .restore_wallet(restored_id, &backup, "secret")
+        .unwrap();
+
+    assert_eq!(node.wallets.get_seed(restored_id).unwrap(), seed);
+    assert_eq!(
+        node.wallets.deterministic_index_get(&restored_id).unwrap(),
+        node.wallets.deterministic_index_get(&wallet_id).unwrap()
+    );
+    assert_eq!(
+        node.wallets.get_representative(restored_id).unwrap(),
+        node.wallets.get_representative(wallet_id).unwrap()
+    );
+    let mut original = node.wallets.get_accounts_of_wallet(&wallet_id).unwrap();
+    let mut restored = node.wallets.get_accounts_of_wallet(&restored_id).unwrap();
+    original.sort();
+    restored.sort();
+    assert_eq!(restored, original);
+    assert!(node
+        .wallets
+        .attempt_password(&restored_id, "secret")
+        .is_ok());
+}
+
+#[test]
+fn restore_fails_with_wrong_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let backup = node.wallets.backup_wallet(&wallet_id, "secret").unwrap();
+
+    let restored_id = WalletId::random();
+    let result = node.wallets.restore_wallet(restored_id, &backup, "wrong");
+
+    assert!(result.is_err());
+    assert!(!node.wallets.wallet_exists(&restored_id));
+}
+
+#[test]
+fn payment_schedule_create_list_cancel() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let source = node
+        .wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap();
+
+    let schedule = node
+        .wallets
+        .schedule_create(NewPaymentSchedule {
+            wallet: wallet_id,
+            source: source.into(),
+            destination: Account::from(1000),
+            amount: Amount::raw(5),
+            cadence: "@daily".parse().unwrap(),
+            start: Some(1_704_067_200), // 2024-01-01 00:00:00 UTC
+            end: None,
+        })
+        .unwrap();
+    assert_eq!(schedule.next, 1_704_067_200);
+    assert_eq!(schedule.status, PaymentScheduleStatus::Active);
+    assert_eq!(
+        node.wallets.schedule_list(&wallet_id).unwrap(),
+        vec![schedule.clone()]
+    );
+
+    node.wallets
+        .schedule_cancel(&wallet_id, schedule.id)
+        .unwrap();
+    let schedules = node.wallets.schedule_list(&wallet_id).unwrap();
+    assert_eq!(schedules[0].status, PaymentScheduleStatus::Cancelled);
+    assert!(node
+        .wallets
+        .schedule_cancel(&wallet_id, schedule.id)
+        .is_err());
+}
+
+#[test]
+fn payment_schedule_requires_wallet_account() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+
+    let result = node.wallets.schedule_create(NewPaymentSchedule {
+        wallet: wallet_id,
+        source: Account::from(42),
+        destination: Account::from(1000),
+        amount: Amount::raw(5),
+        cadence: "@daily".parse().unwrap(),
+        start: None,
+        end: None,
+    });
+
+    assert_eq!(result.unwrap_err().to_string(), "Account not found");
+    assert!(node.wallets.schedule_list(&wallet_id).unwrap().is_empty());
+}
+
+#[test]
+fn watch_only_activity_is_indexed() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    assert!(!node.wallets.is_watched(&DEV_GENESIS_ACCOUNT));
+
+    node.wallets
+        .insert_watch(&wallet_id, &[*DEV_GENESIS_ACCOUNT])
+        .unwrap();
+
+    assert!(node.wallets.is_watched(&DEV_GENESIS_ACCOUNT));
+    let activity = node.wallets.wallet_activity(&wallet_id, 0, 10).unwrap();
+    assert_eq!(activity.len(), 1);
+    assert_eq!(activity[0].hash, DEV_GENESIS_BLOCK.hash());
+    assert_eq!(activity[0].balance, Amount::MAX);
+    assert!(node
+        .wallets
+        .wallet_activity(&wallet_id, 1, 10)
+        .unwrap()
+        .is_empty());
+
+    node.wallets
+        .remove_key(&wallet_id, &DEV_GENESIS_PUB_KEY)
+        .unwrap();
+    assert!(!node.wallets.is_watched(&DEV_GENESIS_ACCOUNT));
+    assert!(node
+        .wallets
+        .wallet_activity(&wallet_id, 0, 10)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn balance_alert_is_raised_when_threshold_is_crossed() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    node.wallets
+        .insert_watch(&wallet_id, &[*DEV_GENESIS_ACCOUNT])
+        .unwrap();
+
+    let alerts = Arc::new(Mutex::new(Vec::new()));
+    let alerts_l = alerts.clone();
+    node.wallets.on_balance_alert(Box::new(move |alert| {
+        alerts_l.lock().unwrap().push(alert.clone());
+    }));
+
+    let threshold = Amount::raw(1000);
+    node.wallets
+        .balance_alert_set(&wallet_id, &DEV_GENESIS_ACCOUNT, threshold)
+        .unwrap();
+    assert_eq!(
+        node.wallets.balance_alerts(&wallet_id).unwrap(),
+        vec![(*DEV_GENESIS_ACCOUNT, threshold)]
+    );
+
+    node.wallets.watch_cemented(&DEV_GENESIS_BLOCK);
+
+    let alerts = alerts.lock().unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].wallet, wallet_id);
+    assert_eq!(alerts[0].direction, BalanceAlertDirection::Above);
+    assert_eq!(alerts[0].previous_balance, Amount::zero());
+    assert_eq!(alerts[0].balance, Amount::MAX);
+}
+
+#[test]
+fn balance_alert_requires_wallet_account() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+
+    assert!(node
+        .wallets
+        .balance_alert_set(&wallet_id, &Account::from(42), Amount::raw(1))
+        .is_err());
+    assert!(node
+        .wallets
+        .balance_alert_remove(&wallet_id, &Account::from(42))
+        .is_err());
+}
+
+#[test]
+fn sweep_skips_destination_and_watch_only_accounts() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let destination = node
+        .wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap();
+    let account = node
+        .wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap();
+    node.wallets
+        .insert_watch(&wallet_id, &[*DEV_GENESIS_ACCOUNT])
+        .unwrap();
+
+    assert!(node
+        .wallets
+        .wallet_sweep(
+            &wallet_id,
+            destination.into(),
+            Some(vec![*DEV_GENESIS_ACCOUNT]),
+            Amount::zero()
+        )
+        .is_err());
+
+    node.wallets
+        .wallet_sweep(&wallet_id, destination.into(), None, Amount::zero())
+        .unwrap();
+
+    assert_timely(Duration::from_secs(5), || {
+        node.wallets
+            .sweep_progress(&wallet_id)
+            .unwrap()
+            .unwrap()
+            .is_done()
+    });
+    let progress = node.wallets.sweep_progress(&wallet_id).unwrap().unwrap();
+    assert_eq!(progress.accounts.len(), 1);
+    assert_eq!(progress.accounts[0].account, account.into());
+    assert_eq!(progress.accounts[0].status, SweepStatus::Empty);
+}
+
+#[test]
+fn precache_skips_accounts_without_activity() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let account = node
+        .wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap();
+    node.wallets
+        .insert_watch(&wallet_id, &[*DEV_GENESIS_ACCOUNT])
+        .unwrap();
+
+    let status = node.wallets.work_cache_status(&wallet_id).unwrap();
+    assert_eq!(status.len(), 1);
+    assert_eq!(status[0].account, account.into());
+    assert_eq!(status[0].root, account.into());
+    assert_eq!(status[0].state, WorkCacheState::Missing);
+    assert_eq!(status[0].last_active, None);
+
+    node.wallets.precache_work();
+
+    assert_never(Duration::from_millis(500), || {
+        let status = &node.wallets.work_cache_status(&wallet_id).unwrap()[0];
+        status.regenerating || status.state != WorkCacheState::Missing
+    });
+}
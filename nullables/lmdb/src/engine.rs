@@ -0,0 +1,86 @@
+use crate::{Iter, LmdbDatabase};
+use lmdb::{DatabaseFlags, WriteFlags};
+
+/// A storage engine that can replace LMDB beneath [`crate::LmdbEnvironment`].
+/// Engines have to provide the LMDB semantics the stores rely on: readers see
+/// the data that was committed when their transaction started, there is only
+/// one writer at a time and keys of a database are sorted bytewise.
+pub trait StorageEngine: Send + Sync {
+    fn begin_ro_txn(&self) -> lmdb::Result<Box<dyn EngineReadTransaction>>;
+    fn begin_rw_txn(&self) -> lmdb::Result<Box<dyn EngineWriteTransaction>>;
+    fn create_db(&self, name: Option<&str>, flags: DatabaseFlags) -> lmdb::Result<LmdbDatabase>;
+    fn open_db(&self, name: Option<&str>) -> lmdb::Result<LmdbDatabase>;
+    fn stat(&self) -> lmdb::Result<EnvironmentStat>;
+    /// Id of the last committed write transaction
+    fn last_txn_id(&self) -> u64;
+    fn vendor(&self) -> String;
+
+    /// Data of this engine is lost when the environment is dropped
+    fn is_in_memory(&self) -> bool;
+}
+
+pub trait EngineReadTransaction: Send {
+    fn get(&self, database: LmdbDatabase, key: &[u8]) -> lmdb::Result<&[u8]>;
+    fn open_ro_cursor(&self, database: LmdbDatabase) -> lmdb::Result<Box<dyn EngineCursor + '_>>;
+    fn count(&self, database: LmdbDatabase) -> u64;
+    fn reset(self: Box<Self>) -> Box<dyn EngineInactiveTransaction>;
+}
+
+pub trait EngineInactiveTransaction: Send {
+    fn renew(self: Box<Self>) -> lmdb::Result<Box<dyn EngineReadTransaction>>;
+}
+
+pub trait EngineWriteTransaction: Send {
+    fn get(&self, database: LmdbDatabase, key: &[u8]) -> lmdb::Result<&[u8]>;
+    fn put(
+        &mut self,
+        database: LmdbDatabase,
+        key: &[u8],
+        data: &[u8],
+        flags: WriteFlags,
+    ) -> lmdb::Result<()>;
+    fn del(&mut self, database: LmdbDatabase, key: &[u8]) -> lmdb::Result<()>;
+    fn create_db(&self, name: Option<&str>, flags: DatabaseFlags) -> lmdb::Result<LmdbDatabase>;
+    fn clear_db(&mut self, database: LmdbDatabase) -> lmdb::Result<()>;
+    fn open_ro_cursor(&self, database: LmdbDatabase) -> lmdb::Result<Box<dyn EngineCursor + '_>>;
+    fn count(&self, database: LmdbDatabase) -> u64;
+    fn commit(self: Box<Self>) -> lmdb::Result<()>;
+}
+
+/// A cursor with the semantics of the LMDB cursor operations `MDB_FIRST`,
+/// `MDB_LAST`, `MDB_NEXT`, `MDB_PREV`, `MDB_SET_RANGE` and `MDB_GET_CURRENT`.
+/// Like with LMDB, the returned data has to stay valid until the transaction
+/// that opened the cursor ends, not only while the cursor exists.
+pub trait EngineCursor {
+    fn get(&self, key: Option<&[u8]>, op: u32) -> lmdb::Result<(Option<&[u8]>, &[u8])>;
+    fn iter_start(&mut self) -> Iter<'_>;
+}
+
+/// Iterates over the entries of a database in ascending key order
+pub trait EngineIterator {
+    fn next_entry(&mut self) -> Option<(&[u8], &[u8])>;
+}
+
+/// Statistics of the whole environment
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct EnvironmentStat {
+    pub page_size: u32,
+    pub depth: u32,
+    pub branch_pages: usize,
+    pub leaf_pages: usize,
+    pub overflow_pages: usize,
+    pub entries: usize,
+}
+
+impl From<lmdb::Stat> for EnvironmentStat {
+    fn from(value: lmdb::Stat) -> Self {
+        Self {
+            page_size: value.page_size(),
+            depth: value.depth(),
+            branch_pages: value.branch_pages(),
+            leaf_pages: value.leaf_pages(),
+            overflow_pages: value.overflow_pages(),
+            entries: value.entries(),
+        }
+    }
+}
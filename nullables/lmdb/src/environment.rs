@@ -1,8 +1,8 @@
-use crate::ConfiguredDatabaseBuilder;
+use crate::{ConfiguredDatabaseBuilder, EnvironmentStat, InMemoryEngine, StorageEngine};
 
 use super::{ConfiguredDatabase, LmdbDatabase, RoTransaction, RwTransaction};
-use lmdb::{DatabaseFlags, EnvironmentFlags, Stat};
-use lmdb_sys::MDB_env;
+use lmdb::{DatabaseFlags, EnvironmentFlags};
+use lmdb_sys::{MDB_env, MDB_envinfo, MDB_SUCCESS};
 use std::path::Path;
 
 pub struct EnvironmentOptions<'a> {
@@ -36,6 +36,40 @@ impl LmdbEnvironment {
         Self(EnvironmentStrategy::Nulled(EnvironmentStub { databases }))
     }
 
+    /// Creates an environment that stores its data in the given engine instead of LMDB
+    pub fn new_with_engine(engine: impl StorageEngine + 'static) -> Self {
+        Self(EnvironmentStrategy::Engine(Box::new(engine)))
+    }
+
+    /// Creates a writable environment that keeps all data in memory
+    pub fn new_in_memory() -> Self {
+        Self::new_with_engine(InMemoryEngine::new())
+    }
+
+    pub fn is_in_memory(&self) -> bool {
+        match &self.0 {
+            EnvironmentStrategy::Engine(e) => e.is_in_memory(),
+            EnvironmentStrategy::Real(_) | EnvironmentStrategy::Nulled(_) => false,
+        }
+    }
+
+    pub fn vendor(&self) -> String {
+        match &self.0 {
+            EnvironmentStrategy::Engine(e) => e.vendor(),
+            // fake version! TODO: read version
+            EnvironmentStrategy::Real(_) | EnvironmentStrategy::Nulled(_) => {
+                format!("lmdb-rkv {}.{}.{}", 0, 14, 0)
+            }
+        }
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        match &self.0 {
+            EnvironmentStrategy::Real(s) => s.read_only,
+            EnvironmentStrategy::Nulled(_) | EnvironmentStrategy::Engine(_) => false,
+        }
+    }
+
     pub fn null_builder() -> EnvironmentStubBuilder {
         EnvironmentStubBuilder::default()
     }
@@ -44,6 +78,7 @@ impl LmdbEnvironment {
         match &self.0 {
             EnvironmentStrategy::Real(s) => s.begin_ro_txn(),
             EnvironmentStrategy::Nulled(s) => s.begin_ro_txn(),
+            EnvironmentStrategy::Engine(e) => e.begin_ro_txn().map(RoTransaction::new_with_engine),
         }
     }
 
@@ -51,6 +86,7 @@ impl LmdbEnvironment {
         match &self.0 {
             EnvironmentStrategy::Real(s) => s.begin_rw_txn(),
             EnvironmentStrategy::Nulled(s) => s.begin_rw_txn(),
+            EnvironmentStrategy::Engine(e) => e.begin_rw_txn().map(RwTransaction::new_with_engine),
         }
     }
 
@@ -62,13 +98,17 @@ impl LmdbEnvironment {
         match &self.0 {
             EnvironmentStrategy::Real(s) => s.create_db(name, flags),
             EnvironmentStrategy::Nulled(s) => s.create_db(name, flags),
+            EnvironmentStrategy::Engine(e) => e.create_db(name, flags),
         }
     }
 
-    pub fn env(&self) -> *mut MDB_env {
+    /// The raw LMDB environment. Fails if the data isn't stored in LMDB.
+    pub fn env(&self) -> lmdb::Result<*mut MDB_env> {
         match &self.0 {
-            EnvironmentStrategy::Real(s) => s.env(),
-            EnvironmentStrategy::Nulled(_) => unimplemented!(),
+            EnvironmentStrategy::Real(s) => Ok(s.env()),
+            EnvironmentStrategy::Nulled(_) | EnvironmentStrategy::Engine(_) => {
+                Err(lmdb::Error::Invalid)
+            }
         }
     }
 
@@ -76,6 +116,7 @@ impl LmdbEnvironment {
         match &self.0 {
             EnvironmentStrategy::Real(s) => s.open_db(name),
             EnvironmentStrategy::Nulled(s) => s.open_db(name),
+            EnvironmentStrategy::Engine(e) => e.open_db(name),
         }
     }
 
@@ -86,10 +127,21 @@ impl LmdbEnvironment {
         Ok(())
     }
 
-    pub fn stat(&self) -> lmdb::Result<Stat> {
+    /// Id of the last committed write transaction, including the ones
+    /// committed by other processes
+    pub fn last_txn_id(&self) -> lmdb::Result<u64> {
         match &self.0 {
-            EnvironmentStrategy::Real(s) => s.stat(),
+            EnvironmentStrategy::Real(s) => s.last_txn_id(),
+            EnvironmentStrategy::Nulled(_) => Ok(0),
+            EnvironmentStrategy::Engine(e) => Ok(e.last_txn_id()),
+        }
+    }
+
+    pub fn stat(&self) -> lmdb::Result<EnvironmentStat> {
+        match &self.0 {
+            EnvironmentStrategy::Real(s) => s.stat().map(EnvironmentStat::from),
             EnvironmentStrategy::Nulled(s) => s.stat(),
+            EnvironmentStrategy::Engine(e) => e.stat(),
         }
     }
 }
@@ -97,13 +149,20 @@ impl LmdbEnvironment {
 enum EnvironmentStrategy {
     Nulled(EnvironmentStub),
     Real(EnvironmentWrapper),
+    Engine(Box<dyn StorageEngine>),
 }
 
-struct EnvironmentWrapper(lmdb::Environment);
+struct EnvironmentWrapper {
+    env: lmdb::Environment,
+    read_only: bool,
+}
 
 impl EnvironmentWrapper {
     fn new(env: lmdb::Environment) -> Self {
-        Self(env)
+        Self {
+            env,
+            read_only: false,
+        }
     }
 
     fn build(options: EnvironmentOptions) -> lmdb::Result<Self> {
@@ -112,11 +171,14 @@ impl EnvironmentWrapper {
             .set_map_size(options.map_size)
             .set_flags(options.flags)
             .open_with_permissions(options.path, options.file_mode.try_into().unwrap())?;
-        Ok(Self(env))
+        Ok(Self {
+            env,
+            read_only: options.flags.contains(EnvironmentFlags::READ_ONLY),
+        })
     }
 
     fn begin_ro_txn(&self) -> lmdb::Result<RoTransaction> {
-        self.0.begin_ro_txn().map(|txn| {
+        self.env.begin_ro_txn().map(|txn| {
             // todo: don't use static life time
             let txn = unsafe {
                 std::mem::transmute::<lmdb::RoTransaction<'_>, lmdb::RoTransaction<'static>>(txn)
@@ -126,7 +188,7 @@ impl EnvironmentWrapper {
     }
 
     fn begin_rw_txn(&self) -> lmdb::Result<RwTransaction> {
-        self.0.begin_rw_txn().map(|txn| {
+        self.env.begin_rw_txn().map(|txn| {
             // todo: don't use static life time
             let txn = unsafe {
                 std::mem::transmute::<lmdb::RwTransaction<'_>, lmdb::RwTransaction<'static>>(txn)
@@ -136,23 +198,37 @@ impl EnvironmentWrapper {
     }
 
     fn create_db(&self, name: Option<&str>, flags: DatabaseFlags) -> lmdb::Result<LmdbDatabase> {
-        self.0.create_db(name, flags).map(LmdbDatabase::new)
+        if self.read_only {
+            // Databases can't be created without a write transaction, so
+            // they have to exist already
+            return self.open_db(name);
+        }
+        self.env.create_db(name, flags).map(LmdbDatabase::new)
     }
 
     fn env(&self) -> *mut MDB_env {
-        self.0.env()
+        self.env.env()
     }
 
     fn open_db(&self, name: Option<&str>) -> lmdb::Result<LmdbDatabase> {
-        self.0.open_db(name).map(LmdbDatabase::new)
+        self.env.open_db(name).map(LmdbDatabase::new)
     }
 
     fn sync(&self, force: bool) -> lmdb::Result<()> {
-        self.0.sync(force)
+        self.env.sync(force)
     }
 
-    fn stat(&self) -> lmdb::Result<Stat> {
-        self.0.stat()
+    fn stat(&self) -> lmdb::Result<lmdb::Stat> {
+        self.env.stat()
+    }
+
+    fn last_txn_id(&self) -> lmdb::Result<u64> {
+        let mut info = unsafe { std::mem::zeroed::<MDB_envinfo>() };
+        let status = unsafe { lmdb_sys::mdb_env_info(self.env.env(), &mut info) };
+        if status != MDB_SUCCESS {
+            return Err(lmdb::Error::from_err_code(status));
+        }
+        Ok(info.me_last_txnid as u64)
     }
 }
 
@@ -184,8 +260,11 @@ impl EnvironmentStub {
         self.create_db(name, DatabaseFlags::empty())
     }
 
-    fn stat(&self) -> lmdb::Result<Stat> {
-        todo!()
+    fn stat(&self) -> lmdb::Result<EnvironmentStat> {
+        Ok(EnvironmentStat {
+            entries: self.databases.iter().map(|db| db.entries.len()).sum(),
+            ..Default::default()
+        })
     }
 }
 
@@ -0,0 +1,731 @@
+use crate::{
+    EngineCursor, EngineInactiveTransaction, EngineIterator, EngineReadTransaction,
+    EngineWriteTransaction, EnvironmentStat, Iter, LmdbDatabase, StorageEngine,
+};
+use lmdb::{DatabaseFlags, WriteFlags};
+use lmdb_sys::{MDB_FIRST, MDB_GET_CURRENT, MDB_LAST, MDB_NEXT, MDB_PREV, MDB_SET_RANGE};
+use std::{
+    cell::Cell,
+    collections::{btree_map, BTreeMap, HashMap},
+    ops::Bound,
+    sync::{Arc, Condvar, Mutex},
+};
+
+type Table = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// A pure in-memory storage engine for tests and ephemeral nodes
+pub struct InMemoryEngine {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    writer_released: Condvar,
+}
+
+struct State {
+    names: Vec<String>,
+    committed: Arc<Vec<TableVersion>>,
+    last_txn_id: u64,
+    writer_active: bool,
+}
+
+impl InMemoryEngine {
+    pub fn new() -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                state: Mutex::new(State {
+                    names: Vec::new(),
+                    committed: Arc::new(Vec::new()),
+                    last_txn_id: 0,
+                    writer_active: false,
+                }),
+                writer_released: Condvar::new(),
+            }),
+        }
+    }
+}
+
+impl Default for InMemoryEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StorageEngine for InMemoryEngine {
+    fn begin_ro_txn(&self) -> lmdb::Result<Box<dyn EngineReadTransaction>> {
+        Ok(Box::new(InMemoryRoTransaction::new(self.shared.clone())))
+    }
+
+    fn begin_rw_txn(&self) -> lmdb::Result<Box<dyn EngineWriteTransaction>> {
+        let mut state = self.shared.state.lock().unwrap();
+        while state.writer_active {
+            state = self.shared.writer_released.wait(state).unwrap();
+        }
+        state.writer_active = true;
+        Ok(Box::new(InMemoryRwTransaction {
+            shared: self.shared.clone(),
+            snapshot: state.committed.clone(),
+            changes: HashMap::new(),
+            active: true,
+        }))
+    }
+
+    fn create_db(&self, name: Option<&str>, _flags: DatabaseFlags) -> lmdb::Result<LmdbDatabase> {
+        Ok(self.shared.create_db(name))
+    }
+
+    fn open_db(&self, name: Option<&str>) -> lmdb::Result<LmdbDatabase> {
+        let state = self.shared.state.lock().unwrap();
+        let name = name.unwrap_or_default();
+        state
+            .names
+            .iter()
+            .position(|n| n == name)
+            .map(|i| LmdbDatabase::new_null(i as u32))
+            .ok_or(lmdb::Error::NotFound)
+    }
+
+    fn stat(&self) -> lmdb::Result<EnvironmentStat> {
+        let state = self.shared.state.lock().unwrap();
+        Ok(EnvironmentStat {
+            entries: state.committed.iter().map(|t| t.len as usize).sum(),
+            ..Default::default()
+        })
+    }
+
+    fn last_txn_id(&self) -> u64 {
+        self.shared.state.lock().unwrap().last_txn_id
+    }
+
+    fn vendor(&self) -> String {
+        "in-memory".to_owned()
+    }
+
+    fn is_in_memory(&self) -> bool {
+        true
+    }
+}
+
+impl Shared {
+    fn create_db(&self, name: Option<&str>) -> LmdbDatabase {
+        let mut state = self.state.lock().unwrap();
+        let name = name.unwrap_or_default();
+        let index = match state.names.iter().position(|n| n == name) {
+            Some(i) => i,
+            None => {
+                state.names.push(name.to_owned());
+                Arc::make_mut(&mut state.committed).push(TableVersion::default());
+                state.names.len() - 1
+            }
+        };
+        LmdbDatabase::new_null(index as u32)
+    }
+}
+
+/// Changes of a write transaction to one table. Deleted keys map to `None`.
+#[derive(Clone, Default)]
+struct Delta {
+    /// All entries below this delta were deleted
+    cleared: bool,
+    entries: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl Delta {
+    fn merge(mut self, newer: Delta) -> Delta {
+        if newer.cleared {
+            return newer;
+        }
+        self.entries.extend(newer.entries);
+        self
+    }
+}
+
+/// The committed content of a table. Readers may still use an older version,
+/// so commits don't modify the base table in place. The changes are stacked on
+/// top of it as deltas which get merged like in an LSM tree: a delta is merged
+/// into the one below when they have a similar size and into the base when it
+/// has grown to half of the base. That keeps the cost of a commit proportional
+/// to the data it changed and the number of deltas logarithmic.
+#[derive(Clone, Default)]
+struct TableVersion {
+    base: Arc<Table>,
+    /// Newest last
+    deltas: Vec<Arc<Delta>>,
+    len: u64,
+}
+
+impl TableVersion {
+    fn apply(&mut self, mut delta: Delta, len: u64) {
+        self.len = len;
+        while let Some(older) = self.deltas.last() {
+            if !delta.cleared && older.entries.len() > delta.entries.len() * 2 {
+                break;
+            }
+            let older = self.deltas.pop().unwrap();
+            delta = Arc::unwrap_or_clone(older).merge(delta);
+        }
+
+        if !self.deltas.is_empty() || (!delta.cleared && delta.entries.len() * 2 < self.base.len())
+        {
+            self.deltas.push(Arc::new(delta));
+            return;
+        }
+
+        if delta.cleared {
+            self.base = Default::default();
+        }
+        let base = Arc::make_mut(&mut self.base);
+        for (key, value) in delta.entries {
+            match value {
+                Some(value) => base.insert(key, value),
+                None => base.remove(&key),
+            };
+        }
+    }
+}
+
+/// A table as seen by a transaction: the uncommitted changes of a write
+/// transaction on top of the committed version of the table
+#[derive(Clone, Copy)]
+struct TableView<'a> {
+    top: Option<&'a Delta>,
+    version: Option<&'a TableVersion>,
+}
+
+impl<'a> TableView<'a> {
+    /// Newest first
+    fn deltas(self) -> impl Iterator<Item = &'a Delta> {
+        let committed = self
+            .version
+            .into_iter()
+            .flat_map(|v| v.deltas.iter().rev().map(|d| d.as_ref()));
+        self.top.into_iter().chain(committed)
+    }
+
+    fn get(self, key: &[u8]) -> Option<&'a [u8]> {
+        for delta in self.deltas() {
+            if let Some(value) = delta.entries.get(key) {
+                return value.as_deref();
+            }
+            if delta.cleared {
+                return None;
+            }
+        }
+        self.version?.base.get(key).map(|v| v.as_slice())
+    }
+
+    /// Finds the nearest entry within `bound` in ascending or descending key order
+    fn seek<'k>(self, mut bound: Bound<&'k [u8]>, ascending: bool) -> Option<(&'a [u8], &'a [u8])>
+    where
+        'a: 'k,
+    {
+        loop {
+            let range = if ascending {
+                (bound, Bound::Unbounded)
+            } else {
+                (Bound::Unbounded, bound)
+            };
+            let mut nearest = None;
+            let mut cleared = false;
+            for delta in self.deltas() {
+                let key = edge_key(delta.entries.range::<[u8], _>(range), ascending);
+                nearest = closer(nearest, key, ascending);
+                if delta.cleared {
+                    cleared = true;
+                    break;
+                }
+            }
+            if let (false, Some(version)) = (cleared, self.version) {
+                let key = edge_key(version.base.range::<[u8], _>(range), ascending);
+                nearest = closer(nearest, key, ascending);
+            }
+
+            let key = nearest?;
+            match self.get(key) {
+                Some(value) => return Some((key, value)),
+                // deleted in a newer delta
+                None => bound = Bound::Excluded(key),
+            }
+        }
+    }
+
+    fn iter(self) -> Iter<'a> {
+        match self.version {
+            Some(version) if self.top.is_none() && version.deltas.is_empty() => {
+                Iter::Stub(version.base.iter())
+            }
+            _ => Iter::Engine(Box::new(TableIter {
+                view: self,
+                last: None,
+            })),
+        }
+    }
+}
+
+fn edge_key<'a, V: 'a>(
+    mut range: btree_map::Range<'a, Vec<u8>, V>,
+    ascending: bool,
+) -> Option<&'a [u8]> {
+    let entry = if ascending {
+        range.next()
+    } else {
+        range.next_back()
+    };
+    entry.map(|(k, _)| k.as_slice())
+}
+
+fn closer<'a>(a: Option<&'a [u8]>, b: Option<&'a [u8]>, ascending: bool) -> Option<&'a [u8]> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if (a < b) == ascending { a } else { b }),
+        (a, b) => a.or(b),
+    }
+}
+
+struct TableIter<'a> {
+    view: TableView<'a>,
+    last: Option<&'a [u8]>,
+}
+
+impl EngineIterator for TableIter<'_> {
+    fn next_entry(&mut self) -> Option<(&[u8], &[u8])> {
+        let bound = self.last.map_or(Bound::Unbounded, Bound::Excluded);
+        let (key, value) = self.view.seek(bound, true)?;
+        self.last = Some(key);
+        Some((key, value))
+    }
+}
+
+struct InMemoryRoTransaction {
+    shared: Arc<Shared>,
+    snapshot: Arc<Vec<TableVersion>>,
+}
+
+impl InMemoryRoTransaction {
+    fn new(shared: Arc<Shared>) -> Self {
+        let snapshot = shared.state.lock().unwrap().committed.clone();
+        Self { shared, snapshot }
+    }
+
+    fn view(&self, database: LmdbDatabase) -> TableView<'_> {
+        TableView {
+            top: None,
+            version: self.snapshot.get(database.as_nulled() as usize),
+        }
+    }
+}
+
+impl EngineReadTransaction for InMemoryRoTransaction {
+    fn get(&self, database: LmdbDatabase, key: &[u8]) -> lmdb::Result<&[u8]> {
+        self.view(database).get(key).ok_or(lmdb::Error::NotFound)
+    }
+
+    fn open_ro_cursor(&self, database: LmdbDatabase) -> lmdb::Result<Box<dyn EngineCursor + '_>> {
+        Ok(Box::new(InMemoryCursor::new(self.view(database))))
+    }
+
+    fn count(&self, database: LmdbDatabase) -> u64 {
+        self.view(database).version.map_or(0, |t| t.len)
+    }
+
+    fn reset(self: Box<Self>) -> Box<dyn EngineInactiveTransaction> {
+        Box::new(InMemoryInactiveTransaction {
+            shared: self.shared,
+        })
+    }
+}
+
+struct InMemoryInactiveTransaction {
+    shared: Arc<Shared>,
+}
+
+impl EngineInactiveTransaction for InMemoryInactiveTransaction {
+    fn renew(self: Box<Self>) -> lmdb::Result<Box<dyn EngineReadTransaction>> {
+        Ok(Box::new(InMemoryRoTransaction::new(self.shared)))
+    }
+}
+
+struct TableChanges {
+    delta: Delta,
+    len: u64,
+}
+
+struct InMemoryRwTransaction {
+    shared: Arc<Shared>,
+    snapshot: Arc<Vec<TableVersion>>,
+    changes: HashMap<usize, TableChanges>,
+    active: bool,
+}
+
+impl InMemoryRwTransaction {
+    fn view(&self, database: LmdbDatabase) -> TableView<'_> {
+        let index = database.as_nulled() as usize;
+        TableView {
+            top: self.changes.get(&index).map(|c| &c.delta),
+            version: self.snapshot.get(index),
+        }
+    }
+
+    fn changes_mut(&mut self, database: LmdbDatabase) -> &mut TableChanges {
+        let index = database.as_nulled() as usize;
+        let len = self.snapshot.get(index).map_or(0, |t| t.len);
+        self.changes.entry(index).or_insert_with(|| TableChanges {
+            delta: Delta::default(),
+            len,
+        })
+    }
+
+    fn release(&mut self, commit: bool) {
+        if !self.active {
+            return;
+        }
+        self.active = false;
+        // Without the snapshot of this transaction the committed tables can
+        // be updated in place if no reader uses them
+        drop(std::mem::take(&mut self.snapshot));
+        let mut state = self.shared.state.lock().unwrap();
+        if commit {
+            let mut tables = Arc::unwrap_or_clone(std::mem::take(&mut state.committed));
+            for (index, changes) in self.changes.drain() {
+                if let Some(table) = tables.get_mut(index) {
+                    table.apply(changes.delta, changes.len);
+                }
+            }
+            state.committed = Arc::new(tables);
+            state.last_txn_id += 1;
+        }
+        state.writer_active = false;
+        self.shared.writer_released.notify_one();
+    }
+}
+
+impl EngineWriteTransaction for InMemoryRwTransaction {
+    fn get(&self, database: LmdbDatabase, key: &[u8]) -> lmdb::Result<&[u8]> {
+        self.view(database).get(key).ok_or(lmdb::Error::NotFound)
+    }
+
+    fn put(
+        &mut self,
+        database: LmdbDatabase,
+        key: &[u8],
+        data: &[u8],
+        flags: WriteFlags,
+    ) -> lmdb::Result<()> {
+        let exists = self.view(database).get(key).is_some();
+        if exists && flags.contains(WriteFlags::NO_OVERWRITE) {
+            return Err(lmdb::Error::KeyExist);
+        }
+        let changes = self.changes_mut(database);
+        changes
+            .delta
+            .entries
+            .insert(key.to_vec(), Some(data.to_vec()));
+        if !exists {
+            changes.len += 1;
+        }
+        Ok(())
+    }
+
+    fn del(&mut self, database: LmdbDatabase, key: &[u8]) -> lmdb::Result<()> {
+        if self.view(database).get(key).is_none() {
+            return Err(lmdb::Error::NotFound);
+        }
+        let changes = self.changes_mut(database);
+        changes.delta.entries.insert(key.to_vec(), None);
+        changes.len -= 1;
+        Ok(())
+    }
+
+    fn create_db(&self, name: Option<&str>, _flags: DatabaseFlags) -> lmdb::Result<LmdbDatabase> {
+        Ok(self.shared.create_db(name))
+    }
+
+    fn clear_db(&mut self, database: LmdbDatabase) -> lmdb::Result<()> {
+        let changes = self.changes_mut(database);
+        changes.delta = Delta {
+            cleared: true,
+            entries: BTreeMap::new(),
+        };
+        changes.len = 0;
+        Ok(())
+    }
+
+    fn open_ro_cursor(&self, database: LmdbDatabase) -> lmdb::Result<Box<dyn EngineCursor + '_>> {
+        Ok(Box::new(InMemoryCursor::new(self.view(database))))
+    }
+
+    fn count(&self, database: LmdbDatabase) -> u64 {
+        let index = database.as_nulled() as usize;
+        match self.changes.get(&index) {
+            Some(changes) => changes.len,
+            None => self.snapshot.get(index).map_or(0, |t| t.len),
+        }
+    }
+
+    fn commit(mut self: Box<Self>) -> lmdb::Result<()> {
+        self.release(true);
+        Ok(())
+    }
+}
+
+impl Drop for InMemoryRwTransaction {
+    fn drop(&mut self) {
+        self.release(false);
+    }
+}
+
+struct InMemoryCursor<'txn> {
+    view: TableView<'txn>,
+    current: Cell<Option<&'txn [u8]>>,
+}
+
+impl<'txn> InMemoryCursor<'txn> {
+    fn new(view: TableView<'txn>) -> Self {
+        Self {
+            view,
+            current: Cell::new(None),
+        }
+    }
+}
+
+impl EngineCursor for InMemoryCursor<'_> {
+    fn get(&self, key: Option<&[u8]>, op: u32) -> lmdb::Result<(Option<&[u8]>, &[u8])> {
+        let current = self.current.get();
+        let entry = if op == MDB_FIRST {
+            self.view.seek(Bound::Unbounded, true)
+        } else if op == MDB_LAST {
+            self.view.seek(Bound::Unbounded, false)
+        } else if op == MDB_NEXT {
+            self.view
+                .seek(current.map_or(Bound::Unbounded, Bound::Excluded), true)
+        } else if op == MDB_PREV {
+            self.view
+                .seek(current.map_or(Bound::Unbounded, Bound::Excluded), false)
+        } else if op == MDB_SET_RANGE {
+            let key = key.ok_or(lmdb::Error::Invalid)?;
+            self.view.seek(Bound::Included(key), true)
+        } else if op == MDB_GET_CURRENT {
+            current.and_then(|k| self.view.get(k).map(|v| (k, v)))
+        } else {
+            return Err(lmdb::Error::Invalid);
+        };
+
+        match entry {
+            Some((k, v)) => {
+                self.current.set(Some(k));
+                Ok((Some(k), v))
+            }
+            None => Err(lmdb::Error::NotFound),
+        }
+    }
+
+    fn iter_start(&mut self) -> Iter<'_> {
+        self.view.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::LmdbEnvironment;
+    use lmdb::{DatabaseFlags, WriteFlags};
+    use lmdb_sys::{MDB_FIRST, MDB_GET_BOTH, MDB_LAST, MDB_NEXT, MDB_PREV, MDB_SET_RANGE};
+
+    #[test]
+    fn write_and_read_back() {
+        let env = LmdbEnvironment::new_in_memory();
+        let db = env.create_db(Some("foo"), DatabaseFlags::empty()).unwrap();
+
+        let mut txn = env.begin_rw_txn().unwrap();
+        txn.put(db, &[1], &[2], WriteFlags::empty()).unwrap();
+        assert_eq!(txn.get(db, &[1]), Ok([2u8].as_slice()));
+        assert_eq!(txn.count(db), 1);
+        txn.commit().unwrap();
+
+        let txn = env.begin_ro_txn().unwrap();
+        assert_eq!(txn.get(db, &[1]), Ok([2u8].as_slice()));
+        assert_eq!(txn.get(db, &[3]), Err(lmdb::Error::NotFound));
+    }
+
+    #[test]
+    fn databases_are_separated() {
+        let env = LmdbEnvironment::new_in_memory();
+        let foo = env.create_db(Some("foo"), DatabaseFlags::empty()).unwrap();
+        let bar = env.create_db(Some("bar"), DatabaseFlags::empty()).unwrap();
+        assert_eq!(env.open_db(Some("foo")), Ok(foo));
+
+        let mut txn = env.begin_rw_txn().unwrap();
+        txn.put(foo, &[1], &[2], WriteFlags::empty()).unwrap();
+
+        assert_eq!(txn.get(bar, &[1]), Err(lmdb::Error::NotFound));
+        assert_eq!(txn.count(bar), 0);
+    }
+
+    #[test]
+    fn readers_see_the_snapshot_of_their_start() {
+        let env = LmdbEnvironment::new_in_memory();
+        let db = env.create_db(Some("foo"), DatabaseFlags::empty()).unwrap();
+        let reader = env.begin_ro_txn().unwrap();
+
+        let mut txn = env.begin_rw_txn().unwrap();
+        txn.put(db, &[1], &[2], WriteFlags::empty()).unwrap();
+        txn.commit().unwrap();
+
+        assert_eq!(reader.get(db, &[1]), Err(lmdb::Error::NotFound));
+        let reader = reader.reset().renew().unwrap();
+        assert_eq!(reader.get(db, &[1]), Ok([2u8].as_slice()));
+    }
+
+    #[test]
+    fn dropped_write_transaction_is_discarded() {
+        let env = LmdbEnvironment::new_in_memory();
+        let db = env.create_db(Some("foo"), DatabaseFlags::empty()).unwrap();
+
+        let mut txn = env.begin_rw_txn().unwrap();
+        txn.put(db, &[1], &[2], WriteFlags::empty()).unwrap();
+        drop(txn);
+
+        let txn = env.begin_rw_txn().unwrap();
+        assert_eq!(txn.get(db, &[1]), Err(lmdb::Error::NotFound));
+    }
+
+    #[test]
+    fn delete_and_clear() {
+        let env = LmdbEnvironment::new_in_memory();
+        let db = env.create_db(Some("foo"), DatabaseFlags::empty()).unwrap();
+        let mut txn = env.begin_rw_txn().unwrap();
+        txn.put(db, &[1], &[1], WriteFlags::empty()).unwrap();
+        txn.put(db, &[2], &[2], WriteFlags::empty()).unwrap();
+        txn.put(db, &[3], &[3], WriteFlags::empty()).unwrap();
+
+        txn.del(db, &[1], None).unwrap();
+        assert_eq!(txn.del(db, &[1], None), Err(lmdb::Error::NotFound));
+        assert_eq!(txn.count(db), 2);
+
+        txn.clear_db(db).unwrap();
+        assert_eq!(txn.count(db), 0);
+    }
+
+    #[test]
+    fn cursor() {
+        let env = LmdbEnvironment::new_in_memory();
+        let db = env.create_db(Some("foo"), DatabaseFlags::empty()).unwrap();
+        let mut txn = env.begin_rw_txn().unwrap();
+        txn.put(db, &[1, 1], &[6], WriteFlags::empty()).unwrap();
+        txn.put(db, &[2, 2], &[7], WriteFlags::empty()).unwrap();
+        txn.put(db, &[3, 3], &[8], WriteFlags::empty()).unwrap();
+        txn.commit().unwrap();
+
+        let txn = env.begin_ro_txn().unwrap();
+        let cursor = txn.open_ro_cursor(db).unwrap();
+        assert_eq!(
+            cursor.get(None, None, MDB_FIRST),
+            Ok((Some([1u8, 1].as_slice()), [6u8].as_slice()))
+        );
+        assert_eq!(
+            cursor.get(None, None, MDB_NEXT),
+            Ok((Some([2u8, 2].as_slice()), [7u8].as_slice()))
+        );
+        assert_eq!(
+            cursor.get(Some(&[2, 3]), None, MDB_SET_RANGE),
+            Ok((Some([3u8, 3].as_slice()), [8u8].as_slice()))
+        );
+        assert_eq!(cursor.get(None, None, MDB_NEXT), Err(lmdb::Error::NotFound));
+        assert_eq!(
+            cursor.get(None, None, MDB_LAST),
+            Ok((Some([3u8, 3].as_slice()), [8u8].as_slice()))
+        );
+        assert_eq!(
+            cursor.get(None, None, MDB_PREV),
+            Ok((Some([2u8, 2].as_slice()), [7u8].as_slice()))
+        );
+        assert_eq!(
+            cursor.get(None, None, MDB_GET_BOTH),
+            Err(lmdb::Error::Invalid)
+        );
+
+        let mut cursor = txn.open_ro_cursor(db).unwrap();
+        assert_eq!(cursor.iter_start().count(), 3);
+    }
+
+    #[test]
+    fn cursor_skips_keys_deleted_in_newer_commits() {
+        let env = LmdbEnvironment::new_in_memory();
+        let db = env.create_db(Some("foo"), DatabaseFlags::empty()).unwrap();
+        let mut txn = env.begin_rw_txn().unwrap();
+        for i in 0..100u8 {
+            txn.put(db, &[i], &[i], WriteFlags::empty()).unwrap();
+        }
+        txn.commit().unwrap();
+        let old_reader = env.begin_ro_txn().unwrap();
+
+        // small commits are stacked on top of the big table
+        for i in (0..100u8).step_by(2) {
+            let mut txn = env.begin_rw_txn().unwrap();
+            txn.del(db, &[i], None).unwrap();
+            txn.commit().unwrap();
+        }
+
+        let txn = env.begin_ro_txn().unwrap();
+        assert_eq!(txn.count(db), 50);
+        let cursor = txn.open_ro_cursor(db).unwrap();
+        assert_eq!(
+            cursor.get(Some(&[10]), None, MDB_SET_RANGE),
+            Ok((Some([11u8].as_slice()), [11u8].as_slice()))
+        );
+        assert_eq!(
+            cursor.get(None, None, MDB_PREV),
+            Ok((Some([9u8].as_slice()), [9u8].as_slice()))
+        );
+        let mut cursor = txn.open_ro_cursor(db).unwrap();
+        let keys: Vec<_> = cursor.iter_start().map(|i| i.unwrap().0[0]).collect();
+        assert_eq!(keys, (1..100).step_by(2).collect::<Vec<_>>());
+
+        assert_eq!(old_reader.count(db), 100);
+        assert_eq!(old_reader.get(db, &[10]), Ok([10u8].as_slice()));
+    }
+
+    #[test]
+    fn clear_hides_older_commits() {
+        let env = LmdbEnvironment::new_in_memory();
+        let db = env.create_db(Some("foo"), DatabaseFlags::empty()).unwrap();
+        for i in 0..10u8 {
+            let mut txn = env.begin_rw_txn().unwrap();
+            txn.put(db, &[i], &[i], WriteFlags::empty()).unwrap();
+            txn.commit().unwrap();
+        }
+
+        let mut txn = env.begin_rw_txn().unwrap();
+        txn.clear_db(db).unwrap();
+        txn.put(db, &[5], &[1], WriteFlags::empty()).unwrap();
+        txn.commit().unwrap();
+
+        let txn = env.begin_ro_txn().unwrap();
+        let mut cursor = txn.open_ro_cursor(db).unwrap();
+        let entries: Vec<_> = cursor.iter_start().map(|i| i.unwrap()).collect();
+        assert_eq!(entries, vec![([5u8].as_slice(), [1u8].as_slice())]);
+    }
+
+    #[test]
+    fn stat() {
+        let env = LmdbEnvironment::new_in_memory();
+        let foo = env.create_db(Some("foo"), DatabaseFlags::empty()).unwrap();
+        let bar = env.create_db(Some("bar"), DatabaseFlags::empty()).unwrap();
+        let mut txn = env.begin_rw_txn().unwrap();
+        txn.put(foo, &[1], &[1], WriteFlags::empty()).unwrap();
+        txn.put(bar, &[1], &[1], WriteFlags::empty()).unwrap();
+        txn.commit().unwrap();
+
+        assert_eq!(env.stat().unwrap().entries, 2);
+    }
+
+    #[test]
+    fn last_txn_id() {
+        let env = LmdbEnvironment::new_in_memory();
+        assert_eq!(env.last_txn_id(), Ok(0));
+
+        env.begin_rw_txn().unwrap().commit().unwrap();
+        drop(env.begin_rw_txn().unwrap());
+
+        assert_eq!(env.last_txn_id(), Ok(1));
+    }
+}
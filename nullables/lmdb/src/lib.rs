@@ -1,13 +1,17 @@
 mod configured_database;
 mod database;
+mod engine;
 mod environment;
+mod in_memory;
 mod ro_cursor;
 mod ro_transaction;
 mod rw_transaction;
 
 pub use configured_database::*;
 pub use database::*;
+pub use engine::*;
 pub use environment::*;
+pub use in_memory::InMemoryEngine;
 pub use lmdb::{DatabaseFlags, Error, WriteFlags};
 pub use ro_cursor::*;
 pub use ro_transaction::*;
@@ -1,4 +1,5 @@
 use super::ConfiguredDatabase;
+use crate::{EngineCursor, EngineIterator};
 use lmdb_sys::{MDB_FIRST, MDB_LAST, MDB_NEXT, MDB_SET_RANGE};
 use std::{cell::Cell, collections::btree_map};
 
@@ -13,6 +14,10 @@ impl<'txn> RoCursor<'txn> {
         }))
     }
 
+    pub fn new_with_engine(cursor: Box<dyn EngineCursor + 'txn>) -> Self {
+        Self(RoCursorStrategy::Engine(cursor))
+    }
+
     pub fn new(cursor: lmdb::RoCursor<'txn>) -> Self {
         Self(RoCursorStrategy::Real(cursor))
     }
@@ -21,6 +26,10 @@ impl<'txn> RoCursor<'txn> {
         match &mut self.0 {
             RoCursorStrategy::Real(s) => Iter::Real(lmdb::Cursor::iter_start(s)),
             RoCursorStrategy::Nulled(s) => s.iter_start(),
+            RoCursorStrategy::Engine(s) => {
+                // the data is owned by the transaction, not by the cursor
+                unsafe { std::mem::transmute::<Iter<'_>, Iter<'txn>>(s.iter_start()) }
+            }
         }
     }
 
@@ -33,6 +42,13 @@ impl<'txn> RoCursor<'txn> {
         match &self.0 {
             RoCursorStrategy::Real(s) => lmdb::Cursor::get(s, key, data, op),
             RoCursorStrategy::Nulled(s) => s.get(key, data, op),
+            RoCursorStrategy::Engine(s) => s.get(key, op).map(|(k, v)| unsafe {
+                // the data is owned by the transaction, not by the cursor
+                (
+                    k.map(|k| std::mem::transmute::<&[u8], &'txn [u8]>(k)),
+                    std::mem::transmute::<&[u8], &'txn [u8]>(v),
+                )
+            }),
         }
     }
 }
@@ -41,6 +57,7 @@ enum RoCursorStrategy<'txn> {
     //todo don't use static lifetimes!
     Real(lmdb::RoCursor<'txn>),
     Nulled(RoCursorStub<'txn>),
+    Engine(Box<dyn EngineCursor + 'txn>),
 }
 
 struct RoCursorStub<'txn> {
@@ -109,6 +126,7 @@ impl<'txn> RoCursorStub<'txn> {
 pub enum Iter<'a> {
     Real(lmdb::Iter<'static>),
     Stub(btree_map::Iter<'a, Vec<u8>, Vec<u8>>),
+    Engine(Box<dyn EngineIterator + 'a>),
 }
 
 impl<'a> Iterator for Iter<'a> {
@@ -123,6 +141,12 @@ impl<'a> Iterator for Iter<'a> {
                     std::mem::transmute::<&'a [u8], &'static [u8]>(v.as_slice()),
                 ))
             }),
+            Iter::Engine(iter) => iter.next_entry().map(|(k, v)| unsafe {
+                Ok((
+                    std::mem::transmute::<&[u8], &'static [u8]>(k),
+                    std::mem::transmute::<&[u8], &'static [u8]>(v),
+                ))
+            }),
         }
     }
 }
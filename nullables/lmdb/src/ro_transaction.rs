@@ -1,5 +1,5 @@
 use super::{ConfiguredDatabase, LmdbDatabase, RoCursor};
-use crate::EMPTY_DATABASE;
+use crate::{EngineInactiveTransaction, EngineReadTransaction, EMPTY_DATABASE};
 
 pub struct RoTransaction {
     strategy: RoTransactionStrategy,
@@ -18,6 +18,12 @@ impl RoTransaction {
         }
     }
 
+    pub fn new_with_engine(txn: Box<dyn EngineReadTransaction>) -> Self {
+        Self {
+            strategy: RoTransactionStrategy::Engine(txn),
+        }
+    }
+
     pub fn reset(self) -> InactiveTransaction {
         match self.strategy {
             RoTransactionStrategy::Real(s) => InactiveTransaction {
@@ -26,6 +32,9 @@ impl RoTransaction {
             RoTransactionStrategy::Nulled(s) => InactiveTransaction {
                 strategy: InactiveTransactionStrategy::Nulled(s.reset()),
             },
+            RoTransactionStrategy::Engine(s) => InactiveTransaction {
+                strategy: InactiveTransactionStrategy::Engine(s.reset()),
+            },
         }
     }
 
@@ -40,6 +49,7 @@ impl RoTransaction {
         match &self.strategy {
             RoTransactionStrategy::Real(s) => s.get(database, key),
             RoTransactionStrategy::Nulled(s) => s.get(database, key),
+            RoTransactionStrategy::Engine(s) => s.get(database, key),
         }
     }
 
@@ -47,6 +57,9 @@ impl RoTransaction {
         match &self.strategy {
             RoTransactionStrategy::Real(s) => s.open_ro_cursor(database),
             RoTransactionStrategy::Nulled(s) => s.open_ro_cursor(database),
+            RoTransactionStrategy::Engine(s) => {
+                s.open_ro_cursor(database).map(RoCursor::new_with_engine)
+            }
         }
     }
 
@@ -54,6 +67,7 @@ impl RoTransaction {
         match &self.strategy {
             RoTransactionStrategy::Real(s) => s.count(database),
             RoTransactionStrategy::Nulled(s) => s.count(database),
+            RoTransactionStrategy::Engine(s) => s.count(database),
         }
     }
 }
@@ -61,6 +75,7 @@ impl RoTransaction {
 enum RoTransactionStrategy {
     Real(RoTransactionWrapper),
     Nulled(RoTransactionStub),
+    Engine(Box<dyn EngineReadTransaction>),
 }
 
 struct RoTransactionWrapper(lmdb::RoTransaction<'static>);
@@ -144,6 +159,7 @@ pub struct InactiveTransaction {
 enum InactiveTransactionStrategy {
     Real(InactiveTransactionWrapper),
     Nulled(NullInactiveTransaction),
+    Engine(Box<dyn EngineInactiveTransaction>),
 }
 
 impl InactiveTransaction {
@@ -155,6 +171,9 @@ impl InactiveTransaction {
             InactiveTransactionStrategy::Nulled(s) => Ok(RoTransaction {
                 strategy: RoTransactionStrategy::Nulled(s.renew()?),
             }),
+            InactiveTransactionStrategy::Engine(s) => Ok(RoTransaction {
+                strategy: RoTransactionStrategy::Engine(s.renew()?),
+            }),
         }
     }
 }
@@ -1,4 +1,5 @@
 use super::{ConfiguredDatabase, LmdbDatabase, RoCursor};
+use crate::EngineWriteTransaction;
 use lmdb::DatabaseFlags;
 
 pub struct RwTransaction {
@@ -18,10 +19,17 @@ impl RwTransaction {
         }
     }
 
+    pub fn new_with_engine(txn: Box<dyn EngineWriteTransaction>) -> Self {
+        Self {
+            strategy: RwTransactionStrategy::Engine(txn),
+        }
+    }
+
     pub fn get(&self, database: LmdbDatabase, key: &[u8]) -> lmdb::Result<&[u8]> {
         match &self.strategy {
             RwTransactionStrategy::Real(s) => s.get(database, key),
             RwTransactionStrategy::Nulled(s) => s.get(database, key),
+            RwTransactionStrategy::Engine(s) => s.get(database, key),
         }
     }
 
@@ -32,10 +40,11 @@ impl RwTransaction {
         data: &[u8],
         flags: lmdb::WriteFlags,
     ) -> lmdb::Result<()> {
-        if let RwTransactionStrategy::Real(s) = &mut self.strategy {
-            s.put(database.as_real(), key, data, flags)?;
+        match &mut self.strategy {
+            RwTransactionStrategy::Real(s) => s.put(database.as_real(), key, data, flags),
+            RwTransactionStrategy::Nulled(_) => Ok(()),
+            RwTransactionStrategy::Engine(s) => s.put(database, key, data, flags),
         }
-        Ok(())
     }
 
     pub fn del(
@@ -44,10 +53,11 @@ impl RwTransaction {
         key: &[u8],
         flags: Option<&[u8]>,
     ) -> lmdb::Result<()> {
-        if let RwTransactionStrategy::Real(s) = &mut self.strategy {
-            s.del(database.as_real(), key, flags)?;
+        match &mut self.strategy {
+            RwTransactionStrategy::Real(s) => s.del(database.as_real(), key, flags),
+            RwTransactionStrategy::Nulled(_) => Ok(()),
+            RwTransactionStrategy::Engine(s) => s.del(database, key),
         }
-        Ok(())
     }
 
     /// ## Safety
@@ -65,6 +75,7 @@ impl RwTransaction {
         match &self.strategy {
             RwTransactionStrategy::Real(s) => s.create_db(name, flags),
             RwTransactionStrategy::Nulled(s) => s.create_db(name, flags),
+            RwTransactionStrategy::Engine(s) => s.create_db(name, flags),
         }
     }
 
@@ -73,23 +84,28 @@ impl RwTransaction {
     /// This method is unsafe in the same ways as `Environment::close_db`, and
     /// should be used accordingly.
     pub unsafe fn drop_db(&mut self, database: LmdbDatabase) -> lmdb::Result<()> {
-        if let RwTransactionStrategy::Real(s) = &mut self.strategy {
-            s.drop_db(database.as_real())?;
+        match &mut self.strategy {
+            RwTransactionStrategy::Real(s) => s.drop_db(database.as_real()),
+            RwTransactionStrategy::Nulled(_) => Ok(()),
+            RwTransactionStrategy::Engine(s) => s.clear_db(database),
         }
-        Ok(())
     }
 
     pub fn clear_db(&mut self, database: LmdbDatabase) -> lmdb::Result<()> {
-        if let RwTransactionStrategy::Real(s) = &mut self.strategy {
-            s.clear_db(database.as_real())?;
+        match &mut self.strategy {
+            RwTransactionStrategy::Real(s) => s.clear_db(database.as_real()),
+            RwTransactionStrategy::Nulled(_) => Ok(()),
+            RwTransactionStrategy::Engine(s) => s.clear_db(database),
         }
-        Ok(())
     }
 
     pub fn open_ro_cursor(&self, database: LmdbDatabase) -> lmdb::Result<RoCursor> {
         match &self.strategy {
             RwTransactionStrategy::Real(s) => s.open_ro_cursor(database),
             RwTransactionStrategy::Nulled(s) => s.open_ro_cursor(database),
+            RwTransactionStrategy::Engine(s) => {
+                s.open_ro_cursor(database).map(RoCursor::new_with_engine)
+            }
         }
     }
 
@@ -97,20 +113,23 @@ impl RwTransaction {
         match &self.strategy {
             RwTransactionStrategy::Real(s) => s.count(database.as_real()),
             RwTransactionStrategy::Nulled(_) => 0,
+            RwTransactionStrategy::Engine(s) => s.count(database),
         }
     }
 
     pub fn commit(self) -> lmdb::Result<()> {
-        if let RwTransactionStrategy::Real(s) = self.strategy {
-            s.commit()?;
+        match self.strategy {
+            RwTransactionStrategy::Real(s) => s.commit(),
+            RwTransactionStrategy::Nulled(_) => Ok(()),
+            RwTransactionStrategy::Engine(s) => s.commit(),
         }
-        Ok(())
     }
 }
 
 enum RwTransactionStrategy {
     Real(RwTransactionWrapper),
     Nulled(RwTransactionStub),
+    Engine(Box<dyn EngineWriteTransaction>),
 }
 
 pub struct RwTransactionWrapper(lmdb::RwTransaction<'static>);
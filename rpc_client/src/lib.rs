@@ -3,7 +3,8 @@ use anyhow::{anyhow, Ok, Result};
 use reqwest::Client;
 pub use reqwest::Url;
 use rsban_core::{
-    Account, Amount, BlockHash, HashOrAccount, JsonBlock, PublicKey, RawKey, WalletId, WorkNonce,
+    Account, Amount, BlockHash, HashOrAccount, JsonBlock, PublicKey, RawKey, UnsignedStateBlock,
+    WalletBackup, WalletId, WorkNonce,
 };
 use rsban_rpc_messages::*;
 use serde::Serialize;
@@ -50,6 +51,27 @@ impl NanoRpcClient {
         self.request(&RpcCommand::block_create(args)).await
     }
 
+    pub async fn block_prepare(&self, args: BlockPrepareArgs) -> Result<UnsignedStateBlock> {
+        self.request(&RpcCommand::block_prepare(args)).await
+    }
+
+    /// Waits for the confirmation of the block. Without a timeout the node waits up to 30 seconds.
+    pub async fn block_submit(
+        &self,
+        args: impl Into<BlockSubmitArgs>,
+    ) -> Result<BlockSubmitResponse> {
+        let args = args.into();
+        let wait = args
+            .timeout
+            .map(|t| Duration::from_millis(t.inner()))
+            .unwrap_or(Duration::from_secs(30));
+        self.request_with_timeout(
+            &RpcCommand::block_submit(args),
+            wait + Duration::from_secs(5),
+        )
+        .await
+    }
+
     pub async fn republish(&self, args: impl Into<RepublishArgs>) -> Result<BlockHashesResponse> {
         let cmd = RpcCommand::republish(args.into());
         self.request(&cmd).await
@@ -249,6 +271,28 @@ impl NanoRpcClient {
         self.request(&cmd).await
     }
 
+    pub async fn account_incoming(
+        &self,
+        args: impl Into<AccountIncomingArgs>,
+    ) -> Result<AccountIncomingResponse> {
+        self.request(&RpcCommand::account_incoming(args)).await
+    }
+
+    pub async fn account_balance_at(
+        &self,
+        args: AccountAtArgs,
+    ) -> Result<AccountBalanceAtResponse> {
+        self.request(&RpcCommand::account_balance_at(args)).await
+    }
+
+    pub async fn account_representative_at(
+        &self,
+        args: AccountAtArgs,
+    ) -> Result<AccountRepresentativeAtResponse> {
+        self.request(&RpcCommand::account_representative_at(args))
+            .await
+    }
+
     pub async fn account_balance(
         &self,
         args: impl Into<AccountBalanceArgs>,
@@ -305,6 +349,16 @@ impl NanoRpcClient {
         self.request(&RpcCommand::wallet_create(seed)).await
     }
 
+    pub async fn wallet_create_from_mnemonic(
+        &self,
+        mnemonic: impl Into<String>,
+    ) -> Result<WalletCreateResponse> {
+        self.request(&RpcCommand::WalletCreate(WalletCreateArgs::with_mnemonic(
+            mnemonic,
+        )))
+        .await
+    }
+
     pub async fn wallet_contains(
         &self,
         wallet: WalletId,
@@ -359,6 +413,11 @@ impl NanoRpcClient {
         self.request(&RpcCommand::block_account(hash)).await
     }
 
+    pub async fn block_at_height(&self, account: Account, height: u64) -> Result<HashRpcMessage> {
+        self.request(&RpcCommand::block_at_height(account, height))
+            .await
+    }
+
     pub async fn block_confirm(&self, hash: BlockHash) -> Result<StartedResponse> {
         self.request(&RpcCommand::block_confirm(hash)).await
     }
@@ -367,6 +426,10 @@ impl NanoRpcClient {
         self.request(&RpcCommand::BlockCount).await
     }
 
+    pub async fn ledger_analytics(&self) -> Result<LedgerAnalyticsResponse> {
+        self.request(&RpcCommand::ledger_analytics()).await
+    }
+
     pub async fn uptime(&self) -> Result<UptimeResponse> {
         self.request(&RpcCommand::uptime()).await
     }
@@ -400,6 +463,43 @@ impl NanoRpcClient {
             .await
     }
 
+    pub async fn wallet_activity(
+        &self,
+        args: impl Into<WalletActivityArgs>,
+    ) -> Result<WalletActivityResponse> {
+        self.request(&RpcCommand::wallet_activity(args.into()))
+            .await
+    }
+
+    pub async fn wallet_alert_set(&self, args: WalletAlertSetArgs) -> Result<SuccessResponse> {
+        self.request(&RpcCommand::wallet_alert_set(args)).await
+    }
+
+    pub async fn wallet_alert_remove(
+        &self,
+        wallet: WalletId,
+        account: Account,
+    ) -> Result<SuccessResponse> {
+        self.request(&RpcCommand::wallet_alert_remove(wallet, account))
+            .await
+    }
+
+    pub async fn wallet_alerts(&self, wallet: WalletId) -> Result<WalletAlertsResponse> {
+        self.request(&RpcCommand::wallet_alerts(wallet)).await
+    }
+
+    pub async fn wallet_sweep(&self, args: WalletSweepArgs) -> Result<StartedResponse> {
+        self.request(&RpcCommand::wallet_sweep(args)).await
+    }
+
+    pub async fn wallet_sweep_status(&self, wallet: WalletId) -> Result<WalletSweepStatusResponse> {
+        self.request(&RpcCommand::wallet_sweep_status(wallet)).await
+    }
+
+    pub async fn wallet_work_status(&self, wallet: WalletId) -> Result<WalletWorkStatusResponse> {
+        self.request(&RpcCommand::wallet_work_status(wallet)).await
+    }
+
     pub async fn wallet_representative(
         &self,
         wallet: WalletId,
@@ -447,6 +547,47 @@ impl NanoRpcClient {
         self.request(&RpcCommand::wallet_export(wallet)).await
     }
 
+    pub async fn wallet_mnemonic(&self, wallet: WalletId) -> Result<MnemonicResponse> {
+        self.request(&RpcCommand::wallet_mnemonic(wallet)).await
+    }
+
+    pub async fn wallet_backup(
+        &self,
+        wallet: WalletId,
+        password: String,
+    ) -> Result<WalletBackupResponse> {
+        self.request(&RpcCommand::wallet_backup(wallet, password))
+            .await
+    }
+
+    pub async fn wallet_restore(
+        &self,
+        backup: WalletBackup,
+        password: String,
+    ) -> Result<WalletRpcMessage> {
+        self.request(&RpcCommand::wallet_restore(backup, password))
+            .await
+    }
+
+    pub async fn wallet_kdf_upgrade(
+        &self,
+        args: WalletKdfUpgradeArgs,
+    ) -> Result<WalletKdfUpgradeResponse> {
+        self.request(&RpcCommand::wallet_kdf_upgrade(args)).await
+    }
+
+    pub async fn schedule_create(&self, args: ScheduleCreateArgs) -> Result<PaymentScheduleDto> {
+        self.request(&RpcCommand::schedule_create(args)).await
+    }
+
+    pub async fn schedule_list(&self, wallet: WalletId) -> Result<ScheduleListResponse> {
+        self.request(&RpcCommand::schedule_list(wallet)).await
+    }
+
+    pub async fn schedule_cancel(&self, wallet: WalletId, id: u64) -> Result<SuccessResponse> {
+        self.request(&RpcCommand::schedule_cancel(wallet, id)).await
+    }
+
     pub async fn password_change(
         &self,
         wallet: WalletId,
@@ -495,6 +636,22 @@ impl NanoRpcClient {
         self.request(&RpcCommand::PopulateBacklog).await
     }
 
+    pub async fn prune(&self, args: PruneArgs) -> Result<PruneResponse> {
+        self.request(&RpcCommand::prune(args)).await
+    }
+
+    pub async fn pruning_status(&self) -> Result<PruningStatusResponse> {
+        self.request(&RpcCommand::pruning_status()).await
+    }
+
+    pub async fn pruning_pause(&self) -> Result<SuccessResponse> {
+        self.request(&RpcCommand::PruningPause).await
+    }
+
+    pub async fn pruning_resume(&self) -> Result<SuccessResponse> {
+        self.request(&RpcCommand::PruningResume).await
+    }
+
     pub async fn representatives(
         &self,
         count: Option<usize>,
@@ -600,6 +757,10 @@ impl NanoRpcClient {
             .await
     }
 
+    pub async fn equivocations(&self, args: EquivocationsArgs) -> Result<EquivocationsResponse> {
+        self.request(&RpcCommand::equivocations(args)).await
+    }
+
     pub async fn confirmation_quorum(
         &self,
         peer_details: Option<bool>,
@@ -630,6 +791,14 @@ impl NanoRpcClient {
         self.request(&RpcCommand::send(args)).await
     }
 
+    pub async fn send_batch(&self, args: SendBatchArgs) -> Result<SendBatchResponse> {
+        self.request(&RpcCommand::send_batch(args)).await
+    }
+
+    pub async fn send_batch_status(&self, source: Account) -> Result<SendBatchResponse> {
+        self.request(&RpcCommand::send_batch_status(source)).await
+    }
+
     pub async fn send_receive(
         &self,
         wallet: WalletId,
@@ -694,10 +863,29 @@ impl NanoRpcClient {
     where
         T: Serialize,
     {
-        let result = self
-            .client
-            .post(self.url.clone())
-            .json(request)
+        self.send_request(self.client.post(self.url.clone()).json(request))
+            .await
+    }
+
+    /// For commands that block on the node side for longer than the default timeout
+    async fn request_with_timeout<T, R>(&self, cmd: &T, timeout: Duration) -> Result<R>
+    where
+        T: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let value = self
+            .send_request(
+                self.client
+                    .post(self.url.clone())
+                    .json(cmd)
+                    .timeout(timeout),
+            )
+            .await?;
+        Ok(serde_json::from_value::<R>(value)?)
+    }
+
+    async fn send_request(&self, request: reqwest::RequestBuilder) -> Result<serde_json::Value> {
+        let result = request
             .send()
             .await?
             .error_for_status()?
@@ -0,0 +1,93 @@
+use crate::{RpcCommand, RpcU64};
+use rsban_core::{Account, Amount, BlockHash};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn account_balance_at(args: AccountAtArgs) -> Self {
+        Self::AccountBalanceAt(args)
+    }
+}
+
+/// Selects a point in the history of an account. Exactly one of `height` and `timestamp` must be set
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct AccountAtArgs {
+    pub account: Account,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<RpcU64>,
+    /// Unix timestamp. Selects the latest block with a local timestamp at or before it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<RpcU64>,
+}
+
+impl AccountAtArgs {
+    pub fn at_height(account: Account, height: u64) -> Self {
+        Self {
+            account,
+            height: Some(height.into()),
+            timestamp: None,
+        }
+    }
+
+    pub fn at_timestamp(account: Account, timestamp: u64) -> Self {
+        Self {
+            account,
+            height: None,
+            timestamp: Some(timestamp.into()),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct AccountBalanceAtResponse {
+    pub balance: Amount,
+    /// The block that set the balance
+    pub block: BlockHash,
+    pub height: RpcU64,
+    pub local_timestamp: RpcU64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_account_balance_at_command() {
+        let args = AccountAtArgs::at_timestamp(Account::from(123), 1000);
+        assert_eq!(
+            to_string_pretty(&RpcCommand::account_balance_at(args)).unwrap(),
+            r#"{
+  "action": "account_balance_at",
+  "account": "ban_111111111111111111111111111111111111111111111111115uwdgas549",
+  "timestamp": "1000"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_account_balance_at_command() {
+        let cmd = RpcCommand::account_balance_at(AccountAtArgs::at_height(Account::from(123), 5));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn serialize_account_balance_at_response() {
+        let response = AccountBalanceAtResponse {
+            balance: Amount::raw(1),
+            block: BlockHash::from(2),
+            height: 3.into(),
+            local_timestamp: 4.into(),
+        };
+        assert_eq!(
+            to_string_pretty(&response).unwrap(),
+            r#"{
+  "balance": "1",
+  "block": "0000000000000000000000000000000000000000000000000000000000000002",
+  "height": "3",
+  "local_timestamp": "4"
+}"#
+        );
+    }
+}
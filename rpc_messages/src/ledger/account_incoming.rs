@@ -0,0 +1,138 @@
+use crate::{RpcBool, RpcCommand, RpcU64};
+use rsban_core::{Account, Amount, BlockHash};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn account_incoming(args: impl Into<AccountIncomingArgs>) -> Self {
+        Self::AccountIncoming(args.into())
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct AccountIncomingArgs {
+    pub account: Account,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<RpcU64>,
+    /// Only sends with a local timestamp at or after this unix timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<RpcU64>,
+    /// Only sends from this account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<Account>,
+}
+
+impl AccountIncomingArgs {
+    pub fn builder(account: Account) -> AccountIncomingArgsBuilder {
+        AccountIncomingArgsBuilder::new(account)
+    }
+}
+
+impl From<Account> for AccountIncomingArgs {
+    fn from(account: Account) -> Self {
+        Self {
+            account,
+            count: None,
+            since: None,
+            source: None,
+        }
+    }
+}
+
+pub struct AccountIncomingArgsBuilder {
+    args: AccountIncomingArgs,
+}
+
+impl AccountIncomingArgsBuilder {
+    fn new(account: Account) -> Self {
+        Self {
+            args: account.into(),
+        }
+    }
+
+    pub fn count(mut self, count: u64) -> Self {
+        self.args.count = Some(count.into());
+        self
+    }
+
+    pub fn since(mut self, timestamp: u64) -> Self {
+        self.args.since = Some(timestamp.into());
+        self
+    }
+
+    pub fn from_source(mut self, source: Account) -> Self {
+        self.args.source = Some(source);
+        self
+    }
+
+    pub fn build(self) -> AccountIncomingArgs {
+        self.args
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct AccountIncomingResponse {
+    pub incoming: Vec<IncomingSendDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct IncomingSendDto {
+    pub hash: BlockHash,
+    pub source: Account,
+    pub amount: Amount,
+    pub local_timestamp: RpcU64,
+    pub confirmed: RpcBool,
+    /// The send isn't receivable anymore
+    pub received: RpcBool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_account_incoming_command() {
+        let args = AccountIncomingArgs::builder(Account::from(123))
+            .count(10)
+            .since(1000)
+            .build();
+        assert_eq!(
+            to_string_pretty(&RpcCommand::account_incoming(args)).unwrap(),
+            r#"{
+  "action": "account_incoming",
+  "account": "ban_111111111111111111111111111111111111111111111111115uwdgas549",
+  "count": "10",
+  "since": "1000"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_account_incoming_command() {
+        let cmd = RpcCommand::account_incoming(
+            AccountIncomingArgs::builder(Account::from(123))
+                .from_source(Account::from(456))
+                .build(),
+        );
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn serialize_account_incoming_response() {
+        let response = AccountIncomingResponse {
+            incoming: vec![IncomingSendDto {
+                hash: BlockHash::from(1),
+                source: Account::from(2),
+                amount: Amount::raw(3),
+                local_timestamp: 4.into(),
+                confirmed: true.into(),
+                received: false.into(),
+            }],
+        };
+        let serialized = to_string_pretty(&response).unwrap();
+        let deserialized: AccountIncomingResponse = from_str(&serialized).unwrap();
+        assert_eq!(response, deserialized);
+    }
+}
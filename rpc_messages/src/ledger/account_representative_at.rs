@@ -0,0 +1,50 @@
+use crate::{AccountAtArgs, RpcCommand, RpcU64};
+use rsban_core::{Account, BlockHash};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn account_representative_at(args: AccountAtArgs) -> Self {
+        Self::AccountRepresentativeAt(args)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct AccountRepresentativeAtResponse {
+    pub representative: Account,
+    /// The block that defines the account state at the requested point
+    pub block: BlockHash,
+    pub height: RpcU64,
+    pub local_timestamp: RpcU64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_account_representative_at_command() {
+        let args = AccountAtArgs::at_height(Account::from(123), 5);
+        assert_eq!(
+            to_string_pretty(&RpcCommand::account_representative_at(args)).unwrap(),
+            r#"{
+  "action": "account_representative_at",
+  "account": "ban_111111111111111111111111111111111111111111111111115uwdgas549",
+  "height": "5"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_account_representative_at_response() {
+        let response = AccountRepresentativeAtResponse {
+            representative: Account::from(1),
+            block: BlockHash::from(2),
+            height: 3.into(),
+            local_timestamp: 4.into(),
+        };
+        let serialized = to_string_pretty(&response).unwrap();
+        let deserialized: AccountRepresentativeAtResponse = from_str(&serialized).unwrap();
+        assert_eq!(response, deserialized);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{RpcCommand, RpcU64};
+use rsban_core::Account;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn block_at_height(account: Account, height: u64) -> Self {
+        Self::BlockAtHeight(BlockAtHeightArgs::new(account, height))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BlockAtHeightArgs {
+    pub account: Account,
+    pub height: RpcU64,
+}
+
+impl BlockAtHeightArgs {
+    pub fn new(account: Account, height: u64) -> Self {
+        Self {
+            account,
+            height: height.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RpcCommand;
+    use rsban_core::Account;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_block_at_height_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::block_at_height(Account::from(123), 5)).unwrap(),
+            r#"{
+  "action": "block_at_height",
+  "account": "ban_111111111111111111111111111111111111111111111111115uwdgas549",
+  "height": "5"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_block_at_height_command() {
+        let cmd = RpcCommand::block_at_height(Account::from(123), 5);
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
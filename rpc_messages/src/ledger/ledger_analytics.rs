@@ -0,0 +1,100 @@
+use crate::{RpcCommand, RpcU64, RpcU8};
+use rsban_core::{Account, Amount};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn ledger_analytics() -> Self {
+        Self::LedgerAnalytics
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct LedgerAnalyticsResponse {
+    /// Unix timestamp of the scan the statistics are based on
+    pub created: RpcU64,
+    pub scan_duration_ms: RpcU64,
+    pub accounts: RpcU64,
+    pub balance_distribution: Vec<BalanceBucketDto>,
+    pub top_accounts: Vec<AccountBalanceDto>,
+    pub active_accounts: Vec<ActiveAccountsDto>,
+    pub epochs: Vec<EpochAccountsDto>,
+    pub online_weight: Amount,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nakamoto_coefficient: Option<RpcU64>,
+}
+
+/// Accounts with a balance of at least `min` and less than `max` raw
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BalanceBucketDto {
+    pub min: Amount,
+    pub max: Amount,
+    pub accounts: RpcU64,
+    pub balance: Amount,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct AccountBalanceDto {
+    pub account: Account,
+    pub balance: Amount,
+}
+
+/// Accounts that were modified within the last `window` seconds
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ActiveAccountsDto {
+    pub window: RpcU64,
+    pub accounts: RpcU64,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct EpochAccountsDto {
+    pub epoch: RpcU8,
+    pub accounts: RpcU64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_ledger_analytics_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::ledger_analytics()).unwrap(),
+            r#"{
+  "action": "ledger_analytics"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_ledger_analytics_response() {
+        let response = LedgerAnalyticsResponse {
+            created: 1.into(),
+            scan_duration_ms: 2.into(),
+            accounts: 3.into(),
+            balance_distribution: vec![BalanceBucketDto {
+                min: Amount::raw(10),
+                max: Amount::raw(100),
+                accounts: 3.into(),
+                balance: Amount::raw(42),
+            }],
+            top_accounts: vec![AccountBalanceDto {
+                account: Account::from(1),
+                balance: Amount::raw(20),
+            }],
+            active_accounts: vec![ActiveAccountsDto {
+                window: 86400.into(),
+                accounts: 1.into(),
+            }],
+            epochs: vec![EpochAccountsDto {
+                epoch: 2.into(),
+                accounts: 3.into(),
+            }],
+            online_weight: Amount::raw(1000),
+            nakamoto_coefficient: Some(4.into()),
+        };
+        let serialized = to_string_pretty(&response).unwrap();
+        let deserialized: LedgerAnalyticsResponse = from_str(&serialized).unwrap();
+        assert_eq!(response, deserialized);
+    }
+}
@@ -1,8 +1,11 @@
 mod account_balance;
+mod account_balance_at;
 mod account_block_count;
 mod account_history;
+mod account_incoming;
 mod account_info;
 mod account_representative;
+mod account_representative_at;
 mod account_weight;
 mod accounts_balances;
 mod accounts_frontiers;
@@ -10,6 +13,7 @@ mod accounts_receivable;
 mod accounts_representatives;
 mod available_supply;
 mod block_account;
+mod block_at_height;
 mod block_confirm;
 mod block_count;
 mod block_info;
@@ -21,21 +25,26 @@ mod delegators_count;
 mod frontier_count;
 mod frontiers;
 mod ledger;
+mod ledger_analytics;
 mod representatives;
 mod successors;
 mod unopened;
 mod weight;
 
 pub use account_balance::*;
+pub use account_balance_at::*;
 pub use account_block_count::*;
 pub use account_history::*;
+pub use account_incoming::*;
 pub use account_info::*;
 pub use account_representative::*;
+pub use account_representative_at::*;
 pub use account_weight::*;
 pub use accounts_balances::*;
 pub use accounts_receivable::*;
 pub use accounts_representatives::*;
 pub use available_supply::*;
+pub use block_at_height::*;
 pub use block_count::*;
 pub use block_info::*;
 pub use blocks::*;
@@ -44,6 +53,7 @@ pub use chain::*;
 pub use delegators::*;
 pub use frontiers::*;
 pub use ledger::*;
+pub use ledger_analytics::*;
 pub use representatives::*;
 pub use unopened::*;
 pub use weight::*;
@@ -21,12 +21,15 @@ pub enum RpcCommand {
     KeyCreate,
     Receive(ReceiveArgs),
     Send(SendArgs),
+    SendBatch(SendBatchArgs),
+    SendBatchStatus(AccountArg),
     WalletAdd(WalletAddArgs),
     WorkPeers,
     WorkPeerAdd(AddressWithPortArgs),
     Telemetry(TelemetryArgs),
     AccountCreate(AccountCreateArgs),
     AccountBalance(AccountBalanceArgs),
+    AccountBalanceAt(AccountAtArgs),
     AccountsCreate(AccountsCreateArgs),
     AccountRemove(WalletWithAccountArgs),
     AccountMove(AccountMoveArgs),
@@ -42,9 +45,11 @@ pub enum RpcCommand {
     AccountKey(AccountArg),
     AccountGet(KeyArg),
     AccountRepresentative(AccountArg),
+    AccountRepresentativeAt(AccountAtArgs),
     AccountWeight(AccountWeightArgs),
     AvailableSupply,
     BlockAccount(HashRpcMessage),
+    BlockAtHeight(BlockAtHeightArgs),
     BlockConfirm(HashRpcMessage),
     DatabaseTxnTracker(()), // TODO
     ConfirmationHistory(ConfirmationHistoryArgs),
@@ -55,15 +60,29 @@ pub enum RpcCommand {
     NanoToRaw(AmountRpcMessage),
     RawToNano(AmountRpcMessage),
     WalletAddWatch(WalletAddWatchArgs),
+    WalletActivity(WalletActivityArgs),
+    WalletAlertSet(WalletAlertSetArgs),
+    WalletAlertRemove(WalletWithAccountArgs),
+    WalletAlerts(WalletRpcMessage),
+    WalletSweep(WalletSweepArgs),
+    WalletSweepStatus(WalletRpcMessage),
     WalletRepresentative(WalletRpcMessage),
     WorkSet(WorkSetArgs),
     WorkGet(WalletWithAccountArgs),
     WalletWorkGet(WalletRpcMessage),
+    WalletWorkStatus(WalletRpcMessage),
     AccountsFrontiers(AccountsRpcMessage),
     WalletFrontiers(WalletRpcMessage),
     Frontiers(FrontiersArgs),
     WalletInfo(WalletRpcMessage),
     WalletExport(WalletRpcMessage),
+    WalletMnemonic(WalletRpcMessage),
+    WalletBackup(WalletWithPasswordArgs),
+    WalletRestore(WalletRestoreArgs),
+    WalletKdfUpgrade(WalletKdfUpgradeArgs),
+    ScheduleCreate(ScheduleCreateArgs),
+    ScheduleList(WalletRpcMessage),
+    ScheduleCancel(ScheduleCancelArgs),
     PasswordChange(WalletWithPasswordArgs),
     PasswordEnter(WalletWithPasswordArgs),
     PasswordValid(WalletRpcMessage),
@@ -71,6 +90,10 @@ pub enum RpcCommand {
     KeyExpand(KeyExpandArgs),
     Peers(PeersArgs),
     PopulateBacklog,
+    Prune(PruneArgs),
+    PruningStatus,
+    PruningPause,
+    PruningResume,
     Representatives(RepresentativesArgs),
     AccountsRepresentatives(AccountsRpcMessage),
     StatsClear,
@@ -95,6 +118,7 @@ pub enum RpcCommand {
     ConfirmationQuorum(ConfirmationQuorumArgs),
     WorkValidate(WorkValidateArgs),
     AccountHistory(AccountHistoryArgs),
+    AccountIncoming(AccountIncomingArgs),
     Sign(SignArgs),
     Process(ProcessArgs),
     WorkCancel(HashRpcMessage),
@@ -117,11 +141,15 @@ pub enum RpcCommand {
     UncheckedKeys(UncheckedKeysArgs),
     ConfirmationInfo(ConfirmationInfoArgs),
     Ledger(LedgerArgs),
+    LedgerAnalytics,
     WorkGenerate(WorkGenerateArgs),
     Republish(RepublishArgs),
     BlockCreate(BlockCreateArgs),
+    BlockPrepare(BlockPrepareArgs),
+    BlockSubmit(BlockSubmitArgs),
     WorkPeersClear,
     Version,
+    Equivocations(EquivocationsArgs),
 }
 
 pub fn check_error(value: &serde_json::Value) -> Result<(), String> {
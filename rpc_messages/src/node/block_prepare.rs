@@ -0,0 +1,104 @@
+use crate::RpcCommand;
+use rsban_core::{Account, Amount, BlockHash};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn block_prepare(args: BlockPrepareArgs) -> Self {
+        Self::BlockPrepare(args)
+    }
+}
+
+/// Prepares an unsigned state block with precomputed work, so that it can be
+/// signed on a machine that has no access to the network.
+/// A send needs `destination` and `amount`, a receive needs the `source` block.
+/// Without both it is a change block, which needs a `representative`.
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BlockPrepareArgs {
+    pub account: Account,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub representative: Option<Account>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination: Option<Account>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<Amount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<BlockHash>,
+}
+
+impl BlockPrepareArgs {
+    pub fn builder(account: Account) -> BlockPrepareArgsBuilder {
+        BlockPrepareArgsBuilder {
+            args: BlockPrepareArgs {
+                account,
+                representative: None,
+                destination: None,
+                amount: None,
+                source: None,
+            },
+        }
+    }
+}
+
+pub struct BlockPrepareArgsBuilder {
+    args: BlockPrepareArgs,
+}
+
+impl BlockPrepareArgsBuilder {
+    pub fn send(mut self, destination: Account, amount: Amount) -> Self {
+        self.args.destination = Some(destination);
+        self.args.amount = Some(amount);
+        self
+    }
+
+    pub fn receive(mut self, source: BlockHash) -> Self {
+        self.args.source = Some(source);
+        self
+    }
+
+    pub fn representative(mut self, representative: Account) -> Self {
+        self.args.representative = Some(representative);
+        self
+    }
+
+    pub fn build(self) -> BlockPrepareArgs {
+        self.args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_block_prepare_command() {
+        let command = RpcCommand::block_prepare(
+            BlockPrepareArgs::builder(Account::from(1))
+                .send(Account::from(2), Amount::raw(3))
+                .build(),
+        );
+
+        assert_eq!(
+            serde_json::to_value(&command).unwrap(),
+            json!({
+                "action": "block_prepare",
+                "account": Account::from(1),
+                "destination": Account::from(2),
+                "amount": "3"
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_block_prepare_command() {
+        let command = RpcCommand::block_prepare(
+            BlockPrepareArgs::builder(Account::from(1))
+                .receive(BlockHash::from(2))
+                .representative(Account::from(3))
+                .build(),
+        );
+        let serialized = serde_json::to_string(&command).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(command, deserialized);
+    }
+}
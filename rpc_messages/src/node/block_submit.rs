@@ -0,0 +1,84 @@
+use crate::{RpcBool, RpcCommand, RpcU64};
+use rsban_core::{BlockHash, JsonBlock};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn block_submit(args: BlockSubmitArgs) -> Self {
+        Self::BlockSubmit(args)
+    }
+}
+
+impl From<JsonBlock> for BlockSubmitArgs {
+    fn from(value: JsonBlock) -> Self {
+        Self::new(value, None)
+    }
+}
+
+/// Processes a signed block and waits until it is confirmed.
+/// A block that is already in the ledger is not an error, so a submit can be retried.
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BlockSubmitArgs {
+    pub block: JsonBlock,
+    /// Milliseconds to wait for the confirmation. Defaults to 30 seconds,
+    /// longer timeouts are capped at 60 seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<RpcU64>,
+}
+
+impl BlockSubmitArgs {
+    pub fn new(block: JsonBlock, timeout: Option<u64>) -> Self {
+        Self {
+            block,
+            timeout: timeout.map(|i| i.into()),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BlockSubmitResponse {
+    pub hash: BlockHash,
+    pub confirmed: RpcBool,
+}
+
+impl BlockSubmitResponse {
+    pub fn new(hash: BlockHash, confirmed: bool) -> Self {
+        Self {
+            hash,
+            confirmed: confirmed.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_core::Block;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_block_submit_command() {
+        let block = Block::new_test_instance().json_representation();
+        let command = RpcCommand::block_submit(BlockSubmitArgs::new(block.clone(), Some(1000)));
+
+        assert_eq!(
+            serde_json::to_value(&command).unwrap(),
+            json!({
+                "action": "block_submit",
+                "block": block,
+                "timeout": "1000"
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_block_submit_response() {
+        let response = BlockSubmitResponse::new(BlockHash::from(1), true);
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({
+                "hash": BlockHash::from(1),
+                "confirmed": "true"
+            })
+        );
+    }
+}
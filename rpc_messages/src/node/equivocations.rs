@@ -0,0 +1,131 @@
+use crate::{RpcCommand, RpcU64};
+use rsban_core::{Account, BlockHash, QualifiedRoot, Signature};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn equivocations(args: EquivocationsArgs) -> Self {
+        Self::Equivocations(args)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct EquivocationsArgs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<RpcU64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub representative: Option<Account>,
+}
+
+impl EquivocationsArgs {
+    pub fn builder() -> EquivocationsArgsBuilder {
+        EquivocationsArgsBuilder {
+            args: EquivocationsArgs::default(),
+        }
+    }
+}
+
+pub struct EquivocationsArgsBuilder {
+    args: EquivocationsArgs,
+}
+
+impl EquivocationsArgsBuilder {
+    pub fn count(mut self, count: u64) -> Self {
+        self.args.count = Some(count.into());
+        self
+    }
+
+    pub fn representative(mut self, representative: Account) -> Self {
+        self.args.representative = Some(representative);
+        self
+    }
+
+    pub fn build(self) -> EquivocationsArgs {
+        self.args
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EquivocationsResponse {
+    pub equivocations: Vec<EquivocationDto>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EquivocationDto {
+    pub representative: Account,
+    pub root: QualifiedRoot,
+    /// Seconds since the UNIX epoch
+    pub detected: RpcU64,
+    pub first: SignedVoteDto,
+    pub second: SignedVoteDto,
+}
+
+/// A vote with everything that is needed to verify its signature
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedVoteDto {
+    pub hash: BlockHash,
+    pub timestamp: RpcU64,
+    pub signature: Signature,
+    pub blocks: Vec<BlockHash>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_equivocations_command_options_none() {
+        let command = RpcCommand::equivocations(EquivocationsArgs::default());
+        let serialized = serde_json::to_value(command).unwrap();
+        assert_eq!(serialized, json!({"action": "equivocations"}));
+    }
+
+    #[test]
+    fn deserialize_equivocations_command_options_some() {
+        let json = r#"{
+            "action": "equivocations",
+            "count": "10",
+            "representative": "ban_1jg8zygjg3pp5w644emqcbmjqpnzmubfni3kfe1s8pooeuxsw49fdq1mco9j"
+        }"#;
+        let deserialized: RpcCommand = serde_json::from_str(json).unwrap();
+        let expected = RpcCommand::equivocations(
+            EquivocationsArgs::builder()
+                .count(10)
+                .representative(
+                    Account::decode_account(
+                        "ban_1jg8zygjg3pp5w644emqcbmjqpnzmubfni3kfe1s8pooeuxsw49fdq1mco9j",
+                    )
+                    .unwrap(),
+                )
+                .build(),
+        );
+        assert_eq!(deserialized, expected);
+    }
+
+    #[test]
+    fn serialize_equivocations_response() {
+        let vote = SignedVoteDto {
+            hash: BlockHash::from(1),
+            timestamp: u64::MAX.into(),
+            signature: Signature::new(),
+            blocks: vec![BlockHash::from(1)],
+        };
+        let response = EquivocationsResponse {
+            equivocations: vec![EquivocationDto {
+                representative: Account::from(2),
+                root: QualifiedRoot::new_test_instance(),
+                detected: 3.into(),
+                first: vote,
+                second: SignedVoteDto {
+                    hash: BlockHash::from(4),
+                    timestamp: u64::MAX.into(),
+                    signature: Signature::new(),
+                    blocks: vec![BlockHash::from(4)],
+                },
+            }],
+        };
+        let serialized = serde_json::to_string(&response).unwrap();
+        let deserialized: EquivocationsResponse = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, response);
+    }
+}
@@ -1,5 +1,7 @@
 mod active_difficulty;
 mod block_create;
+mod block_prepare;
+mod block_submit;
 mod bootstrap;
 mod bootstrap_any;
 mod bootstrap_lazy;
@@ -7,11 +9,16 @@ mod confirmation_active;
 mod confirmation_history;
 mod confirmation_info;
 mod confirmation_quorum;
+mod equivocations;
 mod keepalive;
 mod node_id;
 mod peers;
 mod populate_backlog;
 mod process;
+mod prune;
+mod pruning_pause;
+mod pruning_resume;
+mod pruning_status;
 mod receivable;
 mod receivable_exists;
 mod representatives_online;
@@ -35,6 +42,8 @@ mod work_validate;
 
 pub use active_difficulty::*;
 pub use block_create::*;
+pub use block_prepare::*;
+pub use block_submit::*;
 pub use bootstrap::*;
 pub use bootstrap_any::*;
 pub use bootstrap_lazy::*;
@@ -42,9 +51,12 @@ pub use confirmation_active::*;
 pub use confirmation_history::*;
 pub use confirmation_info::*;
 pub use confirmation_quorum::*;
+pub use equivocations::*;
 pub use node_id::*;
 pub use peers::*;
 pub use process::*;
+pub use prune::*;
+pub use pruning_status::*;
 pub use receivable::*;
 pub use receivable_exists::*;
 pub use representatives_online::*;
@@ -0,0 +1,101 @@
+use crate::{RpcBool, RpcCommand, RpcU64};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn prune(args: PruneArgs) -> Self {
+        Self::Prune(args)
+    }
+}
+
+/// Overrides for a single pruning run. Unset values are taken from the node config
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct PruneArgs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth: Option<RpcU64>,
+    /// Maximum age in seconds of the blocks that are kept
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age: Option<RpcU64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<RpcBool>,
+}
+
+impl PruneArgs {
+    pub fn builder() -> PruneArgsBuilder {
+        PruneArgsBuilder {
+            args: PruneArgs::default(),
+        }
+    }
+}
+
+pub struct PruneArgsBuilder {
+    args: PruneArgs,
+}
+
+impl PruneArgsBuilder {
+    pub fn depth(mut self, depth: u64) -> Self {
+        self.args.depth = Some(depth.into());
+        self
+    }
+
+    pub fn age(mut self, age: u64) -> Self {
+        self.args.age = Some(age.into());
+        self
+    }
+
+    pub fn dry_run(mut self) -> Self {
+        self.args.dry_run = Some(true.into());
+        self
+    }
+
+    pub fn build(self) -> PruneArgs {
+        self.args
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruneResponse {
+    pub dry_run: RpcBool,
+    pub blocks: RpcU64,
+    pub bytes: RpcU64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_prune_command_options_none() {
+        let command = RpcCommand::prune(PruneArgs::default());
+        let serialized = serde_json::to_value(command).unwrap();
+        assert_eq!(serialized, json!({"action": "prune"}));
+    }
+
+    #[test]
+    fn deserialize_prune_command_options_some() {
+        let json = r#"{
+            "action": "prune",
+            "depth": "10",
+            "age": "3600",
+            "dry_run": "true"
+        }"#;
+        let deserialized: RpcCommand = serde_json::from_str(json).unwrap();
+        let expected =
+            RpcCommand::prune(PruneArgs::builder().depth(10).age(3600).dry_run().build());
+        assert_eq!(deserialized, expected);
+    }
+
+    #[test]
+    fn serialize_prune_response() {
+        let response = PruneResponse {
+            dry_run: true.into(),
+            blocks: 2.into(),
+            bytes: 300.into(),
+        };
+        let serialized = serde_json::to_value(&response).unwrap();
+        assert_eq!(
+            serialized,
+            json!({"dry_run": "true", "blocks": "2", "bytes": "300"})
+        );
+    }
+}
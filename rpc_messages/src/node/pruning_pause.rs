@@ -0,0 +1,18 @@
+#[cfg(test)]
+mod tests {
+    use crate::RpcCommand;
+
+    #[test]
+    fn serialize_pruning_pause() {
+        let command = RpcCommand::PruningPause;
+        let serialized = serde_json::to_string(&command).unwrap();
+        assert_eq!(serialized, r#"{"action":"pruning_pause"}"#);
+    }
+
+    #[test]
+    fn deserialize_pruning_pause() {
+        let json = r#"{"action":"pruning_pause"}"#;
+        let deserialized: RpcCommand = serde_json::from_str(json).unwrap();
+        assert!(matches!(deserialized, RpcCommand::PruningPause));
+    }
+}
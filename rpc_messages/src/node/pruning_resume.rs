@@ -0,0 +1,18 @@
+#[cfg(test)]
+mod tests {
+    use crate::RpcCommand;
+
+    #[test]
+    fn serialize_pruning_resume() {
+        let command = RpcCommand::PruningResume;
+        let serialized = serde_json::to_string(&command).unwrap();
+        assert_eq!(serialized, r#"{"action":"pruning_resume"}"#);
+    }
+
+    #[test]
+    fn deserialize_pruning_resume() {
+        let json = r#"{"action":"pruning_resume"}"#;
+        let deserialized: RpcCommand = serde_json::from_str(json).unwrap();
+        assert!(matches!(deserialized, RpcCommand::PruningResume));
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{RpcBool, RpcCommand, RpcU64};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn pruning_status() -> Self {
+        Self::PruningStatus
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruningStatusResponse {
+    pub enabled: RpcBool,
+    pub running: RpcBool,
+    /// Pruning was paused with `pruning_pause`
+    pub paused: RpcBool,
+    pub dry_run: RpcBool,
+    /// Blocks pruned (or counted by a dry run) in the current or last run
+    pub blocks: RpcU64,
+    pub bytes: RpcU64,
+    /// Seconds since the UNIX epoch. 0 if pruning never ran
+    pub started: RpcU64,
+    /// Total number of pruned blocks in the ledger
+    pub pruned_count: RpcU64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_pruning_status_command() {
+        let serialized = serde_json::to_value(RpcCommand::pruning_status()).unwrap();
+        assert_eq!(serialized, json!({"action": "pruning_status"}));
+    }
+
+    #[test]
+    fn deserialize_pruning_status_response() {
+        let response = PruningStatusResponse {
+            enabled: true.into(),
+            running: false.into(),
+            paused: false.into(),
+            dry_run: false.into(),
+            blocks: 10.into(),
+            bytes: 2000.into(),
+            started: 1234.into(),
+            pruned_count: 42.into(),
+        };
+        let serialized = serde_json::to_string(&response).unwrap();
+        let deserialized: PruningStatusResponse = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, response);
+    }
+}
@@ -8,11 +8,20 @@ mod password_enter;
 mod password_valid;
 mod receive;
 mod receive_minimum;
+mod schedule_cancel;
+mod schedule_create;
+mod schedule_list;
 mod search_receivable;
 mod search_receivable_all;
 mod send;
+mod send_batch;
+mod wallet_activity;
 mod wallet_add;
 mod wallet_add_watch;
+mod wallet_alert_remove;
+mod wallet_alert_set;
+mod wallet_alerts;
+mod wallet_backup;
 mod wallet_balances;
 mod wallet_change_seed;
 mod wallet_contains;
@@ -22,17 +31,23 @@ mod wallet_export;
 mod wallet_frontiers;
 mod wallet_history;
 mod wallet_info;
+mod wallet_kdf_upgrade;
 mod wallet_ledger;
 mod wallet_lock;
 mod wallet_locked;
+mod wallet_mnemonic;
 mod wallet_receivable;
 mod wallet_representative;
 mod wallet_representative_set;
 mod wallet_republish;
+mod wallet_restore;
+mod wallet_sweep;
+mod wallet_sweep_status;
 mod wallet_with_account;
 mod wallet_with_count;
 mod wallet_with_password;
 mod wallet_work_get;
+mod wallet_work_status;
 mod work_get;
 mod work_set;
 
@@ -40,22 +55,36 @@ pub use account_create::*;
 pub use account_move::*;
 pub use accounts_create::*;
 pub use receive::*;
+pub use schedule_cancel::*;
+pub use schedule_create::*;
+pub use schedule_list::*;
 pub use send::*;
+pub use send_batch::*;
+pub use wallet_activity::*;
 pub use wallet_add::*;
 pub use wallet_add_watch::*;
+pub use wallet_alert_set::*;
+pub use wallet_alerts::*;
+pub use wallet_backup::*;
 pub use wallet_balances::*;
 pub use wallet_change_seed::*;
 pub use wallet_create::*;
 pub use wallet_export::*;
 pub use wallet_history::*;
 pub use wallet_info::*;
+pub use wallet_kdf_upgrade::*;
 pub use wallet_ledger::*;
+pub use wallet_mnemonic::*;
 pub use wallet_receivable::*;
 pub use wallet_representative::*;
 pub use wallet_representative_set::*;
+pub use wallet_restore::*;
+pub use wallet_sweep::*;
+pub use wallet_sweep_status::*;
 pub use wallet_with_account::*;
 pub use wallet_with_count::*;
 pub use wallet_with_password::*;
 pub use wallet_work_get::*;
+pub use wallet_work_status::*;
 pub use work_get::*;
 pub use work_set::*;
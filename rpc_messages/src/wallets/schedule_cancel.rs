@@ -0,0 +1,34 @@
+use crate::{RpcCommand, RpcU64};
+use rsban_core::WalletId;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn schedule_cancel(wallet: WalletId, id: u64) -> Self {
+        Self::ScheduleCancel(ScheduleCancelArgs {
+            wallet,
+            id: id.into(),
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ScheduleCancelArgs {
+    pub wallet: WalletId,
+    pub id: RpcU64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_schedule_cancel_command() {
+        let json = r#"{
+            "action": "schedule_cancel",
+            "wallet": "0000000000000000000000000000000000000000000000000000000000000001",
+            "id": "42"
+        }"#;
+        let deserialized: RpcCommand = serde_json::from_str(json).unwrap();
+        assert_eq!(deserialized, RpcCommand::schedule_cancel(1.into(), 42));
+    }
+}
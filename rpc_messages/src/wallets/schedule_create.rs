@@ -0,0 +1,116 @@
+use crate::{RpcCommand, RpcU32, RpcU64};
+use rsban_core::{Account, Amount, BlockHash, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn schedule_create(args: ScheduleCreateArgs) -> Self {
+        Self::ScheduleCreate(args)
+    }
+}
+
+/// Creates a recurring payment. `cadence` is a cron expression evaluated in UTC,
+/// `start` and `end` are unix timestamps in seconds. Occurrences that were missed
+/// while the node was offline are paid one after another when it is running again.
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ScheduleCreateArgs {
+    pub wallet: WalletId,
+    pub source: Account,
+    pub destination: Account,
+    pub amount: Amount,
+    pub cadence: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<RpcU64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<RpcU64>,
+}
+
+impl ScheduleCreateArgs {
+    pub fn new(
+        wallet: WalletId,
+        source: Account,
+        destination: Account,
+        amount: Amount,
+        cadence: impl Into<String>,
+    ) -> Self {
+        Self {
+            wallet,
+            source,
+            destination,
+            amount,
+            cadence: cadence.into(),
+            start: None,
+            end: None,
+        }
+    }
+
+    pub fn with_start(mut self, start: u64) -> Self {
+        self.start = Some(start.into());
+        self
+    }
+
+    pub fn with_end(mut self, end: u64) -> Self {
+        self.end = Some(end.into());
+        self
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PaymentScheduleDto {
+    pub id: RpcU64,
+    pub wallet: WalletId,
+    pub source: Account,
+    pub destination: Account,
+    pub amount: Amount,
+    pub cadence: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<RpcU64>,
+    pub next: RpcU64,
+    pub occurrences: RpcU64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_block: Option<BlockHash>,
+    pub status: String,
+    /// Failed attempts to pay the next occurrence
+    pub failures: RpcU32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_schedule_create_command() {
+        let command = RpcCommand::schedule_create(
+            ScheduleCreateArgs::new(
+                1.into(),
+                Account::from(2),
+                Account::from(3),
+                Amount::raw(4),
+                "0 12 1 * *",
+            )
+            .with_end(1_800_000_000),
+        );
+        let serialized = serde_json::to_value(&command).unwrap();
+        assert_eq!(serialized["action"], "schedule_create");
+        assert_eq!(serialized["amount"], "4");
+        assert_eq!(serialized["cadence"], "0 12 1 * *");
+        assert_eq!(serialized["end"], "1800000000");
+        assert!(serialized.get("start").is_none());
+    }
+
+    #[test]
+    fn deserialize_schedule_create_command() {
+        let command = RpcCommand::schedule_create(
+            ScheduleCreateArgs::new(
+                1.into(),
+                Account::from(2),
+                Account::from(3),
+                Amount::raw(4),
+                "@daily",
+            )
+            .with_start(1_700_000_000),
+        );
+        let json = serde_json::to_string(&command).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, command);
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{PaymentScheduleDto, RpcCommand, WalletRpcMessage};
+use rsban_core::WalletId;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn schedule_list(wallet: WalletId) -> Self {
+        Self::ScheduleList(WalletRpcMessage::new(wallet))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ScheduleListResponse {
+    pub schedules: Vec<PaymentScheduleDto>,
+}
+
+impl ScheduleListResponse {
+    pub fn new(schedules: Vec<PaymentScheduleDto>) -> Self {
+        Self { schedules }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_schedule_list_command() {
+        assert_eq!(
+            serde_json::to_string_pretty(&RpcCommand::schedule_list(1.into())).unwrap(),
+            r#"{
+  "action": "schedule_list",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000001"
+}"#
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{AccountArg, RpcBool, RpcCommand};
+use rsban_core::{Account, Amount, BlockHash, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn send_batch(args: SendBatchArgs) -> Self {
+        Self::SendBatch(args)
+    }
+
+    pub fn send_batch_status(source: Account) -> Self {
+        Self::SendBatchStatus(AccountArg::new(source))
+    }
+}
+
+/// Sends all payments from one source account. Every send needs a unique id,
+/// so a batch that failed part way can be submitted again to resume it.
+/// The payments are sent in the background, see `send_batch_status`.
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SendBatchArgs {
+    pub wallet: WalletId,
+    pub source: Account,
+    pub sends: Vec<BatchSendDto>,
+}
+
+impl SendBatchArgs {
+    pub fn new(wallet: WalletId, source: Account, sends: Vec<BatchSendDto>) -> Self {
+        Self {
+            wallet,
+            source,
+            sends,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BatchSendDto {
+    pub destination: Account,
+    pub amount: Amount,
+    pub id: String,
+}
+
+impl BatchSendDto {
+    pub fn new(destination: Account, amount: Amount, id: impl Into<String>) -> Self {
+        Self {
+            destination,
+            amount,
+            id: id.into(),
+        }
+    }
+}
+
+/// Progress of the latest batch send of a source account
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SendBatchResponse {
+    pub done: RpcBool,
+    pub sends: Vec<BatchSendResultDto>,
+}
+
+impl SendBatchResponse {
+    pub fn new(done: bool, sends: Vec<BatchSendResultDto>) -> Self {
+        Self {
+            done: done.into(),
+            sends,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BatchSendResultDto {
+    pub id: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block: Option<BlockHash>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_send_batch_command() {
+        let command = RpcCommand::send_batch(SendBatchArgs::new(
+            1.into(),
+            Account::from(2),
+            vec![BatchSendDto::new(
+                Account::from(3),
+                Amount::raw(4),
+                "payout-1",
+            )],
+        ));
+        let serialized = serde_json::to_value(&command).unwrap();
+        assert_eq!(serialized["action"], "send_batch");
+        assert_eq!(serialized["sends"][0]["amount"], "4");
+        assert_eq!(serialized["sends"][0]["id"], "payout-1");
+    }
+
+    #[test]
+    fn deserialize_send_batch_command() {
+        let command = RpcCommand::send_batch(SendBatchArgs::new(
+            1.into(),
+            Account::from(2),
+            vec![
+                BatchSendDto::new(Account::from(3), Amount::raw(4), "a"),
+                BatchSendDto::new(Account::from(5), Amount::raw(6), "b"),
+            ],
+        ));
+        let json = serde_json::to_string(&command).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, command);
+    }
+
+    #[test]
+    fn serialize_send_batch_status_command() {
+        assert_eq!(
+            serde_json::to_value(RpcCommand::send_batch_status(Account::from(1))).unwrap(),
+            json!({
+                "action": "send_batch_status",
+                "account": Account::from(1)
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_send_batch_response() {
+        let response = SendBatchResponse::new(
+            false,
+            vec![
+                BatchSendResultDto {
+                    id: "a".to_owned(),
+                    status: "sent".to_owned(),
+                    block: Some(BlockHash::from(1)),
+                    error: None,
+                },
+                BatchSendResultDto {
+                    id: "b".to_owned(),
+                    status: "pending".to_owned(),
+                    block: None,
+                    error: None,
+                },
+            ],
+        );
+        let serialized = serde_json::to_value(&response).unwrap();
+        assert_eq!(serialized["done"], "false");
+        assert_eq!(
+            serialized["sends"][1],
+            json!({"id": "b", "status": "pending"})
+        );
+    }
+}
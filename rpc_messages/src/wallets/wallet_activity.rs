@@ -0,0 +1,134 @@
+use crate::{RpcCommand, RpcU64};
+use rsban_core::{Account, Amount, BlockHash, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_activity(args: WalletActivityArgs) -> Self {
+        Self::WalletActivity(args)
+    }
+}
+
+impl From<WalletId> for WalletActivityArgs {
+    fn from(value: WalletId) -> Self {
+        Self::builder(value).build()
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletActivityArgs {
+    pub wallet: WalletId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<RpcU64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<RpcU64>,
+}
+
+impl WalletActivityArgs {
+    pub fn builder(wallet: WalletId) -> WalletActivityArgsBuilder {
+        WalletActivityArgsBuilder {
+            args: Self {
+                wallet,
+                offset: None,
+                count: None,
+            },
+        }
+    }
+}
+
+pub struct WalletActivityArgsBuilder {
+    args: WalletActivityArgs,
+}
+
+impl WalletActivityArgsBuilder {
+    pub fn offset(mut self, value: u64) -> Self {
+        self.args.offset = Some(value.into());
+        self
+    }
+
+    pub fn count(mut self, value: u64) -> Self {
+        self.args.count = Some(value.into());
+        self
+    }
+
+    pub fn build(self) -> WalletActivityArgs {
+        self.args
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletActivityResponse {
+    pub activity: Vec<WalletActivityEntry>,
+}
+
+impl WalletActivityResponse {
+    pub fn new(activity: Vec<WalletActivityEntry>) -> Self {
+        Self { activity }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletActivityEntry {
+    pub account: Account,
+    pub hash: BlockHash,
+    #[serde(rename = "type")]
+    pub block_type: String,
+    pub amount: Amount,
+    pub balance: Amount,
+    pub height: RpcU64,
+    pub local_timestamp: RpcU64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_wallet_activity_command() {
+        let command =
+            RpcCommand::wallet_activity(WalletActivityArgs::builder(1.into()).count(10).build());
+        let serialized = serde_json::to_value(&command).unwrap();
+        assert_eq!(serialized["action"], "wallet_activity");
+        assert_eq!(serialized["count"], "10");
+        assert!(serialized.get("offset").is_none());
+    }
+
+    #[test]
+    fn deserialize_wallet_activity_command() {
+        let command = RpcCommand::wallet_activity(
+            WalletActivityArgs::builder(1.into())
+                .offset(20)
+                .count(10)
+                .build(),
+        );
+        let json = serde_json::to_string(&command).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, command);
+    }
+
+    #[test]
+    fn serialize_wallet_activity_entry() {
+        let entry = WalletActivityEntry {
+            account: Account::zero(),
+            hash: BlockHash::from(1),
+            block_type: "receive".to_owned(),
+            amount: Amount::raw(2),
+            balance: Amount::raw(3),
+            height: 4.into(),
+            local_timestamp: 5.into(),
+        };
+        let serialized = serde_json::to_value(&entry).unwrap();
+        assert_eq!(
+            serialized,
+            json!({
+                "account": "ban_1111111111111111111111111111111111111111111111111111hifc8npp",
+                "hash": "0000000000000000000000000000000000000000000000000000000000000001",
+                "type": "receive",
+                "amount": "2",
+                "balance": "3",
+                "height": "4",
+                "local_timestamp": "5"
+            })
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use crate::{RpcCommand, WalletWithAccountArgs};
+use rsban_core::{Account, WalletId};
+
+impl RpcCommand {
+    pub fn wallet_alert_remove(wallet: WalletId, account: Account) -> Self {
+        Self::WalletAlertRemove(WalletWithAccountArgs::new(wallet, account))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_wallet_alert_remove_command() {
+        let command = RpcCommand::wallet_alert_remove(1.into(), Account::from(2));
+        let serialized = serde_json::to_value(&command).unwrap();
+        assert_eq!(serialized["action"], "wallet_alert_remove");
+        let deserialized: RpcCommand = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, command);
+    }
+}
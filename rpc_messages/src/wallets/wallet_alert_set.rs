@@ -0,0 +1,47 @@
+use crate::RpcCommand;
+use rsban_core::{Account, Amount, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_alert_set(args: WalletAlertSetArgs) -> Self {
+        Self::WalletAlertSet(args)
+    }
+}
+
+/// Raises a balance alert whenever the confirmed balance of the account
+/// crosses the threshold
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletAlertSetArgs {
+    pub wallet: WalletId,
+    pub account: Account,
+    pub threshold: Amount,
+}
+
+impl WalletAlertSetArgs {
+    pub fn new(wallet: WalletId, account: Account, threshold: Amount) -> Self {
+        Self {
+            wallet,
+            account,
+            threshold,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_wallet_alert_set_command() {
+        let command = RpcCommand::wallet_alert_set(WalletAlertSetArgs::new(
+            1.into(),
+            Account::from(2),
+            Amount::raw(3),
+        ));
+        let serialized = serde_json::to_value(&command).unwrap();
+        assert_eq!(serialized["action"], "wallet_alert_set");
+        assert_eq!(serialized["threshold"], "3");
+        let deserialized: RpcCommand = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, command);
+    }
+}
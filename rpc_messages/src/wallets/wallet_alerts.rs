@@ -0,0 +1,58 @@
+use crate::{RpcCommand, WalletRpcMessage};
+use rsban_core::{Account, Amount, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_alerts(wallet: WalletId) -> Self {
+        Self::WalletAlerts(WalletRpcMessage::new(wallet))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletAlertsResponse {
+    pub alerts: Vec<WalletAlertDto>,
+}
+
+impl WalletAlertsResponse {
+    pub fn new(alerts: Vec<WalletAlertDto>) -> Self {
+        Self { alerts }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletAlertDto {
+    pub account: Account,
+    pub threshold: Amount,
+}
+
+impl WalletAlertDto {
+    pub fn new(account: Account, threshold: Amount) -> Self {
+        Self { account, threshold }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_wallet_alerts_command() {
+        let command = RpcCommand::wallet_alerts(1.into());
+        let serialized = serde_json::to_value(&command).unwrap();
+        assert_eq!(serialized["action"], "wallet_alerts");
+    }
+
+    #[test]
+    fn serialize_wallet_alerts_response() {
+        let response =
+            WalletAlertsResponse::new(vec![WalletAlertDto::new(Account::zero(), Amount::raw(5))]);
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({"alerts": [{
+                "account": "ban_1111111111111111111111111111111111111111111111111111hifc8npp",
+                "threshold": "5"
+            }]})
+        );
+    }
+}
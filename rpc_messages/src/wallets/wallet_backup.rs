@@ -0,0 +1,50 @@
+use crate::{RpcCommand, WalletWithPasswordArgs};
+use rsban_core::{WalletBackup, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_backup(wallet: WalletId, password: String) -> Self {
+        Self::WalletBackup(WalletWithPasswordArgs::new(wallet, password))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletBackupResponse {
+    pub backup: WalletBackup,
+}
+
+impl WalletBackupResponse {
+    pub fn new(backup: WalletBackup) -> Self {
+        Self { backup }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_backup_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_backup(
+                WalletId::zero(),
+                "password".to_string()
+            ))
+            .unwrap(),
+            r#"{
+  "action": "wallet_backup",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+  "password": "password"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_backup_command() {
+        let cmd = RpcCommand::wallet_backup(WalletId::zero(), "password".to_string());
+        let serialized = serde_json::to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
@@ -11,7 +11,11 @@ impl RpcCommand {
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct WalletChangeSeedArgs {
     pub wallet: WalletId,
-    pub seed: RawKey,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<RawKey>,
+    /// BIP39 mnemonic which can be used instead of the hex encoded seed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub count: Option<RpcU32>,
 }
@@ -20,7 +24,17 @@ impl WalletChangeSeedArgs {
     pub fn new(wallet: WalletId, seed: RawKey) -> WalletChangeSeedArgs {
         WalletChangeSeedArgs {
             wallet,
-            seed,
+            seed: Some(seed),
+            mnemonic: None,
+            count: None,
+        }
+    }
+
+    pub fn with_mnemonic(wallet: WalletId, mnemonic: impl Into<String>) -> WalletChangeSeedArgs {
+        WalletChangeSeedArgs {
+            wallet,
+            seed: None,
+            mnemonic: Some(mnemonic.into()),
             count: None,
         }
     }
@@ -39,7 +53,8 @@ impl WalletChangeSeedArgsBuilder {
         Self {
             args: WalletChangeSeedArgs {
                 wallet,
-                seed,
+                seed: Some(seed),
+                mnemonic: None,
                 count: None,
             },
         }
@@ -70,7 +85,8 @@ impl From<WalletWithSeedArgs> for WalletChangeSeedArgs {
     fn from(wallet_with_seed: WalletWithSeedArgs) -> Self {
         Self {
             wallet: wallet_with_seed.wallet,
-            seed: wallet_with_seed.seed,
+            seed: Some(wallet_with_seed.seed),
+            mnemonic: None,
             count: None,
         }
     }
@@ -130,10 +146,12 @@ mod tests {
                 );
                 assert_eq!(
                     args.seed,
-                    RawKey::decode_hex(
-                        "74F2B37AAD20F4A260F0A5B3CB3D7FB51673212263E58A380BC10474BB039CEE"
+                    Some(
+                        RawKey::decode_hex(
+                            "74F2B37AAD20F4A260F0A5B3CB3D7FB51673212263E58A380BC10474BB039CEE"
+                        )
+                        .unwrap()
                     )
-                    .unwrap()
                 );
                 assert_eq!(args.count, None);
             }
@@ -155,8 +173,12 @@ mod tests {
         );
         assert_eq!(
             deserialized.seed,
-            RawKey::decode_hex("74F2B37AAD20F4A260F0A5B3CB3D7FB51673212263E58A380BC10474BB039CEE")
+            Some(
+                RawKey::decode_hex(
+                    "74F2B37AAD20F4A260F0A5B3CB3D7FB51673212263E58A380BC10474BB039CEE"
+                )
                 .unwrap()
+            )
         );
         assert_eq!(deserialized.count, None);
     }
@@ -175,8 +197,12 @@ mod tests {
         );
         assert_eq!(
             deserialized.seed,
-            RawKey::decode_hex("74F2B37AAD20F4A260F0A5B3CB3D7FB51673212263E58A380BC10474BB039CEE")
+            Some(
+                RawKey::decode_hex(
+                    "74F2B37AAD20F4A260F0A5B3CB3D7FB51673212263E58A380BC10474BB039CEE"
+                )
                 .unwrap()
+            )
         );
         assert_eq!(deserialized.count, Some(5.into()));
     }
@@ -192,7 +218,7 @@ mod tests {
             .build();
 
         assert_eq!(args.wallet, wallet);
-        assert_eq!(args.seed, seed);
+        assert_eq!(args.seed, Some(seed));
         assert_eq!(args.count, Some(count.into()));
     }
 
@@ -204,7 +230,7 @@ mod tests {
         let args = WalletChangeSeedArgs::builder(wallet, seed).build();
 
         assert_eq!(args.wallet, wallet);
-        assert_eq!(args.seed, seed);
+        assert_eq!(args.seed, Some(seed));
         assert_eq!(args.count, None);
     }
 
@@ -217,10 +243,22 @@ mod tests {
         let args: WalletChangeSeedArgs = wallet_with_seed.into();
 
         assert_eq!(args.wallet, wallet);
-        assert_eq!(args.seed, seed);
+        assert_eq!(args.seed, Some(seed));
         assert_eq!(args.count, None);
     }
 
+    #[test]
+    fn deserialize_wallet_change_seed_args_mnemonic() {
+        let json = r#"{"wallet":"000D1BAEC8EC208142C99059B393051BAC8380F9B5A2E6B2489A277D81789F3F","mnemonic":"abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"}"#;
+        let deserialized: WalletChangeSeedArgs = serde_json::from_str(json).unwrap();
+
+        assert_eq!(deserialized.seed, None);
+        assert_eq!(
+            deserialized.mnemonic.as_deref(),
+            Some("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about")
+        );
+    }
+
     #[test]
     fn deserialize_wallet_change_seed_dto() {
         let json = r#"{"success":"","last_restored_account":"ban_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3","restored_count":"15"}"#;
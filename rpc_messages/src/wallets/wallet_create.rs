@@ -12,11 +12,24 @@ impl RpcCommand {
 pub struct WalletCreateArgs {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub seed: Option<RawKey>,
+    /// BIP39 mnemonic which can be used instead of the hex encoded seed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
 }
 
 impl WalletCreateArgs {
     pub fn new(seed: Option<RawKey>) -> Self {
-        WalletCreateArgs { seed }
+        WalletCreateArgs {
+            seed,
+            mnemonic: None,
+        }
+    }
+
+    pub fn with_mnemonic(mnemonic: impl Into<String>) -> Self {
+        WalletCreateArgs {
+            seed: None,
+            mnemonic: Some(mnemonic.into()),
+        }
     }
 }
 
@@ -56,6 +69,20 @@ mod tests {
         )
     }
 
+    #[test]
+    fn serialize_wallet_create_command_mnemonic() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::WalletCreate(WalletCreateArgs::with_mnemonic(
+                "legal winner thank year wave sausage worth useful legal winner thank yellow"
+            )))
+            .unwrap(),
+            r#"{
+  "action": "wallet_create",
+  "mnemonic": "legal winner thank year wave sausage worth useful legal winner thank yellow"
+}"#
+        )
+    }
+
     #[test]
     fn deserialize_wallet_create_command_seed_none() {
         let cmd = RpcCommand::wallet_create(None);
@@ -0,0 +1,125 @@
+use crate::{RpcCommand, RpcU32};
+use rsban_core::{KdfParams, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_kdf_upgrade(args: WalletKdfUpgradeArgs) -> Self {
+        Self::WalletKdfUpgrade(args)
+    }
+}
+
+/// Parameters that are not given are taken from the node's `[node.wallet_kdf]` config
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletKdfUpgradeArgs {
+    pub wallet: WalletId,
+    pub password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_cost: Option<RpcU32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_cost: Option<RpcU32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lanes: Option<RpcU32>,
+}
+
+impl WalletKdfUpgradeArgs {
+    pub fn new(wallet: WalletId, password: String) -> Self {
+        Self {
+            wallet,
+            password,
+            variant: None,
+            memory_cost: None,
+            time_cost: None,
+            lanes: None,
+        }
+    }
+
+    pub fn builder(wallet: WalletId, password: String) -> WalletKdfUpgradeArgsBuilder {
+        WalletKdfUpgradeArgsBuilder {
+            args: Self::new(wallet, password),
+        }
+    }
+}
+
+pub struct WalletKdfUpgradeArgsBuilder {
+    args: WalletKdfUpgradeArgs,
+}
+
+impl WalletKdfUpgradeArgsBuilder {
+    pub fn variant(mut self, variant: impl Into<String>) -> Self {
+        self.args.variant = Some(variant.into());
+        self
+    }
+
+    pub fn memory_cost(mut self, memory_cost: u32) -> Self {
+        self.args.memory_cost = Some(memory_cost.into());
+        self
+    }
+
+    pub fn time_cost(mut self, time_cost: u32) -> Self {
+        self.args.time_cost = Some(time_cost.into());
+        self
+    }
+
+    pub fn lanes(mut self, lanes: u32) -> Self {
+        self.args.lanes = Some(lanes.into());
+        self
+    }
+
+    pub fn build(self) -> WalletKdfUpgradeArgs {
+        self.args
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletKdfUpgradeResponse {
+    pub kdf: KdfParams,
+}
+
+impl WalletKdfUpgradeResponse {
+    pub fn new(kdf: KdfParams) -> Self {
+        Self { kdf }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_wallet_kdf_upgrade_command() {
+        let command = RpcCommand::wallet_kdf_upgrade(
+            WalletKdfUpgradeArgs::builder(1.into(), "password".to_string())
+                .variant("argon2id")
+                .memory_cost(1024)
+                .build(),
+        );
+        let serialized = serde_json::to_value(&command).unwrap();
+        assert_eq!(serialized["action"], "wallet_kdf_upgrade");
+        assert_eq!(serialized["variant"], "argon2id");
+        assert_eq!(serialized["memory_cost"], "1024");
+        assert!(serialized.get("time_cost").is_none());
+    }
+
+    #[test]
+    fn deserialize_wallet_kdf_upgrade_command() {
+        let json = r#"{
+            "action": "wallet_kdf_upgrade",
+            "wallet": "0000000000000000000000000000000000000000000000000000000000000001",
+            "password": "password",
+            "time_cost": "3",
+            "lanes": "2"
+        }"#;
+        let deserialized: RpcCommand = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            deserialized,
+            RpcCommand::wallet_kdf_upgrade(
+                WalletKdfUpgradeArgs::builder(1.into(), "password".to_string())
+                    .time_cost(3)
+                    .lanes(2)
+                    .build()
+            )
+        );
+    }
+}
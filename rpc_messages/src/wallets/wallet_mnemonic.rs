@@ -0,0 +1,47 @@
+use crate::{common::WalletRpcMessage, RpcCommand};
+use rsban_core::WalletId;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_mnemonic(wallet: WalletId) -> Self {
+        Self::WalletMnemonic(WalletRpcMessage::new(wallet))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct MnemonicResponse {
+    pub mnemonic: String,
+}
+
+impl MnemonicResponse {
+    pub fn new(mnemonic: impl Into<String>) -> Self {
+        Self {
+            mnemonic: mnemonic.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_mnemonic_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_mnemonic(WalletId::zero())).unwrap(),
+            r#"{
+  "action": "wallet_mnemonic",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_mnemonic_command() {
+        let cmd = RpcCommand::wallet_mnemonic(WalletId::zero());
+        let serialized = serde_json::to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::RpcCommand;
+use rsban_core::WalletBackup;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_restore(backup: WalletBackup, password: String) -> Self {
+        Self::WalletRestore(WalletRestoreArgs::new(backup, password))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletRestoreArgs {
+    pub backup: WalletBackup,
+    pub password: String,
+}
+
+impl WalletRestoreArgs {
+    pub fn new(backup: WalletBackup, password: String) -> Self {
+        Self { backup, password }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsban_core::KeyDerivationFunction;
+
+    #[test]
+    fn deserialize_wallet_restore_command() {
+        let json = r#"{
+            "action": "wallet_restore",
+            "password": "password",
+            "backup": {
+                "version": 1,
+                "kdf": {"algorithm": "argon2d", "version": 16, "mem_cost": 8, "time_cost": 1, "lanes": 1},
+                "salt": "00",
+                "iv": "01",
+                "ciphertext": "02",
+                "mac": "03"
+            }
+        }"#;
+        let deserialized: RpcCommand = serde_json::from_str(json).unwrap();
+        let RpcCommand::WalletRestore(args) = deserialized else {
+            panic!("Deserialized to wrong variant");
+        };
+        assert_eq!(args.password, "password");
+        assert_eq!(args.backup.kdf, KeyDerivationFunction::new(8).params());
+        assert_eq!(args.backup.ciphertext, "02");
+    }
+}
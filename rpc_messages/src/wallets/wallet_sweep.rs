@@ -0,0 +1,92 @@
+use crate::RpcCommand;
+use rsban_core::{Account, Amount, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_sweep(args: WalletSweepArgs) -> Self {
+        Self::WalletSweep(args)
+    }
+}
+
+/// Moves the funds of all accounts of a wallet, or of the given `accounts`,
+/// to `destination`. Receivable blocks below `threshold` are left alone.
+/// The sweep runs in the background, see `wallet_sweep_status`.
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletSweepArgs {
+    pub wallet: WalletId,
+    pub destination: Account,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accounts: Option<Vec<Account>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<Amount>,
+}
+
+impl WalletSweepArgs {
+    pub fn builder(wallet: WalletId, destination: Account) -> WalletSweepArgsBuilder {
+        WalletSweepArgsBuilder {
+            args: WalletSweepArgs {
+                wallet,
+                destination,
+                accounts: None,
+                threshold: None,
+            },
+        }
+    }
+}
+
+pub struct WalletSweepArgsBuilder {
+    args: WalletSweepArgs,
+}
+
+impl WalletSweepArgsBuilder {
+    pub fn accounts(mut self, accounts: Vec<Account>) -> Self {
+        self.args.accounts = Some(accounts);
+        self
+    }
+
+    pub fn threshold(mut self, threshold: Amount) -> Self {
+        self.args.threshold = Some(threshold);
+        self
+    }
+
+    pub fn build(self) -> WalletSweepArgs {
+        self.args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_wallet_sweep_command() {
+        let command = RpcCommand::wallet_sweep(
+            WalletSweepArgs::builder(WalletId::from(1), Account::from(2))
+                .accounts(vec![Account::from(3)])
+                .threshold(Amount::raw(4))
+                .build(),
+        );
+
+        assert_eq!(
+            serde_json::to_value(&command).unwrap(),
+            json!({
+                "action": "wallet_sweep",
+                "wallet": WalletId::from(1),
+                "destination": Account::from(2),
+                "accounts": [Account::from(3)],
+                "threshold": "4"
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_wallet_sweep_command() {
+        let command = RpcCommand::wallet_sweep(
+            WalletSweepArgs::builder(WalletId::from(1), Account::from(2)).build(),
+        );
+        let serialized = serde_json::to_string(&command).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(command, deserialized);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{RpcBool, RpcCommand, RpcU64, WalletRpcMessage};
+use rsban_core::{Account, Amount, BlockHash, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_sweep_status(wallet: WalletId) -> Self {
+        Self::WalletSweepStatus(WalletRpcMessage::new(wallet))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletSweepStatusResponse {
+    pub destination: Account,
+    pub threshold: Amount,
+    pub done: RpcBool,
+    /// Number of accounts that were processed
+    pub processed: RpcU64,
+    pub total: RpcU64,
+    /// Sum of all amounts that were sent to the destination
+    pub swept: Amount,
+    pub accounts: Vec<SweepAccountDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SweepAccountDto {
+    pub account: Account,
+    pub status: String,
+    pub received: Vec<BlockHash>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent: Option<BlockHash>,
+    pub amount: Amount,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_wallet_sweep_status_command() {
+        assert_eq!(
+            serde_json::to_value(RpcCommand::wallet_sweep_status(WalletId::from(1))).unwrap(),
+            json!({
+                "action": "wallet_sweep_status",
+                "wallet": WalletId::from(1)
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_sweep_account_dto() {
+        let dto = SweepAccountDto {
+            account: Account::from(1),
+            status: "swept".to_string(),
+            received: vec![BlockHash::from(2)],
+            sent: Some(BlockHash::from(3)),
+            amount: Amount::raw(4),
+            error: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&dto).unwrap(),
+            json!({
+                "account": Account::from(1),
+                "status": "swept",
+                "received": [BlockHash::from(2)],
+                "sent": BlockHash::from(3),
+                "amount": "4"
+            })
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{RpcBool, RpcCommand, RpcU64, WalletRpcMessage};
+use rsban_core::{Account, Root, WalletId, WorkNonce};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_work_status(wallet: WalletId) -> Self {
+        Self::WalletWorkStatus(WalletRpcMessage::new(wallet))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletWorkStatusResponse {
+    /// Number of accounts whose cached work is valid for any next block
+    pub valid: RpcU64,
+    pub receive_only: RpcU64,
+    pub invalid: RpcU64,
+    pub missing: RpcU64,
+    /// Number of accounts whose work is currently regenerated
+    pub regenerating: RpcU64,
+    pub accounts: Vec<WorkCacheDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WorkCacheDto {
+    pub account: Account,
+    pub root: Root,
+    pub work: WorkNonce,
+    pub difficulty: RpcU64,
+    pub state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_active: Option<RpcU64>,
+    pub regenerating: RpcBool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_wallet_work_status_command() {
+        assert_eq!(
+            serde_json::to_value(RpcCommand::wallet_work_status(WalletId::from(1))).unwrap(),
+            json!({
+                "action": "wallet_work_status",
+                "wallet": WalletId::from(1)
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_work_cache_dto() {
+        let dto = WorkCacheDto {
+            account: Account::from(1),
+            root: Root::from(2),
+            work: 3.into(),
+            difficulty: 4.into(),
+            state: "receive_only".to_string(),
+            last_active: None,
+            regenerating: true.into(),
+        };
+        assert_eq!(
+            serde_json::to_value(&dto).unwrap(),
+            json!({
+                "account": Account::from(1),
+                "root": Root::from(2),
+                "work": "0000000000000003",
+                "difficulty": "4",
+                "state": "receive_only",
+                "regenerating": "true"
+            })
+        );
+    }
+}
@@ -0,0 +1,18 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{AccountAtArgs, AccountBalanceAtResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn account_balance_at(
+        &self,
+        args: AccountAtArgs,
+    ) -> anyhow::Result<AccountBalanceAtResponse> {
+        let tx = self.node.ledger.read_txn();
+        let block = self.load_account_block_at(&tx, &args)?;
+        Ok(AccountBalanceAtResponse {
+            balance: block.balance(),
+            block: block.hash(),
+            height: block.height().into(),
+            local_timestamp: block.timestamp().into(),
+        })
+    }
+}
@@ -85,6 +85,7 @@ impl<'a> AccountHistoryHelper<'a> {
     pub(crate) fn account_history(mut self) -> anyhow::Result<AccountHistoryResponse> {
         let tx = self.ledger.read_txn();
         self.initialize(&tx)?;
+        self.seek_to_offset(&tx);
         let mut history = Vec::new();
         let mut next_block = self.ledger.any().get_block(&tx, &self.current_block_hash);
         while let Some(block) = next_block {
@@ -107,6 +108,28 @@ impl<'a> AccountHistoryHelper<'a> {
         Ok(self.create_response(history))
     }
 
+    /// Jumps directly to the block at the requested offset if the block height
+    /// index is available, instead of walking the chain block by block
+    fn seek_to_offset(&mut self, tx: &LmdbReadTransaction) {
+        if self.offset == 0 || !self.ledger.block_height_index_enabled() {
+            return;
+        }
+        let Some(block) = self.ledger.any().get_block(tx, &self.current_block_hash) else {
+            return;
+        };
+
+        let height = if self.reverse {
+            block.height().checked_add(self.offset)
+        } else {
+            block.height().checked_sub(self.offset)
+        };
+
+        self.current_block_hash = height
+            .and_then(|h| self.ledger.store.block_height.get(tx, &self.account, h))
+            .unwrap_or_default();
+        self.offset = 0;
+    }
+
     fn go_to_next_block(&mut self, tx: &LmdbReadTransaction, block: &Block) -> Option<SavedBlock> {
         self.current_block_hash = if self.reverse {
             self.ledger
@@ -0,0 +1,54 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::bail;
+use rsban_core::PendingKey;
+use rsban_rpc_messages::{
+    unwrap_u64_or, unwrap_u64_or_zero, AccountIncomingArgs, AccountIncomingResponse,
+    IncomingSendDto,
+};
+
+impl RpcCommandHandler {
+    pub(crate) fn account_incoming(
+        &self,
+        args: AccountIncomingArgs,
+    ) -> anyhow::Result<AccountIncomingResponse> {
+        if !self.node.ledger.incoming_index_enabled() {
+            bail!("Incoming sends index is not enabled");
+        }
+
+        let count = unwrap_u64_or(args.count, 1024);
+        let since = unwrap_u64_or_zero(args.since);
+        let tx = self.node.ledger.read_txn();
+
+        let incoming = self
+            .node
+            .store
+            .incoming
+            .iter(&tx, &args.account, since)
+            .filter(|send| args.source.map_or(true, |source| send.source == source))
+            .take(count as usize)
+            .map(|send| {
+                let receivable = self
+                    .node
+                    .ledger
+                    .any()
+                    .get_pending(&tx, &PendingKey::new(args.account, send.hash))
+                    .is_some();
+                let confirmed = self
+                    .node
+                    .ledger
+                    .confirmed()
+                    .block_exists_or_pruned(&tx, &send.hash);
+                IncomingSendDto {
+                    hash: send.hash,
+                    source: send.source,
+                    amount: send.amount,
+                    local_timestamp: send.timestamp.into(),
+                    confirmed: confirmed.into(),
+                    received: (!receivable).into(),
+                }
+            })
+            .collect();
+
+        Ok(AccountIncomingResponse { incoming })
+    }
+}
@@ -0,0 +1,24 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::anyhow;
+use rsban_rpc_messages::{AccountAtArgs, AccountRepresentativeAtResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn account_representative_at(
+        &self,
+        args: AccountAtArgs,
+    ) -> anyhow::Result<AccountRepresentativeAtResponse> {
+        let tx = self.node.ledger.read_txn();
+        let block = self.load_account_block_at(&tx, &args)?;
+        let representative = self
+            .node
+            .ledger
+            .block_representative(&tx, &block)
+            .ok_or_else(|| anyhow!(Self::BLOCK_NOT_FOUND))?;
+        Ok(AccountRepresentativeAtResponse {
+            representative: representative.into(),
+            block: block.hash(),
+            height: block.height().into(),
+            local_timestamp: block.timestamp().into(),
+        })
+    }
+}
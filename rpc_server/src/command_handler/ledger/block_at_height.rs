@@ -0,0 +1,18 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::anyhow;
+use rsban_rpc_messages::{BlockAtHeightArgs, HashRpcMessage};
+
+impl RpcCommandHandler {
+    pub(crate) fn block_at_height(
+        &self,
+        args: BlockAtHeightArgs,
+    ) -> anyhow::Result<HashRpcMessage> {
+        let tx = self.node.ledger.read_txn();
+        self.load_account(&tx, &args.account)?;
+        self.node
+            .ledger
+            .account_hash_at_height(&tx, &args.account, args.height.into())
+            .map(HashRpcMessage::new)
+            .ok_or_else(|| anyhow!(Self::BLOCK_NOT_FOUND))
+    }
+}
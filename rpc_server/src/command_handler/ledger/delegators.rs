@@ -12,6 +12,20 @@ impl RpcCommandHandler {
 
         let mut delegators: HashMap<Account, Amount> = HashMap::new();
         let tx = self.node.ledger.read_txn();
+
+        if self.node.ledger.delegators_index_enabled() {
+            let start = start_account.inc().unwrap_or_default();
+            delegators.extend(
+                self.node
+                    .store
+                    .delegators
+                    .iter(&tx, &representative, &start)
+                    .filter(|(_, balance)| *balance >= threshold)
+                    .take(count as usize),
+            );
+            return DelegatorsResponse::new(delegators);
+        }
+
         let mut iter = self
             .node
             .store
@@ -1,5 +1,5 @@
 use crate::command_handler::RpcCommandHandler;
-use rsban_core::PublicKey;
+use rsban_core::{Account, PublicKey};
 use rsban_rpc_messages::{AccountArg, CountResponse};
 
 impl RpcCommandHandler {
@@ -8,6 +8,17 @@ impl RpcCommandHandler {
         let mut count = 0;
 
         let tx = self.node.ledger.read_txn();
+
+        if self.node.ledger.delegators_index_enabled() {
+            count = self
+                .node
+                .store
+                .delegators
+                .iter(&tx, &representative, &Account::zero())
+                .count() as u64;
+            return CountResponse::new(count);
+        }
+
         let mut iter = self.node.store.account.begin(&tx);
 
         while let Some((_, info)) = iter.current() {
@@ -0,0 +1,59 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::bail;
+use rsban_rpc_messages::{
+    AccountBalanceDto, ActiveAccountsDto, BalanceBucketDto, EpochAccountsDto,
+    LedgerAnalyticsResponse,
+};
+
+impl RpcCommandHandler {
+    pub(crate) fn ledger_analytics(&self) -> anyhow::Result<LedgerAnalyticsResponse> {
+        if !self.node.ledger_analytics.enabled() {
+            bail!("Ledger analytics are disabled");
+        }
+        let Some(report) = self.node.ledger_analytics.report() else {
+            bail!("Ledger analytics are not available yet");
+        };
+
+        Ok(LedgerAnalyticsResponse {
+            created: report.created.into(),
+            scan_duration_ms: (report.scan_duration.as_millis() as u64).into(),
+            accounts: report.accounts.into(),
+            balance_distribution: report
+                .balance_distribution
+                .iter()
+                .map(|bucket| BalanceBucketDto {
+                    min: bucket.min,
+                    max: bucket.max,
+                    accounts: bucket.accounts.into(),
+                    balance: bucket.balance,
+                })
+                .collect(),
+            top_accounts: report
+                .top_accounts
+                .iter()
+                .map(|(account, balance)| AccountBalanceDto {
+                    account: *account,
+                    balance: *balance,
+                })
+                .collect(),
+            active_accounts: report
+                .active_accounts
+                .iter()
+                .map(|(window, accounts)| ActiveAccountsDto {
+                    window: window.as_secs().into(),
+                    accounts: (*accounts).into(),
+                })
+                .collect(),
+            epochs: report
+                .epochs
+                .iter()
+                .map(|(epoch, accounts)| EpochAccountsDto {
+                    epoch: epoch.epoch_number().into(),
+                    accounts: (*accounts).into(),
+                })
+                .collect(),
+            online_weight: report.online_weight,
+            nakamoto_coefficient: report.nakamoto_coefficient.map(|c| c.into()),
+        })
+    }
+}
@@ -1,7 +1,10 @@
 mod account_balance;
+mod account_balance_at;
 mod account_history;
+mod account_incoming;
 mod account_info;
 mod account_representative;
+mod account_representative_at;
 mod account_weight;
 mod accounts_balances;
 mod accounts_frontiers;
@@ -9,6 +12,7 @@ mod accounts_receivable;
 mod accounts_representatives;
 mod available_supply;
 mod block_account;
+mod block_at_height;
 mod block_confirm;
 mod block_count;
 mod block_info;
@@ -20,6 +24,7 @@ mod delegators_count;
 mod frontier_count;
 mod frontiers;
 mod ledger;
+mod ledger_analytics;
 mod representatives;
 mod unopened;
 
@@ -5,8 +5,9 @@ mod wallets;
 
 use anyhow::anyhow;
 use rsban_core::{Account, AccountInfo, BlockHash, SavedBlock};
+use rsban_ledger::AccountHistoryPoint;
 use rsban_node::Node;
-use rsban_rpc_messages::{RpcCommand, RpcError, StatsType};
+use rsban_rpc_messages::{AccountAtArgs, RpcCommand, RpcError, StatsType};
 use rsban_store_lmdb::Transaction;
 use serde_json::{to_value, Value};
 use std::sync::{Arc, Mutex};
@@ -41,12 +42,15 @@ impl RpcCommandHandler {
 
     fn call_handler(&self, command: RpcCommand) -> anyhow::Result<serde_json::Value> {
         self.check_control_enabled(&command)?;
+        self.check_read_only(&command)?;
         let response = match command {
             RpcCommand::AccountBalance(args) => to_value(self.account_balance(args)),
+            RpcCommand::AccountBalanceAt(args) => to_value(self.account_balance_at(args)?),
             RpcCommand::AccountBlockCount(args) => to_value(self.account_block_count(args)?),
             RpcCommand::AccountCreate(args) => to_value(self.account_create(args)?),
             RpcCommand::AccountGet(args) => to_value(account_get(args)),
             RpcCommand::AccountHistory(args) => to_value(self.account_history(args)?),
+            RpcCommand::AccountIncoming(args) => to_value(self.account_incoming(args)?),
             RpcCommand::AccountInfo(args) => to_value(self.account_info(args)?),
             RpcCommand::AccountKey(args) => to_value(account_key(args)),
             RpcCommand::AccountList(args) => to_value(self.account_list(args)?),
@@ -54,6 +58,9 @@ impl RpcCommandHandler {
             RpcCommand::AccountsReceivable(args) => to_value(self.accounts_receivable(args)),
             RpcCommand::AccountRemove(args) => to_value(self.account_remove(args)?),
             RpcCommand::AccountRepresentative(args) => to_value(self.account_representative(args)?),
+            RpcCommand::AccountRepresentativeAt(args) => {
+                to_value(self.account_representative_at(args)?)
+            }
             RpcCommand::AccountWeight(args) => to_value(self.account_weight(args)),
             RpcCommand::AccountsRepresentatives(args) => {
                 to_value(self.accounts_representatives(args))
@@ -66,9 +73,12 @@ impl RpcCommandHandler {
             RpcCommand::Blocks(args) => to_value(self.blocks(args)?),
             RpcCommand::BlockConfirm(args) => to_value(self.block_confirm(args)?),
             RpcCommand::BlockAccount(args) => to_value(self.block_account(args)?),
+            RpcCommand::BlockAtHeight(args) => to_value(self.block_at_height(args)?),
             RpcCommand::BlockCount => to_value(self.block_count()),
             RpcCommand::Receive(args) => to_value(self.receive(args)?),
             RpcCommand::BlockCreate(args) => to_value(self.block_create(args)?),
+            RpcCommand::BlockPrepare(args) => to_value(self.block_prepare(args)?),
+            RpcCommand::BlockSubmit(args) => to_value(self.block_submit(args)?),
             RpcCommand::BlockHash(args) => to_value(block_hash(args)),
             RpcCommand::Bootstrap(args) => to_value(self.bootstrap(args)?),
             RpcCommand::BootstrapAny(args) => to_value(self.bootstrap_any(args)?),
@@ -98,15 +108,33 @@ impl RpcCommandHandler {
             RpcCommand::UncheckedGet(args) => to_value(self.unchecked_get(args)?),
             RpcCommand::WalletAdd(args) => to_value(self.wallet_add(args)?),
             RpcCommand::WalletAddWatch(args) => to_value(self.wallet_add_watch(args)?),
+            RpcCommand::WalletActivity(args) => to_value(self.wallet_activity(args)?),
+            RpcCommand::WalletAlertSet(args) => to_value(self.wallet_alert_set(args)?),
+            RpcCommand::WalletAlertRemove(args) => to_value(self.wallet_alert_remove(args)?),
+            RpcCommand::WalletAlerts(args) => to_value(self.wallet_alerts(args)?),
+            RpcCommand::WalletSweep(args) => to_value(self.wallet_sweep(args)?),
+            RpcCommand::WalletSweepStatus(args) => to_value(self.wallet_sweep_status(args)?),
+            RpcCommand::WalletWorkStatus(args) => to_value(self.wallet_work_status(args)?),
             RpcCommand::WalletBalances(args) => to_value(self.wallet_balances(args)),
             RpcCommand::PopulateBacklog => to_value(self.populate_backlog()),
+            RpcCommand::Prune(args) => to_value(self.prune(args)?),
+            RpcCommand::PruningStatus => to_value(self.pruning_status()),
+            RpcCommand::PruningPause => to_value(self.pruning_pause()),
+            RpcCommand::PruningResume => to_value(self.pruning_resume()),
             RpcCommand::ValidateAccountNumber(args) => to_value(validate_account_number(args)),
             RpcCommand::UncheckedKeys(args) => to_value(self.unchecked_keys(args)),
-            RpcCommand::WalletChangeSeed(args) => to_value(self.wallet_change_seed(args)),
+            RpcCommand::WalletChangeSeed(args) => to_value(self.wallet_change_seed(args)?),
             RpcCommand::WalletContains(args) => to_value(self.wallet_contains(args)?),
             RpcCommand::WalletCreate(args) => to_value(self.wallet_create(args)?),
             RpcCommand::WalletDestroy(args) => to_value(self.wallet_destroy(args)?),
             RpcCommand::WalletExport(args) => to_value(self.wallet_export(args)?),
+            RpcCommand::WalletMnemonic(args) => to_value(self.wallet_mnemonic(args)?),
+            RpcCommand::WalletBackup(args) => to_value(self.wallet_backup(args)?),
+            RpcCommand::WalletRestore(args) => to_value(self.wallet_restore(args)?),
+            RpcCommand::WalletKdfUpgrade(args) => to_value(self.wallet_kdf_upgrade(args)?),
+            RpcCommand::ScheduleCreate(args) => to_value(self.schedule_create(args)?),
+            RpcCommand::ScheduleList(args) => to_value(self.schedule_list(args)?),
+            RpcCommand::ScheduleCancel(args) => to_value(self.schedule_cancel(args)?),
             RpcCommand::WalletFrontiers(args) => to_value(self.wallet_frontiers(args)?),
             RpcCommand::WalletInfo(args) => to_value(self.wallet_info(args)?),
             RpcCommand::PasswordValid(args) => to_value(self.password_valid(args)?),
@@ -127,12 +155,15 @@ impl RpcCommandHandler {
             RpcCommand::NanoToRaw(args) => to_value(nano_to_raw(args)?),
             RpcCommand::RawToNano(args) => to_value(raw_to_nano(args)),
             RpcCommand::Ledger(args) => to_value(self.ledger(args)),
+            RpcCommand::LedgerAnalytics => to_value(self.ledger_analytics()?),
             RpcCommand::Receivable(args) => to_value(self.receivable(args)),
             RpcCommand::Stop => to_value(self.stop()),
             RpcCommand::Representatives(args) => to_value(self.representatives(args)),
             RpcCommand::StatsClear => to_value(self.stats_clear()),
             RpcCommand::Unopened(args) => to_value(self.unopened(args)),
             RpcCommand::Send(args) => to_value(self.send(args)?),
+            RpcCommand::SendBatch(args) => to_value(self.send_batch(args)?),
+            RpcCommand::SendBatchStatus(args) => to_value(self.send_batch_status(args)?),
             RpcCommand::AccountsBalances(args) => to_value(self.accounts_balances(args)),
             RpcCommand::Chain(args) => to_value(self.chain(args, false)),
             RpcCommand::Successors(args) => to_value(self.chain(args, true)),
@@ -147,6 +178,7 @@ impl RpcCommandHandler {
             RpcCommand::ConfirmationHistory(args) => to_value(self.confirmation_history(args)),
             RpcCommand::Version => to_value(self.version()),
             RpcCommand::ActiveDifficulty => to_value(self.active_difficulty()),
+            RpcCommand::Equivocations(args) => to_value(self.equivocations(args)),
 
             // Not implemented:
             RpcCommand::AccountRepresentativeSet(_) => self.not_implemented(),
@@ -168,6 +200,14 @@ impl RpcCommandHandler {
         }
     }
 
+    fn check_read_only(&self, command: &RpcCommand) -> anyhow::Result<()> {
+        if self.node.flags.read_only && !allowed_in_read_only(command) {
+            Err(anyhow!("Command is not available on a read-only node"))
+        } else {
+            Ok(())
+        }
+    }
+
     fn load_block_any(
         &self,
         txn: &dyn Transaction,
@@ -192,6 +232,23 @@ impl RpcCommandHandler {
             .ok_or_else(|| anyhow!(Self::ACCOUNT_NOT_FOUND))
     }
 
+    fn load_account_block_at(
+        &self,
+        txn: &dyn Transaction,
+        args: &AccountAtArgs,
+    ) -> anyhow::Result<SavedBlock> {
+        let point = match (args.height, args.timestamp) {
+            (Some(height), None) => AccountHistoryPoint::Height(height.into()),
+            (None, Some(timestamp)) => AccountHistoryPoint::Timestamp(timestamp.into()),
+            _ => return Err(anyhow!("Either height or timestamp is required")),
+        };
+        self.load_account(txn, &args.account)?;
+        self.node
+            .ledger
+            .account_block_at(txn, &args.account, point)
+            .ok_or_else(|| anyhow!(Self::BLOCK_NOT_FOUND))
+    }
+
     const BLOCK_NOT_FOUND: &str = "Block not found";
     const NOT_IMPLEMENTED: &str = "Not implemented yet";
     const ACCOUNT_NOT_FOUND: &str = "Account not found";
@@ -209,6 +266,7 @@ fn requires_control(command: &RpcCommand) -> bool {
         | RpcCommand::AccountRepresentativeSet(_)
         | RpcCommand::AccountsCreate(_)
         | RpcCommand::BlockCreate(_)
+        | RpcCommand::BlockPrepare(_)
         | RpcCommand::BootstrapLazy(_)
         | RpcCommand::DatabaseTxnTracker(_)
         | RpcCommand::Keepalive(_)
@@ -216,26 +274,41 @@ fn requires_control(command: &RpcCommand) -> bool {
         | RpcCommand::NodeId
         | RpcCommand::PasswordChange(_)
         | RpcCommand::PopulateBacklog
+        | RpcCommand::Prune(_)
+        | RpcCommand::PruningPause
+        | RpcCommand::PruningResume
         | RpcCommand::Receive(_)
         | RpcCommand::ReceiveMinimum
         | RpcCommand::ReceiveMinimumSet(_)
         | RpcCommand::SearchReceivable(_)
+        | RpcCommand::ScheduleCancel(_)
+        | RpcCommand::ScheduleCreate(_)
+        | RpcCommand::ScheduleList(_)
         | RpcCommand::SearchReceivableAll
         | RpcCommand::Send(_)
+        | RpcCommand::SendBatch(_)
         | RpcCommand::Stop
         | RpcCommand::UncheckedClear
         | RpcCommand::Unopened(_)
         | RpcCommand::WalletAdd(_)
         | RpcCommand::WalletAddWatch(_)
+        | RpcCommand::WalletAlertRemove(_)
+        | RpcCommand::WalletAlertSet(_)
+        | RpcCommand::WalletBackup(_)
         | RpcCommand::WalletChangeSeed(_)
         | RpcCommand::WalletCreate(_)
         | RpcCommand::WalletDestroy(_)
         | RpcCommand::WalletLock(_)
+        | RpcCommand::WalletMnemonic(_)
         | RpcCommand::WalletLedger(_)
         | RpcCommand::WalletRepresentativeSet(_)
         | RpcCommand::WalletReceivable(_)
         | RpcCommand::WalletRepublish(_)
+        | RpcCommand::WalletRestore(_)
+        | RpcCommand::WalletSweep(_)
+        | RpcCommand::WalletKdfUpgrade(_)
         | RpcCommand::WalletWorkGet(_)
+        | RpcCommand::WalletWorkStatus(_)
         | RpcCommand::WorkGenerate(_)
         | RpcCommand::WorkCancel(_)
         | RpcCommand::WorkGet(_)
@@ -252,6 +325,58 @@ fn requires_control(command: &RpcCommand) -> bool {
     }
 }
 
+/// Ledger queries and utilities that can be served by a node which opened
+/// the ledger of another node read-only
+fn allowed_in_read_only(command: &RpcCommand) -> bool {
+    matches!(
+        command,
+        RpcCommand::AccountBalance(_)
+            | RpcCommand::AccountBalanceAt(_)
+            | RpcCommand::AccountBlockCount(_)
+            | RpcCommand::AccountGet(_)
+            | RpcCommand::AccountHistory(_)
+            | RpcCommand::AccountIncoming(_)
+            | RpcCommand::AccountInfo(_)
+            | RpcCommand::AccountKey(_)
+            | RpcCommand::AccountRepresentative(_)
+            | RpcCommand::AccountRepresentativeAt(_)
+            | RpcCommand::AccountWeight(_)
+            | RpcCommand::AccountsBalances(_)
+            | RpcCommand::AccountsFrontiers(_)
+            | RpcCommand::AccountsReceivable(_)
+            | RpcCommand::AccountsRepresentatives(_)
+            | RpcCommand::AvailableSupply
+            | RpcCommand::BlockAccount(_)
+            | RpcCommand::BlockAtHeight(_)
+            | RpcCommand::BlockCount
+            | RpcCommand::BlockHash(_)
+            | RpcCommand::BlockInfo(_)
+            | RpcCommand::Blocks(_)
+            | RpcCommand::BlocksInfo(_)
+            | RpcCommand::Chain(_)
+            | RpcCommand::Delegators(_)
+            | RpcCommand::DelegatorsCount(_)
+            | RpcCommand::DeterministicKey(_)
+            | RpcCommand::FrontierCount
+            | RpcCommand::Frontiers(_)
+            | RpcCommand::KeyCreate
+            | RpcCommand::KeyExpand(_)
+            | RpcCommand::Ledger(_)
+            | RpcCommand::NanoToRaw(_)
+            | RpcCommand::PruningStatus
+            | RpcCommand::RawToNano(_)
+            | RpcCommand::Receivable(_)
+            | RpcCommand::ReceivableExists(_)
+            | RpcCommand::Representatives(_)
+            | RpcCommand::Successors(_)
+            | RpcCommand::Unopened(_)
+            | RpcCommand::Uptime
+            | RpcCommand::ValidateAccountNumber(_)
+            | RpcCommand::Version
+            | RpcCommand::WorkValidate(_)
+    )
+}
+
 #[cfg(test)]
 use serde::de::DeserializeOwned;
 
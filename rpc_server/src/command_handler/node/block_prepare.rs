@@ -0,0 +1,105 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::bail;
+use rsban_core::{
+    Amount, BlockDetails, BlockHash, Epoch, Link, PendingKey, PublicKey, UnsignedStateBlock,
+};
+use rsban_rpc_messages::BlockPrepareArgs;
+
+impl RpcCommandHandler {
+    pub(crate) fn block_prepare(
+        &self,
+        args: BlockPrepareArgs,
+    ) -> anyhow::Result<UnsignedStateBlock> {
+        if !self.node.distributed_work.work_generation_enabled() {
+            bail!("Work generation is disabled");
+        }
+        if args.source.is_some() && args.destination.is_some() {
+            bail!("Either destination or source can be given, not both");
+        }
+
+        let tx = self.node.ledger.read_txn();
+        let info = self.node.ledger.any().get_account(&tx, &args.account);
+        let (previous, current_rep, mut balance, mut epoch) = match &info {
+            Some(info) => (info.head, info.representative, info.balance, info.epoch),
+            None => (
+                BlockHash::zero(),
+                PublicKey::zero(),
+                Amount::zero(),
+                Epoch::Epoch0,
+            ),
+        };
+        if info.is_none() && args.source.is_none() {
+            bail!(Self::ACCOUNT_NOT_FOUND);
+        }
+
+        let representative = args
+            .representative
+            .map(PublicKey::from)
+            .unwrap_or(current_rep);
+        if representative.is_zero() {
+            bail!("Representative account required");
+        }
+
+        let mut is_send = false;
+        let mut is_receive = false;
+        let link: Link = if let Some(destination) = args.destination {
+            let amount = args.amount.unwrap_or_default();
+            if amount.is_zero() {
+                bail!("Amount required");
+            }
+            if amount > balance {
+                bail!("Insufficient balance");
+            }
+            balance = balance - amount;
+            is_send = true;
+            destination.into()
+        } else if let Some(source) = args.source {
+            let Some(pending) = self
+                .node
+                .ledger
+                .any()
+                .get_pending(&tx, &PendingKey::new(args.account, source))
+            else {
+                bail!("Unreceivable");
+            };
+            balance += pending.amount;
+            epoch = std::cmp::max(epoch, pending.epoch);
+            is_receive = true;
+            source.into()
+        } else {
+            if args.representative.is_none() {
+                bail!("Representative account required");
+            }
+            Link::zero()
+        };
+        drop(tx);
+
+        let root = if previous.is_zero() {
+            args.account.into()
+        } else {
+            previous.into()
+        };
+        let difficulty = self
+            .node
+            .network_params
+            .work
+            .threshold(&BlockDetails::new(epoch, is_send, is_receive, false));
+
+        let Some(work) =
+            self.node
+                .distributed_work
+                .make_blocking(root, difficulty, Some(args.account))
+        else {
+            bail!("Work generation cancellation or failure");
+        };
+
+        Ok(UnsignedStateBlock::new(
+            args.account,
+            previous,
+            representative,
+            balance,
+            link,
+            work,
+        ))
+    }
+}
@@ -0,0 +1,50 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_core::Block;
+use rsban_rpc_messages::{BlockSubmitArgs, BlockSubmitResponse, ProcessArgs};
+use std::{
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+impl RpcCommandHandler {
+    const DEFAULT_SUBMIT_TIMEOUT: Duration = Duration::from_secs(30);
+    /// Waiting blocks an RPC worker thread, so clients can't wait longer than this
+    const MAX_SUBMIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+    pub(crate) fn block_submit(
+        &self,
+        args: BlockSubmitArgs,
+    ) -> anyhow::Result<BlockSubmitResponse> {
+        let block: Block = args.block.clone().into();
+        let hash = block.hash();
+
+        // A resubmitted block only waits for the confirmation
+        let exists = {
+            let tx = self.node.ledger.read_txn();
+            self.node.ledger.any().block_exists_or_pruned(&tx, &hash)
+        };
+        if !exists {
+            self.process(ProcessArgs::from(args.block))?;
+        }
+
+        let timeout = args
+            .timeout
+            .map(|t| Duration::from_millis(t.inner()))
+            .unwrap_or(Self::DEFAULT_SUBMIT_TIMEOUT)
+            .min(Self::MAX_SUBMIT_TIMEOUT);
+        let start = Instant::now();
+        loop {
+            let confirmed = {
+                let tx = self.node.ledger.read_txn();
+                self.node
+                    .ledger
+                    .confirmed()
+                    .block_exists_or_pruned(&tx, &hash)
+            };
+            if confirmed || start.elapsed() >= timeout {
+                return Ok(BlockSubmitResponse::new(hash, confirmed));
+            }
+            sleep(Duration::from_millis(50));
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_core::{Account, BlockHash, PublicKey, Vote};
+use rsban_node::consensus::Equivocation;
+use rsban_rpc_messages::{
+    EquivocationDto, EquivocationsArgs, EquivocationsResponse, SignedVoteDto,
+};
+use std::time::UNIX_EPOCH;
+
+impl RpcCommandHandler {
+    pub(crate) fn equivocations(&self, args: EquivocationsArgs) -> EquivocationsResponse {
+        let count = args.count.map(|i| i.inner() as usize).unwrap_or(usize::MAX);
+        let detector = &self.node.equivocation_detector;
+        let equivocations = match args.representative {
+            Some(representative) => {
+                let mut result = detector.equivocations_by(&PublicKey::from(representative));
+                result.truncate(count);
+                result
+            }
+            None => detector.equivocations(count),
+        };
+
+        EquivocationsResponse {
+            equivocations: equivocations.iter().map(to_dto).collect(),
+        }
+    }
+}
+
+fn to_dto(equivocation: &Equivocation) -> EquivocationDto {
+    EquivocationDto {
+        representative: Account::from(equivocation.representative),
+        root: equivocation.root.clone(),
+        detected: equivocation
+            .detected
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            .into(),
+        first: signed_vote(equivocation.first_hash, &equivocation.first_vote),
+        second: signed_vote(equivocation.second_hash, &equivocation.second_vote),
+    }
+}
+
+fn signed_vote(hash: BlockHash, vote: &Vote) -> SignedVoteDto {
+    SignedVoteDto {
+        hash,
+        timestamp: vote.timestamp().into(),
+        signature: vote.signature.clone(),
+        blocks: vote.hashes.clone(),
+    }
+}
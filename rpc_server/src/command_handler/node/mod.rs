@@ -1,5 +1,7 @@
 mod active_difficulty;
 mod block_create;
+mod block_prepare;
+mod block_submit;
 mod bootstrap;
 mod bootstrap_any;
 mod bootstrap_lazy;
@@ -7,11 +9,16 @@ mod confirmation_active;
 mod confirmation_history;
 mod confirmation_info;
 mod confirmation_quorum;
+mod equivocations;
 mod keepalive;
 mod node_id;
 mod peers;
 mod populate_backlog;
 mod process;
+mod prune;
+mod pruning_pause;
+mod pruning_resume;
+mod pruning_status;
 mod receivable;
 mod receivable_exists;
 mod representatives_online;
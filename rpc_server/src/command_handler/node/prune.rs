@@ -0,0 +1,37 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::bail;
+use rsban_core::utils::seconds_since_epoch;
+use rsban_rpc_messages::{unwrap_bool_or_false, PruneArgs, PruneResponse};
+use std::time::Duration;
+
+impl RpcCommandHandler {
+    pub(crate) fn prune(&self, args: PruneArgs) -> anyhow::Result<PruneResponse> {
+        let dry_run = unwrap_bool_or_false(args.dry_run);
+        if !dry_run && !self.node.ledger.pruning_enabled() {
+            bail!("Pruning is not enabled");
+        }
+
+        let pruning = &self.node.ledger_pruning;
+        if pruning.is_paused() {
+            bail!("Pruning is paused");
+        }
+        let mut options = pruning.configured_options(pruning.batch_size(), true);
+        if let Some(depth) = args.depth {
+            options.max_depth = depth.inner();
+        }
+        if let Some(age) = args.age {
+            if age.inner() > seconds_since_epoch() {
+                bail!("Invalid age");
+            }
+            options.max_age = Some(Duration::from_secs(age.inner()));
+        }
+        options.dry_run = dry_run;
+
+        let result = pruning.prune(&options);
+        Ok(PruneResponse {
+            dry_run: dry_run.into(),
+            blocks: result.blocks.into(),
+            bytes: result.bytes.into(),
+        })
+    }
+}
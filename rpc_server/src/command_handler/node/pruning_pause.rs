@@ -0,0 +1,9 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::SuccessResponse;
+
+impl RpcCommandHandler {
+    pub(crate) fn pruning_pause(&self) -> SuccessResponse {
+        self.node.ledger_pruning.pause();
+        SuccessResponse::new()
+    }
+}
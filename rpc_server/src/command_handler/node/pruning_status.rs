@@ -0,0 +1,26 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::PruningStatusResponse;
+use std::sync::atomic::Ordering;
+
+impl RpcCommandHandler {
+    pub(crate) fn pruning_status(&self) -> PruningStatusResponse {
+        let status = self.node.ledger_pruning.status();
+        PruningStatusResponse {
+            enabled: self.node.ledger.pruning_enabled().into(),
+            running: status.running.into(),
+            paused: status.paused.into(),
+            dry_run: status.dry_run.into(),
+            blocks: status.progress.blocks.into(),
+            bytes: status.progress.bytes.into(),
+            started: status.started.into(),
+            pruned_count: self
+                .node
+                .ledger
+                .store
+                .cache
+                .pruned_count
+                .load(Ordering::SeqCst)
+                .into(),
+        }
+    }
+}
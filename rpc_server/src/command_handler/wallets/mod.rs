@@ -8,11 +8,20 @@ mod password_enter;
 mod password_valid;
 mod receive;
 mod receive_minimum;
+mod schedule_cancel;
+mod schedule_create;
+mod schedule_list;
 mod search_receivable;
 mod search_receivable_all;
 mod send;
+mod send_batch;
+mod wallet_activity;
 mod wallet_add;
 mod wallet_add_watch;
+mod wallet_alert_remove;
+mod wallet_alert_set;
+mod wallet_alerts;
+mod wallet_backup;
 mod wallet_balances;
 mod wallet_change_seed;
 mod wallet_contains;
@@ -22,13 +31,19 @@ mod wallet_export;
 mod wallet_frontiers;
 mod wallet_history;
 mod wallet_info;
+mod wallet_kdf_upgrade;
 mod wallet_ledger;
 mod wallet_lock;
 mod wallet_locked;
+mod wallet_mnemonic;
 mod wallet_receivable;
 mod wallet_representative;
 mod wallet_representative_set;
 mod wallet_republish;
+mod wallet_restore;
+mod wallet_sweep;
+mod wallet_sweep_status;
 mod wallet_work_get;
+mod wallet_work_status;
 mod work_get;
 mod work_set;
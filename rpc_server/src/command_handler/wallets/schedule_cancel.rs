@@ -0,0 +1,14 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{ScheduleCancelArgs, SuccessResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn schedule_cancel(
+        &self,
+        args: ScheduleCancelArgs,
+    ) -> anyhow::Result<SuccessResponse> {
+        self.node
+            .wallets
+            .schedule_cancel(&args.wallet, args.id.inner())?;
+        Ok(SuccessResponse::new())
+    }
+}
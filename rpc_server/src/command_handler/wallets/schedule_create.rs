@@ -0,0 +1,38 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_node::wallets::{NewPaymentSchedule, PaymentSchedule};
+use rsban_rpc_messages::{PaymentScheduleDto, ScheduleCreateArgs};
+
+impl RpcCommandHandler {
+    pub(crate) fn schedule_create(
+        &self,
+        args: ScheduleCreateArgs,
+    ) -> anyhow::Result<PaymentScheduleDto> {
+        let schedule = self.node.wallets.schedule_create(NewPaymentSchedule {
+            wallet: args.wallet,
+            source: args.source,
+            destination: args.destination,
+            amount: args.amount,
+            cadence: args.cadence.parse()?,
+            start: args.start.map(|i| i.inner()),
+            end: args.end.map(|i| i.inner()),
+        })?;
+        Ok(to_dto(&schedule))
+    }
+}
+
+pub(crate) fn to_dto(schedule: &PaymentSchedule) -> PaymentScheduleDto {
+    PaymentScheduleDto {
+        id: schedule.id.into(),
+        wallet: schedule.wallet,
+        source: schedule.source,
+        destination: schedule.destination,
+        amount: schedule.amount,
+        cadence: schedule.cadence.to_string(),
+        end: schedule.end.map(|i| i.into()),
+        next: schedule.next.into(),
+        occurrences: schedule.occurrences.into(),
+        last_block: schedule.last_block,
+        status: schedule.status.as_str().to_owned(),
+        failures: schedule.failures.into(),
+    }
+}
@@ -0,0 +1,15 @@
+use super::schedule_create::to_dto;
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{ScheduleListResponse, WalletRpcMessage};
+
+impl RpcCommandHandler {
+    pub(crate) fn schedule_list(
+        &self,
+        args: WalletRpcMessage,
+    ) -> anyhow::Result<ScheduleListResponse> {
+        let schedules = self.node.wallets.schedule_list(&args.wallet)?;
+        Ok(ScheduleListResponse::new(
+            schedules.iter().map(to_dto).collect(),
+        ))
+    }
+}
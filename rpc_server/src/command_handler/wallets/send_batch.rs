@@ -0,0 +1,51 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::{anyhow, bail};
+use rsban_node::wallets::{BatchSendItem, BatchSendProgress, WalletsExt};
+use rsban_rpc_messages::{AccountArg, BatchSendResultDto, SendBatchArgs, SendBatchResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn send_batch(&self, args: SendBatchArgs) -> anyhow::Result<SendBatchResponse> {
+        if !self.node.distributed_work.work_generation_enabled() {
+            bail!("Work generation is disabled");
+        }
+
+        let items: Vec<_> = args
+            .sends
+            .into_iter()
+            .map(|send| BatchSendItem::new(send.destination, send.amount, send.id))
+            .collect();
+
+        let progress = self
+            .node
+            .wallets
+            .send_batch(&args.wallet, args.source, &items)?;
+
+        let done = progress.next_pending().is_none();
+        Ok(to_response(progress, done))
+    }
+
+    pub(crate) fn send_batch_status(&self, args: AccountArg) -> anyhow::Result<SendBatchResponse> {
+        let (progress, in_progress) = self
+            .node
+            .wallets
+            .batch_send_progress(&args.account)
+            .ok_or_else(|| anyhow!("No batch send found for this account"))?;
+        Ok(to_response(progress, !in_progress))
+    }
+}
+
+fn to_response(progress: BatchSendProgress, done: bool) -> SendBatchResponse {
+    SendBatchResponse::new(
+        done,
+        progress
+            .results
+            .into_iter()
+            .map(|result| BatchSendResultDto {
+                id: result.id,
+                status: result.status.as_str().to_owned(),
+                block: result.block,
+                error: result.error,
+            })
+            .collect(),
+    )
+}
@@ -0,0 +1,31 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{WalletActivityArgs, WalletActivityEntry, WalletActivityResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_activity(
+        &self,
+        args: WalletActivityArgs,
+    ) -> anyhow::Result<WalletActivityResponse> {
+        let offset = args.offset.map(|i| i.inner()).unwrap_or_default() as usize;
+        let count = args.count.map(|i| i.inner() as usize).unwrap_or(usize::MAX);
+        let activity = self
+            .node
+            .wallets
+            .wallet_activity(&args.wallet, offset, count)?;
+
+        Ok(WalletActivityResponse::new(
+            activity
+                .into_iter()
+                .map(|a| WalletActivityEntry {
+                    account: a.account,
+                    hash: a.hash,
+                    block_type: a.subtype.as_str().to_owned(),
+                    amount: a.amount,
+                    balance: a.balance,
+                    height: a.height.into(),
+                    local_timestamp: a.local_timestamp.into(),
+                })
+                .collect(),
+        ))
+    }
+}
@@ -0,0 +1,14 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{SuccessResponse, WalletWithAccountArgs};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_alert_remove(
+        &self,
+        args: WalletWithAccountArgs,
+    ) -> anyhow::Result<SuccessResponse> {
+        self.node
+            .wallets
+            .balance_alert_remove(&args.wallet, &args.account)?;
+        Ok(SuccessResponse::new())
+    }
+}
@@ -0,0 +1,14 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{SuccessResponse, WalletAlertSetArgs};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_alert_set(
+        &self,
+        args: WalletAlertSetArgs,
+    ) -> anyhow::Result<SuccessResponse> {
+        self.node
+            .wallets
+            .balance_alert_set(&args.wallet, &args.account, args.threshold)?;
+        Ok(SuccessResponse::new())
+    }
+}
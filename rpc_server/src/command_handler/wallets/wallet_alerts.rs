@@ -0,0 +1,17 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{WalletAlertDto, WalletAlertsResponse, WalletRpcMessage};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_alerts(
+        &self,
+        args: WalletRpcMessage,
+    ) -> anyhow::Result<WalletAlertsResponse> {
+        let alerts = self.node.wallets.balance_alerts(&args.wallet)?;
+        Ok(WalletAlertsResponse::new(
+            alerts
+                .into_iter()
+                .map(|(account, threshold)| WalletAlertDto::new(account, threshold))
+                .collect(),
+        ))
+    }
+}
@@ -0,0 +1,15 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_rpc_messages::{WalletBackupResponse, WalletWithPasswordArgs};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_backup(
+        &self,
+        args: WalletWithPasswordArgs,
+    ) -> anyhow::Result<WalletBackupResponse> {
+        let backup = self
+            .node
+            .wallets
+            .backup_wallet(&args.wallet, &args.password)?;
+        Ok(WalletBackupResponse::new(backup))
+    }
+}
@@ -1,4 +1,6 @@
 use crate::command_handler::RpcCommandHandler;
+use anyhow::bail;
+use rsban_core::{Mnemonic, RawKey};
 use rsban_node::wallets::WalletsExt;
 use rsban_rpc_messages::{WalletChangeSeedArgs, WalletChangeSeedResponse};
 
@@ -6,16 +8,34 @@ impl RpcCommandHandler {
     pub(crate) fn wallet_change_seed(
         &self,
         args: WalletChangeSeedArgs,
-    ) -> WalletChangeSeedResponse {
-        let (restored_count, last_restored_account) = self
-            .node
-            .wallets
-            .change_seed(
-                args.wallet,
-                &args.seed,
-                args.count.unwrap_or_default().inner(),
-            )
-            .unwrap();
-        WalletChangeSeedResponse::new(last_restored_account, restored_count)
+    ) -> anyhow::Result<WalletChangeSeedResponse> {
+        let Some(seed) = seed_or_mnemonic(args.seed, args.mnemonic.as_deref())? else {
+            bail!("Either seed or mnemonic is required");
+        };
+        let (restored_count, last_restored_account) = self.node.wallets.change_seed(
+            args.wallet,
+            &seed,
+            args.count.unwrap_or_default().inner(),
+        )?;
+        Ok(WalletChangeSeedResponse::new(
+            last_restored_account,
+            restored_count,
+        ))
+    }
+}
+
+/// A seed can be given either as hex or as a 24 word BIP39 mnemonic
+pub(crate) fn seed_or_mnemonic(
+    seed: Option<RawKey>,
+    mnemonic: Option<&str>,
+) -> anyhow::Result<Option<RawKey>> {
+    match (seed, mnemonic) {
+        (Some(_), Some(_)) => bail!("Only one of seed or mnemonic can be given"),
+        (Some(seed), None) => Ok(Some(seed)),
+        (None, Some(mnemonic)) => {
+            let mnemonic: Mnemonic = mnemonic.parse()?;
+            Ok(Some(mnemonic.to_seed()?))
+        }
+        (None, None) => Ok(None),
     }
 }
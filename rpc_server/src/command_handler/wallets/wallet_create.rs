@@ -1,3 +1,4 @@
+use super::wallet_change_seed::seed_or_mnemonic;
 use crate::command_handler::RpcCommandHandler;
 use rsban_core::WalletId;
 use rsban_node::wallets::WalletsExt;
@@ -8,12 +9,13 @@ impl RpcCommandHandler {
         &self,
         args: WalletCreateArgs,
     ) -> anyhow::Result<WalletCreateResponse> {
+        let seed = seed_or_mnemonic(args.seed, args.mnemonic.as_deref())?;
         let wallet = WalletId::random();
         self.node.wallets.create(wallet);
 
         let last_restored_account;
         let restored_count;
-        if let Some(seed) = args.seed {
+        if let Some(seed) = seed {
             let (count, last) = self.node.wallets.change_seed(wallet, &seed, 0)?;
             last_restored_account = Some(last);
             restored_count = Some(count.into());
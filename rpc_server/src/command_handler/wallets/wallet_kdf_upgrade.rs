@@ -0,0 +1,30 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_core::{KdfVariant, KeyDerivationFunction};
+use rsban_rpc_messages::{WalletKdfUpgradeArgs, WalletKdfUpgradeResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_kdf_upgrade(
+        &self,
+        args: WalletKdfUpgradeArgs,
+    ) -> anyhow::Result<WalletKdfUpgradeResponse> {
+        let defaults = &self.node.config.wallet_kdf;
+        let variant = match &args.variant {
+            Some(variant) => variant.parse::<KdfVariant>()?,
+            None => defaults.variant,
+        };
+        let kdf = KeyDerivationFunction::with_params(
+            variant,
+            args.memory_cost
+                .map(|i| i.inner())
+                .unwrap_or(defaults.memory_cost),
+            args.time_cost
+                .map(|i| i.inner())
+                .unwrap_or(defaults.time_cost),
+            args.lanes.map(|i| i.inner()).unwrap_or(defaults.lanes),
+        )?;
+        self.node
+            .wallets
+            .kdf_upgrade(&args.wallet, &args.password, &kdf)?;
+        Ok(WalletKdfUpgradeResponse::new(kdf.params()))
+    }
+}
@@ -0,0 +1,13 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_core::Mnemonic;
+use rsban_rpc_messages::{MnemonicResponse, WalletRpcMessage};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_mnemonic(
+        &self,
+        args: WalletRpcMessage,
+    ) -> anyhow::Result<MnemonicResponse> {
+        let seed = self.node.wallets.get_seed(args.wallet)?;
+        Ok(MnemonicResponse::new(Mnemonic::from(&seed).to_string()))
+    }
+}
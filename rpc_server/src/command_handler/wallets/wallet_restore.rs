@@ -0,0 +1,16 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_core::WalletId;
+use rsban_rpc_messages::{WalletRestoreArgs, WalletRpcMessage};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_restore(
+        &self,
+        args: WalletRestoreArgs,
+    ) -> anyhow::Result<WalletRpcMessage> {
+        let wallet = WalletId::random();
+        self.node
+            .wallets
+            .restore_wallet(wallet, &args.backup, &args.password)?;
+        Ok(WalletRpcMessage::new(wallet))
+    }
+}
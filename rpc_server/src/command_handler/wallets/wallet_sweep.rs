@@ -0,0 +1,19 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::bail;
+use rsban_node::wallets::WalletsExt;
+use rsban_rpc_messages::{StartedResponse, WalletSweepArgs};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_sweep(&self, args: WalletSweepArgs) -> anyhow::Result<StartedResponse> {
+        if !self.node.distributed_work.work_generation_enabled() {
+            bail!("Work generation is disabled");
+        }
+        self.node.wallets.wallet_sweep(
+            &args.wallet,
+            args.destination,
+            args.accounts,
+            args.threshold.unwrap_or_default(),
+        )?;
+        Ok(StartedResponse::new(true))
+    }
+}
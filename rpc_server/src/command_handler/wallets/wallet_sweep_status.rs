@@ -0,0 +1,37 @@
+use crate::command_handler::RpcCommandHandler;
+use anyhow::anyhow;
+use rsban_rpc_messages::{SweepAccountDto, WalletRpcMessage, WalletSweepStatusResponse};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_sweep_status(
+        &self,
+        args: WalletRpcMessage,
+    ) -> anyhow::Result<WalletSweepStatusResponse> {
+        let progress = self
+            .node
+            .wallets
+            .sweep_progress(&args.wallet)?
+            .ok_or_else(|| anyhow!("No sweep found for this wallet"))?;
+
+        Ok(WalletSweepStatusResponse {
+            destination: progress.destination,
+            threshold: progress.threshold,
+            done: progress.is_done().into(),
+            processed: (progress.processed() as u64).into(),
+            total: (progress.accounts.len() as u64).into(),
+            swept: progress.total_swept(),
+            accounts: progress
+                .accounts
+                .into_iter()
+                .map(|a| SweepAccountDto {
+                    account: a.account,
+                    status: a.status.as_str().to_owned(),
+                    received: a.received,
+                    sent: a.sent,
+                    amount: a.amount,
+                    error: a.error,
+                })
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use crate::command_handler::RpcCommandHandler;
+use rsban_node::wallets::WorkCacheState;
+use rsban_rpc_messages::{WalletRpcMessage, WalletWorkStatusResponse, WorkCacheDto};
+
+impl RpcCommandHandler {
+    pub(crate) fn wallet_work_status(
+        &self,
+        args: WalletRpcMessage,
+    ) -> anyhow::Result<WalletWorkStatusResponse> {
+        let entries = self.node.wallets.work_cache_status(&args.wallet)?;
+        let count = |state| entries.iter().filter(|e| e.state == state).count() as u64;
+
+        Ok(WalletWorkStatusResponse {
+            valid: count(WorkCacheState::Valid).into(),
+            receive_only: count(WorkCacheState::ReceiveOnly).into(),
+            invalid: count(WorkCacheState::Invalid).into(),
+            missing: count(WorkCacheState::Missing).into(),
+            regenerating: (entries.iter().filter(|e| e.regenerating).count() as u64).into(),
+            accounts: entries
+                .iter()
+                .map(|e| WorkCacheDto {
+                    account: e.account,
+                    root: e.root,
+                    work: e.work.into(),
+                    difficulty: e.difficulty.into(),
+                    state: e.state.as_str().to_owned(),
+                    last_active: e.last_active.map(|t| t.into()),
+                    regenerating: e.regenerating.into(),
+                })
+                .collect(),
+        })
+    }
+}
@@ -2,7 +2,9 @@ pub(crate) mod command_handler;
 mod config;
 mod server;
 mod toml;
+mod work_server;
 
 pub use config::*;
 pub use server::*;
 pub use toml::*;
+pub use work_server::*;
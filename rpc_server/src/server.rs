@@ -43,7 +43,7 @@ async fn handle_rpc(
 }
 
 /// JSON is the default and the only accepted content type!
-async fn set_json_content<B>(mut request: Request<B>) -> Request<B> {
+pub(crate) async fn set_json_content<B>(mut request: Request<B>) -> Request<B> {
     request
         .headers_mut()
         .insert("Content-Type", "application/json".parse().unwrap());
@@ -0,0 +1,147 @@
+use crate::server::set_json_content;
+use anyhow::{bail, Context, Result};
+use axum::{extract::State, middleware::map_request, routing::post, Json, Router};
+use rsban_core::{
+    work::{WorkPool, WorkPoolImpl},
+    DifficultyV1, Root,
+};
+use rsban_rpc_messages::{
+    RpcCommand, RpcError, SuccessResponse, WorkGenerateArgs, WorkGenerateDto,
+};
+use serde_json::{to_value, Value};
+use std::{future::Future, sync::Arc};
+use tokio::{net::TcpListener, sync::oneshot};
+use tracing::info;
+
+/// Serves the `work_generate` and `work_cancel` actions from a local work pool,
+/// so that dedicated machines can generate work for other nodes
+pub async fn run_work_server<F>(
+    work_pool: Arc<WorkPoolImpl>,
+    max_multiplier: f64,
+    listener: TcpListener,
+    shutdown: F,
+) -> Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let max_difficulty = DifficultyV1::from_multiplier(max_multiplier, work_pool.threshold_base());
+    let work_server = WorkServer {
+        work_pool,
+        max_difficulty,
+    };
+
+    let app = Router::new()
+        .route("/", post(handle_work_request))
+        .layer(map_request(set_json_content))
+        .with_state(work_server);
+
+    info!("Work server listening address: {}", listener.local_addr()?);
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown)
+        .await
+        .context("Failed to run the work server")
+}
+
+async fn handle_work_request(
+    State(work_server): State<WorkServer>,
+    Json(command): Json<RpcCommand>,
+) -> Json<Value> {
+    Json(work_server.handle(command).await)
+}
+
+#[derive(Clone)]
+struct WorkServer {
+    work_pool: Arc<WorkPoolImpl>,
+    max_difficulty: u64,
+}
+
+impl WorkServer {
+    async fn handle(&self, command: RpcCommand) -> Value {
+        self.call_handler(command)
+            .await
+            .unwrap_or_else(|e| to_value(RpcError::new(e.to_string())).unwrap())
+    }
+
+    async fn call_handler(&self, command: RpcCommand) -> Result<Value> {
+        let response = match command {
+            RpcCommand::WorkGenerate(args) => to_value(self.work_generate(args).await?),
+            RpcCommand::WorkCancel(args) => {
+                self.work_pool.cancel(&args.hash.into());
+                to_value(SuccessResponse::new())
+            }
+            _ => bail!("Action not supported by the work server"),
+        }?;
+        Ok(response)
+    }
+
+    async fn work_generate(&self, args: WorkGenerateArgs) -> Result<WorkGenerateDto> {
+        if !self.work_pool.work_generation_enabled() {
+            bail!("Work generation is disabled");
+        }
+
+        let root: Root = args.hash.into();
+        if root.is_zero() {
+            bail!("Invalid block hash");
+        }
+
+        let base_difficulty = self.work_pool.threshold_base();
+        let difficulty = match (args.difficulty, args.multiplier) {
+            (Some(difficulty), _) => difficulty.inner(),
+            (None, Some(multiplier)) if multiplier.inner() > 0 => {
+                DifficultyV1::from_multiplier(multiplier.inner() as f64, base_difficulty)
+            }
+            (None, Some(_)) => bail!("Invalid multiplier"),
+            (None, None) => base_difficulty,
+        };
+        if difficulty > self.max_difficulty {
+            bail!("Difficulty out of range");
+        }
+
+        let (tx, rx) = oneshot::channel();
+        // Axum drops this future when the client disconnects
+        let mut cancel_guard = CancelOnDrop {
+            work_pool: &self.work_pool,
+            root,
+            done: false,
+        };
+        self.work_pool.generate_async(
+            root,
+            difficulty,
+            Some(Box::new(move |work| {
+                let _ = tx.send(work);
+            })),
+        );
+        let work = rx.await.ok().flatten();
+        cancel_guard.done = true;
+        let Some(work) = work else {
+            bail!("Work generation cancelled");
+        };
+
+        let result_difficulty = self.work_pool.difficulty(&root, work);
+        Ok(WorkGenerateDto::new(
+            work.into(),
+            result_difficulty,
+            Some(DifficultyV1::to_multiplier(
+                result_difficulty,
+                base_difficulty,
+            )),
+            args.hash,
+        ))
+    }
+}
+
+/// Stops the work generation if the request is dropped before the work is done
+struct CancelOnDrop<'a> {
+    work_pool: &'a WorkPoolImpl,
+    root: Root,
+    done: bool,
+}
+
+impl Drop for CancelOnDrop<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.work_pool.cancel(&self.root);
+        }
+    }
+}
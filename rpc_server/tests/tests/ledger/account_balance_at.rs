@@ -0,0 +1,85 @@
+use rsban_core::{Account, Amount};
+use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsban_rpc_messages::AccountAtArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn account_balance_at_height() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .account_balance_at(AccountAtArgs::at_height(*DEV_GENESIS_ACCOUNT, 1))
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.balance, Amount::MAX);
+    assert_eq!(result.block, *DEV_GENESIS_HASH);
+    assert_eq!(result.height, 1.into());
+}
+
+#[test]
+fn account_balance_at_timestamp() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .account_balance_at(AccountAtArgs::at_timestamp(*DEV_GENESIS_ACCOUNT, u64::MAX))
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.balance, Amount::MAX);
+    assert_eq!(result.block, *DEV_GENESIS_HASH);
+}
+
+#[test]
+fn account_balance_at_fails_without_height_or_timestamp() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let args = AccountAtArgs {
+        account: *DEV_GENESIS_ACCOUNT,
+        height: None,
+        timestamp: None,
+    };
+    let result = node
+        .runtime
+        .block_on(async { server.client.account_balance_at(args).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Either height or timestamp is required\"".to_string())
+    );
+}
+
+#[test]
+fn account_balance_at_fails_with_account_not_found() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .account_balance_at(AccountAtArgs::at_height(Account::zero(), 1))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Account not found\"".to_string())
+    );
+}
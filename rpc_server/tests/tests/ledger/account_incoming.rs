@@ -0,0 +1,41 @@
+use rsban_ledger::DEV_GENESIS_ACCOUNT;
+use rsban_node::config::NodeFlags;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn account_incoming() {
+    let mut system = System::new();
+    let mut flags = NodeFlags::new();
+    flags.enable_incoming_index = true;
+    let node = system.build_node().flags(flags).finish();
+    assert!(node.ledger.incoming_index_enabled());
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .account_incoming(*DEV_GENESIS_ACCOUNT)
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.incoming, Vec::new());
+}
+
+#[test]
+fn account_incoming_fails_without_index() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.account_incoming(*DEV_GENESIS_ACCOUNT).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Incoming sends index is not enabled\"".to_string())
+    );
+}
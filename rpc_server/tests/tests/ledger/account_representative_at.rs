@@ -0,0 +1,42 @@
+use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsban_rpc_messages::AccountAtArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn account_representative_at_height() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .account_representative_at(AccountAtArgs::at_height(*DEV_GENESIS_ACCOUNT, 1))
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.representative, *DEV_GENESIS_ACCOUNT);
+    assert_eq!(result.block, *DEV_GENESIS_HASH);
+}
+
+#[test]
+fn account_representative_at_fails_with_height_out_of_range() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .account_representative_at(AccountAtArgs::at_height(*DEV_GENESIS_ACCOUNT, 2))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Block not found\"".to_string())
+    );
+}
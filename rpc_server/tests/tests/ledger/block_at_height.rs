@@ -0,0 +1,82 @@
+use rsban_core::Account;
+use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsban_node::config::NodeFlags;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn block_at_height() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .block_at_height(*DEV_GENESIS_ACCOUNT, 1)
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.hash, *DEV_GENESIS_HASH);
+}
+
+#[test]
+fn block_at_height_from_index() {
+    let mut system = System::new();
+    let mut flags = NodeFlags::new();
+    flags.enable_block_height_index = true;
+    let node = system.build_node().flags(flags).finish();
+    assert!(node.ledger.block_height_index_enabled());
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .block_at_height(*DEV_GENESIS_ACCOUNT, 1)
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.hash, *DEV_GENESIS_HASH);
+}
+
+#[test]
+fn block_at_height_fails_with_height_out_of_range() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    for height in [0, 2] {
+        let result = node.runtime.block_on(async {
+            server
+                .client
+                .block_at_height(*DEV_GENESIS_ACCOUNT, height)
+                .await
+        });
+
+        assert_eq!(
+            result.err().map(|e| e.to_string()),
+            Some("node returned error: \"Block not found\"".to_string())
+        );
+    }
+}
+
+#[test]
+fn block_at_height_fails_with_account_not_found() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.block_at_height(Account::zero(), 1).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Account not found\"".to_string())
+    );
+}
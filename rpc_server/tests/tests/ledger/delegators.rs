@@ -1,5 +1,6 @@
 use rsban_core::Amount;
 use rsban_ledger::DEV_GENESIS_ACCOUNT;
+use rsban_node::config::NodeFlags;
 use std::collections::HashMap;
 use test_helpers::{setup_rpc_client_and_server, System};
 
@@ -23,3 +24,27 @@ fn delegators_rpc_response() {
 
     assert_eq!(result.delegators, delegators);
 }
+
+#[test]
+fn delegators_from_index() {
+    let mut system = System::new();
+    let mut flags = NodeFlags::new();
+    flags.enable_delegators_index = true;
+    let node = system.build_node().flags(flags).finish();
+    assert!(node.ledger.delegators_index_enabled());
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .delegators(*DEV_GENESIS_ACCOUNT)
+            .await
+            .unwrap()
+    });
+
+    let mut delegators = HashMap::new();
+    delegators.insert(*DEV_GENESIS_ACCOUNT, Amount::MAX);
+
+    assert_eq!(result.delegators, delegators);
+}
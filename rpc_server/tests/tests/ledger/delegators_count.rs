@@ -1,4 +1,5 @@
 use rsban_ledger::DEV_GENESIS_ACCOUNT;
+use rsban_node::config::NodeFlags;
 use test_helpers::{setup_rpc_client_and_server, System};
 
 #[test]
@@ -18,3 +19,23 @@ fn delegators_count_rpc_response() {
 
     assert_eq!(result.count, 1.into());
 }
+
+#[test]
+fn delegators_count_from_index() {
+    let mut system = System::new();
+    let mut flags = NodeFlags::new();
+    flags.enable_delegators_index = true;
+    let node = system.build_node().flags(flags).finish();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .delegators_count(*DEV_GENESIS_ACCOUNT)
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.count, 1.into());
+}
@@ -0,0 +1,42 @@
+use rsban_core::Amount;
+use rsban_ledger::DEV_GENESIS_ACCOUNT;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn ledger_analytics() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.ledger_analytics.enabled = true;
+    let node = system.build_node().config(config).finish();
+    node.ledger_analytics.refresh();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.ledger_analytics().await.unwrap() });
+
+    assert_eq!(result.accounts, 1.into());
+    assert_eq!(result.top_accounts.len(), 1);
+    assert_eq!(result.top_accounts[0].account, *DEV_GENESIS_ACCOUNT);
+    assert_eq!(result.top_accounts[0].balance, Amount::MAX);
+    assert_eq!(result.balance_distribution.len(), 1);
+    assert_eq!(result.epochs.len(), 1);
+}
+
+#[test]
+fn ledger_analytics_fails_when_disabled() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.ledger_analytics().await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Ledger analytics are disabled\"".to_string())
+    );
+}
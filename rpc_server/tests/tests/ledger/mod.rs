@@ -1,8 +1,11 @@
 mod account_balance;
+mod account_balance_at;
 mod account_block_count;
 mod account_history;
+mod account_incoming;
 mod account_info;
 mod account_representative;
+mod account_representative_at;
 mod account_weight;
 mod accounts_balances;
 mod accounts_frontiers;
@@ -10,6 +13,7 @@ mod accounts_receivable;
 mod accounts_representatives;
 mod available_supply;
 mod block_account;
+mod block_at_height;
 mod block_confirm;
 mod block_count;
 mod block_info;
@@ -21,6 +25,7 @@ mod delegators_count;
 mod frontier_count;
 mod frontiers;
 mod ledger;
+mod ledger_analytics;
 mod representatives;
 mod successors;
 mod unopened;
@@ -0,0 +1,116 @@
+use rsban_core::{
+    Amount, Block, BlockHash, JsonBlock, JsonStateBlock, Link, PrivateKey, Signature,
+};
+use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsban_rpc_messages::BlockPrepareArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn block_prepare_send() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+    let key1 = PrivateKey::new();
+
+    let unsigned = node.runtime.block_on(async {
+        server
+            .client
+            .block_prepare(
+                BlockPrepareArgs::builder(*DEV_GENESIS_ACCOUNT)
+                    .send(key1.account(), Amount::raw(100))
+                    .build(),
+            )
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(unsigned.account, *DEV_GENESIS_ACCOUNT);
+    assert_eq!(unsigned.previous, *DEV_GENESIS_HASH);
+    assert_eq!(unsigned.balance, Amount::MAX - Amount::raw(100));
+    assert_eq!(unsigned.link, Link::from(key1.account()));
+    assert_eq!(unsigned.representative, *DEV_GENESIS_ACCOUNT);
+
+    let block: Block = JsonBlock::State(JsonStateBlock {
+        account: unsigned.account,
+        previous: unsigned.previous,
+        representative: unsigned.representative,
+        balance: unsigned.balance,
+        link: unsigned.link,
+        link_as_account: None,
+        signature: Signature::new(),
+        work: unsigned.work,
+    })
+    .into();
+    assert_eq!(block.hash(), unsigned.hash);
+    assert!(node.network_params.work.validate_entry_block(&block));
+}
+
+#[test]
+fn block_prepare_fails_for_unopened_account() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .block_prepare(
+                BlockPrepareArgs::builder(PrivateKey::new().account())
+                    .representative(*DEV_GENESIS_ACCOUNT)
+                    .build(),
+            )
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Account not found\"".to_string())
+    );
+}
+
+#[test]
+fn block_prepare_fails_with_unreceivable_source() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .block_prepare(
+                BlockPrepareArgs::builder(PrivateKey::new().account())
+                    .receive(BlockHash::from(42))
+                    .representative(*DEV_GENESIS_ACCOUNT)
+                    .build(),
+            )
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Unreceivable\"".to_string())
+    );
+}
+
+#[test]
+fn block_prepare_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .block_prepare(
+                BlockPrepareArgs::builder(*DEV_GENESIS_ACCOUNT)
+                    .representative(*DEV_GENESIS_ACCOUNT)
+                    .build(),
+            )
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
@@ -0,0 +1,64 @@
+use rsban_core::{Amount, JsonBlock, JsonStateBlock, PrivateKey, Signature};
+use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_BLOCK};
+use rsban_rpc_messages::{BlockPrepareArgs, BlockSubmitArgs};
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn block_submit_existing_block_waits_for_confirmation() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .block_submit(BlockSubmitArgs::new(
+                DEV_GENESIS_BLOCK.json_representation(),
+                Some(0),
+            ))
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.hash, DEV_GENESIS_BLOCK.hash());
+    assert_eq!(result.confirmed, true.into());
+}
+
+#[test]
+fn block_submit_fails_with_bad_signature() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let unsigned = node.runtime.block_on(async {
+        server
+            .client
+            .block_prepare(
+                BlockPrepareArgs::builder(*DEV_GENESIS_ACCOUNT)
+                    .send(PrivateKey::new().account(), Amount::raw(100))
+                    .build(),
+            )
+            .await
+            .unwrap()
+    });
+    let block = JsonBlock::State(JsonStateBlock {
+        account: unsigned.account,
+        previous: unsigned.previous,
+        representative: unsigned.representative,
+        balance: unsigned.balance,
+        link: unsigned.link,
+        link_as_account: None,
+        signature: Signature::from_bytes([1; 64]),
+        work: unsigned.work,
+    });
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.block_submit(block).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Bad signature\"".to_string())
+    );
+    assert!(!node.block_exists(&unsigned.hash));
+}
@@ -0,0 +1,73 @@
+use rsban_core::{Account, BlockHash, PrivateKey, QualifiedRoot, Vote};
+use rsban_rpc_messages::EquivocationsArgs;
+use std::sync::Arc;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn equivocations_empty() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .equivocations(EquivocationsArgs::default())
+            .await
+            .unwrap()
+    });
+
+    assert!(result.equivocations.is_empty());
+}
+
+#[test]
+fn equivocations() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let key = PrivateKey::from(42);
+    let root = QualifiedRoot::new_test_instance();
+    let hash1 = BlockHash::from(1);
+    let hash2 = BlockHash::from(2);
+    let vote1 = Arc::new(Vote::new_final(&key, vec![hash1]));
+    let vote2 = Arc::new(Vote::new_final(&key, vec![hash2]));
+    node.equivocation_detector.vote(&vote1, hash1, root.clone());
+    node.equivocation_detector.vote(&vote2, hash2, root.clone());
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .equivocations(
+                EquivocationsArgs::builder()
+                    .representative(key.account())
+                    .build(),
+            )
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.equivocations.len(), 1);
+    let equivocation = &result.equivocations[0];
+    assert_eq!(equivocation.representative, key.account());
+    assert_eq!(equivocation.root, root);
+    assert_eq!(equivocation.first.hash, hash1);
+    assert_eq!(equivocation.first.signature, vote1.signature);
+    assert_eq!(equivocation.second.hash, hash2);
+    assert_eq!(equivocation.second.signature, vote2.signature);
+
+    let other = node.runtime.block_on(async {
+        server
+            .client
+            .equivocations(
+                EquivocationsArgs::builder()
+                    .representative(Account::from(1))
+                    .build(),
+            )
+            .await
+            .unwrap()
+    });
+    assert!(other.equivocations.is_empty());
+}
@@ -1,14 +1,20 @@
 mod block_create;
+mod block_prepare;
+mod block_submit;
 mod bootstrap;
 mod bootstrap_any;
 mod bootstrap_lazy;
 mod confirmation_active;
 mod confirmation_info;
 mod confirmation_quorum;
+mod equivocations;
 mod node_id;
 mod peers;
 mod populate_backlog;
 mod process;
+mod prune;
+mod pruning_status;
+mod read_only;
 mod receivable;
 mod receivable_exists;
 mod representatives_online;
@@ -24,4 +30,5 @@ mod unchecked_keys;
 mod uptime;
 mod work_cancel;
 mod work_generate;
+mod work_server;
 mod work_validate;
@@ -0,0 +1,95 @@
+use rsban_rpc_messages::PruneArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn prune_dry_run_keeps_genesis() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .prune(PruneArgs::builder().depth(1).dry_run().build())
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.dry_run, true.into());
+    assert_eq!(result.blocks, 0.into());
+    assert_eq!(result.bytes, 0.into());
+}
+
+#[test]
+fn prune_fails_when_pruning_is_disabled() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.prune(PruneArgs::default()).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Pruning is not enabled\"".to_string())
+    );
+}
+
+#[test]
+fn prune_requires_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .prune(PruneArgs::builder().dry_run().build())
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
+
+#[test]
+fn prune_rejects_age_before_epoch() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .prune(PruneArgs::builder().age(u64::MAX).dry_run().build())
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid age\"".to_string())
+    );
+}
+
+#[test]
+fn prune_fails_while_paused() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        server.client.pruning_pause().await.unwrap();
+        server
+            .client
+            .prune(PruneArgs::builder().dry_run().build())
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Pruning is paused\"".to_string())
+    );
+}
@@ -0,0 +1,56 @@
+use rsban_rpc_messages::PruneArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn pruning_status_before_first_run() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let status = node
+        .runtime
+        .block_on(async { server.client.pruning_status().await.unwrap() });
+
+    assert_eq!(status.enabled, false.into());
+    assert_eq!(status.running, false.into());
+    assert_eq!(status.started, 0.into());
+    assert_eq!(status.pruned_count, 0.into());
+}
+
+#[test]
+fn pruning_status_after_dry_run() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let status = node.runtime.block_on(async {
+        server
+            .client
+            .prune(PruneArgs::builder().dry_run().build())
+            .await
+            .unwrap();
+        server.client.pruning_status().await.unwrap()
+    });
+
+    assert_eq!(status.running, false.into());
+    assert_eq!(status.dry_run, true.into());
+    assert_ne!(status.started, 0.into());
+}
+
+#[test]
+fn pruning_status_paused() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let (paused, resumed) = node.runtime.block_on(async {
+        server.client.pruning_pause().await.unwrap();
+        let paused = server.client.pruning_status().await.unwrap();
+        server.client.pruning_resume().await.unwrap();
+        let resumed = server.client.pruning_status().await.unwrap();
+        (paused, resumed)
+    });
+
+    assert_eq!(paused.paused, true.into());
+    assert_eq!(resumed.paused, false.into());
+}
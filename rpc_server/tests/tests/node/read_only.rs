@@ -0,0 +1,55 @@
+use rsban_core::BlockHash;
+use rsban_node::config::NodeFlags;
+use std::time::Duration;
+use test_helpers::{assert_timely_eq, setup_rpc_client_and_server, System};
+
+#[test]
+fn read_only_node_serves_ledger_queries() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let read_only = system.make_read_only_node(&node, NodeFlags::default());
+    let server = setup_rpc_client_and_server(read_only.clone(), false);
+
+    let result = read_only
+        .runtime
+        .block_on(async { server.client.block_count().await.unwrap() });
+
+    assert_eq!(result.count, 1.into());
+    assert_eq!(result.cemented, 1.into());
+}
+
+#[test]
+fn read_only_node_picks_up_ledger_changes() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let mut flags = NodeFlags::default();
+    flags.read_only_reload_interval = Duration::from_millis(100);
+    let read_only = system.make_read_only_node(&node, flags);
+
+    let mut txn = node.ledger.rw_txn();
+    node.ledger.store.pruned.put(&mut txn, &BlockHash::from(42));
+    txn.commit();
+
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || read_only.ledger.pruned_count(),
+        1,
+    );
+}
+
+#[test]
+fn read_only_node_rejects_wallet_commands() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let read_only = system.make_read_only_node(&node, NodeFlags::default());
+    let server = setup_rpc_client_and_server(read_only.clone(), true);
+
+    let result = read_only
+        .runtime
+        .block_on(async { server.client.wallet_create(None).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Command is not available on a read-only node\"".to_string())
+    );
+}
@@ -0,0 +1,120 @@
+use rsban_core::{work::WorkPoolImpl, BlockHash, Root};
+use rsban_rpc_client::{NanoRpcClient, Url};
+use rsban_rpc_messages::{SuccessResponse, WorkGenerateArgs};
+use rsban_rpc_server::run_work_server;
+use std::sync::Arc;
+use tokio::{net::TcpListener, runtime::Runtime, sync::oneshot};
+
+struct WorkServerGuard {
+    runtime: Runtime,
+    client: NanoRpcClient,
+    work_pool: Arc<WorkPoolImpl>,
+    tx_stop: Option<oneshot::Sender<()>>,
+}
+
+impl Drop for WorkServerGuard {
+    fn drop(&mut self) {
+        if let Some(tx_stop) = self.tx_stop.take() {
+            let _ = tx_stop.send(());
+        }
+        self.work_pool.stop();
+    }
+}
+
+fn setup_work_server(work_pool: WorkPoolImpl) -> WorkServerGuard {
+    let runtime = Runtime::new().unwrap();
+    let work_pool = Arc::new(work_pool);
+    let listener = runtime.block_on(TcpListener::bind("127.0.0.1:0")).unwrap();
+    let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+    let (tx_stop, rx_stop) = oneshot::channel::<()>();
+    runtime.spawn(run_work_server(
+        work_pool.clone(),
+        64.0,
+        listener,
+        async move {
+            let _ = rx_stop.await;
+        },
+    ));
+
+    WorkServerGuard {
+        runtime,
+        client: NanoRpcClient::new(url),
+        work_pool,
+        tx_stop: Some(tx_stop),
+    }
+}
+
+#[test]
+fn generate_work() {
+    let server = setup_work_server(WorkPoolImpl::new_dev());
+    let hash = BlockHash::from(1);
+
+    let result = server
+        .runtime
+        .block_on(server.client.work_generate(hash))
+        .unwrap();
+
+    let work: u64 = result.work.into();
+    assert_eq!(result.hash, hash);
+    assert_eq!(
+        result.difficulty.inner(),
+        server.work_pool.difficulty(&Root::from(hash), work)
+    );
+    assert!(result.difficulty.inner() >= server.work_pool.threshold_base());
+}
+
+#[test]
+fn generate_work_fails_when_difficulty_is_too_high() {
+    let server = setup_work_server(WorkPoolImpl::new_dev());
+
+    let result = server.runtime.block_on(
+        server.client.work_generate(
+            WorkGenerateArgs::build(BlockHash::from(1))
+                .difficulty(u64::MAX)
+                .build(),
+        ),
+    );
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Difficulty out of range\"".to_string())
+    );
+}
+
+#[test]
+fn generate_work_fails_when_work_generation_is_disabled() {
+    let server = setup_work_server(WorkPoolImpl::disabled());
+
+    let result = server
+        .runtime
+        .block_on(server.client.work_generate(BlockHash::from(1)));
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Work generation is disabled\"".to_string())
+    );
+}
+
+#[test]
+fn cancel_work() {
+    let server = setup_work_server(WorkPoolImpl::new_dev());
+
+    let result = server
+        .runtime
+        .block_on(server.client.work_cancel(BlockHash::from(1)))
+        .unwrap();
+
+    assert_eq!(result, SuccessResponse::new());
+}
+
+#[test]
+fn reject_other_actions() {
+    let server = setup_work_server(WorkPoolImpl::new_dev());
+
+    let result = server.runtime.block_on(server.client.block_count());
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Action not supported by the work server\"".to_string())
+    );
+}
@@ -0,0 +1,132 @@
+use rsban_core::{Account, Amount, WalletId};
+use rsban_node::wallets::WalletsExt;
+use rsban_rpc_messages::ScheduleCreateArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn schedule_create_list_and_cancel() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let source: Account = node
+        .wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap()
+        .into();
+
+    let (created, listed) = node.runtime.block_on(async {
+        let created = server
+            .client
+            .schedule_create(
+                ScheduleCreateArgs::new(
+                    wallet_id,
+                    source,
+                    Account::from(1000),
+                    Amount::raw(10),
+                    "0 12 1 * *",
+                )
+                .with_start(1_704_067_200) // 2024-01-01 00:00:00 UTC
+                .with_end(1_735_689_600), // 2025-01-01 00:00:00 UTC
+            )
+            .await
+            .unwrap();
+        let listed = server.client.schedule_list(wallet_id).await.unwrap();
+        (created, listed)
+    });
+
+    assert_eq!(created.next.inner(), 1_704_110_400); // 2024-01-01 12:00:00 UTC
+    assert_eq!(created.status, "active");
+    assert_eq!(created.cadence, "0 12 1 * *");
+    assert_eq!(listed.schedules, vec![created]);
+
+    let id = listed.schedules[0].id.inner();
+    let listed = node.runtime.block_on(async {
+        server.client.schedule_cancel(wallet_id, id).await.unwrap();
+        server.client.schedule_list(wallet_id).await.unwrap()
+    });
+    assert_eq!(listed.schedules[0].status, "cancelled");
+}
+
+#[test]
+fn schedule_create_fails_with_invalid_cadence() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let source: Account = node
+        .wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap()
+        .into();
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .schedule_create(ScheduleCreateArgs::new(
+                wallet_id,
+                source,
+                Account::from(1000),
+                Amount::raw(10),
+                "every day",
+            ))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"cron expression must have 5 fields: every day\"".to_string())
+    );
+}
+
+#[test]
+fn schedule_cancel_fails_for_unknown_schedule() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.schedule_cancel(wallet_id, 42).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"payment schedule not found\"".to_string())
+    );
+}
+
+#[test]
+fn schedule_create_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .schedule_create(ScheduleCreateArgs::new(
+                WalletId::zero(),
+                Account::zero(),
+                Account::zero(),
+                Amount::raw(1),
+                "@daily",
+            ))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
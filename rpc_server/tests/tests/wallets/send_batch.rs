@@ -0,0 +1,188 @@
+use rsban_core::{Account, Amount, WalletId, DEV_GENESIS_KEY};
+use rsban_ledger::DEV_GENESIS_ACCOUNT;
+use rsban_node::wallets::WalletsExt;
+use rsban_rpc_messages::{BatchSendDto, SendBatchArgs};
+use std::time::Duration;
+use test_helpers::{assert_timely, setup_rpc_client_and_server, System};
+
+#[test]
+fn send_batch() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+    node.wallets
+        .insert_adhoc2(&wallet, &DEV_GENESIS_KEY.raw_key(), false)
+        .unwrap();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let sends = || {
+        vec![
+            BatchSendDto::new(Account::from(1), Amount::raw(100), "payout-1"),
+            BatchSendDto::new(Account::from(2), Amount::raw(200), "payout-2"),
+            BatchSendDto::new(Account::from(3), Amount::raw(300), "payout-3"),
+        ]
+    };
+
+    let started = node.runtime.block_on(async {
+        server
+            .client
+            .send_batch(SendBatchArgs::new(wallet, *DEV_GENESIS_ACCOUNT, sends()))
+            .await
+            .unwrap()
+    });
+    assert_eq!(started.done, false.into());
+    assert!(started.sends.iter().all(|s| s.status == "pending"));
+
+    assert_timely(Duration::from_secs(5), || {
+        !node.wallets.batch_send_in_progress(&DEV_GENESIS_ACCOUNT)
+    });
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .send_batch_status(*DEV_GENESIS_ACCOUNT)
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.done, true.into());
+    assert_eq!(result.sends.len(), 3);
+    assert!(result.sends.iter().all(|s| s.status == "sent"));
+    // The status of a finished batch is dropped once it was read
+    assert!(node
+        .wallets
+        .batch_send_progress(&DEV_GENESIS_ACCOUNT)
+        .is_none());
+
+    let tx = node.ledger.read_txn();
+    let blocks: Vec<_> = result
+        .sends
+        .iter()
+        .map(|s| node.ledger.get_block(&tx, &s.block.unwrap()).unwrap())
+        .collect();
+    assert_eq!(blocks[1].previous(), blocks[0].hash());
+    assert_eq!(blocks[2].previous(), blocks[1].hash());
+    assert_eq!(
+        node.ledger
+            .any()
+            .account_balance(&tx, &DEV_GENESIS_ACCOUNT)
+            .unwrap(),
+        Amount::MAX - Amount::raw(600)
+    );
+    drop(tx);
+
+    // Submitting the same batch again doesn't send anything new
+    let resumed = node.runtime.block_on(async {
+        server
+            .client
+            .send_batch(SendBatchArgs::new(wallet, *DEV_GENESIS_ACCOUNT, sends()))
+            .await
+            .unwrap()
+    });
+    assert_eq!(resumed.done, true.into());
+    assert!(resumed.sends.iter().all(|s| s.status == "already_sent"));
+    assert_eq!(resumed.sends[2].block, result.sends[2].block);
+}
+
+#[test]
+fn send_batch_fails_with_insufficient_balance() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+    let source: Account = node
+        .wallets
+        .deterministic_insert2(&wallet, false)
+        .unwrap()
+        .into();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .send_batch(SendBatchArgs::new(
+                wallet,
+                source,
+                vec![BatchSendDto::new(Account::from(1), Amount::raw(1), "a")],
+            ))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"insufficient balance\"".to_string())
+    );
+}
+
+#[test]
+fn send_batch_fails_with_duplicate_ids() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .send_batch(SendBatchArgs::new(
+                WalletId::zero(),
+                *DEV_GENESIS_ACCOUNT,
+                vec![
+                    BatchSendDto::new(Account::from(1), Amount::raw(1), "a"),
+                    BatchSendDto::new(Account::from(2), Amount::raw(1), "a"),
+                ],
+            ))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"duplicate send id: a\"".to_string())
+    );
+}
+
+#[test]
+fn send_batch_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .send_batch(SendBatchArgs::new(
+                WalletId::zero(),
+                *DEV_GENESIS_ACCOUNT,
+                vec![BatchSendDto::new(Account::from(1), Amount::raw(1), "a")],
+            ))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
+
+#[test]
+fn send_batch_status_fails_without_batch() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.send_batch_status(Account::from(1)).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"No batch send found for this account\"".to_string())
+    );
+}
@@ -0,0 +1,121 @@
+use rsban_core::{Amount, WalletId};
+use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsban_node::wallets::WalletsExt;
+use rsban_rpc_messages::{WalletActivityArgs, WalletAlertDto, WalletAlertSetArgs};
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn wallet_activity() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::zero();
+    node.wallets.create(wallet_id);
+
+    let (all, page) = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_add_watch(wallet_id, vec![*DEV_GENESIS_ACCOUNT])
+            .await
+            .unwrap();
+        let all = server.client.wallet_activity(wallet_id).await.unwrap();
+        let page = server
+            .client
+            .wallet_activity(WalletActivityArgs::builder(wallet_id).offset(1).build())
+            .await
+            .unwrap();
+        (all, page)
+    });
+
+    assert_eq!(all.activity.len(), 1);
+    assert_eq!(all.activity[0].account, *DEV_GENESIS_ACCOUNT);
+    assert_eq!(all.activity[0].hash, *DEV_GENESIS_HASH);
+    assert_eq!(all.activity[0].balance, Amount::MAX);
+    assert_eq!(all.activity[0].height.inner(), 1);
+    assert!(page.activity.is_empty());
+}
+
+#[test]
+fn wallet_activity_fails_with_wallet_not_found() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_activity(WalletId::random()).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Wallet not found\"".to_string())
+    );
+}
+
+#[test]
+fn wallet_alert_set_list_and_remove() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::zero();
+    node.wallets.create(wallet_id);
+
+    let (alerts, removed) = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_add_watch(wallet_id, vec![*DEV_GENESIS_ACCOUNT])
+            .await
+            .unwrap();
+        server
+            .client
+            .wallet_alert_set(WalletAlertSetArgs::new(
+                wallet_id,
+                *DEV_GENESIS_ACCOUNT,
+                Amount::raw(1000),
+            ))
+            .await
+            .unwrap();
+        let alerts = server.client.wallet_alerts(wallet_id).await.unwrap();
+        server
+            .client
+            .wallet_alert_remove(wallet_id, *DEV_GENESIS_ACCOUNT)
+            .await
+            .unwrap();
+        let removed = server.client.wallet_alerts(wallet_id).await.unwrap();
+        (alerts, removed)
+    });
+
+    assert_eq!(
+        alerts.alerts,
+        vec![WalletAlertDto::new(*DEV_GENESIS_ACCOUNT, Amount::raw(1000))]
+    );
+    assert!(removed.alerts.is_empty());
+}
+
+#[test]
+fn wallet_alert_set_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_alert_set(WalletAlertSetArgs::new(
+                WalletId::zero(),
+                *DEV_GENESIS_ACCOUNT,
+                Amount::raw(1),
+            ))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
@@ -0,0 +1,93 @@
+use rsban_core::WalletId;
+use rsban_node::wallets::WalletsExt;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn wallet_backup_and_restore() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    node.wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap();
+
+    let restored = node.runtime.block_on(async {
+        let backup = server
+            .client
+            .wallet_backup(wallet_id, "secret".to_string())
+            .await
+            .unwrap()
+            .backup;
+        server
+            .client
+            .wallet_restore(backup, "secret".to_string())
+            .await
+            .unwrap()
+            .wallet
+    });
+
+    assert_ne!(restored, wallet_id);
+    assert_eq!(
+        node.wallets.get_seed(restored).unwrap(),
+        node.wallets.get_seed(wallet_id).unwrap()
+    );
+    assert_eq!(
+        node.wallets.get_accounts_of_wallet(&restored).unwrap(),
+        node.wallets.get_accounts_of_wallet(&wallet_id).unwrap()
+    );
+}
+
+#[test]
+fn wallet_restore_fails_with_wrong_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let wallet_count = node.wallets.wallet_ids().len();
+
+    let result = node.runtime.block_on(async {
+        let backup = server
+            .client
+            .wallet_backup(wallet_id, "secret".to_string())
+            .await
+            .unwrap()
+            .backup;
+        server
+            .client
+            .wallet_restore(backup, "wrong".to_string())
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"wallet backup integrity check failed\"".to_string())
+    );
+    assert_eq!(node.wallets.wallet_ids().len(), wallet_count);
+}
+
+#[test]
+fn wallet_backup_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_backup(WalletId::zero(), "secret".to_string())
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
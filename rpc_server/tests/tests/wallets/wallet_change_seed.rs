@@ -1,6 +1,6 @@
 use rsban_core::{RawKey, WalletId};
 use rsban_node::wallets::WalletsExt;
-use rsban_rpc_messages::WalletWithSeedArgs;
+use rsban_rpc_messages::{WalletChangeSeedArgs, WalletWithSeedArgs};
 use test_helpers::{setup_rpc_client_and_server, System};
 
 #[test]
@@ -46,3 +46,56 @@ fn wallet_change_seed_fails_without_enable_control() {
         Some("node returned error: \"RPC control is disabled\"".to_string())
     );
 }
+
+#[test]
+fn wallet_change_seed_with_mnemonic() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::zero();
+    node.wallets.create(wallet_id);
+
+    node.runtime.block_on(async {
+        server
+            .client
+            .wallet_change_seed(WalletChangeSeedArgs::with_mnemonic(
+                wallet_id,
+                "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic bless",
+            ))
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(
+        node.wallets.get_seed(wallet_id).unwrap(),
+        RawKey::from_bytes([0x80; 32])
+    );
+}
+
+#[test]
+fn wallet_change_seed_fails_with_invalid_mnemonic() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::zero();
+    node.wallets.create(wallet_id);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_change_seed(WalletChangeSeedArgs::with_mnemonic(
+                wallet_id,
+                "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic zoo",
+            ))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"invalid mnemonic checksum\"".to_string())
+    );
+}
@@ -35,6 +35,28 @@ fn wallet_create_seed_some() {
     assert!(wallets.contains(&result.wallet));
 }
 
+#[test]
+fn wallet_create_mnemonic() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_create_from_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art")
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(
+        node.wallets.get_seed(result.wallet).unwrap(),
+        RawKey::zero()
+    );
+    assert_eq!(result.restored_count, Some(1.into()));
+}
+
 #[test]
 fn wallet_create_fails_without_enable_control() {
     let mut system = System::new();
@@ -0,0 +1,124 @@
+use rsban_core::{KdfVariant, WalletId};
+use rsban_node::wallets::WalletsExt;
+use rsban_rpc_messages::WalletKdfUpgradeArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn wallet_kdf_upgrade() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let account = node
+        .wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap();
+    let seed = node.wallets.get_seed(wallet_id).unwrap();
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_kdf_upgrade(
+                WalletKdfUpgradeArgs::builder(wallet_id, "".to_string())
+                    .variant("argon2id")
+                    .time_cost(2)
+                    .build(),
+            )
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.kdf.algorithm, KdfVariant::Argon2id.as_str());
+    assert_eq!(result.kdf.time_cost, 2);
+    assert_eq!(result.kdf.mem_cost, node.config.wallet_kdf.memory_cost);
+    assert_eq!(
+        node.wallets.wallet_kdf_params(&wallet_id).unwrap(),
+        result.kdf
+    );
+    assert_eq!(node.wallets.get_seed(wallet_id).unwrap(), seed);
+    assert!(node.wallets.valid_password(&wallet_id).unwrap());
+
+    node.wallets.lock(&wallet_id).unwrap();
+    node.wallets.enter_password(wallet_id, "").unwrap();
+    assert_eq!(
+        node.wallets.get_accounts_of_wallet(&wallet_id).unwrap(),
+        vec![account.into()]
+    );
+}
+
+#[test]
+fn wallet_kdf_upgrade_fails_with_wrong_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let params = node.wallets.wallet_kdf_params(&wallet_id).unwrap();
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_kdf_upgrade(WalletKdfUpgradeArgs::new(wallet_id, "wrong".to_string()))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid password\"".to_string())
+    );
+    assert_eq!(node.wallets.wallet_kdf_params(&wallet_id).unwrap(), params);
+}
+
+#[test]
+fn wallet_kdf_upgrade_rejects_too_expensive_params() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let params = node.wallets.wallet_kdf_params(&wallet_id).unwrap();
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_kdf_upgrade(
+                WalletKdfUpgradeArgs::builder(wallet_id, "".to_string())
+                    .memory_cost(u32::MAX)
+                    .build(),
+            )
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"invalid key derivation function parameters\"".to_string())
+    );
+    assert_eq!(node.wallets.wallet_kdf_params(&wallet_id).unwrap(), params);
+}
+
+#[test]
+fn wallet_kdf_upgrade_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_kdf_upgrade(WalletKdfUpgradeArgs::new(WalletId::zero(), "".to_string()))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
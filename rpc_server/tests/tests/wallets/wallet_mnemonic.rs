@@ -0,0 +1,61 @@
+use rsban_core::{RawKey, WalletId};
+use rsban_node::wallets::WalletsExt;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn wallet_mnemonic() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::zero();
+    node.wallets.create(wallet_id);
+    node.wallets
+        .change_seed(wallet_id, &RawKey::from_bytes([0x80; 32]), 0)
+        .unwrap();
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_mnemonic(wallet_id).await.unwrap() });
+
+    assert_eq!(
+        result.mnemonic,
+        "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic bless"
+    );
+}
+
+#[test]
+fn wallet_mnemonic_fails_when_wallet_is_locked() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::zero();
+    node.wallets.create(wallet_id);
+    node.wallets.lock(&wallet_id).unwrap();
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_mnemonic(wallet_id).await });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn wallet_mnemonic_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_mnemonic(WalletId::zero()).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
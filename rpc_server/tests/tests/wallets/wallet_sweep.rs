@@ -0,0 +1,116 @@
+use rsban_core::{PrivateKey, WalletId};
+use rsban_node::wallets::WalletsExt;
+use rsban_rpc_messages::WalletSweepArgs;
+use std::time::Duration;
+use test_helpers::{assert_timely, setup_rpc_client_and_server, System};
+
+#[test]
+fn wallet_sweep_empty_accounts() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::zero();
+    node.wallets.create(wallet_id);
+    node.wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap();
+    node.wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap();
+    let destination = PrivateKey::new().account();
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_sweep(WalletSweepArgs::builder(wallet_id, destination).build())
+            .await
+            .unwrap()
+    });
+    assert_eq!(result.started, true.into());
+
+    assert_timely(Duration::from_secs(5), || {
+        node.wallets
+            .sweep_progress(&wallet_id)
+            .unwrap()
+            .unwrap()
+            .is_done()
+    });
+
+    let status = node
+        .runtime
+        .block_on(async { server.client.wallet_sweep_status(wallet_id).await.unwrap() });
+
+    assert_eq!(status.destination, destination);
+    assert_eq!(status.done, true.into());
+    assert_eq!(status.processed.inner(), 2);
+    assert_eq!(status.total.inner(), 2);
+    assert!(status.swept.is_zero());
+    assert!(status.accounts.iter().all(|a| a.status == "empty"));
+}
+
+#[test]
+fn wallet_sweep_fails_with_account_not_found() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::zero();
+    node.wallets.create(wallet_id);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_sweep(
+                WalletSweepArgs::builder(wallet_id, PrivateKey::new().account())
+                    .accounts(vec![PrivateKey::new().account()])
+                    .build(),
+            )
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Account not found\"".to_string())
+    );
+}
+
+#[test]
+fn wallet_sweep_status_fails_without_sweep() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let wallet_id = WalletId::zero();
+    node.wallets.create(wallet_id);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_sweep_status(wallet_id).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"No sweep found for this wallet\"".to_string())
+    );
+}
+
+#[test]
+fn wallet_sweep_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        server
+            .client
+            .wallet_sweep(
+                WalletSweepArgs::builder(WalletId::zero(), PrivateKey::new().account()).build(),
+            )
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
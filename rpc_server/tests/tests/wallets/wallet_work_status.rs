@@ -0,0 +1,86 @@
+use rsban_core::WalletId;
+use rsban_node::wallets::WalletsExt;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn wallet_work_status() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::zero();
+    node.wallets.create(wallet_id);
+    let missing = node
+        .wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap();
+    let cached = node
+        .wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap();
+    node.wallets
+        .work_cache_blocking2(&wallet_id, &cached, &cached.into())
+        .unwrap();
+
+    let status = node
+        .runtime
+        .block_on(async { server.client.wallet_work_status(wallet_id).await.unwrap() });
+
+    assert_eq!(status.valid, 1.into());
+    assert_eq!(status.missing, 1.into());
+    assert_eq!(status.accounts.len(), 2);
+
+    let missing_entry = status
+        .accounts
+        .iter()
+        .find(|a| a.account == missing.into())
+        .unwrap();
+    assert_eq!(missing_entry.state, "missing");
+    assert_eq!(missing_entry.work, 0.into());
+
+    let cached_entry = status
+        .accounts
+        .iter()
+        .find(|a| a.account == cached.into())
+        .unwrap();
+    assert_eq!(cached_entry.state, "valid");
+    assert_eq!(cached_entry.root, cached.into());
+    assert_eq!(
+        cached_entry.difficulty.inner(),
+        node.network_params
+            .work
+            .difficulty(&cached.into(), cached_entry.work.into())
+    );
+}
+
+#[test]
+fn wallet_work_status_fails_with_wallet_not_found() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_work_status(WalletId::zero()).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Wallet not found\"".to_string())
+    );
+}
+
+#[test]
+fn wallet_work_status_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let server = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { server.client.wallet_work_status(WalletId::zero()).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+}
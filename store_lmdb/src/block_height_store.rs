@@ -0,0 +1,136 @@
+use crate::{LmdbDatabase, LmdbEnv, LmdbWriteTransaction, Transaction};
+use lmdb::{DatabaseFlags, WriteFlags};
+use rsban_core::{Account, BlockHash};
+use std::sync::Arc;
+
+/// Secondary index of the block table, so that the block at a given height of
+/// an account chain can be found without walking the chain.
+pub struct LmdbBlockHeightStore {
+    _env: Arc<LmdbEnv>,
+
+    /// (Account, Height) -> BlockHash
+    database: LmdbDatabase,
+}
+
+impl LmdbBlockHeightStore {
+    pub fn new(env: Arc<LmdbEnv>) -> anyhow::Result<Self> {
+        let database = env
+            .environment
+            .create_db(Some("block_heights"), DatabaseFlags::empty())?;
+        Ok(Self {
+            _env: env,
+            database,
+        })
+    }
+
+    pub fn database(&self) -> LmdbDatabase {
+        self.database
+    }
+
+    pub fn put(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        account: &Account,
+        height: u64,
+        hash: &BlockHash,
+    ) {
+        txn.put(
+            self.database,
+            &height_key(account, height),
+            hash.as_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn get(&self, txn: &dyn Transaction, account: &Account, height: u64) -> Option<BlockHash> {
+        match txn.get(self.database, &height_key(account, height)) {
+            Ok(bytes) => BlockHash::from_slice(bytes),
+            Err(lmdb::Error::NotFound) => None,
+            Err(e) => panic!("Could not load block height: {:?}", e),
+        }
+    }
+
+    pub fn del(&self, txn: &mut LmdbWriteTransaction, account: &Account, height: u64) {
+        match txn.delete(self.database, &height_key(account, height), None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => {}
+            Err(e) => panic!("Could not delete block height: {:?}", e),
+        }
+    }
+
+    /// Total number of entries in the index
+    pub fn count(&self, txn: &dyn Transaction) -> u64 {
+        txn.count(self.database)
+    }
+
+    pub fn clear(&self, txn: &mut LmdbWriteTransaction) {
+        txn.clear_db(self.database).unwrap();
+    }
+}
+
+fn height_key(account: &Account, height: u64) -> [u8; 40] {
+    let mut key = [0; 40];
+    key[..32].copy_from_slice(account.as_bytes());
+    key[32..].copy_from_slice(&height.to_be_bytes());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestDbFile;
+
+    #[test]
+    fn put_and_get() {
+        let fixture = Fixture::new();
+        let account = Account::from(1);
+        let mut txn = fixture.env.tx_begin_write();
+        fixture
+            .store
+            .put(&mut txn, &account, 1, &BlockHash::from(100));
+        fixture
+            .store
+            .put(&mut txn, &account, 2, &BlockHash::from(101));
+
+        assert_eq!(
+            fixture.store.get(&txn, &account, 2),
+            Some(BlockHash::from(101))
+        );
+        assert_eq!(fixture.store.get(&txn, &account, 3), None);
+        assert_eq!(fixture.store.get(&txn, &Account::from(2), 1), None);
+    }
+
+    #[test]
+    fn delete() {
+        let fixture = Fixture::new();
+        let account = Account::from(1);
+        let mut txn = fixture.env.tx_begin_write();
+        fixture
+            .store
+            .put(&mut txn, &account, 1, &BlockHash::from(100));
+
+        fixture.store.del(&mut txn, &account, 1);
+        fixture.store.del(&mut txn, &account, 1);
+
+        assert_eq!(fixture.store.get(&txn, &account, 1), None);
+        assert_eq!(fixture.store.count(&txn), 0);
+    }
+
+    struct Fixture {
+        env: Arc<LmdbEnv>,
+        store: LmdbBlockHeightStore,
+        _db_file: TestDbFile,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let db_file = TestDbFile::random();
+            let env = Arc::new(LmdbEnv::new(&db_file.path).unwrap());
+            Self {
+                store: LmdbBlockHeightStore::new(env.clone()).unwrap(),
+                env,
+                _db_file: db_file,
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use crate::{LmdbDatabase, LmdbEnv, LmdbWriteTransaction, RoCursor, Transaction};
+use lmdb::{DatabaseFlags, WriteFlags};
+use lmdb_sys::{MDB_cursor_op, MDB_NEXT, MDB_SET_RANGE};
+use rsban_core::{Account, Amount, PublicKey};
+use std::sync::Arc;
+
+/// Secondary index of the account table, so that the delegators of a
+/// representative can be found without scanning all accounts.
+pub struct LmdbDelegatorStore {
+    _env: Arc<LmdbEnv>,
+
+    /// (Representative, Account) -> Amount
+    database: LmdbDatabase,
+}
+
+impl LmdbDelegatorStore {
+    pub fn new(env: Arc<LmdbEnv>) -> anyhow::Result<Self> {
+        let database = env
+            .environment
+            .create_db(Some("delegators"), DatabaseFlags::empty())?;
+        Ok(Self {
+            _env: env,
+            database,
+        })
+    }
+
+    pub fn database(&self) -> LmdbDatabase {
+        self.database
+    }
+
+    pub fn put(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        representative: &PublicKey,
+        account: &Account,
+        balance: Amount,
+    ) {
+        txn.put(
+            self.database,
+            &delegator_key(representative, account),
+            &balance.to_be_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn get(
+        &self,
+        txn: &dyn Transaction,
+        representative: &PublicKey,
+        account: &Account,
+    ) -> Option<Amount> {
+        match txn.get(self.database, &delegator_key(representative, account)) {
+            Ok(bytes) => Some(Amount::from_be_bytes(bytes.try_into().unwrap())),
+            Err(lmdb::Error::NotFound) => None,
+            Err(e) => panic!("Could not load delegator: {:?}", e),
+        }
+    }
+
+    pub fn del(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        representative: &PublicKey,
+        account: &Account,
+    ) {
+        match txn.delete(self.database, &delegator_key(representative, account), None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => {}
+            Err(e) => panic!("Could not delete delegator: {:?}", e),
+        }
+    }
+
+    /// Total number of entries in the index
+    pub fn count(&self, txn: &dyn Transaction) -> u64 {
+        txn.count(self.database)
+    }
+
+    pub fn clear(&self, txn: &mut LmdbWriteTransaction) {
+        txn.clear_db(self.database).unwrap();
+    }
+
+    /// Iterates the delegators of `representative` in account order, starting at `start`
+    pub fn iter<'txn>(
+        &self,
+        txn: &'txn dyn Transaction,
+        representative: &PublicKey,
+        start: &Account,
+    ) -> DelegatorIterator<'txn> {
+        let cursor = txn
+            .open_ro_cursor(self.database)
+            .expect("Could not read delegators database");
+        DelegatorIterator {
+            cursor,
+            representative: *representative,
+            start: delegator_key(representative, start),
+            operation: MDB_SET_RANGE,
+        }
+    }
+}
+
+pub struct DelegatorIterator<'txn> {
+    cursor: RoCursor<'txn>,
+    representative: PublicKey,
+    start: [u8; 64],
+    operation: MDB_cursor_op,
+}
+
+impl<'txn> Iterator for DelegatorIterator<'txn> {
+    type Item = (Account, Amount);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = if self.operation == MDB_SET_RANGE {
+            Some(self.start.as_slice())
+        } else {
+            None
+        };
+        match self.cursor.get(key, None, self.operation) {
+            Err(lmdb::Error::NotFound) => None,
+            Ok((Some(k), v)) => {
+                self.operation = MDB_NEXT;
+                let (representative, account) = k.split_at(32);
+                if representative != self.representative.as_bytes() {
+                    return None;
+                }
+                Some((
+                    Account::from_slice(account).unwrap(),
+                    Amount::from_be_bytes(v.try_into().unwrap()),
+                ))
+            }
+            Ok(_) => unreachable!(),
+            Err(e) => panic!("Could not read delegators: {:?}", e),
+        }
+    }
+}
+
+fn delegator_key(representative: &PublicKey, account: &Account) -> [u8; 64] {
+    let mut key = [0; 64];
+    key[..32].copy_from_slice(representative.as_bytes());
+    key[32..].copy_from_slice(account.as_bytes());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestDbFile;
+
+    #[test]
+    fn iterate_delegators_of_representative() {
+        let fixture = Fixture::new();
+        let rep1 = PublicKey::from(1);
+        let rep2 = PublicKey::from(2);
+        let mut txn = fixture.env.tx_begin_write();
+        fixture
+            .store
+            .put(&mut txn, &rep1, &Account::from(10), Amount::raw(100));
+        fixture
+            .store
+            .put(&mut txn, &rep1, &Account::from(11), Amount::raw(200));
+        fixture
+            .store
+            .put(&mut txn, &rep2, &Account::from(12), Amount::raw(300));
+
+        let delegators: Vec<_> = fixture.store.iter(&txn, &rep1, &Account::zero()).collect();
+        assert_eq!(
+            delegators,
+            vec![
+                (Account::from(10), Amount::raw(100)),
+                (Account::from(11), Amount::raw(200))
+            ]
+        );
+
+        let delegators: Vec<_> = fixture
+            .store
+            .iter(&txn, &rep1, &Account::from(11))
+            .collect();
+        assert_eq!(delegators, vec![(Account::from(11), Amount::raw(200))]);
+
+        assert_eq!(
+            fixture
+                .store
+                .iter(&txn, &PublicKey::from(3), &Account::zero())
+                .next(),
+            None
+        );
+    }
+
+    #[test]
+    fn delete_delegator() {
+        let fixture = Fixture::new();
+        let rep = PublicKey::from(1);
+        let account = Account::from(10);
+        let mut txn = fixture.env.tx_begin_write();
+        fixture
+            .store
+            .put(&mut txn, &rep, &account, Amount::raw(100));
+
+        fixture.store.del(&mut txn, &rep, &account);
+        fixture.store.del(&mut txn, &rep, &account);
+
+        assert_eq!(fixture.store.get(&txn, &rep, &account), None);
+        assert_eq!(fixture.store.count(&txn), 0);
+    }
+
+    struct Fixture {
+        env: Arc<LmdbEnv>,
+        store: LmdbDelegatorStore,
+        _db_file: TestDbFile,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let db_file = TestDbFile::random();
+            let env = Arc::new(LmdbEnv::new(&db_file.path).unwrap());
+            Self {
+                store: LmdbDelegatorStore::new(env.clone()).unwrap(),
+                env,
+                _db_file: db_file,
+            }
+        }
+    }
+}
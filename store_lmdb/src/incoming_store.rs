@@ -0,0 +1,209 @@
+use crate::{LmdbDatabase, LmdbEnv, LmdbWriteTransaction, RoCursor, Transaction};
+use lmdb::{DatabaseFlags, WriteFlags};
+use lmdb_sys::{MDB_cursor_op, MDB_NEXT, MDB_SET_RANGE};
+use rsban_core::{Account, Amount, BlockHash};
+use std::sync::Arc;
+
+/// A send block as seen from its destination account
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct IncomingSend {
+    pub hash: BlockHash,
+    /// Local timestamp of the send block
+    pub timestamp: u64,
+    pub source: Account,
+    pub amount: Amount,
+}
+
+/// Secondary index of all send blocks by their destination. In contrast to the
+/// pending table it keeps sends that were already received or pruned.
+pub struct LmdbIncomingStore {
+    _env: Arc<LmdbEnv>,
+
+    /// (Destination, Timestamp, BlockHash) -> (Source, Amount)
+    database: LmdbDatabase,
+}
+
+impl LmdbIncomingStore {
+    pub fn new(env: Arc<LmdbEnv>) -> anyhow::Result<Self> {
+        let database = env
+            .environment
+            .create_db(Some("incoming"), DatabaseFlags::empty())?;
+        Ok(Self {
+            _env: env,
+            database,
+        })
+    }
+
+    pub fn database(&self) -> LmdbDatabase {
+        self.database
+    }
+
+    pub fn put(&self, txn: &mut LmdbWriteTransaction, destination: &Account, send: &IncomingSend) {
+        let mut value = [0; 48];
+        value[..32].copy_from_slice(send.source.as_bytes());
+        value[32..].copy_from_slice(&send.amount.to_be_bytes());
+        txn.put(
+            self.database,
+            &incoming_key(destination, send.timestamp, &send.hash),
+            &value,
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn del(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        destination: &Account,
+        timestamp: u64,
+        hash: &BlockHash,
+    ) {
+        match txn.delete(
+            self.database,
+            &incoming_key(destination, timestamp, hash),
+            None,
+        ) {
+            Ok(()) | Err(lmdb::Error::NotFound) => {}
+            Err(e) => panic!("Could not delete incoming send: {:?}", e),
+        }
+    }
+
+    /// Total number of entries in the index
+    pub fn count(&self, txn: &dyn Transaction) -> u64 {
+        txn.count(self.database)
+    }
+
+    pub fn clear(&self, txn: &mut LmdbWriteTransaction) {
+        txn.clear_db(self.database).unwrap();
+    }
+
+    /// Iterates the sends to `destination` ordered by timestamp, starting at `since`
+    pub fn iter<'txn>(
+        &self,
+        txn: &'txn dyn Transaction,
+        destination: &Account,
+        since: u64,
+    ) -> IncomingIterator<'txn> {
+        let cursor = txn
+            .open_ro_cursor(self.database)
+            .expect("Could not read incoming database");
+        IncomingIterator {
+            cursor,
+            destination: *destination,
+            start: incoming_key(destination, since, &BlockHash::zero()),
+            operation: MDB_SET_RANGE,
+        }
+    }
+}
+
+pub struct IncomingIterator<'txn> {
+    cursor: RoCursor<'txn>,
+    destination: Account,
+    start: [u8; 72],
+    operation: MDB_cursor_op,
+}
+
+impl<'txn> Iterator for IncomingIterator<'txn> {
+    type Item = IncomingSend;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = if self.operation == MDB_SET_RANGE {
+            Some(self.start.as_slice())
+        } else {
+            None
+        };
+        match self.cursor.get(key, None, self.operation) {
+            Err(lmdb::Error::NotFound) => None,
+            Ok((Some(k), v)) => {
+                self.operation = MDB_NEXT;
+                if &k[..32] != self.destination.as_bytes() {
+                    return None;
+                }
+                Some(IncomingSend {
+                    timestamp: u64::from_be_bytes(k[32..40].try_into().unwrap()),
+                    hash: BlockHash::from_slice(&k[40..]).unwrap(),
+                    source: Account::from_slice(&v[..32]).unwrap(),
+                    amount: Amount::from_be_bytes(v[32..].try_into().unwrap()),
+                })
+            }
+            Ok(_) => unreachable!(),
+            Err(e) => panic!("Could not read incoming sends: {:?}", e),
+        }
+    }
+}
+
+fn incoming_key(destination: &Account, timestamp: u64, hash: &BlockHash) -> [u8; 72] {
+    let mut key = [0; 72];
+    key[..32].copy_from_slice(destination.as_bytes());
+    key[32..40].copy_from_slice(&timestamp.to_be_bytes());
+    key[40..].copy_from_slice(hash.as_bytes());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestDbFile;
+
+    #[test]
+    fn iterate_by_timestamp() {
+        let fixture = Fixture::new();
+        let destination = Account::from(1);
+        let later = send(2, 200);
+        let earlier = send(3, 100);
+        let mut txn = fixture.env.tx_begin_write();
+        fixture.store.put(&mut txn, &destination, &later);
+        fixture.store.put(&mut txn, &destination, &earlier);
+        fixture
+            .store
+            .put(&mut txn, &Account::from(2), &send(4, 150));
+
+        let sends: Vec<_> = fixture.store.iter(&txn, &destination, 0).collect();
+        assert_eq!(sends, vec![earlier, later.clone()]);
+
+        let sends: Vec<_> = fixture.store.iter(&txn, &destination, 101).collect();
+        assert_eq!(sends, vec![later]);
+    }
+
+    #[test]
+    fn delete() {
+        let fixture = Fixture::new();
+        let destination = Account::from(1);
+        let send = send(2, 200);
+        let mut txn = fixture.env.tx_begin_write();
+        fixture.store.put(&mut txn, &destination, &send);
+
+        fixture
+            .store
+            .del(&mut txn, &destination, send.timestamp, &send.hash);
+
+        assert_eq!(fixture.store.iter(&txn, &destination, 0).next(), None);
+    }
+
+    fn send(hash: u64, timestamp: u64) -> IncomingSend {
+        IncomingSend {
+            hash: BlockHash::from(hash),
+            timestamp,
+            source: Account::from(hash + 1000),
+            amount: Amount::raw(hash as u128),
+        }
+    }
+
+    struct Fixture {
+        env: Arc<LmdbEnv>,
+        store: LmdbIncomingStore,
+        _db_file: TestDbFile,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let db_file = TestDbFile::random();
+            let env = Arc::new(LmdbEnv::new(&db_file.path).unwrap());
+            Self {
+                store: LmdbIncomingStore::new(env.clone()).unwrap(),
+                env,
+                _db_file: db_file,
+            }
+        }
+    }
+}
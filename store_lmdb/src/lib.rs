@@ -5,10 +5,13 @@ extern crate num_derive;
 extern crate anyhow;
 
 mod account_store;
+mod block_height_store;
 mod block_store;
 mod confirmation_height_store;
+mod delegator_store;
 mod fan;
 mod final_vote_store;
+mod incoming_store;
 mod iterator;
 mod lmdb_config;
 mod lmdb_env;
@@ -22,10 +25,13 @@ mod version_store;
 mod wallet_store;
 
 pub use account_store::{ConfiguredAccountDatabaseBuilder, LmdbAccountStore};
+pub use block_height_store::LmdbBlockHeightStore;
 pub use block_store::{ConfiguredBlockDatabaseBuilder, LmdbBlockStore};
 pub use confirmation_height_store::*;
+pub use delegator_store::{DelegatorIterator, LmdbDelegatorStore};
 pub use fan::Fan;
 pub use final_vote_store::LmdbFinalVoteStore;
+pub use incoming_store::{IncomingIterator, IncomingSend, LmdbIncomingStore};
 pub use iterator::{BinaryDbIterator, LmdbIterator, LmdbIteratorImpl};
 pub use lmdb_config::{LmdbConfig, SyncStrategy};
 pub use lmdb_env::*;
@@ -37,7 +43,7 @@ pub use rep_weight_store::*;
 use rsban_nullable_lmdb::{
     InactiveTransaction, LmdbDatabase, LmdbEnvironment, RoCursor, RoTransaction, RwTransaction,
 };
-pub use store::{create_backup_file, LedgerCache, LmdbStore};
+pub use store::{create_backup_file, LedgerCache, LedgerIndex, LmdbStore};
 pub use version_store::LmdbVersionStore;
 pub use wallet_store::{Fans, KeyType, LmdbWalletStore, WalletValue};
 
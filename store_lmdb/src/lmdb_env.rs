@@ -27,6 +27,9 @@ use tracing::debug;
 pub struct EnvOptions {
     pub config: LmdbConfig,
     pub use_no_mem_init: bool,
+    /// Opens an existing database without write access. Other processes can
+    /// keep writing to the same file
+    pub read_only: bool,
 }
 
 pub struct NullLmdbEnvBuilder {
@@ -89,6 +92,10 @@ impl LmdbEnv {
         }
     }
 
+    pub fn new_in_memory() -> Self {
+        Self::new_with_env(LmdbEnvironment::new_in_memory())
+    }
+
     pub fn new(path: impl AsRef<Path>) -> anyhow::Result<Self> {
         Self::new_with_options(path, &EnvOptions::default())
     }
@@ -132,7 +139,9 @@ impl LmdbEnv {
             path.extension() == Some(&OsStr::new("ldb")),
             "invalid filename extension for lmdb database file"
         );
-        try_create_parent_dir(path)?;
+        if !options.read_only {
+            try_create_parent_dir(path)?;
+        }
         let mut map_size = options.config.map_size;
         let max_instrumented_map_size = 16 * 1024 * 1024;
         if memory_intensive_instrumentation() && map_size > max_instrumented_map_size {
@@ -160,6 +169,10 @@ impl LmdbEnv {
         if !memory_intensive_instrumentation() && options.use_no_mem_init {
             environment_flags |= EnvironmentFlags::NO_MEM_INIT;
         }
+
+        if options.read_only {
+            environment_flags |= EnvironmentFlags::READ_ONLY;
+        }
         let env_options = EnvironmentOptions {
             max_dbs: options.config.max_databases,
             map_size,
@@ -186,8 +199,11 @@ impl LmdbEnv {
     }
 
     pub fn file_path(&self) -> anyhow::Result<PathBuf> {
+        if self.environment.is_in_memory() {
+            bail!("in-memory env has no file path");
+        }
         let mut path: *const c_char = std::ptr::null();
-        let status = unsafe { lmdb_sys::mdb_env_get_path(self.environment.env(), &mut path) };
+        let status = unsafe { lmdb_sys::mdb_env_get_path(self.environment.env()?, &mut path) };
         if status != MDB_SUCCESS {
             bail!("could not get env path");
         }
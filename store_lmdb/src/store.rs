@@ -1,13 +1,14 @@
 use crate::{
-    EnvOptions, LmdbAccountStore, LmdbBlockStore, LmdbConfirmationHeightStore, LmdbDatabase,
-    LmdbEnv, LmdbFinalVoteStore, LmdbOnlineWeightStore, LmdbPeerStore, LmdbPendingStore,
-    LmdbPrunedStore, LmdbReadTransaction, LmdbRepWeightStore, LmdbVersionStore,
-    LmdbWriteTransaction, NullTransactionTracker, TransactionTracker, STORE_VERSION_CURRENT,
+    EnvOptions, IncomingSend, LmdbAccountStore, LmdbBlockHeightStore, LmdbBlockStore,
+    LmdbConfirmationHeightStore, LmdbDatabase, LmdbDelegatorStore, LmdbEnv, LmdbFinalVoteStore,
+    LmdbIncomingStore, LmdbOnlineWeightStore, LmdbPeerStore, LmdbPendingStore, LmdbPrunedStore,
+    LmdbReadTransaction, LmdbRepWeightStore, LmdbVersionStore, LmdbWriteTransaction,
+    NullTransactionTracker, Transaction, TransactionTracker, STORE_VERSION_CURRENT,
     STORE_VERSION_MINIMUM,
 };
 use lmdb::{DatabaseFlags, WriteFlags};
 use lmdb_sys::{MDB_CP_COMPACT, MDB_SUCCESS};
-use rsban_core::utils::seconds_since_epoch;
+use rsban_core::{utils::seconds_since_epoch, Account, BlockHash, PendingKey, SavedBlock};
 use serde::{Deserialize, Serialize};
 use std::{
     ffi::CString,
@@ -19,6 +20,30 @@ use std::{
 };
 use tracing::{debug, error, info, warn};
 
+/// Optional secondary indexes of the ledger tables
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LedgerIndex {
+    Delegators,
+    BlockHeight,
+    Incoming,
+}
+
+impl LedgerIndex {
+    pub const ALL: [LedgerIndex; 3] = [
+        LedgerIndex::Delegators,
+        LedgerIndex::BlockHeight,
+        LedgerIndex::Incoming,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LedgerIndex::Delegators => "delegators",
+            LedgerIndex::BlockHeight => "block height",
+            LedgerIndex::Incoming => "incoming sends",
+        }
+    }
+}
+
 #[derive(PartialEq, Eq)]
 pub enum Vacuuming {
     Needed,
@@ -63,6 +88,9 @@ pub struct LmdbStore {
     pub confirmation_height: Arc<LmdbConfirmationHeightStore>,
     pub final_vote: Arc<LmdbFinalVoteStore>,
     pub version: Arc<LmdbVersionStore>,
+    pub delegators: Arc<LmdbDelegatorStore>,
+    pub block_height: Arc<LmdbBlockHeightStore>,
+    pub incoming: Arc<LmdbIncomingStore>,
 }
 
 pub struct LmdbStoreBuilder<'a> {
@@ -114,6 +142,30 @@ impl LmdbStore {
         Self::new_with_env(LmdbEnv::new_null()).unwrap()
     }
 
+    /// Creates a store which keeps all tables in memory. Nothing is persisted,
+    /// so it is only useful for tests and ephemeral nodes
+    pub fn new_in_memory() -> Self {
+        let store = Self::new_with_env(LmdbEnv::new_in_memory()).unwrap();
+        let mut txn = store.tx_begin_write();
+        store.version.put(&mut txn, STORE_VERSION_CURRENT);
+        txn.commit();
+        store
+    }
+
+    pub fn is_in_memory(&self) -> bool {
+        self.env.environment.is_in_memory()
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.env.environment.is_read_only()
+    }
+
+    /// Id of the last committed write transaction. It changes when another
+    /// process writes to the store, too.
+    pub fn last_txn_id(&self) -> anyhow::Result<u64> {
+        Ok(self.env.environment.last_txn_id()?)
+    }
+
     pub fn open(path: &Path) -> LmdbStoreBuilder<'_> {
         LmdbStoreBuilder::new(path)
     }
@@ -125,12 +177,36 @@ impl LmdbStore {
         backup_before_upgrade: bool,
     ) -> anyhow::Result<Self> {
         let path = path.as_ref();
+        if options.read_only {
+            return Self::open_read_only(path, options, txn_tracker);
+        }
         upgrade_if_needed(path, backup_before_upgrade)?;
 
         let env = LmdbEnv::new_with_txn_tracker(path, options, txn_tracker)?;
         Self::new_with_env(env)
     }
 
+    fn open_read_only(
+        path: &Path,
+        options: &EnvOptions,
+        txn_tracker: Arc<dyn TransactionTracker>,
+    ) -> anyhow::Result<Self> {
+        if !path.exists() {
+            bail!("database file {:?} does not exist", path);
+        }
+        let env = LmdbEnv::new_with_txn_tracker(path, options, txn_tracker)?;
+        let store = Self::new_with_env(env)?;
+        let version = store.version.get(&store.tx_begin_read());
+        if version != Some(STORE_VERSION_CURRENT) {
+            bail!(
+                "database version {:?} is not the current version {}. It has to be upgraded by a writable node first",
+                version,
+                STORE_VERSION_CURRENT
+            );
+        }
+        Ok(store)
+    }
+
     fn new_with_env(env: LmdbEnv) -> anyhow::Result<Self> {
         let env = Arc::new(env);
         Ok(Self {
@@ -145,6 +221,9 @@ impl LmdbStore {
             confirmation_height: Arc::new(LmdbConfirmationHeightStore::new(env.clone())?),
             final_vote: Arc::new(LmdbFinalVoteStore::new(env.clone())?),
             version: Arc::new(LmdbVersionStore::new(env.clone())?),
+            delegators: Arc::new(LmdbDelegatorStore::new(env.clone())?),
+            block_height: Arc::new(LmdbBlockHeightStore::new(env.clone())?),
+            incoming: Arc::new(LmdbIncomingStore::new(env.clone())?),
             env,
         })
     }
@@ -168,21 +247,179 @@ impl LmdbStore {
         Ok(())
     }
 
+    pub fn index_count(&self, txn: &dyn Transaction, index: LedgerIndex) -> u64 {
+        match index {
+            LedgerIndex::Delegators => self.delegators.count(txn),
+            LedgerIndex::BlockHeight => self.block_height.count(txn),
+            LedgerIndex::Incoming => self.incoming.count(txn),
+        }
+    }
+
+    /// Whether the index exists and its last rebuild wasn't interrupted
+    pub fn index_built(&self, txn: &dyn Transaction, index: LedgerIndex) -> bool {
+        self.index_count(txn, index) > 0 && !self.version.index_rebuilding(txn, index)
+    }
+
+    /// Recreates the index and returns the number of indexed entries. The
+    /// transaction is committed after every batch, so that other writers
+    /// aren't blocked for the whole rebuild.
+    pub fn rebuild_index(&self, txn: &mut LmdbWriteTransaction, index: LedgerIndex) -> u64 {
+        self.version.set_index_rebuilding(txn, index, true);
+        let count = match index {
+            LedgerIndex::Delegators => self.rebuild_delegators(txn),
+            LedgerIndex::BlockHeight => self.rebuild_block_heights(txn),
+            LedgerIndex::Incoming => self.rebuild_incoming(txn),
+        };
+        self.version.set_index_rebuilding(txn, index, false);
+        count
+    }
+
+    /// Recreates the delegators index from the account table and returns the number of indexed accounts
+    fn rebuild_delegators(&self, txn: &mut LmdbWriteTransaction) -> u64 {
+        const BATCH_SIZE: usize = 10_000;
+        self.delegators.clear(txn);
+
+        let mut count = 0;
+        let mut start = Some(Account::zero());
+        while let Some(from) = start {
+            let batch: Vec<_> = self
+                .account
+                .iter_range(txn, from..)
+                .take(BATCH_SIZE)
+                .collect();
+
+            for (account, info) in &batch {
+                self.delegators
+                    .put(txn, &info.representative, account, info.balance);
+            }
+            count += batch.len() as u64;
+            txn.refresh();
+
+            start = if batch.len() < BATCH_SIZE {
+                None
+            } else {
+                batch.last().and_then(|(account, _)| account.inc())
+            };
+        }
+        count
+    }
+
+    /// Recreates the block height index from the block table and returns the number of indexed blocks
+    fn rebuild_block_heights(&self, txn: &mut LmdbWriteTransaction) -> u64 {
+        const BATCH_SIZE: usize = 10_000;
+        self.block_height.clear(txn);
+
+        let mut count = 0;
+        let mut start = Some(BlockHash::zero());
+        while let Some(from) = start {
+            let mut batch = Vec::with_capacity(BATCH_SIZE);
+            {
+                let mut it = self.block.begin_at_hash(txn, &from);
+                while let Some((hash, block)) = it.current() {
+                    if batch.len() >= BATCH_SIZE {
+                        break;
+                    }
+                    batch.push((block.account(), block.height(), *hash));
+                    it.next();
+                }
+            }
+
+            for (account, height, hash) in &batch {
+                self.block_height.put(txn, account, *height, hash);
+            }
+            count += batch.len() as u64;
+            txn.refresh();
+
+            start = if batch.len() < BATCH_SIZE {
+                None
+            } else {
+                batch.last().and_then(|(_, _, hash)| hash.inc())
+            };
+        }
+        count
+    }
+
+    /// Recreates the incoming sends index from the block table and returns the number of indexed sends.
+    /// Sends whose amount can't be determined anymore, because the previous block was pruned
+    /// and the send was already received, are skipped.
+    fn rebuild_incoming(&self, txn: &mut LmdbWriteTransaction) -> u64 {
+        const BATCH_SIZE: usize = 10_000;
+        self.incoming.clear(txn);
+
+        let mut count = 0;
+        let mut start = Some(BlockHash::zero());
+        while let Some(from) = start {
+            let mut batch = Vec::with_capacity(BATCH_SIZE);
+            let mut sends = Vec::new();
+            {
+                let mut it = self.block.begin_at_hash(txn, &from);
+                while let Some((hash, block)) = it.current() {
+                    if batch.len() >= BATCH_SIZE {
+                        break;
+                    }
+                    batch.push(*hash);
+                    if let Some(send) = self.incoming_send(txn, block) {
+                        sends.push(send);
+                    }
+                    it.next();
+                }
+            }
+
+            for (destination, send) in &sends {
+                self.incoming.put(txn, destination, send);
+            }
+            count += sends.len() as u64;
+            txn.refresh();
+
+            start = if batch.len() < BATCH_SIZE {
+                None
+            } else {
+                batch.last().and_then(|hash| hash.inc())
+            };
+        }
+        count
+    }
+
+    fn incoming_send(
+        &self,
+        txn: &dyn Transaction,
+        block: &SavedBlock,
+    ) -> Option<(Account, IncomingSend)> {
+        let destination = block.destination()?;
+        let hash = block.hash();
+        let amount = match self.block.get(txn, &block.previous()) {
+            Some(previous) => previous.balance() - block.balance(),
+            None => {
+                self.pending
+                    .get(txn, &PendingKey::new(destination, hash))?
+                    .amount
+            }
+        };
+        Some((
+            destination,
+            IncomingSend {
+                hash,
+                timestamp: block.timestamp(),
+                source: block.account(),
+                amount,
+            },
+        ))
+    }
+
     pub fn memory_stats(&self) -> anyhow::Result<MemoryStats> {
         let stats = self.env.environment.stat()?;
         Ok(MemoryStats {
-            branch_pages: stats.branch_pages(),
-            depth: stats.depth(),
-            entries: stats.entries(),
-            leaf_pages: stats.leaf_pages(),
-            overflow_pages: stats.overflow_pages(),
-            page_size: stats.page_size(),
+            branch_pages: stats.branch_pages,
+            depth: stats.depth,
+            entries: stats.entries,
+            leaf_pages: stats.leaf_pages,
+            overflow_pages: stats.overflow_pages,
+            page_size: stats.page_size,
         })
     }
 
     pub fn vendor(&self) -> String {
-        // fake version! TODO: read version
-        format!("lmdb-rkv {}.{}.{}", 0, 14, 0)
+        self.env.environment.vendor()
     }
 
     pub fn tx_begin_read(&self) -> LmdbReadTransaction {
@@ -313,9 +550,12 @@ fn vacuum_after_upgrade(env: Arc<LmdbEnv>, path: &Path) -> anyhow::Result<()> {
     }
 }
 fn copy_db(env: &LmdbEnv, destination: &Path) -> anyhow::Result<()> {
+    if env.environment.is_in_memory() {
+        bail!("an in-memory database cannot be copied");
+    }
     let c_path = CString::new(destination.as_os_str().to_str().unwrap()).unwrap();
     let status =
-        unsafe { lmdb_sys::mdb_env_copy2(env.environment.env(), c_path.as_ptr(), MDB_CP_COMPACT) };
+        unsafe { lmdb_sys::mdb_env_copy2(env.environment.env()?, c_path.as_ptr(), MDB_CP_COMPACT) };
     ensure_success(status)
 }
 
@@ -354,7 +594,7 @@ pub fn create_backup_file(env: &LmdbEnv) -> anyhow::Result<()> {
             .ok_or_else(|| anyhow!("invalid backup path"))?,
     )?;
     let status =
-        unsafe { lmdb_sys::mdb_env_copy(env.environment.env(), backup_path_cstr.as_ptr()) };
+        unsafe { lmdb_sys::mdb_env_copy(env.environment.env()?, backup_path_cstr.as_ptr()) };
     if status != MDB_SUCCESS {
         error!("{:?} backup failed", source_path);
         Err(anyhow!("backup failed"))
@@ -428,6 +668,42 @@ mod tests {
         assert_eq!(store.version.get(&txn), Some(STORE_VERSION_MINIMUM));
     }
 
+    #[test]
+    fn open_read_only() -> anyhow::Result<()> {
+        let file = TestDbFile::random();
+        {
+            let store = LmdbStore::open(&file.path).build()?;
+            let mut txn = store.tx_begin_write();
+            store.pruned.put(&mut txn, &BlockHash::from(1));
+            txn.commit();
+        }
+
+        let options = EnvOptions {
+            read_only: true,
+            ..Default::default()
+        };
+        let store = LmdbStore::open(&file.path).options(&options).build()?;
+        assert!(store.is_read_only());
+        assert!(store
+            .pruned
+            .exists(&store.tx_begin_read(), &BlockHash::from(1)));
+        Ok(())
+    }
+
+    #[test]
+    fn open_read_only_fails_if_db_is_missing() {
+        let file = TestDbFile::random();
+        let options = EnvOptions {
+            read_only: true,
+            ..Default::default()
+        };
+        assert!(LmdbStore::open(&file.path)
+            .options(&options)
+            .build()
+            .is_err());
+        assert!(!file.path.exists());
+    }
+
     fn assert_upgrade_fails(path: &Path, error_msg: &str) {
         match LmdbStore::open(path).build() {
             Ok(_) => panic!("store should not be created!"),
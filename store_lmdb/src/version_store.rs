@@ -1,4 +1,6 @@
-use crate::{LmdbDatabase, LmdbEnv, LmdbWriteTransaction, Transaction, STORE_VERSION_CURRENT};
+use crate::{
+    LedgerIndex, LmdbDatabase, LmdbEnv, LmdbWriteTransaction, Transaction, STORE_VERSION_CURRENT,
+};
 use core::panic;
 use lmdb::{DatabaseFlags, WriteFlags};
 use std::{path::Path, sync::Arc};
@@ -69,6 +71,26 @@ impl LmdbVersionStore {
         let db = self.db_handle();
         load_version(txn, db)
     }
+
+    /// Whether a rebuild of the index was started but didn't finish
+    pub fn index_rebuilding(&self, txn: &dyn Transaction, index: LedgerIndex) -> bool {
+        txn.get(self.db_handle, &rebuilding_key(index)).is_ok()
+    }
+
+    pub fn set_index_rebuilding(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        index: LedgerIndex,
+        rebuilding: bool,
+    ) {
+        let key = rebuilding_key(index);
+        if rebuilding {
+            txn.put(self.db_handle, &key, &[1], WriteFlags::empty())
+                .unwrap();
+        } else {
+            let _ = txn.delete(self.db_handle, &key, None);
+        }
+    }
 }
 
 fn load_version(txn: &dyn Transaction, db: LmdbDatabase) -> Option<i32> {
@@ -89,3 +111,11 @@ fn value_bytes(version: i32) -> [u8; 32] {
 fn version_key() -> [u8; 32] {
     value_bytes(1)
 }
+
+fn rebuilding_key(index: LedgerIndex) -> [u8; 32] {
+    value_bytes(match index {
+        LedgerIndex::Delegators => 2,
+        LedgerIndex::BlockHeight => 3,
+        LedgerIndex::Incoming => 4,
+    })
+}
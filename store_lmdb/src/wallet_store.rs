@@ -9,7 +9,7 @@ use rsban_core::{
         BufferReader, BufferWriter, Deserialize, FixedSizeSerialize, MutStreamAdapter, Serialize,
         Stream, StreamExt,
     },
-    Account, KeyDerivationFunction, PublicKey, RawKey,
+    Account, KdfVariant, KeyDerivationFunction, PublicKey, RawKey,
 };
 use std::io::Write;
 use std::{
@@ -91,7 +91,7 @@ pub struct LmdbWalletStore {
 }
 
 impl LmdbWalletStore {
-    pub const VERSION_CURRENT: u32 = 4;
+    pub const VERSION_CURRENT: u32 = 5;
     pub fn new(
         fanout: usize,
         kdf: KeyDerivationFunction,
@@ -141,6 +141,7 @@ impl LmdbWalletStore {
                 &Self::deterministic_index_special(),
                 &WalletValue::new(RawKey::zero(), 0),
             );
+            store.kdf_put(txn, &store.kdf);
         }
         {
             let key = store.entry_get_raw(txn, &Self::wallet_key_special()).key;
@@ -190,6 +191,7 @@ impl LmdbWalletStore {
         store.ensure_key_exists(txn, &Self::salt_special())?;
         store.ensure_key_exists(txn, &Self::check_special())?;
         store.ensure_key_exists(txn, &Self::representative_special())?;
+        store.upgrade(txn)?;
         let mut guard = store.fans.lock().unwrap();
         guard.password.value_set(RawKey::zero());
         let key = store.entry_get_raw(txn, &Self::wallet_key_special()).key;
@@ -242,10 +244,16 @@ impl LmdbWalletStore {
         PublicKey::from(6)
     }
 
-    pub fn special_count() -> PublicKey {
+    /// Parameters of the KDF that derives the key which encrypts the wallet key.
+    /// Wallets that were created before this key existed use the node's default KDF
+    pub fn kdf_special() -> PublicKey {
         PublicKey::from(7)
     }
 
+    pub fn special_count() -> PublicKey {
+        PublicKey::from(8)
+    }
+
     pub fn initialize(&self, txn: &mut LmdbWriteTransaction, path: &Path) -> anyhow::Result<()> {
         let path_str = path
             .as_os_str()
@@ -361,15 +369,66 @@ impl LmdbWalletStore {
         self.check(txn) == check
     }
 
-    pub fn derive_key(&self, txn: &dyn Transaction, password: &str) -> RawKey {
+    pub fn kdf(&self, txn: &dyn Transaction) -> anyhow::Result<KeyDerivationFunction> {
+        let value = self.entry_get_raw(txn, &Self::kdf_special());
+        if value.key.is_zero() {
+            return Ok(self.kdf.clone());
+        }
+        Self::decode_kdf(&value.key).ok_or_else(|| anyhow!("invalid wallet kdf parameters"))
+    }
+
+    /// Upgrades the wallet to the current version. Version 4 wallets don't
+    /// store their KDF, they were encrypted with the default KDF of the node
+    pub fn upgrade(&self, txn: &mut LmdbWriteTransaction) -> anyhow::Result<()> {
+        let version = self.version(txn);
+        if version == 4 {
+            if self.entry_get_raw(txn, &Self::kdf_special()).key.is_zero() {
+                self.kdf_put(txn, &self.kdf);
+            }
+            self.version_put(txn, 5);
+        } else if version != Self::VERSION_CURRENT {
+            bail!("unsupported wallet version {}", version);
+        }
+        Ok(())
+    }
+
+    pub fn kdf_put(&self, txn: &mut LmdbWriteTransaction, kdf: &KeyDerivationFunction) {
+        self.entry_put_raw(
+            txn,
+            &Self::kdf_special(),
+            &WalletValue::new(Self::encode_kdf(kdf), 0),
+        );
+    }
+
+    fn encode_kdf(kdf: &KeyDerivationFunction) -> RawKey {
+        let mut bytes = [0; 32];
+        bytes[0] = 1;
+        bytes[1] = kdf.variant().as_u8();
+        bytes[2..6].copy_from_slice(&kdf.mem_cost().to_be_bytes());
+        bytes[6..10].copy_from_slice(&kdf.time_cost().to_be_bytes());
+        bytes[10..14].copy_from_slice(&kdf.lanes().to_be_bytes());
+        RawKey::from_bytes(bytes)
+    }
+
+    fn decode_kdf(value: &RawKey) -> Option<KeyDerivationFunction> {
+        let bytes = value.as_bytes();
+        if bytes[0] != 1 {
+            return None;
+        }
+        let variant = KdfVariant::from_u8(bytes[1])?;
+        let read_u32 = |i: usize| u32::from_be_bytes(bytes[i..i + 4].try_into().unwrap());
+        KeyDerivationFunction::with_params(variant, read_u32(2), read_u32(6), read_u32(10)).ok()
+    }
+
+    pub fn derive_key(&self, txn: &dyn Transaction, password: &str) -> anyhow::Result<RawKey> {
         let salt = self.salt(txn);
-        self.kdf.hash_password(password, salt.as_bytes())
+        self.kdf(txn)?.hash_password(password, salt.as_bytes())
     }
 
     pub fn rekey(&self, txn: &mut LmdbWriteTransaction, password: &str) -> anyhow::Result<()> {
         let mut guard = self.fans.lock().unwrap();
         if self.valid_password_locked(&guard, txn) {
-            let password_new = self.derive_key(txn, password);
+            let password_new = self.derive_key(txn, password)?;
             let wallet_key = self.wallet_key_locked(&guard, txn);
             guard.password.value_set(password_new);
             let iv = self.salt(txn).initialization_vector_low();
@@ -386,6 +445,39 @@ impl LmdbWalletStore {
         }
     }
 
+    /// Re-encrypts the wallet key with a key that is derived from the password
+    /// with new KDF parameters. The wallet key itself and thus the seed and all
+    /// entries stay unchanged. A locked wallet stays locked.
+    pub fn upgrade_kdf(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        password: &str,
+        kdf: &KeyDerivationFunction,
+    ) -> anyhow::Result<()> {
+        let mut guard = self.fans.lock().unwrap();
+        let unlocked = self.valid_password_locked(&guard, txn);
+        let iv = self.salt(txn).initialization_vector_low();
+        let encrypted = self.entry_get_raw(txn, &Self::wallet_key_special()).key;
+        let wallet_key = encrypted.decrypt(&self.derive_key(txn, password)?, &iv);
+        if !self.check_wallet_key(txn, &wallet_key) {
+            bail!("invalid password");
+        }
+
+        let password_new = kdf.hash_password(password, self.salt(txn).as_bytes())?;
+        let encrypted = wallet_key.encrypt(&password_new, &iv);
+        self.kdf_put(txn, kdf);
+        self.entry_put_raw(
+            txn,
+            &Self::wallet_key_special(),
+            &WalletValue::new(encrypted, 0),
+        );
+        if unlocked {
+            guard.password.value_set(password_new);
+        }
+        guard.wallet_key_mem.value_set(encrypted);
+        Ok(())
+    }
+
     pub fn begin<'txn>(&self, txn: &'txn dyn Transaction) -> WalletIterator<'txn> {
         LmdbIteratorImpl::new_iterator(
             txn,
@@ -505,13 +597,15 @@ impl LmdbWalletStore {
 
     pub fn attempt_password(&self, txn: &dyn Transaction, password: &str) -> bool {
         let is_valid = {
+            let Ok(password_key) = self.derive_key(txn, password) else {
+                return false;
+            };
             let mut guard = self.fans.lock().unwrap();
-            let password_key = self.derive_key(txn, password);
             guard.password.value_set(password_key);
             self.valid_password_locked(&guard, txn)
         };
 
-        if is_valid && self.version(txn) != 4 {
+        if is_valid && self.version(txn) != Self::VERSION_CURRENT {
             panic!("invalid wallet store version!");
         }
 
@@ -533,6 +627,19 @@ impl LmdbWalletStore {
         result
     }
 
+    /// Accounts that were inserted without a private key
+    pub fn watch_accounts(&self, txn: &dyn Transaction) -> Vec<Account> {
+        let mut result = Vec::new();
+        let mut it = self.begin(txn);
+        while let Some((k, v)) = it.current() {
+            if v.key.is_zero() {
+                result.push(k.into());
+            }
+            it.next();
+        }
+        result
+    }
+
     pub fn representative(&self, txn: &dyn Transaction) -> PublicKey {
         let value = self.entry_get_raw(txn, &Self::representative_special());
         PublicKey::from_bytes(*value.key.as_bytes())
@@ -14,6 +14,7 @@ use tokio::runtime::Runtime;
 use views::AppView;
 
 fn main() -> eframe::Result {
+    tracing_subscriber::fmt::init();
     let runtime = Runtime::new().unwrap();
     let runtime_handle = runtime.handle().clone();
 
@@ -1,8 +1,16 @@
 use chrono::{DateTime, TimeZone, Utc};
 use rsban_core::{Account, BlockHash};
-use rsban_messages::{AscPullAckType, AscPullReqType, HashType, Message, MessageType};
+use rsban_messages::{
+    AscPullAckType, AscPullReqType, DeserializedMessage, HashType, Message, MessageType,
+    ProtocolInfo,
+};
 use rsban_network::{ChannelDirection, ChannelId};
-use std::collections::HashMap;
+use rsban_node::transport::{self, MessageRecordingWriter};
+use std::{
+    collections::HashMap,
+    io::Write,
+    net::{Ipv6Addr, SocketAddrV6},
+};
 
 #[derive(Clone)]
 pub(crate) struct RecordedMessage {
@@ -182,6 +190,24 @@ impl MessageCollection {
         self.filtered.len()
     }
 
+    /// Writes all captured messages, regardless of the current filter
+    pub fn write_to(
+        &self,
+        writer: &mut MessageRecordingWriter<impl Write>,
+        protocol: ProtocolInfo,
+    ) -> anyhow::Result<()> {
+        for message in &self.all_messages {
+            writer.write(&transport::RecordedMessage {
+                timestamp: message.date.into(),
+                channel_id: message.channel_id,
+                direction: message.direction,
+                peer_addr: SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0),
+                message: DeserializedMessage::new(message.message.clone(), protocol),
+            })?;
+        }
+        Ok(())
+    }
+
     pub fn add(&mut self, message: RecordedMessage) {
         if self.filter.include(&message) {
             self.filtered.push(message.clone());
@@ -3,12 +3,17 @@ use crate::{
     message_rate_calculator::{MessageRates, MessageRatesCalculator},
 };
 use chrono::Utc;
+use rsban_core::Networks;
+use rsban_messages::ProtocolInfo;
 use rsban_network::ChannelDirection;
-use rsban_node::NodeCallbacks;
+use rsban_node::{transport::MessageRecordingWriter, NodeCallbacks};
 use rsban_nullable_clock::{SteadyClock, Timestamp};
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc, RwLock,
+use std::{
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
 };
 
 pub(crate) struct MessageRecorder {
@@ -44,6 +49,18 @@ impl MessageRecorder {
         self.messages.write().unwrap().clear();
     }
 
+    /// Saves all captured messages in the recording file format, so that they can be
+    /// replayed into a node later
+    pub fn save(&self, path: impl AsRef<Path>, network: Networks) -> anyhow::Result<()> {
+        let protocol = ProtocolInfo::default_for(network);
+        let mut writer = MessageRecordingWriter::create(path)?;
+        self.messages
+            .read()
+            .unwrap()
+            .write_to(&mut writer, protocol)?;
+        writer.flush()
+    }
+
     pub fn record(&self, message: RecordedMessage, now: Timestamp) {
         {
             let mut rates = self.rate_calc.write().unwrap();
@@ -25,7 +25,11 @@ impl AppView {
             ui.horizontal(|ui| {
                 NodeRunnerView::new(&mut self.model.node_runner).show(ui);
                 ui.separator();
-                MessageRecorderControlsView::new(&self.model.msg_recorder).show(ui);
+                MessageRecorderControlsView::new(
+                    &self.model.msg_recorder,
+                    self.model.node_runner.network(),
+                )
+                .show(ui);
             });
             ui.add_space(1.0);
         });
@@ -1,18 +1,23 @@
 use crate::message_recorder::MessageRecorder;
+use chrono::Utc;
 use eframe::egui::Ui;
+use rsban_core::Networks;
+use tracing::error;
 
 pub(crate) struct MessageRecorderControlsView<'a> {
     recorder: &'a MessageRecorder,
+    network: Networks,
 }
 
 impl<'a> MessageRecorderControlsView<'a> {
-    pub(crate) fn new(recorder: &'a MessageRecorder) -> Self {
-        Self { recorder }
+    pub(crate) fn new(recorder: &'a MessageRecorder, network: Networks) -> Self {
+        Self { recorder, network }
     }
 
     pub fn show(&self, ui: &mut Ui) {
         self.capture_check_box(ui);
         self.clear_button(ui);
+        self.save_button(ui);
     }
 
     fn capture_check_box(&self, ui: &mut Ui) {
@@ -30,4 +35,13 @@ impl<'a> MessageRecorderControlsView<'a> {
             self.recorder.clear();
         }
     }
+
+    fn save_button(&self, ui: &mut Ui) {
+        if ui.button("save").clicked() {
+            let file_name = format!("messages_{}.rsbrec", Utc::now().format("%Y%m%d_%H%M%S"));
+            if let Err(e) = self.recorder.save(&file_name, self.network) {
+                error!("Could not save messages to {}: {:?}", file_name, e);
+            }
+        }
+    }
 }
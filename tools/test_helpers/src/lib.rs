@@ -18,6 +18,7 @@ use rsban_rpc_client::{NanoRpcClient, Url};
 use rsban_rpc_server::run_rpc_server;
 use std::{
     net::{IpAddr, Ipv6Addr, SocketAddr, TcpListener},
+    path::PathBuf,
     sync::{
         atomic::{AtomicU16, Ordering},
         Arc, OnceLock,
@@ -32,6 +33,7 @@ pub struct System {
     network_params: NetworkParams,
     pub work: Arc<WorkPoolImpl>,
     pub nodes: Vec<Arc<Node>>,
+    read_only_nodes: Vec<Arc<Node>>,
     pub initialization_blocks: Vec<Block>,
     pub initialization_blocks_cemented: Vec<Block>,
 }
@@ -50,6 +52,7 @@ impl System {
             )),
             network_params,
             nodes: Vec::new(),
+            read_only_nodes: Vec::new(),
             initialization_blocks: Vec::new(),
             initialization_blocks_cemented: Vec::new(),
         }
@@ -156,8 +159,21 @@ impl System {
         node
     }
 
+    /// Opens the ledger of the given node read-only, like a query node that runs next to it
+    pub fn make_read_only_node(&mut self, node: &Node, mut flags: NodeFlags) -> Arc<Node> {
+        flags.read_only = true;
+        let read_only = self.new_node_at(node.data_path.clone(), Self::default_config(), flags);
+        read_only.start();
+        self.read_only_nodes.push(read_only.clone());
+        read_only
+    }
+
     fn new_node(&self, config: NodeConfig, flags: NodeFlags) -> Arc<Node> {
         let path = unique_path().expect("Could not get a unique path");
+        self.new_node_at(path, config, flags)
+    }
+
+    fn new_node_at(&self, path: PathBuf, config: NodeConfig, flags: NodeFlags) -> Arc<Node> {
         let node = NodeBuilder::new(self.network_params.network.current_network)
             .runtime(self.runtime.tokio.handle().clone())
             .data_path(path)
@@ -171,6 +187,9 @@ impl System {
     }
 
     fn stop(&mut self) {
+        for node in &self.read_only_nodes {
+            node.stop();
+        }
         for node in &self.nodes {
             node.stop();
             std::fs::remove_dir_all(&node.data_path).expect("Could not delete node data dir");
@@ -30,6 +30,12 @@ pub enum Topic {
     Telemetry,
     /// New block arrival message
     NewUnconfirmedBlock,
+    /// A representative signed conflicting final votes
+    Equivocation,
+    /// Peer connection lifecycle events (connect, handshake, disconnect, exclusion)
+    Peers,
+    /// A confirmed balance of a wallet account crossed its alert threshold
+    BalanceAlert,
     /// Auxiliary length, not a valid topic, must be the last enum
     Length,
 }
@@ -179,6 +185,9 @@ pub fn to_topic(topic: impl AsRef<str>) -> Topic {
         "bootstrap" => Topic::Bootstrap,
         "telemetry" => Topic::Telemetry,
         "new_unconfirmed_block" => Topic::NewUnconfirmedBlock,
+        "equivocation" => Topic::Equivocation,
+        "peers" => Topic::Peers,
+        "balance_alert" => Topic::BalanceAlert,
         _ => Topic::Invalid,
     }
 }
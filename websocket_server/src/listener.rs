@@ -25,7 +25,7 @@ pub struct WebsocketListener {
     endpoint: Mutex<SocketAddr>,
     tx_stop: Mutex<Option<oneshot::Sender<()>>>,
     wallets: Arc<Wallets>,
-    topic_subscriber_count: Arc<[AtomicUsize; 11]>,
+    topic_subscriber_count: Arc<[AtomicUsize; Topic::Length as usize]>,
     sessions: Arc<Mutex<Vec<Weak<WebsocketSessionEntry>>>>,
     tokio: tokio::runtime::Handle,
     bound: Mutex<bool>,
@@ -112,6 +112,20 @@ impl WebsocketListener {
         }
     }
 
+    /// Same as [`broadcast`](Self::broadcast), but can be used from within the async runtime
+    pub async fn broadcast_async(&self, message: &OutgoingMessageEnvelope) {
+        let sessions: Vec<_> = self
+            .sessions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|s| s.upgrade())
+            .collect();
+        for session in sessions {
+            let _ = session.write(message).await;
+        }
+    }
+
     /// Broadcast block confirmation. The content of the message depends on subscription options (such as "include_block")
     pub fn broadcast_confirmation(
         &self,
@@ -232,7 +246,7 @@ impl WebsocketListenerExt for Arc<WebsocketListener> {
 async fn accept_connection(
     stream: TcpStream,
     wallets: Arc<Wallets>,
-    topic_subscriber_count: Arc<[AtomicUsize; 11]>,
+    topic_subscriber_count: Arc<[AtomicUsize; Topic::Length as usize]>,
     remote_endpoint: SocketAddr,
     tx_send: mpsc::Sender<OutgoingMessageEnvelope>,
     mut rx_send: mpsc::Receiver<OutgoingMessageEnvelope>,
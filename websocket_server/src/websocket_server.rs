@@ -3,20 +3,22 @@ use rsban_core::{
     Account, Amount, BlockHash, BlockType, SavedBlock, Vote, VoteCode, VoteWithWeightInfo,
 };
 use rsban_messages::TelemetryData;
+use rsban_network::{ChannelDirection, ChannelInfo, NetworkInfo, PeerEvent};
 use rsban_node::{
     bootstrap::{BootstrapCallbackData, BootstrapInitiator, BootstrapStarted, BootstrapStopped},
     config::WebsocketConfig,
     consensus::{
-        ActiveElections, ElectionStatus, ElectionStatusType, ProcessLiveDispatcher, VoteProcessor,
+        ActiveElections, ElectionStatus, ElectionStatusType, Equivocation, EquivocationDetector,
+        ProcessLiveDispatcher, VoteProcessor,
     },
-    wallets::Wallets,
+    wallets::{BalanceAlert, Wallets},
     Telemetry,
 };
 use rsban_websocket_messages::{new_block_arrived_message, OutgoingMessageEnvelope, Topic};
 use serde::{Deserialize, Serialize};
 use std::{
     net::{IpAddr, SocketAddr, SocketAddrV6},
-    sync::Arc,
+    sync::{Arc, RwLock},
     time::UNIX_EPOCH,
 };
 use tracing::error;
@@ -30,6 +32,8 @@ pub fn create_websocket_server(
     vote_processor: &VoteProcessor,
     process_live_dispatcher: &ProcessLiveDispatcher,
     bootstrap_initiator: &BootstrapInitiator,
+    equivocation_detector: &EquivocationDetector,
+    network_info: &RwLock<NetworkInfo>,
 ) -> Option<Arc<WebsocketListener>> {
     if !config.enabled {
         return None;
@@ -41,7 +45,20 @@ pub fn create_websocket_server(
     };
 
     let endpoint = SocketAddr::new(address, config.port);
-    let server = Arc::new(WebsocketListener::new(endpoint, wallets, tokio.clone()));
+    let server = Arc::new(WebsocketListener::new(
+        endpoint,
+        wallets.clone(),
+        tokio.clone(),
+    ));
+
+    let server_w = Arc::downgrade(&server);
+    wallets.on_balance_alert(Box::new(move |alert| {
+        if let Some(server) = server_w.upgrade() {
+            if server.any_subscriber(Topic::BalanceAlert) {
+                server.broadcast(&balance_alert(alert));
+            }
+        }
+    }));
 
     let server_w = Arc::downgrade(&server);
     active_elections.on_election_ended(Box::new(
@@ -143,9 +160,46 @@ pub fn create_websocket_server(
         }
     }));
 
+    let server_w: std::sync::Weak<WebsocketListener> = Arc::downgrade(&server);
+    equivocation_detector.on_equivocation(Box::new(move |equivocation| {
+        if let Some(server) = server_w.upgrade() {
+            if server.any_subscriber(Topic::Equivocation) {
+                server.broadcast(&equivocation_detected(equivocation));
+            }
+        }
+    }));
+
+    // Peer events are raised from within the async runtime and while the network info
+    // is locked, so they are queued and broadcast by a separate task. If the subscribers
+    // can't keep up, events are dropped instead of piling up in memory
+    let (tx_peer_event, mut rx_peer_event) = tokio::sync::mpsc::channel(PEER_EVENT_QUEUE_SIZE);
+    let server_w = Arc::downgrade(&server);
+    network_info
+        .write()
+        .unwrap()
+        .on_peer_event(Arc::new(move |event| {
+            if let Some(server) = server_w.upgrade() {
+                if server.any_subscriber(Topic::Peers) {
+                    let _ = tx_peer_event.try_send(peer_event(event));
+                }
+            }
+        }));
+
+    let server_w = Arc::downgrade(&server);
+    tokio.spawn(async move {
+        while let Some(message) = rx_peer_event.recv().await {
+            let Some(server) = server_w.upgrade() else {
+                break;
+            };
+            server.broadcast_async(&message).await;
+        }
+    });
+
     Some(server)
 }
 
+const PEER_EVENT_QUEUE_SIZE: usize = 1024;
+
 fn telemetry_received(data: &TelemetryData, endpoint: SocketAddrV6) -> OutgoingMessageEnvelope {
     OutgoingMessageEnvelope::new(
         Topic::Telemetry,
@@ -281,3 +335,148 @@ fn bootstrap_started(bootstrap_callback_data: &BootstrapCallbackData) -> Outgoin
         },
     )
 }
+
+pub fn equivocation_detected(equivocation: &Equivocation) -> OutgoingMessageEnvelope {
+    OutgoingMessageEnvelope::new(
+        Topic::Equivocation,
+        EquivocationDetected {
+            representative: Account::from(equivocation.representative).encode_account(),
+            root: equivocation.root.root.to_string(),
+            previous: equivocation.root.previous.to_string(),
+            first: SignedVote::new(&equivocation.first_hash, &equivocation.first_vote),
+            second: SignedVote::new(&equivocation.second_hash, &equivocation.second_vote),
+        },
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EquivocationDetected {
+    pub representative: String,
+    pub root: String,
+    pub previous: String,
+    pub first: SignedVote,
+    pub second: SignedVote,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SignedVote {
+    pub hash: String,
+    pub signature: String,
+    pub timestamp: String,
+    pub blocks: Vec<String>,
+}
+
+impl SignedVote {
+    fn new(hash: &BlockHash, vote: &Vote) -> Self {
+        Self {
+            hash: hash.to_string(),
+            signature: vote.signature.encode_hex(),
+            timestamp: vote.timestamp().to_string(),
+            blocks: vote.hashes.iter().map(|h| h.to_string()).collect(),
+        }
+    }
+}
+
+pub fn balance_alert(alert: &BalanceAlert) -> OutgoingMessageEnvelope {
+    let mut result = OutgoingMessageEnvelope::new(
+        Topic::BalanceAlert,
+        BalanceAlertReceived {
+            wallet: alert.wallet.encode_hex(),
+            account: alert.account.encode_account(),
+            threshold: alert.threshold.to_string_dec(),
+            previous_balance: alert.previous_balance.to_string_dec(),
+            balance: alert.balance.to_string_dec(),
+            direction: alert.direction.as_str().to_owned(),
+        },
+    );
+    result.hash = Some(alert.hash);
+    result
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BalanceAlertReceived {
+    pub wallet: String,
+    pub account: String,
+    pub threshold: String,
+    pub previous_balance: String,
+    pub balance: String,
+    pub direction: String,
+}
+
+pub fn peer_event(event: &PeerEvent) -> OutgoingMessageEnvelope {
+    let message = match event {
+        PeerEvent::Connected(channel) | PeerEvent::RealtimeUpgrade(channel) => {
+            PeerEventReceived::new(event, channel)
+        }
+        PeerEvent::HandshakeCompleted {
+            channel,
+            node_id,
+            protocol_version,
+        } => PeerEventReceived {
+            node_id: Some(node_id.to_string()),
+            protocol_version: Some(protocol_version.to_string()),
+            ..PeerEventReceived::new(event, channel)
+        },
+        PeerEvent::Disconnected { channel, reason } => PeerEventReceived {
+            reason: Some(reason.as_str().to_owned()),
+            ..PeerEventReceived::new(event, channel)
+        },
+        PeerEvent::Excluded {
+            peer_addr,
+            permanent,
+        } => PeerEventReceived {
+            event: event.as_str().to_owned(),
+            address: peer_addr.ip().to_string(),
+            port: peer_addr.port().to_string(),
+            channel_id: None,
+            direction: None,
+            node_id: None,
+            protocol_version: None,
+            reason: None,
+            permanent: Some(permanent.to_string()),
+        },
+    };
+    OutgoingMessageEnvelope::new(Topic::Peers, message)
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PeerEventReceived {
+    pub event: String,
+    pub address: String,
+    pub port: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permanent: Option<String>,
+}
+
+impl PeerEventReceived {
+    fn new(event: &PeerEvent, channel: &ChannelInfo) -> Self {
+        let peer_addr = channel.peer_addr();
+        Self {
+            event: event.as_str().to_owned(),
+            address: peer_addr.ip().to_string(),
+            port: peer_addr.port().to_string(),
+            channel_id: Some(channel.channel_id().to_string()),
+            direction: Some(
+                match channel.direction() {
+                    ChannelDirection::Inbound => "inbound",
+                    ChannelDirection::Outbound => "outbound",
+                }
+                .to_owned(),
+            ),
+            node_id: channel.node_id().map(|id| id.to_string()),
+            protocol_version: None,
+            reason: None,
+            permanent: None,
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use core::panic;
 use futures_util::{SinkExt, StreamExt};
 use rsban_core::{
-    Account, Amount, Block, JsonBlock, Networks, PrivateKey, SendBlockArgs,
-    UnsavedBlockLatticeBuilder, Vote, VoteCode, DEV_GENESIS_KEY,
+    utils::{TEST_ENDPOINT_1, TEST_ENDPOINT_2},
+    Account, Amount, Block, BlockHash, JsonBlock, Networks, PrivateKey, QualifiedRoot,
+    SendBlockArgs, UnsavedBlockLatticeBuilder, Vote, VoteCode, DEV_GENESIS_KEY,
 };
 use rsban_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
 use rsban_messages::{Message, Publish};
+use rsban_network::{ChannelDirection, ChannelMode};
 use rsban_node::{
     bootstrap::{BootstrapInitiatorExt, BootstrapStarted},
     config::{NetworkConstants, NodeConfig, WebsocketConfig},
@@ -13,8 +15,8 @@ use rsban_node::{
 };
 use rsban_websocket_messages::{OutgoingMessageEnvelope, Topic};
 use rsban_websocket_server::{
-    create_websocket_server, vote_received, BlockConfirmed, TelemetryReceived, VoteReceived,
-    WebsocketListener, WebsocketListenerExt,
+    create_websocket_server, vote_received, BlockConfirmed, EquivocationDetected,
+    PeerEventReceived, TelemetryReceived, VoteReceived, WebsocketListener, WebsocketListenerExt,
 };
 use std::{sync::Arc, time::Duration};
 use test_helpers::{assert_timely, get_available_port, make_fake_channel, System};
@@ -708,6 +710,118 @@ fn new_unconfirmed_block() {
     });
 }
 
+#[test]
+fn equivocation() {
+    let mut system = System::new();
+    let (node1, _websocket) = create_node_with_websocket(&mut system);
+    node1.runtime.block_on(async {
+        let mut ws_stream = connect_websocket(&node1).await;
+        ws_stream
+            .send(tungstenite::Message::Text(
+                r#"{"action": "subscribe", "topic": "equivocation", "ack": true }"#.to_string(),
+            ))
+            .await
+            .unwrap();
+        //await ack
+        ws_stream.next().await.unwrap().unwrap();
+
+        let key = PrivateKey::from(42);
+        let hash1 = BlockHash::from(1);
+        let hash2 = BlockHash::from(2);
+        let node_l = node1.clone();
+        let key_l = key.clone();
+        spawn_blocking(move || {
+            let root = QualifiedRoot::new_test_instance();
+            node_l.equivocation_detector.vote(
+                &Arc::new(Vote::new_final(&key_l, vec![hash1])),
+                hash1,
+                root.clone(),
+            );
+            node_l.equivocation_detector.vote(
+                &Arc::new(Vote::new_final(&key_l, vec![hash2])),
+                hash2,
+                root,
+            );
+        })
+        .await
+        .unwrap();
+
+        let tungstenite::Message::Text(response) = ws_stream.next().await.unwrap().unwrap() else {
+            panic!("not a text message");
+        };
+
+        let response_json: OutgoingMessageEnvelope = serde_json::from_str(&response).unwrap();
+        assert_eq!(response_json.topic, Some(Topic::Equivocation));
+        let message: EquivocationDetected =
+            serde_json::from_value(response_json.message.unwrap()).unwrap();
+        assert_eq!(message.representative, key.account().encode_account());
+        assert_eq!(message.first.hash, hash1.to_string());
+        assert_eq!(message.second.hash, hash2.to_string());
+    });
+}
+
+#[test]
+fn peers() {
+    let mut system = System::new();
+    let (node1, _websocket) = create_node_with_websocket(&mut system);
+    node1.runtime.block_on(async {
+        let mut ws_stream = connect_websocket(&node1).await;
+        ws_stream
+            .send(tungstenite::Message::Text(
+                r#"{"action": "subscribe", "topic": "peers", "ack": true }"#.to_string(),
+            ))
+            .await
+            .unwrap();
+        //await ack
+        ws_stream.next().await.unwrap().unwrap();
+
+        let node_l = node1.clone();
+        let channel_id = spawn_blocking(move || {
+            let now = node_l.steady_clock.now();
+            let mut network = node_l.network_info.write().unwrap();
+            let channel = network
+                .add(
+                    TEST_ENDPOINT_1,
+                    TEST_ENDPOINT_2,
+                    ChannelDirection::Outbound,
+                    ChannelMode::Realtime,
+                    now,
+                )
+                .unwrap();
+            // A peer gets excluded when it misbehaves repeatedly
+            network.peer_misbehaved(channel.channel_id(), now);
+            network.peer_misbehaved(channel.channel_id(), now);
+            network.purge(now, Duration::from_secs(300));
+            channel.channel_id()
+        })
+        .await
+        .unwrap();
+
+        let mut events = Vec::new();
+        while events.len() < 3 {
+            let tungstenite::Message::Text(response) = ws_stream.next().await.unwrap().unwrap()
+            else {
+                panic!("not a text message");
+            };
+            let response_json: OutgoingMessageEnvelope = serde_json::from_str(&response).unwrap();
+            assert_eq!(response_json.topic, Some(Topic::Peers));
+            let event: PeerEventReceived =
+                serde_json::from_value(response_json.message.unwrap()).unwrap();
+            if event.address == TEST_ENDPOINT_2.ip().to_string() {
+                events.push(event);
+            }
+        }
+
+        assert_eq!(events[0].event, "connected");
+        assert_eq!(events[0].channel_id, Some(channel_id.to_string()));
+        assert_eq!(events[0].direction, Some("outbound".to_owned()));
+        assert_eq!(events[1].event, "excluded");
+        assert_eq!(events[1].permanent, Some("false".to_owned()));
+        assert_eq!(events[2].event, "disconnected");
+        assert_eq!(events[2].reason, Some("misbehaved".to_owned()));
+    });
+}
+
 fn create_node_with_websocket(system: &mut System) -> (Arc<Node>, Arc<WebsocketListener>) {
     let websocket_port = get_available_port();
     let config = NodeConfig {
@@ -732,6 +846,8 @@ fn create_node_with_websocket(system: &mut System) -> (Arc<Node>, Arc<WebsocketL
         &node.vote_processor,
         &node.process_live_dispatcher,
         &node.bootstrap_initiator,
+        &node.equivocation_detector,
+        &node.network_info,
     )
     .unwrap();
 